@@ -1,11 +1,11 @@
 use std::collections::HashMap;
 
-use fresnel_fir_ir::types::FresnelFirIR;
+use fresnel_fir_ir::types::{FresnelFirIR, InputSpace};
 
 use crate::graph::NdaGraph;
 use crate::predicate::{compile_expr, CompiledExpr, TypeContext};
 use crate::protocol::compile_protocol;
-use crate::validate::{validate_ir, ValidationError};
+use crate::validate::{validate_graph, validate_ir, GraphError, ValidationError};
 
 #[derive(Debug, thiserror::Error)]
 pub enum CompileError {
@@ -17,16 +17,58 @@ pub enum CompileError {
 
     #[error("Protocol compilation error: {0}")]
     Protocol(#[from] crate::protocol::ProtocolCompileError),
+
+    #[error("Invalid graph for protocol '{protocol}': {}", .errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))]
+    Graph {
+        protocol: String,
+        errors: Vec<GraphError>,
+    },
+
+    #[error("Input space check failed: {0}")]
+    InputSpace(String),
 }
 
+/// Problems that do not stop compilation but make the result suspect.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CompileWarning {
+    /// No input vector satisfies the input constraints, so a campaign would
+    /// draw nothing. `unsat_core` names constraints that already contradict
+    /// each other, or is empty when the checker could not narrow them down.
+    #[error("input constraints are unsatisfiable (core: {})", .unsat_core.join(", "))]
+    EmptyInputSpace { unsat_core: Vec<String> },
+}
+
+/// Satisfiability check for an input space, supplied by a crate with a SAT
+/// solver. Returns the names of an unsatisfiable core when no vector
+/// satisfies the constraints, `None` when one does, or an error when the
+/// constraints cannot be checked at all.
+pub type InputSpaceCheck = dyn Fn(&InputSpace) -> Result<Option<Vec<String>>, String>;
+
 #[derive(Debug, Clone)]
 pub struct CompiledIR {
     pub graphs: HashMap<String, NdaGraph>,
     pub predicates: HashMap<String, CompiledExpr>,
     pub type_context: TypeContext,
+    pub warnings: Vec<CompileWarning>,
 }
 
+/// Compile `ir` without checking its input space for satisfiability, so it
+/// never reports [`CompileWarning::EmptyInputSpace`].
+#[deprecated(
+    note = "skips the input space check; use `fresnel_fir_explore::solver::search::compile_checked`"
+)]
 pub fn compile(ir: &FresnelFirIR) -> Result<CompiledIR, CompileError> {
+    compile_with_input_check(ir, &|_| Ok(None))
+}
+
+/// Compile `ir`, running `check` over the input space and recording
+/// [`CompileWarning::EmptyInputSpace`] if it reports UNSAT. The compiler
+/// has no SAT solver of its own; `compile_checked` in the explore crate
+/// supplies one.
+pub fn compile_with_input_check(
+    ir: &FresnelFirIR,
+    check: &InputSpaceCheck,
+) -> Result<CompiledIR, CompileError> {
     // 1. Validate
     validate_ir(ir).map_err(CompileError::Validation)?;
 
@@ -51,13 +93,24 @@ pub fn compile(ir: &FresnelFirIR) -> Result<CompiledIR, CompileError> {
     // 4. Compile protocols into NDA graphs
     let mut graphs = HashMap::new();
     for (name, protocol) in &ir.protocols {
-        let graph = compile_protocol(protocol, &ctx, &ir.protocols)?;
+        let graph = compile_protocol(name, protocol, &ctx, &ir.protocols)?;
+        validate_graph(&graph).map_err(|errors| CompileError::Graph {
+            protocol: name.clone(),
+            errors,
+        })?;
         graphs.insert(name.clone(), graph);
     }
 
+    // 5. Check that some input vector satisfies the constraints
+    let mut warnings = Vec::new();
+    if let Some(unsat_core) = check(&ir.inputs).map_err(CompileError::InputSpace)? {
+        warnings.push(CompileWarning::EmptyInputSpace { unsat_core });
+    }
+
     Ok(CompiledIR {
         graphs,
         predicates,
         type_context: ctx,
+        warnings,
     })
 }
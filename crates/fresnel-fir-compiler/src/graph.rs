@@ -1,15 +1,61 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
 use crate::predicate::CompiledExpr;
 
 pub type NodeId = u32;
 
+/// Where a graph node was compiled from: a path into the IR document,
+/// e.g. `protocols.document_lifecycle.root.children[1].body`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SourceSpan {
+    pub path: String,
+}
+
+impl SourceSpan {
+    /// The root node of the named protocol.
+    pub fn protocol_root(protocol: &str) -> Self {
+        Self {
+            path: format!("protocols.{protocol}.root"),
+        }
+    }
+
+    /// The span of `field` within this node.
+    pub fn field(&self, field: &str) -> Self {
+        Self {
+            path: format!("{}.{field}", self.path),
+        }
+    }
+
+    /// The span of element `index` of the list `field` within this node.
+    pub fn item(&self, field: &str, index: usize) -> Self {
+        Self {
+            path: format!("{}.{field}[{index}]", self.path),
+        }
+    }
+}
+
+impl fmt::Display for SourceSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.path)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum GraphNode {
     Terminal {
         action: String,
         guard: Option<CompiledExpr>,
+        /// The `call` node this terminal was compiled from.
+        source_span: Option<SourceSpan>,
     },
     Branch {
         alternatives: Vec<BranchEdge>,
+        /// The `alt` node this branch was compiled from.
+        source_span: Option<SourceSpan>,
     },
     LoopEntry {
         body_start: NodeId,
@@ -37,6 +83,18 @@ pub struct NdaGraph {
     pub exit: NodeId,
 }
 
+impl GraphNode {
+    /// The spec element this node was compiled from, if known.
+    pub fn source_span(&self) -> Option<&SourceSpan> {
+        match self {
+            GraphNode::Terminal { source_span, .. } | GraphNode::Branch { source_span, .. } => {
+                source_span.as_ref()
+            }
+            _ => None,
+        }
+    }
+}
+
 impl NdaGraph {
     pub fn new() -> Self {
         let mut graph = NdaGraph {
@@ -68,3 +126,52 @@ impl Default for NdaGraph {
         Self::new()
     }
 }
+
+/// All nodes reachable from `start`, following edges, branch targets and
+/// loop bodies. Ids that do not name a node are ignored.
+pub fn reachable_nodes(graph: &NdaGraph, start: NodeId) -> HashSet<NodeId> {
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(current) = queue.pop_front() {
+        let mut successors: Vec<NodeId> = graph
+            .edges
+            .iter()
+            .filter(|&&(from, _)| from == current)
+            .map(|&(_, to)| to)
+            .collect();
+        match graph.nodes.get(current as usize) {
+            Some(GraphNode::Branch { alternatives, .. }) => {
+                successors.extend(alternatives.iter().map(|alt| alt.target));
+            }
+            Some(GraphNode::LoopEntry { body_start, .. }) => successors.push(*body_start),
+            _ => {}
+        }
+
+        for next in successors {
+            if (next as usize) < graph.nodes.len() && visited.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    visited
+}
+
+/// Actions and branch ids reachable from the graph's entry: the
+/// denominator for coverage that can actually be achieved.
+pub fn reachable_actions(graph: &NdaGraph) -> HashSet<String> {
+    let mut actions = HashSet::new();
+    for id in reachable_nodes(graph, graph.entry) {
+        match &graph.nodes[id as usize] {
+            GraphNode::Terminal { action, .. } => {
+                actions.insert(action.clone());
+            }
+            GraphNode::Branch { alternatives, .. } => {
+                actions.extend(alternatives.iter().map(|alt| alt.id.clone()));
+            }
+            _ => {}
+        }
+    }
+    actions
+}
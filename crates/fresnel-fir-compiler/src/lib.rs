@@ -4,4 +4,5 @@ pub mod predicate;
 pub mod protocol;
 pub mod validate;
 
+#[allow(deprecated)]
 pub use compile::compile;
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use fresnel_fir_ir::expr::{Expr, FnClassification, Literal, OpKind, QuantifierKind};
+use fresnel_fir_ir::expr::{
+    Expr, FnClassification, InstanceFilter, Literal, OpKind, QuantifierKind,
+};
 use fresnel_fir_ir::types::FresnelFirIR;
 
 // ── Values ───────────────────────────────────────────────────────────
@@ -125,6 +127,7 @@ pub enum CompiledExpr {
         kind: QuantifierKind,
         var: String,
         domain: String,
+        filter: Option<InstanceFilter>,
         body: Box<CompiledExpr>,
     },
     FnCall {
@@ -157,6 +160,9 @@ pub enum EvalError {
 
     #[error("Cannot evaluate: {reason}")]
     Unsupported { reason: String },
+
+    #[error("Integer overflow in {op}")]
+    Overflow { op: String },
 }
 
 // ── Compilation ──────────────────────────────────────────────────────
@@ -186,6 +192,7 @@ pub fn compile_expr(expr: &Expr, _ctx: &TypeContext) -> Result<CompiledExpr, Com
             kind,
             var,
             domain,
+            filter,
             body,
         } => {
             let compiled_body = compile_expr(body, _ctx)?;
@@ -193,6 +200,7 @@ pub fn compile_expr(expr: &Expr, _ctx: &TypeContext) -> Result<CompiledExpr, Com
                 kind: kind.clone(),
                 var: var.clone(),
                 domain: domain.clone(),
+                filter: filter.clone(),
                 body: Box::new(compiled_body),
             })
         }
@@ -294,10 +302,71 @@ fn eval_op(op: &OpKind, args: &[CompiledExpr], env: &ValueEnv) -> Result<Value,
                 }),
             }
         }
+        OpKind::Xor => {
+            let mut parity = false;
+            for arg in args {
+                match eval_expr(arg, env)? {
+                    Value::Bool(b) => parity ^= b,
+                    other => {
+                        return Err(EvalError::TypeError {
+                            expected: "bool".to_string(),
+                            actual: format!("{other:?}"),
+                        })
+                    }
+                }
+            }
+            Ok(Value::Bool(parity))
+        }
+        OpKind::Iff => {
+            let left = eval_expr(&args[0], env)?;
+            let right = eval_expr(&args[1], env)?;
+            match (&left, &right) {
+                (Value::Bool(a), Value::Bool(b)) => Ok(Value::Bool(a == b)),
+                _ => Err(EvalError::TypeError {
+                    expected: "bool".to_string(),
+                    actual: format!("{left:?}, {right:?}"),
+                }),
+            }
+        }
         OpKind::Lt => eval_int_compare(args, env, |a, b| a < b),
         OpKind::Lte => eval_int_compare(args, env, |a, b| a <= b),
         OpKind::Gt => eval_int_compare(args, env, |a, b| a > b),
         OpKind::Gte => eval_int_compare(args, env, |a, b| a >= b),
+        OpKind::Sum => {
+            let mut total: i64 = 0;
+            for arg in args {
+                match eval_expr(arg, env)? {
+                    Value::Int(i) => {
+                        total = total.checked_add(i).ok_or_else(|| EvalError::Overflow {
+                            op: "sum".to_string(),
+                        })?
+                    }
+                    other => {
+                        return Err(EvalError::TypeError {
+                            expected: "int".to_string(),
+                            actual: format!("{other:?}"),
+                        })
+                    }
+                }
+            }
+            Ok(Value::Int(total))
+        }
+        OpKind::Sub => {
+            let (a, b) = eval_int_operands(args, env)?;
+            a.checked_sub(b)
+                .map(Value::Int)
+                .ok_or_else(|| EvalError::Overflow {
+                    op: "sub".to_string(),
+                })
+        }
+        OpKind::In | OpKind::NotIn => {
+            let needle = eval_expr(&args[0], env)?;
+            let mut found = false;
+            for arg in &args[1..] {
+                found |= eval_expr(arg, env)? == needle;
+            }
+            Ok(Value::Bool(found == (*op == OpKind::In)))
+        }
     }
 }
 
@@ -306,10 +375,15 @@ fn eval_int_compare(
     env: &ValueEnv,
     cmp: fn(i64, i64) -> bool,
 ) -> Result<Value, EvalError> {
+    let (a, b) = eval_int_operands(args, env)?;
+    Ok(Value::Bool(cmp(a, b)))
+}
+
+fn eval_int_operands(args: &[CompiledExpr], env: &ValueEnv) -> Result<(i64, i64), EvalError> {
     let left = eval_expr(&args[0], env)?;
     let right = eval_expr(&args[1], env)?;
     match (&left, &right) {
-        (Value::Int(a), Value::Int(b)) => Ok(Value::Bool(cmp(*a, *b))),
+        (Value::Int(a), Value::Int(b)) => Ok((*a, *b)),
         _ => Err(EvalError::TypeError {
             expected: "int".to_string(),
             actual: format!("{left:?}, {right:?}"),
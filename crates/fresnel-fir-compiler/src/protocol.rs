@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use fresnel_fir_ir::types::{Protocol, ProtocolNode};
 
-use crate::graph::{BranchEdge, GraphNode, NdaGraph, NodeId};
+use crate::graph::{BranchEdge, GraphNode, NdaGraph, NodeId, SourceSpan};
 use crate::predicate::{compile_expr, TypeContext};
 
 #[derive(Debug, thiserror::Error)]
@@ -14,14 +14,25 @@ pub enum ProtocolCompileError {
     GuardCompile(#[from] crate::predicate::CompileError),
 }
 
-/// Compile a protocol into an NDA graph.
+/// Compile the protocol `name` into an NDA graph.
+///
+/// Terminal and branch nodes carry the IR path of the spec element they
+/// came from; nodes inlined through a `ref` point into the referenced
+/// protocol.
 pub fn compile_protocol(
+    name: &str,
     protocol: &Protocol,
     ctx: &TypeContext,
     all_protocols: &HashMap<String, Protocol>,
 ) -> Result<NdaGraph, ProtocolCompileError> {
     let mut graph = NdaGraph::new();
-    let (body_entry, body_exit) = compile_node(&protocol.root, ctx, all_protocols, &mut graph)?;
+    let (body_entry, body_exit) = compile_node(
+        &protocol.root,
+        &SourceSpan::protocol_root(name),
+        ctx,
+        all_protocols,
+        &mut graph,
+    )?;
     graph.add_edge(graph.entry, body_entry);
     graph.add_edge(body_exit, graph.exit);
     Ok(graph)
@@ -30,6 +41,7 @@ pub fn compile_protocol(
 /// Compile a protocol node, returning (entry_node_id, exit_node_id) for the subgraph.
 fn compile_node(
     node: &ProtocolNode,
+    span: &SourceSpan,
     ctx: &TypeContext,
     all_protocols: &HashMap<String, Protocol>,
     graph: &mut NdaGraph,
@@ -39,6 +51,7 @@ fn compile_node(
             let id = graph.add_node(GraphNode::Terminal {
                 action: action.clone(),
                 guard: None,
+                source_span: Some(span.clone()),
             });
             Ok((id, id))
         }
@@ -53,8 +66,9 @@ fn compile_node(
             let mut first_entry = None;
             let mut prev_exit = None;
 
-            for child in children {
-                let (entry, exit) = compile_node(child, ctx, all_protocols, graph)?;
+            for (i, child) in children.iter().enumerate() {
+                let (entry, exit) =
+                    compile_node(child, &span.item("children", i), ctx, all_protocols, graph)?;
                 if first_entry.is_none() {
                     first_entry = Some(entry);
                 }
@@ -72,9 +86,10 @@ fn compile_node(
             let join = graph.add_node(GraphNode::Start); // placeholder join
 
             let mut alternatives = Vec::new();
-            for branch in branches {
+            for (i, branch) in branches.iter().enumerate() {
+                let body_span = span.item("branches", i).field("body");
                 let (body_entry, body_exit) =
-                    compile_node(&branch.body, ctx, all_protocols, graph)?;
+                    compile_node(&branch.body, &body_span, ctx, all_protocols, graph)?;
                 graph.add_edge(body_exit, join);
 
                 let guard = if let Some(guard_expr) = &branch.guard {
@@ -91,12 +106,16 @@ fn compile_node(
                 });
             }
 
-            let branch_id = graph.add_node(GraphNode::Branch { alternatives });
+            let branch_id = graph.add_node(GraphNode::Branch {
+                alternatives,
+                source_span: Some(span.clone()),
+            });
             Ok((branch_id, join))
         }
 
         ProtocolNode::Repeat { min, max, body } => {
-            let (body_entry, body_exit) = compile_node(body, ctx, all_protocols, graph)?;
+            let (body_entry, body_exit) =
+                compile_node(body, &span.field("body"), ctx, all_protocols, graph)?;
             let loop_exit = graph.add_node(GraphNode::LoopExit);
             let loop_entry = graph.add_node(GraphNode::LoopEntry {
                 body_start: body_entry,
@@ -117,7 +136,13 @@ fn compile_node(
                     name: protocol.clone(),
                 }
             })?;
-            compile_node(&referenced.root, ctx, all_protocols, graph)
+            compile_node(
+                &referenced.root,
+                &SourceSpan::protocol_root(protocol),
+                ctx,
+                all_protocols,
+                graph,
+            )
         }
     }
 }
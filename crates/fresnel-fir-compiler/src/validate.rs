@@ -2,6 +2,8 @@ use std::collections::HashSet;
 
 use fresnel_fir_ir::types::{FresnelFirIR, ProtocolNode};
 
+use crate::graph::{reachable_nodes, GraphNode, NdaGraph, NodeId};
+
 #[derive(Debug, thiserror::Error)]
 pub enum ValidationError {
     #[error("Dangling entity reference: refinement '{refinement}' references entity '{entity}' which doesn't exist")]
@@ -27,6 +29,142 @@ pub enum ValidationError {
     },
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum GraphError {
+    #[error("Dangling edge: {from} -> {to} references a node that doesn't exist")]
+    DanglingEdge { from: NodeId, to: NodeId },
+
+    #[error(
+        "Dangling branch target: branch '{branch_id}' targets node {target} which doesn't exist"
+    )]
+    DanglingBranchTarget { branch_id: String, target: NodeId },
+
+    #[error(
+        "Dangling loop body: loop at node {node} starts at node {body_start} which doesn't exist"
+    )]
+    DanglingLoopBody { node: NodeId, body_start: NodeId },
+
+    #[error("Dangling {endpoint} node: {id} doesn't exist")]
+    DanglingEndpoint { endpoint: &'static str, id: NodeId },
+
+    #[error("Exit node {exit} is not reachable from entry node {entry}")]
+    UnreachableExit { entry: NodeId, exit: NodeId },
+
+    #[error("Illegal cycle: edge {from} -> {to} closes a cycle that bypasses every loop entry")]
+    IllegalCycle { from: NodeId, to: NodeId },
+}
+
+/// Check that a graph only references real nodes, can run to its exit,
+/// and only cycles through `LoopEntry` nodes.
+pub fn validate_graph(graph: &NdaGraph) -> Result<(), Vec<GraphError>> {
+    let mut errors = Vec::new();
+    let exists = |id: NodeId| (id as usize) < graph.nodes.len();
+
+    for (endpoint, id) in [("entry", graph.entry), ("exit", graph.exit)] {
+        if !exists(id) {
+            errors.push(GraphError::DanglingEndpoint { endpoint, id });
+        }
+    }
+    for &(from, to) in &graph.edges {
+        if !exists(from) || !exists(to) {
+            errors.push(GraphError::DanglingEdge { from, to });
+        }
+    }
+    for (idx, node) in graph.nodes.iter().enumerate() {
+        match node {
+            GraphNode::Branch { alternatives, .. } => {
+                for alt in alternatives.iter().filter(|alt| !exists(alt.target)) {
+                    errors.push(GraphError::DanglingBranchTarget {
+                        branch_id: alt.id.clone(),
+                        target: alt.target,
+                    });
+                }
+            }
+            GraphNode::LoopEntry { body_start, .. } if !exists(*body_start) => {
+                errors.push(GraphError::DanglingLoopBody {
+                    node: idx as NodeId,
+                    body_start: *body_start,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if errors.is_empty() && !reachable_nodes(graph, graph.entry).contains(&graph.exit) {
+        errors.push(GraphError::UnreachableExit {
+            entry: graph.entry,
+            exit: graph.exit,
+        });
+    }
+    if errors.is_empty() {
+        if let Some((from, to)) = illegal_cycle_edge(graph) {
+            errors.push(GraphError::IllegalCycle { from, to });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The first edge found closing a cycle with no `LoopEntry` on it.
+///
+/// Repeat bodies loop back through their `LoopEntry`, which bounds the
+/// iterations; any other cycle would be followed until the pass runs out
+/// of steps. Searched depth-first over the graph with loop entries removed.
+fn illegal_cycle_edge(graph: &NdaGraph) -> Option<(NodeId, NodeId)> {
+    let is_loop_entry =
+        |id: NodeId| matches!(graph.nodes[id as usize], GraphNode::LoopEntry { .. });
+    let successors = |id: NodeId| -> Vec<NodeId> {
+        let mut next: Vec<NodeId> = graph
+            .edges
+            .iter()
+            .filter(|&&(from, _)| from == id)
+            .map(|&(_, to)| to)
+            .collect();
+        if let GraphNode::Branch { alternatives, .. } = &graph.nodes[id as usize] {
+            next.extend(alternatives.iter().map(|alt| alt.target));
+        }
+        next.retain(|&to| !is_loop_entry(to));
+        next
+    };
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Unvisited,
+        OnStack,
+        Done,
+    }
+    let mut marks = vec![Mark::Unvisited; graph.nodes.len()];
+    for root in 0..graph.nodes.len() as NodeId {
+        if marks[root as usize] != Mark::Unvisited || is_loop_entry(root) {
+            continue;
+        }
+        marks[root as usize] = Mark::OnStack;
+        let mut stack = vec![(root, successors(root), 0)];
+        while let Some((node, next, cursor)) = stack.last_mut() {
+            let node = *node;
+            let Some(&to) = next.get(*cursor) else {
+                marks[node as usize] = Mark::Done;
+                stack.pop();
+                continue;
+            };
+            *cursor += 1;
+            match marks[to as usize] {
+                Mark::Unvisited => {
+                    marks[to as usize] = Mark::OnStack;
+                    stack.push((to, successors(to), 0));
+                }
+                Mark::OnStack => return Some((node, to)),
+                Mark::Done => {}
+            }
+        }
+    }
+    None
+}
+
 pub fn validate_ir(ir: &FresnelFirIR) -> Result<(), Vec<ValidationError>> {
     let mut errors = Vec::new();
     validate_entity_refs(ir, &mut errors);
@@ -0,0 +1,66 @@
+use std::collections::HashSet;
+
+use fresnel_fir_compiler::graph::{reachable_actions, BranchEdge, GraphNode, NdaGraph};
+
+fn terminal(action: &str) -> GraphNode {
+    GraphNode::Terminal {
+        action: action.to_string(),
+        guard: None,
+        source_span: None,
+    }
+}
+
+#[test]
+fn test_reachable_actions_excludes_orphan_terminal() {
+    // Start -> Alt(create, read) -> End, plus an orphan "delete" terminal.
+    let mut g = NdaGraph::new();
+    let create = g.add_node(terminal("create"));
+    let read = g.add_node(terminal("read"));
+    let branch = g.add_node(GraphNode::Branch {
+        alternatives: vec![
+            BranchEdge {
+                id: "create_path".to_string(),
+                weight: 50.0,
+                target: create,
+                guard: None,
+            },
+            BranchEdge {
+                id: "read_path".to_string(),
+                weight: 50.0,
+                target: read,
+                guard: None,
+            },
+        ],
+        source_span: None,
+    });
+    let delete = g.add_node(terminal("delete"));
+    g.add_edge(g.entry, branch);
+    g.add_edge(create, g.exit);
+    g.add_edge(read, g.exit);
+    g.add_edge(delete, g.exit);
+
+    let reachable = reachable_actions(&g);
+    let expected: HashSet<String> = ["create", "read", "create_path", "read_path"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    assert_eq!(reachable, expected);
+    assert!(!reachable.contains("delete"));
+}
+
+#[test]
+fn test_reachable_actions_follows_loop_body() {
+    let mut g = NdaGraph::new();
+    let body = g.add_node(terminal("poll"));
+    let loop_exit = g.add_node(GraphNode::LoopExit);
+    let loop_entry = g.add_node(GraphNode::LoopEntry {
+        body_start: body,
+        min: 1,
+        max: 3,
+    });
+    g.add_edge(g.entry, loop_entry);
+    g.add_edge(loop_entry, loop_exit);
+    g.add_edge(loop_exit, g.exit);
+
+    assert_eq!(reachable_actions(&g), HashSet::from(["poll".to_string()]));
+}
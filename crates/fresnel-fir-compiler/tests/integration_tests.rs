@@ -1,10 +1,18 @@
+use fresnel_fir_compiler::compile::{compile_with_input_check, CompileError, CompiledIR};
+use fresnel_fir_compiler::graph::GraphNode;
 use fresnel_fir_ir::parse::parse_ir;
+use fresnel_fir_ir::types::FresnelFirIR;
+
+/// The compiler has no SAT solver; these tests skip the input space check.
+fn compile(ir: &FresnelFirIR) -> Result<CompiledIR, CompileError> {
+    compile_with_input_check(ir, &|_| Ok(None))
+}
 
 #[test]
 fn test_full_compilation_pipeline() {
     let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
     let ir = parse_ir(json).unwrap();
-    let result = fresnel_fir_compiler::compile(&ir);
+    let result = compile(&ir);
     assert!(
         result.is_ok(),
         "Compilation failed: {:?}",
@@ -32,7 +40,7 @@ fn test_compilation_rejects_invalid_ir() {
         "bindings": { "runtime": "wasm", "entry": "main.wasm", "actions": {}, "event_hooks": { "mode": "function_intercept", "observe": [], "capture": [] } }
     }"#;
     let ir = parse_ir(json).unwrap();
-    let result = fresnel_fir_compiler::compile(&ir);
+    let result = compile(&ir);
     assert!(result.is_err());
 }
 
@@ -40,7 +48,7 @@ fn test_compilation_rejects_invalid_ir() {
 fn test_compiled_ir_has_correct_graphs() {
     let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
     let ir = parse_ir(json).unwrap();
-    let compiled = fresnel_fir_compiler::compile(&ir).unwrap();
+    let compiled = compile(&ir).unwrap();
     // Should have graphs for both protocols: document_lifecycle and idle
     assert!(compiled.graphs.contains_key("document_lifecycle"));
     assert!(compiled.graphs.contains_key("idle"));
@@ -50,7 +58,7 @@ fn test_compiled_ir_has_correct_graphs() {
 fn test_compiled_ir_has_predicates_from_refinements() {
     let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
     let ir = parse_ir(json).unwrap();
-    let compiled = fresnel_fir_compiler::compile(&ir).unwrap();
+    let compiled = compile(&ir).unwrap();
     // Should have compiled predicates for refinements
     assert!(compiled.predicates.contains_key("AuthenticatedUser"));
     assert!(compiled.predicates.contains_key("OwnedDocument"));
@@ -61,9 +69,104 @@ fn test_compiled_ir_has_predicates_from_refinements() {
 fn test_compiled_ir_has_property_predicates() {
     let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
     let ir = parse_ir(json).unwrap();
-    let compiled = fresnel_fir_compiler::compile(&ir).unwrap();
+    let compiled = compile(&ir).unwrap();
     // Should have compiled predicates for invariant properties
     assert!(compiled
         .predicates
         .contains_key("property:ownership_isolation"));
 }
+
+/// Follow a `SourceSpan` path such as `protocols.p.root.children[1]`
+/// through the raw IR document.
+fn resolve_ir_path<'a>(doc: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    let mut value = doc;
+    for segment in path.split('.') {
+        let (field, index) = match segment.split_once('[') {
+            Some((field, rest)) => (field, Some(rest.strip_suffix(']')?.parse::<usize>().ok()?)),
+            None => (segment, None),
+        };
+        value = value.get(field)?;
+        if let Some(index) = index {
+            value = value.get(index)?;
+        }
+    }
+    Some(value)
+}
+
+#[test]
+fn test_terminal_spans_reference_ir_call_nodes() {
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let doc: serde_json::Value = serde_json::from_str(json).unwrap();
+    let compiled = compile(&parse_ir(json).unwrap()).unwrap();
+
+    let mut terminals = 0;
+    for graph in compiled.graphs.values() {
+        for node in &graph.nodes {
+            match node {
+                GraphNode::Terminal {
+                    action,
+                    source_span,
+                    ..
+                } => {
+                    let span = source_span.as_ref().expect("terminal without a span");
+                    let target = resolve_ir_path(&doc, &span.path)
+                        .unwrap_or_else(|| panic!("span {span} is not an IR path"));
+                    assert_eq!(target["type"], "call", "span {span}");
+                    assert_eq!(target["action"], action.as_str(), "span {span}");
+                    terminals += 1;
+                }
+                GraphNode::Branch { source_span, .. } => {
+                    let span = source_span.as_ref().expect("branch without a span");
+                    let target = resolve_ir_path(&doc, &span.path).unwrap();
+                    assert_eq!(target["type"], "alt", "span {span}");
+                }
+                _ => {}
+            }
+        }
+    }
+    assert!(terminals > 0);
+}
+
+fn contradictory_inputs_ir() -> fresnel_fir_ir::types::FresnelFirIR {
+    let json = r#"{
+        "entities": {},
+        "refinements": {},
+        "functions": {},
+        "protocols": {},
+        "effects": {},
+        "properties": {},
+        "generators": {},
+        "exploration": { "weights": { "scope": "per_alt_branch_and_model_state", "initial": "from_protocol", "decay": "per_epoch" }, "directives_allowed": [], "adaptation_signals": [], "strategy": { "initial": "pseudo_random_traversal", "fallback": "targeted_on_violation" }, "epoch_size": 100, "coverage_floor_threshold": 0.05, "concurrency": { "mode": "deterministic_interleaving", "threads": 4 } },
+        "inputs": {
+            "domains": { "role": { "type": "enum", "values": ["admin", "guest"] } },
+            "constraints": [
+                { "name": "admin_and_guest", "rule": ["and", ["eq", "role", "admin"], ["eq", "role", "guest"]] }
+            ],
+            "coverage": { "targets": [], "seed": 42, "reproducible": true }
+        },
+        "bindings": { "runtime": "wasm", "entry": "main.wasm", "actions": {}, "event_hooks": { "mode": "function_intercept", "observe": [], "capture": [] } }
+    }"#;
+    parse_ir(json).unwrap()
+}
+
+#[test]
+fn test_compile_warns_on_empty_input_space() {
+    use fresnel_fir_compiler::compile::CompileWarning;
+
+    let ir = contradictory_inputs_ir();
+    let unsat = |inputs: &fresnel_fir_ir::types::InputSpace| {
+        Ok(Some(
+            inputs.constraints.iter().map(|c| c.name.clone()).collect(),
+        ))
+    };
+    let compiled = compile_with_input_check(&ir, &unsat).unwrap();
+    assert_eq!(
+        compiled.warnings,
+        vec![CompileWarning::EmptyInputSpace {
+            unsat_core: vec!["admin_and_guest".to_string()]
+        }]
+    );
+
+    // Without a checker, nothing is reported.
+    assert!(compile(&ir).unwrap().warnings.is_empty());
+}
@@ -1,4 +1,6 @@
-use fresnel_fir_compiler::predicate::{compile_expr, eval_expr, TypeContext, Value, ValueEnv};
+use fresnel_fir_compiler::predicate::{
+    compile_expr, eval_expr, EvalError, TypeContext, Value, ValueEnv,
+};
 use fresnel_fir_ir::expr::Expr;
 use fresnel_fir_ir::parse::parse_ir;
 
@@ -177,6 +179,30 @@ fn test_compile_complex_nested() {
     assert_eq!(result, Value::Bool(true));
 }
 
+#[test]
+fn test_eval_arithmetic_overflow_is_error() {
+    let ctx = make_test_context();
+    let mut env = ValueEnv::new();
+    env.set_field("doc", "size", Value::Int(i64::MAX));
+    let eval = |json: serde_json::Value| {
+        let expr: Expr = serde_json::from_value(json).unwrap();
+        eval_expr(&compile_expr(&expr, &ctx).unwrap(), &env)
+    };
+
+    assert_eq!(
+        eval(serde_json::json!(["add", ["field", "doc", "size"], 0])).unwrap(),
+        Value::Int(i64::MAX)
+    );
+    assert!(matches!(
+        eval(serde_json::json!(["add", ["field", "doc", "size"], 1])),
+        Err(EvalError::Overflow { ref op }) if op == "sum"
+    ));
+    assert!(matches!(
+        eval(serde_json::json!(["sub", -2, ["field", "doc", "size"]])),
+        Err(EvalError::Overflow { ref op }) if op == "sub"
+    ));
+}
+
 #[test]
 fn test_compile_derived_fn_call() {
     let ctx = make_test_context();
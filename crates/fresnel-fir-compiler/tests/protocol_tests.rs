@@ -27,7 +27,7 @@ fn test_single_call_produces_start_terminal_end() {
     let proto = parse_protocol(serde_json::json!({
         "root": { "type": "call", "action": "read" }
     }));
-    let graph = compile_protocol(&proto, &ctx, &protocols).unwrap();
+    let graph = compile_protocol("test", &proto, &ctx, &protocols).unwrap();
     // Should have: Start -> Terminal(read) -> End
     assert!(graph.nodes.len() >= 3);
     assert!(matches!(
@@ -60,7 +60,7 @@ fn test_seq_chains_nodes() {
             ]
         }
     }));
-    let graph = compile_protocol(&proto, &ctx, &protocols).unwrap();
+    let graph = compile_protocol("test", &proto, &ctx, &protocols).unwrap();
     // Should have 2 terminal nodes in sequence
     let terminals: Vec<_> = graph
         .nodes
@@ -91,7 +91,7 @@ fn test_alt_produces_branch_node() {
             ]
         }
     }));
-    let graph = compile_protocol(&proto, &ctx, &protocols).unwrap();
+    let graph = compile_protocol("test", &proto, &ctx, &protocols).unwrap();
     // Should have a Branch node
     let branches: Vec<_> = graph
         .nodes
@@ -99,7 +99,7 @@ fn test_alt_produces_branch_node() {
         .filter(|n| matches!(n, GraphNode::Branch { .. }))
         .collect();
     assert_eq!(branches.len(), 1);
-    if let GraphNode::Branch { alternatives } = &branches[0] {
+    if let GraphNode::Branch { alternatives, .. } = &branches[0] {
         assert_eq!(alternatives.len(), 2);
         assert_eq!(alternatives[0].id, "a");
         assert!((alternatives[0].weight - 60.0).abs() < f64::EPSILON);
@@ -120,7 +120,7 @@ fn test_repeat_produces_loop_structure() {
             "body": { "type": "call", "action": "read" }
         }
     }));
-    let graph = compile_protocol(&proto, &ctx, &protocols).unwrap();
+    let graph = compile_protocol("test", &proto, &ctx, &protocols).unwrap();
     // Should have LoopEntry and LoopExit nodes
     let loop_entries: Vec<_> = graph
         .nodes
@@ -148,7 +148,7 @@ fn test_ref_inlines_referenced_protocol() {
     let proto = parse_protocol(serde_json::json!({
         "root": { "type": "ref", "protocol": "idle" }
     }));
-    let graph = compile_protocol(&proto, &ctx, &protocols).unwrap();
+    let graph = compile_protocol("test", &proto, &ctx, &protocols).unwrap();
     // Should have inlined the "read" terminal from the idle protocol
     let terminals: Vec<_> = graph
         .nodes
@@ -166,7 +166,7 @@ fn test_full_document_lifecycle_compiles() {
     let ctx = make_test_context();
     let protocols = get_protocols();
     let proto = protocols.get("document_lifecycle").unwrap();
-    let graph = compile_protocol(proto, &ctx, &protocols).unwrap();
+    let graph = compile_protocol("document_lifecycle", proto, &ctx, &protocols).unwrap();
     // Sanity checks on the compiled graph
     assert!(graph.nodes.len() > 5);
     assert!(!graph.edges.is_empty());
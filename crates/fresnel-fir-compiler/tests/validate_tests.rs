@@ -1,4 +1,5 @@
-use fresnel_fir_compiler::validate::{validate_ir, ValidationError};
+use fresnel_fir_compiler::graph::{GraphNode, NdaGraph};
+use fresnel_fir_compiler::validate::{validate_graph, validate_ir, GraphError, ValidationError};
 use fresnel_fir_ir::parse::parse_ir;
 
 /// Helper to build a minimal valid IR JSON with overrides for specific sections.
@@ -227,3 +228,90 @@ fn test_repeat_min_exceeds_max() {
         .iter()
         .any(|e| matches!(e, ValidationError::InvalidRepeatBounds { .. })));
 }
+
+fn linear_graph() -> NdaGraph {
+    let mut g = NdaGraph::new();
+    let a = g.add_node(GraphNode::Terminal {
+        action: "create".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    g.add_edge(g.entry, a);
+    g.add_edge(a, g.exit);
+    g
+}
+
+#[test]
+fn test_valid_graph_passes() {
+    assert!(validate_graph(&linear_graph()).is_ok());
+}
+
+#[test]
+fn test_graph_edge_out_of_range() {
+    let mut g = linear_graph();
+    g.add_edge(g.entry, 42);
+    let errors = validate_graph(&g).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, GraphError::DanglingEdge { to: 42, .. })));
+}
+
+#[test]
+fn test_graph_unreachable_exit() {
+    let mut g = NdaGraph::new();
+    let a = g.add_node(GraphNode::Terminal {
+        action: "create".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    g.add_edge(g.entry, a);
+    let errors = validate_graph(&g).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, GraphError::UnreachableExit { .. })));
+}
+
+#[test]
+fn test_terminal_back_edge_is_illegal_cycle() {
+    let mut g = NdaGraph::new();
+    let a = g.add_node(GraphNode::Terminal {
+        action: "create".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    let b = g.add_node(GraphNode::Terminal {
+        action: "read".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    g.add_edge(g.entry, a);
+    g.add_edge(a, b);
+    g.add_edge(b, g.exit);
+    g.add_edge(b, a);
+    let errors = validate_graph(&g).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, GraphError::IllegalCycle { from, to } if *from == b && *to == a)));
+}
+
+#[test]
+fn test_cycle_through_loop_entry_is_allowed() {
+    let mut g = NdaGraph::new();
+    let body = g.add_node(GraphNode::Terminal {
+        action: "read".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    let loop_exit = g.add_node(GraphNode::LoopExit);
+    let loop_entry = g.add_node(GraphNode::LoopEntry {
+        body_start: body,
+        min: 0,
+        max: 3,
+    });
+    g.add_edge(g.entry, loop_entry);
+    g.add_edge(loop_entry, body);
+    g.add_edge(body, loop_entry);
+    g.add_edge(loop_entry, loop_exit);
+    g.add_edge(loop_exit, g.exit);
+    assert!(validate_graph(&g).is_ok());
+}
@@ -5,6 +5,7 @@
 
 use std::time::Instant;
 
+use fresnel_fir_explore::traversal::weight_table::WeightTable;
 use serde::{Deserialize, Serialize};
 
 /// A timestamped data point in a coverage curve.
@@ -29,6 +30,10 @@ pub struct FindingRatePoint {
     pub findings_in_epoch: u32,
     /// Cumulative findings so far.
     pub cumulative_findings: u32,
+    /// `total_steps` when the epoch was recorded; the epoch spans the
+    /// steps since the previous point.
+    #[serde(default)]
+    pub end_step: u64,
 }
 
 /// Per-epoch adaptation statistics.
@@ -67,6 +72,9 @@ pub struct CampaignAnalytics {
     pub elapsed_secs: f64,
     /// Campaign state.
     pub state: CampaignPhase,
+    /// Heaviest branches at the last weight snapshot, heaviest first.
+    #[serde(default)]
+    pub top_weights: Vec<(String, f64)>,
 }
 
 /// Campaign lifecycle phase.
@@ -90,6 +98,7 @@ impl CampaignAnalytics {
             peak_coverage: 0.0,
             elapsed_secs: 0.0,
             state: CampaignPhase::Compiled,
+            top_weights: Vec::new(),
         }
     }
 
@@ -113,13 +122,14 @@ impl CampaignAnalytics {
         }
     }
 
-    /// Record finding rate for an epoch.
+    /// Record finding rate for an epoch ending at the current `total_steps`.
     pub fn record_finding_rate(&mut self, epoch: u64, findings_in_epoch: u32) {
         self.total_findings += findings_in_epoch;
         self.finding_rates.push(FindingRatePoint {
             epoch,
             findings_in_epoch,
             cumulative_findings: self.total_findings,
+            end_step: self.total_steps,
         });
     }
 
@@ -138,6 +148,11 @@ impl CampaignAnalytics {
         self.elapsed_secs = secs;
     }
 
+    /// Snapshot the `n` heaviest branches of `weights` in a model state.
+    pub fn record_top_weights(&mut self, weights: &WeightTable, n: usize, model_state_hash: u64) {
+        self.top_weights = weights.top_n(n, model_state_hash);
+    }
+
     /// Compute finding rate (findings per 1000 steps).
     pub fn finding_rate_per_k_steps(&self) -> f64 {
         if self.total_steps == 0 {
@@ -147,6 +162,20 @@ impl CampaignAnalytics {
         }
     }
 
+    /// Findings per 1000 steps in the most recent epoch, over the steps
+    /// between its `end_step` and the previous epoch's.
+    pub fn recent_finding_rate_per_k(&self) -> f64 {
+        let Some((last, earlier)) = self.finding_rates.split_last() else {
+            return 0.0;
+        };
+        let start = earlier.last().map_or(0, |prev| prev.end_step);
+        let epoch_steps = last.end_step.saturating_sub(start);
+        if epoch_steps == 0 {
+            return 0.0;
+        }
+        (last.findings_in_epoch as f64 / epoch_steps as f64) * 1000.0
+    }
+
     /// Compute coverage velocity (coverage % gained per 1000 steps).
     pub fn coverage_velocity(&self) -> f64 {
         if self.coverage_curve.len() < 2 || self.total_steps == 0 {
@@ -172,6 +201,51 @@ impl CampaignAnalytics {
         productive as f64 / self.finding_rates.len() as f64
     }
 
+    /// Coverage curve as `(step, coverage_fraction)` points, ordered by
+    /// step. When several measurements share a step, the latest wins.
+    pub fn coverage_series(&self) -> Vec<(u64, f64)> {
+        let mut series: Vec<(u64, f64)> = self
+            .coverage_curve
+            .iter()
+            .map(|point| (point.step, point.percent))
+            .collect();
+        series.sort_by_key(|&(step, _)| step);
+        // `dedup_by` keeps the first of a run; walk backwards so the
+        // most recently recorded measurement survives.
+        series.reverse();
+        series.dedup_by_key(|&mut (step, _)| step);
+        series.reverse();
+        series
+    }
+
+    /// Coverage series as CSV text with a `step,coverage` header.
+    pub fn coverage_series_csv(&self) -> String {
+        let mut csv = String::from("step,coverage\n");
+        for (step, coverage) in self.coverage_series() {
+            csv.push_str(&format!("{step},{coverage}\n"));
+        }
+        csv
+    }
+
+    /// Per-epoch statistics as CSV text, one row per epoch.
+    pub fn epoch_stats_csv(&self) -> String {
+        let mut csv = String::from(
+            "epoch,signals_processed,directives_emitted,coverage_delta_rate,guard_failures,timeouts\n",
+        );
+        for stats in &self.epoch_stats {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                stats.epoch,
+                stats.signals_processed,
+                stats.directives_emitted,
+                stats.coverage_delta_rate,
+                stats.guard_failures,
+                stats.timeouts,
+            ));
+        }
+        csv
+    }
+
     /// Generate a summary for MCP tool responses.
     pub fn summary(&self) -> AnalyticsSummary {
         AnalyticsSummary {
@@ -309,6 +383,45 @@ mod tests {
         assert_eq!(s.state, CampaignPhase::Complete);
     }
 
+    #[test]
+    fn test_coverage_series_is_monotonic_and_ends_at_peak() {
+        let mut a = CampaignAnalytics::new();
+        a.record_coverage(0, 0, 20);
+        a.record_coverage(500, 10, 20);
+        a.record_coverage(250, 4, 20); // Recorded out of order.
+        a.record_coverage(1000, 15, 20);
+        a.record_coverage(1000, 16, 20); // Re-measured at the same step.
+
+        let series = a.coverage_series();
+        assert_eq!(series, vec![(0, 0.0), (250, 0.2), (500, 0.5), (1000, 0.8)]);
+        assert!(series.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(series.last().unwrap().1, a.peak_coverage);
+
+        assert_eq!(
+            a.coverage_series_csv(),
+            "step,coverage\n0,0\n250,0.2\n500,0.5\n1000,0.8\n"
+        );
+    }
+
+    #[test]
+    fn test_epoch_stats_csv() {
+        let mut a = CampaignAnalytics::new();
+        a.record_epoch(EpochStats {
+            epoch: 1,
+            signals_processed: 12,
+            directives_emitted: 3,
+            coverage_delta_rate: 0.25,
+            guard_failures: 2,
+            timeouts: 0,
+        });
+
+        let csv = a.epoch_stats_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("epoch,"));
+        assert_eq!(lines[1], "1,12,3,0.25,2,0");
+    }
+
     #[test]
     fn test_zero_steps_rates() {
         let a = CampaignAnalytics::new();
@@ -316,4 +429,19 @@ mod tests {
         assert_eq!(a.coverage_velocity(), 0.0);
         assert_eq!(a.adaptation_effectiveness(), 0.0);
     }
+
+    #[test]
+    fn test_record_top_weights() {
+        let mut weights = WeightTable::new();
+        weights.set_default("read", 10.0);
+        weights.set_default("write", 50.0);
+        weights.set("delete", 0, 30.0);
+
+        let mut a = CampaignAnalytics::new();
+        a.record_top_weights(&weights, 2, 0);
+        assert_eq!(
+            a.top_weights,
+            vec![("write".to_string(), 50.0), ("delete".to_string(), 30.0)]
+        );
+    }
 }
@@ -1,12 +1,19 @@
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
-use fresnel_fir_compiler::compile;
 use fresnel_fir_compiler::compile::CompiledIR;
+use fresnel_fir_explore::solver::pipeline::{run_pipeline, FractureOrder, PipelineConfig};
+use fresnel_fir_explore::solver::search::compile_checked;
+use fresnel_fir_explore::solver::TestVector;
+use fresnel_fir_explore::traversal::runner::CampaignResult;
+use fresnel_fir_explore::traversal::signal::{normalize_details, Finding, Severity, SignalType};
 use fresnel_fir_ir::parse::parse_ir;
+use fresnel_fir_ir::types::{DomainType, FresnelFirIR, InputSpace};
 
 use crate::analytics::{CampaignAnalytics, CampaignPhase};
 use crate::limits::{EngineLimits, ResourceLimits, StopReason};
+use crate::sarif::{findings_to_sarif, rule_id};
 
 #[derive(Debug, thiserror::Error)]
 pub enum CampaignError {
@@ -24,6 +31,9 @@ pub enum CampaignError {
 
     #[error("Limit exceeded: {0}")]
     LimitExceeded(String),
+
+    #[error("Exploration error: {0}")]
+    Explore(#[from] fresnel_fir_explore::ExploreError),
 }
 
 /// Budget estimates computed from IR complexity.
@@ -31,13 +41,27 @@ pub enum CampaignError {
 pub struct Budget {
     pub min_iterations: u64,
     pub min_timeout_secs: u64,
+    /// Last epoch whose finding rate extended the budget; each epoch
+    /// extends it at most once.
+    pub extended_at_epoch: Option<u64>,
 }
 
+/// Findings per 1000 steps in the latest epoch above which
+/// `CampaignManager::recompute_budget` extends the iteration budget.
+pub const BUDGET_EXTENSION_FINDING_RATE: f64 = 1.0;
+
+/// Branches kept in a campaign's analytics weight snapshot.
+pub const TOP_WEIGHTS_REPORTED: usize = 10;
+
 /// State for a single campaign.
 #[derive(Debug, Clone)]
 pub struct CampaignState {
     pub id: String,
     pub compiled: CompiledIR,
+    /// Content hash of the source IR; keys cross-campaign memory.
+    pub ir_hash: String,
+    /// Input space the solver generates test vectors from.
+    pub inputs: InputSpace,
     pub budget: Budget,
     pub resource_limits: ResourceLimits,
     pub phase: CampaignPhase,
@@ -50,6 +74,9 @@ pub struct CampaignState {
     pub coverage_total: u32,
     /// Stop reason (if finished).
     pub stop_reason: Option<StopReason>,
+    /// Cancellation token shared with in-flight solver/traversal work.
+    /// Set by `abort`.
+    pub cancel: Arc<AtomicBool>,
 }
 
 /// A finding record for MCP tool responses.
@@ -61,6 +88,30 @@ pub struct FindingRecord {
     pub action: String,
     pub details: String,
     pub model_generation: u64,
+    pub severity: Severity,
+}
+
+/// One unique finding: the first record with its signature, and how often
+/// that signature was reported.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DedupedFinding {
+    #[serde(flatten)]
+    pub finding: FindingRecord,
+    pub occurrences: u64,
+    pub first_seqno: u64,
+    pub last_seqno: u64,
+}
+
+/// Root-cause signature of a finding: its type, action and details with
+/// numbers (addresses, fuel counts, generations) blanked out, so the same
+/// crash reached along different paths collapses to one entry.
+pub fn finding_signature(finding: &FindingRecord) -> String {
+    format!(
+        "{}|{}|{}",
+        finding.finding_type,
+        finding.action,
+        normalize_details(&finding.details)
+    )
 }
 
 /// Coverage target status.
@@ -71,12 +122,118 @@ pub struct CoverageTarget {
     pub hit_count: u64,
 }
 
+/// Test vectors generated by the solver for a campaign's input space.
+#[derive(Debug, Clone)]
+pub struct VectorSet {
+    /// Unique satisfying assignments.
+    pub vectors: Vec<TestVector>,
+    /// Subspaces aborted as UNSAT during fracturing.
+    pub unsat_pruned: usize,
+    /// Whether `vectors` is every satisfying assignment. False when
+    /// generation stopped at a requested limit.
+    pub exhaustive: bool,
+}
+
+impl VectorSet {
+    /// At most the first `limit` vectors (all of them when `None`).
+    fn truncated(mut self, limit: Option<usize>) -> Self {
+        if let Some(limit) = limit {
+            if limit < self.vectors.len() {
+                self.vectors.truncate(limit);
+                self.exhaustive = false;
+            }
+        }
+        self
+    }
+
+    /// Whether this set can answer a request for `limit` vectors.
+    fn covers(&self, limit: Option<usize>) -> bool {
+        self.exhaustive || limit.is_some_and(|limit| self.vectors.len() >= limit)
+    }
+}
+
+/// Everything the manager tracks for one campaign, guarded by one lock so
+/// the state, findings and analytics stay consistent with each other.
+#[derive(Debug)]
+struct CampaignEntry {
+    state: CampaignState,
+    /// The parsed source IR, for exports that point back into it.
+    ir: FresnelFirIR,
+    /// Traversal findings as reported, for SARIF export.
+    traversal_findings: Vec<Finding>,
+    /// Unique findings in order of first occurrence.
+    findings: Vec<DedupedFinding>,
+    /// Signature -> index into `findings`.
+    finding_index: HashMap<String, usize>,
+    coverage: Vec<CoverageTarget>,
+    analytics: CampaignAnalytics,
+    vectors: Option<VectorSet>,
+}
+
+impl CampaignEntry {
+    fn record_finding(&mut self, finding: FindingRecord) {
+        self.state.findings_count += 1;
+        let signature = finding_signature(&finding);
+        if let Some(&index) = self.finding_index.get(&signature) {
+            let existing = &mut self.findings[index];
+            existing.occurrences += 1;
+            existing.last_seqno = existing.last_seqno.max(finding.seqno);
+            return;
+        }
+        self.finding_index.insert(signature, self.findings.len());
+        self.findings.push(DedupedFinding {
+            first_seqno: finding.seqno,
+            last_seqno: finding.seqno,
+            occurrences: 1,
+            finding,
+        });
+    }
+
+    fn record_traversal_finding(&mut self, finding: Finding) {
+        // Sequence numbers start at 1, so `since_seqno = 0` sees the first.
+        let record = finding_record(&finding, u64::from(self.state.findings_count) + 1);
+        self.traversal_findings.push(finding);
+        self.record_finding(record);
+    }
+}
+
+/// The record summarising a traversal finding, typed by its SARIF rule id.
+fn finding_record(finding: &Finding, seqno: u64) -> FindingRecord {
+    let (action, details) = match &finding.signal.signal_type {
+        SignalType::Crash { action, message } => (action.clone(), message.clone()),
+        SignalType::PropertyViolation { property, details } => (property.clone(), details.clone()),
+        SignalType::Discrepancy {
+            action,
+            model_value,
+            observed_value,
+        } => (
+            action.clone(),
+            format!("model={model_value} observed={observed_value}"),
+        ),
+        SignalType::Timeout { action, .. } => (action.clone(), String::new()),
+        other => (String::new(), format!("{other:?}")),
+    };
+    FindingRecord {
+        id: finding.id,
+        seqno,
+        finding_type: rule_id(&finding.signal.signal_type).to_string(),
+        action,
+        details,
+        model_generation: finding.model_generation,
+        severity: finding.severity,
+    }
+}
+
+type SharedEntry = Arc<RwLock<CampaignEntry>>;
+
 /// Manages all active campaigns.
+///
+/// Internally synchronized: each campaign sits behind its own `RwLock`, so
+/// reads of a campaign never block each other, writes to one campaign are
+/// serialized, and campaigns never contend with one another. The map lock
+/// is only held to look up, insert or remove an entry.
 pub struct CampaignManager {
-    campaigns: Mutex<HashMap<String, CampaignState>>,
-    findings: Mutex<HashMap<String, Vec<FindingRecord>>>,
-    coverage: Mutex<HashMap<String, Vec<CoverageTarget>>>,
-    analytics: Mutex<HashMap<String, CampaignAnalytics>>,
+    campaigns: RwLock<HashMap<String, SharedEntry>>,
     next_id: Mutex<u64>,
     engine_limits: EngineLimits,
 }
@@ -84,10 +241,7 @@ pub struct CampaignManager {
 impl CampaignManager {
     pub fn new() -> Self {
         Self {
-            campaigns: Mutex::new(HashMap::new()),
-            findings: Mutex::new(HashMap::new()),
-            coverage: Mutex::new(HashMap::new()),
-            analytics: Mutex::new(HashMap::new()),
+            campaigns: RwLock::new(HashMap::new()),
             next_id: Mutex::new(1),
             engine_limits: EngineLimits::default(),
         }
@@ -96,13 +250,7 @@ impl CampaignManager {
     /// Compile IR JSON and create a new campaign.
     pub fn compile(&self, ir_json: &str) -> Result<String, CampaignError> {
         // Check engine limits.
-        let campaign_count = self.campaigns.lock().unwrap().len();
-        if campaign_count as u32 >= self.engine_limits.max_concurrent_campaigns {
-            return Err(CampaignError::LimitExceeded(format!(
-                "Too many concurrent campaigns ({}/{})",
-                campaign_count, self.engine_limits.max_concurrent_campaigns
-            )));
-        }
+        self.check_campaign_limit(self.active_campaign_count())?;
         if ir_json.len() as u64 > self.engine_limits.max_ir_json_bytes {
             return Err(CampaignError::LimitExceeded(format!(
                 "IR JSON too large ({} bytes, max {})",
@@ -112,7 +260,7 @@ impl CampaignManager {
         }
 
         let ir = parse_ir(ir_json)?;
-        let compiled = compile(&ir)?;
+        let compiled = compile_checked(&ir)?;
         let budget = estimate_budget(&ir);
 
         let campaign_id = {
@@ -125,6 +273,8 @@ impl CampaignManager {
         let state = CampaignState {
             id: campaign_id.clone(),
             compiled,
+            ir_hash: ir.content_hash(),
+            inputs: ir.inputs.clone(),
             budget,
             resource_limits: ResourceLimits::default(),
             phase: CampaignPhase::Compiled,
@@ -133,74 +283,110 @@ impl CampaignManager {
             coverage_hit: 0,
             coverage_total: 0,
             stop_reason: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+        };
+        let entry = CampaignEntry {
+            state,
+            ir,
+            traversal_findings: Vec::new(),
+            findings: Vec::new(),
+            finding_index: HashMap::new(),
+            coverage: Vec::new(),
+            analytics: CampaignAnalytics::new(),
+            vectors: None,
         };
 
-        self.campaigns
-            .lock()
-            .unwrap()
-            .insert(campaign_id.clone(), state);
-        self.findings
-            .lock()
-            .unwrap()
-            .insert(campaign_id.clone(), Vec::new());
-        self.coverage
-            .lock()
-            .unwrap()
-            .insert(campaign_id.clone(), Vec::new());
-        self.analytics
-            .lock()
-            .unwrap()
-            .insert(campaign_id.clone(), CampaignAnalytics::new());
+        // Compilation ran unlocked, so re-check the limit before inserting.
+        let mut campaigns = self.campaigns.write().unwrap();
+        self.check_campaign_limit(campaigns.len())?;
+        campaigns.insert(campaign_id.clone(), Arc::new(RwLock::new(entry)));
 
         Ok(campaign_id)
     }
 
+    fn check_campaign_limit(&self, campaign_count: usize) -> Result<(), CampaignError> {
+        if campaign_count as u32 >= self.engine_limits.max_concurrent_campaigns {
+            return Err(CampaignError::LimitExceeded(format!(
+                "Too many concurrent campaigns ({}/{})",
+                campaign_count, self.engine_limits.max_concurrent_campaigns
+            )));
+        }
+        Ok(())
+    }
+
+    /// The lock guarding one campaign, looked up without holding the map
+    /// lock any longer than the lookup.
+    fn entry(&self, id: &str) -> Option<SharedEntry> {
+        self.campaigns.read().unwrap().get(id).cloned()
+    }
+
+    /// Read from one campaign under its shared lock.
+    fn read<T>(&self, id: &str, f: impl FnOnce(&CampaignEntry) -> T) -> Option<T> {
+        let entry = self.entry(id)?;
+        let guard = entry.read().unwrap();
+        Some(f(&guard))
+    }
+
+    /// Modify one campaign under its exclusive lock.
+    fn write<T>(&self, id: &str, f: impl FnOnce(&mut CampaignEntry) -> T) -> Option<T> {
+        let entry = self.entry(id)?;
+        let mut guard = entry.write().unwrap();
+        Some(f(&mut guard))
+    }
+
     /// Get a clone of a campaign's state.
     pub fn get_campaign(&self, id: &str) -> Option<CampaignState> {
-        self.campaigns.lock().unwrap().get(id).cloned()
+        self.read(id, |entry| entry.state.clone())
     }
 
     /// Number of active campaigns.
     pub fn active_campaign_count(&self) -> usize {
-        self.campaigns.lock().unwrap().len()
+        self.campaigns.read().unwrap().len()
     }
 
     /// Transition a campaign to a new phase.
     pub fn set_phase(&self, id: &str, phase: CampaignPhase) -> Result<(), CampaignError> {
-        {
-            let mut campaigns = self.campaigns.lock().unwrap();
-            let state = campaigns
-                .get_mut(id)
-                .ok_or_else(|| CampaignError::NotFound(id.to_string()))?;
-            state.phase = phase.clone();
-        }
-        // Lock released before acquiring analytics lock to prevent deadlock.
-        if let Some(analytics) = self.analytics.lock().unwrap().get_mut(id) {
-            analytics.state = phase;
-        }
-        Ok(())
+        self.write(id, |entry| {
+            entry.state.phase = phase.clone();
+            entry.analytics.state = phase;
+        })
+        .ok_or_else(|| CampaignError::NotFound(id.to_string()))
     }
 
-    /// Record a finding for a campaign.
+    /// Record a finding for a campaign. A finding whose signature was
+    /// seen before only bumps that entry's occurrence count.
     pub fn add_finding(&self, campaign_id: &str, finding: FindingRecord) {
-        if let Some(findings) = self.findings.lock().unwrap().get_mut(campaign_id) {
-            findings.push(finding);
-        }
-        if let Some(state) = self.campaigns.lock().unwrap().get_mut(campaign_id) {
-            state.findings_count += 1;
-        }
+        self.write(campaign_id, |entry| entry.record_finding(finding));
     }
 
-    /// Get findings for a campaign, optionally since a sequence number.
-    pub fn get_findings(&self, campaign_id: &str, since_seqno: Option<u64>) -> Vec<FindingRecord> {
-        let findings = self.findings.lock().unwrap();
-        match findings.get(campaign_id) {
-            Some(list) => match since_seqno {
-                Some(seqno) => list.iter().filter(|f| f.seqno > seqno).cloned().collect(),
-                None => list.clone(),
-            },
-            None => Vec::new(),
-        }
+    /// Record a traversal finding: kept as reported for `export_sarif`,
+    /// and summarised into the unique findings like `add_finding`, with
+    /// the campaign's finding count including it as its sequence number.
+    pub fn add_traversal_finding(&self, campaign_id: &str, finding: Finding) {
+        self.write(campaign_id, |entry| entry.record_traversal_finding(finding));
+    }
+
+    /// A campaign's traversal findings as a SARIF log.
+    pub fn export_sarif(&self, campaign_id: &str) -> Result<serde_json::Value, CampaignError> {
+        self.read(campaign_id, |entry| {
+            findings_to_sarif(&entry.traversal_findings, &entry.ir)
+        })
+        .ok_or_else(|| CampaignError::NotFound(campaign_id.to_string()))
+    }
+
+    /// Get a campaign's unique findings, optionally only those first seen
+    /// after a sequence number.
+    pub fn get_findings(&self, campaign_id: &str, since_seqno: Option<u64>) -> Vec<DedupedFinding> {
+        self.read(campaign_id, |entry| match since_seqno {
+            Some(seqno) => entry
+                .findings
+                .iter()
+                .filter(|f| f.first_seqno > seqno)
+                .cloned()
+                .collect(),
+            None => entry.findings.clone(),
+        })
+        .unwrap_or_default()
     }
 
     /// Update coverage data for a campaign.
@@ -208,56 +394,172 @@ impl CampaignManager {
         let hit = targets.iter().filter(|t| t.status == "hit").count() as u32;
         let total = targets.len() as u32;
 
-        if let Some(state) = self.campaigns.lock().unwrap().get_mut(campaign_id) {
-            state.coverage_hit = hit;
-            state.coverage_total = total;
-        }
-        if let Some(cov) = self.coverage.lock().unwrap().get_mut(campaign_id) {
-            *cov = targets;
-        }
+        self.write(campaign_id, |entry| {
+            entry.state.coverage_hit = hit;
+            entry.state.coverage_total = total;
+            entry.coverage = targets;
+        });
     }
 
     /// Get coverage data for a campaign.
     pub fn get_coverage(&self, campaign_id: &str) -> Vec<CoverageTarget> {
-        self.coverage
-            .lock()
-            .unwrap()
-            .get(campaign_id)
-            .cloned()
+        self.read(campaign_id, |entry| entry.coverage.clone())
             .unwrap_or_default()
     }
 
     /// Abort a campaign.
     pub fn abort(&self, campaign_id: &str) -> Result<CampaignState, CampaignError> {
-        let result = {
-            let mut campaigns = self.campaigns.lock().unwrap();
-            let state = campaigns
-                .get_mut(campaign_id)
-                .ok_or_else(|| CampaignError::NotFound(campaign_id.to_string()))?;
-
-            state.phase = CampaignPhase::Aborted;
-            state.stop_reason = Some(StopReason::UserAborted);
-            state.clone()
-        };
-        // Lock released before acquiring analytics lock to prevent deadlock.
-        if let Some(analytics) = self.analytics.lock().unwrap().get_mut(campaign_id) {
-            analytics.state = CampaignPhase::Aborted;
-        }
+        self.write(campaign_id, |entry| {
+            entry.state.phase = CampaignPhase::Aborted;
+            entry.state.stop_reason = Some(StopReason::UserAborted);
+            entry.state.cancel.store(true, Ordering::Relaxed);
+            entry.analytics.state = CampaignPhase::Aborted;
+            entry.state.clone()
+        })
+        .ok_or_else(|| CampaignError::NotFound(campaign_id.to_string()))
+    }
+
+    /// Record why a campaign's run ended, e.g. from a traversal
+    /// `CampaignResult::stop_reason`.
+    pub fn set_stop_reason(
+        &self,
+        campaign_id: &str,
+        reason: impl Into<StopReason>,
+    ) -> Result<(), CampaignError> {
+        let reason = reason.into();
+        self.write(campaign_id, |entry| entry.state.stop_reason = Some(reason))
+            .ok_or_else(|| CampaignError::NotFound(campaign_id.to_string()))
+    }
 
-        Ok(result)
+    /// Fold a finished traversal run into a campaign: its findings (see
+    /// `add_traversal_finding`), its steps and finding rate as one
+    /// analytics epoch, the heaviest branches of its final weights
+    /// (state-independent view), and why it stopped (see
+    /// `set_stop_reason`). The budget is then extended as in
+    /// `recompute_budget`.
+    pub fn record_run(
+        &self,
+        campaign_id: &str,
+        result: &CampaignResult,
+    ) -> Result<(), CampaignError> {
+        self.write(campaign_id, |entry| {
+            for finding in &result.findings {
+                entry.record_traversal_finding(finding.clone());
+            }
+            entry.state.steps_executed += result.total_actions;
+            entry.analytics.set_total_steps(entry.state.steps_executed);
+            let epoch = entry.analytics.finding_rates.len() as u64;
+            entry
+                .analytics
+                .record_finding_rate(epoch, result.findings.len() as u32);
+            entry
+                .analytics
+                .record_top_weights(&result.weight_table, TOP_WEIGHTS_REPORTED, 0);
+            entry.state.stop_reason = Some(result.stop_reason.into());
+            entry.state.budget = extended_budget(&entry.state.budget, &entry.analytics);
+        })
+        .ok_or_else(|| CampaignError::NotFound(campaign_id.to_string()))
     }
 
     /// Get analytics for a campaign.
     pub fn get_analytics(&self, campaign_id: &str) -> Option<CampaignAnalytics> {
-        self.analytics.lock().unwrap().get(campaign_id).cloned()
+        self.read(campaign_id, |entry| entry.analytics.clone())
+    }
+
+    /// Replace the analytics snapshot for a campaign.
+    pub fn update_analytics(&self, campaign_id: &str, analytics: CampaignAnalytics) {
+        self.write(campaign_id, |entry| entry.analytics = analytics);
+    }
+
+    /// Extend a campaign's iteration budget while findings keep arriving.
+    ///
+    /// When the latest epoch found more than `BUDGET_EXTENSION_FINDING_RATE`
+    /// findings per 1000 steps, `min_iterations` grows by half of the larger
+    /// of the budget and the steps run so far, or by a quarter once coverage
+    /// has stopped growing. The result depends only on the analytics
+    /// snapshot and the current budget. `record_run` applies this after
+    /// every run.
+    pub fn recompute_budget(&self, campaign_id: &str) -> Result<Budget, CampaignError> {
+        self.write(campaign_id, |entry| {
+            entry.state.budget = extended_budget(&entry.state.budget, &entry.analytics);
+            entry.state.budget.clone()
+        })
+        .ok_or_else(|| CampaignError::NotFound(campaign_id.to_string()))
+    }
+
+    /// Get up to `limit` solver-generated test vectors for a campaign
+    /// (every vector when `None`).
+    ///
+    /// Runs the fracture/solve/abort pipeline on first use, fracturing by
+    /// every enum and bool domain in name order, and caches the result.
+    /// A limit caps each leaf's search at `limit` vectors, so a limited
+    /// request never enumerates the whole space; a later request for more
+    /// reruns the pipeline. The campaign is not locked while it runs.
+    pub fn get_vectors(
+        &self,
+        campaign_id: &str,
+        limit: Option<usize>,
+    ) -> Result<VectorSet, CampaignError> {
+        let (cached, inputs, cancel) = self
+            .read(campaign_id, |entry| {
+                (
+                    entry.vectors.clone(),
+                    entry.state.inputs.clone(),
+                    entry.state.cancel.clone(),
+                )
+            })
+            .ok_or_else(|| CampaignError::NotFound(campaign_id.to_string()))?;
+        if let Some(cached) = cached.filter(|cached| cached.covers(limit)) {
+            return Ok(cached.truncated(limit));
+        }
+
+        let mut fracture_variables: Vec<String> = inputs
+            .domains
+            .iter()
+            .filter(|(_, d)| !matches!(d.domain_type, DomainType::Int { .. }))
+            .map(|(name, _)| name.clone())
+            .collect();
+        fracture_variables.sort();
+
+        let config = PipelineConfig {
+            seed: inputs.coverage.seed,
+            max_vectors_per_leaf: limit.unwrap_or(0),
+            fracture_variables: FractureOrder::Manual(fracture_variables),
+            cancel: Some(cancel),
+            solver_timeout: None,
+        };
+        let result = run_pipeline(&inputs, &config)?;
+        if result.cancelled {
+            return Err(CampaignError::InvalidState(format!(
+                "vector generation cancelled: campaign {} was aborted",
+                campaign_id
+            )));
+        }
+        // Leaf searches find distinct vectors, so if no leaf reached the
+        // cap, fewer than `limit` vectors came back in total.
+        let set = VectorSet {
+            exhaustive: limit.is_none_or(|limit| result.vectors.len() < limit),
+            vectors: result.vectors,
+            unsat_pruned: result.unsat_count,
+        };
+
+        // A concurrent caller may have cached its own run in the meantime;
+        // keep the first that covers this request so every caller sees the
+        // same set.
+        Ok(self
+            .write(campaign_id, |entry| {
+                match entry.vectors.as_ref().filter(|cached| cached.covers(limit)) {
+                    Some(cached) => cached.clone(),
+                    None => entry.vectors.insert(set.clone()).clone(),
+                }
+            })
+            .unwrap_or(set)
+            .truncated(limit))
     }
 
     /// Remove a completed/aborted campaign.
     pub fn remove_campaign(&self, campaign_id: &str) {
-        self.campaigns.lock().unwrap().remove(campaign_id);
-        self.findings.lock().unwrap().remove(campaign_id);
-        self.coverage.lock().unwrap().remove(campaign_id);
-        self.analytics.lock().unwrap().remove(campaign_id);
+        self.campaigns.write().unwrap().remove(campaign_id);
     }
 }
 
@@ -277,11 +579,11 @@ fn estimate_budget(ir: &fresnel_fir_ir::types::FresnelFirIR) -> Budget {
         .domains
         .values()
         .map(|d| match &d.domain_type {
-            fresnel_fir_ir::types::DomainType::Bool => 2u64,
-            fresnel_fir_ir::types::DomainType::Enum { values } => values.len().max(1) as u64,
-            fresnel_fir_ir::types::DomainType::Int { min, max } => {
+            DomainType::Bool => 2u64,
+            DomainType::Enum { values, .. } => values.len().max(1) as u64,
+            DomainType::Int { min, max, stride } => {
                 if max >= min {
-                    ((max - min) as u64).saturating_add(1)
+                    ((max - min) as u64 / (*stride).max(1) as u64).saturating_add(1)
                 } else {
                     1 // Invalid range, treat as single value
                 }
@@ -307,5 +609,33 @@ fn estimate_budget(ir: &fresnel_fir_ir::types::FresnelFirIR) -> Budget {
     Budget {
         min_iterations,
         min_timeout_secs,
+        extended_at_epoch: None,
+    }
+}
+
+/// `budget` extended for the latest epoch in `analytics`, if that epoch's
+/// finding rate calls for it and it has not extended the budget already.
+fn extended_budget(budget: &Budget, analytics: &CampaignAnalytics) -> Budget {
+    let Some(epoch) = analytics.finding_rates.last().map(|r| r.epoch) else {
+        return budget.clone();
+    };
+    if budget.extended_at_epoch >= Some(epoch)
+        || analytics.recent_finding_rate_per_k() <= BUDGET_EXTENSION_FINDING_RATE
+    {
+        return budget.clone();
+    }
+
+    let base = budget.min_iterations.max(analytics.total_steps);
+    let extension = if analytics.coverage_velocity() > 0.0 {
+        base / 2
+    } else {
+        base / 4
+    };
+    let min_iterations = base.saturating_add(extension);
+    Budget {
+        min_iterations,
+        // Same rate as `estimate_budget`: 1 second per 100 iterations.
+        min_timeout_secs: budget.min_timeout_secs.max(min_iterations / 100),
+        extended_at_epoch: Some(epoch),
     }
 }
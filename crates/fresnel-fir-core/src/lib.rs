@@ -3,3 +3,4 @@ pub mod campaign;
 pub mod limits;
 pub mod mcp;
 pub mod memory;
+pub mod sarif;
@@ -4,6 +4,7 @@
 //! concurrent campaigns. When limits are hit, the engine produces
 //! partial results rather than failing.
 
+use fresnel_fir_explore::traversal::runner::StopReason as RunStopReason;
 use serde::{Deserialize, Serialize};
 
 /// Resource limits for a single campaign.
@@ -59,6 +60,7 @@ impl Default for EngineLimits {
 
 /// Reason a campaign was stopped.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum StopReason {
     /// All passes completed normally.
     Complete,
@@ -72,6 +74,41 @@ pub enum StopReason {
     UserAborted,
     /// Memory limit exceeded (advisory).
     MemoryLimitExceeded,
+    /// A finding met the campaign's stop-on-finding severity.
+    StopOnFinding,
+    /// Paused at a stop condition; the campaign can be resumed.
+    Paused,
+    /// Coverage and findings stopped growing.
+    Saturated,
+}
+
+impl StopReason {
+    /// Stable name reported over MCP, matching the serde form.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StopReason::Complete => "complete",
+            StopReason::WallTimeExceeded => "wall_time_exceeded",
+            StopReason::IterationLimitExceeded => "iteration_limit_exceeded",
+            StopReason::FindingLimitExceeded => "finding_limit_exceeded",
+            StopReason::UserAborted => "user_aborted",
+            StopReason::MemoryLimitExceeded => "memory_limit_exceeded",
+            StopReason::StopOnFinding => "stop_on_finding",
+            StopReason::Paused => "paused",
+            StopReason::Saturated => "saturated",
+        }
+    }
+}
+
+impl From<RunStopReason> for StopReason {
+    fn from(reason: RunStopReason) -> Self {
+        match reason {
+            RunStopReason::MaxPasses => StopReason::Complete,
+            RunStopReason::Cancelled => StopReason::UserAborted,
+            RunStopReason::Paused => StopReason::Paused,
+            RunStopReason::StopOnFinding => StopReason::StopOnFinding,
+            RunStopReason::Saturated => StopReason::Saturated,
+        }
+    }
 }
 
 /// Check resource usage against limits.
@@ -1,7 +1,11 @@
 use serde_json::{json, Value};
 
+use fresnel_fir_explore::solver::coverage::validate_targets;
+use fresnel_fir_explore::solver::DomainValue;
+
 use crate::analytics::CampaignPhase;
 use crate::campaign::CampaignManager;
+use crate::limits::StopReason;
 
 /// Server state shared across MCP request handling.
 pub struct McpState {
@@ -110,7 +114,7 @@ fn handle_tools_list() -> Value {
             },
             {
                 "name": "fresnel_fir_findings",
-                "description": "Get findings from a campaign, optionally since a sequence number for incremental polling",
+                "description": "Get unique findings from a campaign, duplicates collapsed with an occurrence count, optionally since a sequence number for incremental polling",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
@@ -120,7 +124,7 @@ fn handle_tools_list() -> Value {
                         },
                         "since_seqno": {
                             "type": "integer",
-                            "description": "Only return findings after this sequence number (for incremental polling)"
+                            "description": "Only return findings first seen after this sequence number (for incremental polling)"
                         }
                     },
                     "required": ["campaign_id"]
@@ -157,6 +161,48 @@ fn handle_tools_list() -> Value {
             {
                 "name": "fresnel_fir_analytics",
                 "description": "Get detailed analytics for a campaign including coverage curves, finding rates, and adaptation effectiveness",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "campaign_id": {
+                            "type": "string",
+                            "description": "Campaign ID"
+                        },
+                        "format": {
+                            "type": "string",
+                            "enum": ["json", "csv"],
+                            "description": "Encoding of the coverage series and epoch stats (default: json)"
+                        }
+                    },
+                    "required": ["campaign_id"]
+                }
+            },
+            {
+                "name": "fresnel_fir_vectors",
+                "description": "Get the test vectors generated by the solver for a campaign's input space",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "campaign_id": {
+                            "type": "string",
+                            "description": "Campaign ID"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of vectors to generate and return (optional; default every vector)"
+                        },
+                        "domain_filter": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Only include assignments for these domains (optional)"
+                        }
+                    },
+                    "required": ["campaign_id"]
+                }
+            },
+            {
+                "name": "fresnel_fir_export_sarif",
+                "description": "Export a campaign's findings as a SARIF 2.1.0 log for CI systems and code scanners",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
@@ -185,6 +231,8 @@ fn handle_tools_call(params: &Value, state: &McpState) -> Value {
         "fresnel_fir_coverage" => tool_fresnel_fir_coverage(&arguments, state),
         "fresnel_fir_abort" => tool_fresnel_fir_abort(&arguments, state),
         "fresnel_fir_analytics" => tool_fresnel_fir_analytics(&arguments, state),
+        "fresnel_fir_vectors" => tool_fresnel_fir_vectors(&arguments, state),
+        "fresnel_fir_export_sarif" => tool_fresnel_fir_export_sarif(&arguments, state),
         _ => tool_error(&format!("Unknown tool: {tool_name}")),
     }
 }
@@ -196,6 +244,7 @@ fn tool_fresnel_fir_compile(args: &Value, state: &McpState) -> Value {
         Ok(campaign_id) => {
             let campaign = state.manager.get_campaign(&campaign_id);
             let budget = campaign
+                .as_ref()
                 .map(|c| {
                     json!({
                         "min_iterations": c.budget.min_iterations,
@@ -203,11 +252,26 @@ fn tool_fresnel_fir_compile(args: &Value, state: &McpState) -> Value {
                     })
                 })
                 .unwrap_or(json!(null));
+            let ir_hash = campaign
+                .as_ref()
+                .map(|c| json!(c.ir_hash))
+                .unwrap_or(json!(null));
+            let warnings: Vec<String> = campaign
+                .map(|c| {
+                    validate_targets(&c.inputs)
+                        .iter()
+                        .map(ToString::to_string)
+                        .chain(c.compiled.warnings.iter().map(ToString::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
 
             tool_success(json!({
                 "result": "pass",
                 "campaign_id": campaign_id,
+                "ir_hash": ir_hash,
                 "budget": budget,
+                "warnings": warnings,
             }))
         }
         Err(e) => tool_success(json!({
@@ -296,13 +360,17 @@ fn tool_fresnel_fir_fuzz_status(args: &Value, state: &McpState) -> Value {
             "iterations_done": campaign.steps_executed,
             "iterations_total": campaign.budget.min_iterations,
         },
+        "budget": {
+            "min_iterations": campaign.budget.min_iterations,
+            "min_timeout_secs": campaign.budget.min_timeout_secs,
+        },
         "coverage": {
             "targets_hit": campaign.coverage_hit,
             "targets_total": campaign.coverage_total,
             "percent": coverage_percent,
         },
         "findings_count": campaign.findings_count,
-        "stop_reason": campaign.stop_reason.as_ref().map(|r| format!("{:?}", r)),
+        "stop_reason": campaign.stop_reason.as_ref().map(StopReason::as_str),
     }))
 }
 
@@ -319,7 +387,12 @@ fn tool_fresnel_fir_findings(args: &Value, state: &McpState) -> Value {
     let since_seqno = args.get("since_seqno").and_then(|v| v.as_u64());
     let findings = state.manager.get_findings(campaign_id, since_seqno);
 
-    let next_seqno = findings.last().map(|f| f.seqno + 1).unwrap_or(0);
+    // Polling again with `since_seqno = next_seqno` returns only findings
+    // first seen after these.
+    let next_seqno = findings
+        .last()
+        .map(|f| f.first_seqno)
+        .unwrap_or(since_seqno.unwrap_or(0));
 
     tool_success(json!({
         "findings": findings,
@@ -384,9 +457,26 @@ fn tool_fresnel_fir_analytics(args: &Value, state: &McpState) -> Value {
         None => return tool_error("Missing required parameter: campaign_id"),
     };
 
+    let csv = match args.get("format").and_then(|v| v.as_str()) {
+        None | Some("json") => false,
+        Some("csv") => true,
+        Some(other) => return tool_error(&format!("Unsupported format: {other}")),
+    };
+
     match state.manager.get_analytics(campaign_id) {
         Some(analytics) => {
             let summary = analytics.summary();
+            let (coverage_series, epoch_stats) = if csv {
+                (
+                    json!(analytics.coverage_series_csv()),
+                    json!(analytics.epoch_stats_csv()),
+                )
+            } else {
+                (
+                    json!(analytics.coverage_series()),
+                    json!(analytics.epoch_stats),
+                )
+            };
             tool_success(json!({
                 "campaign_id": campaign_id,
                 "summary": {
@@ -402,12 +492,81 @@ fn tool_fresnel_fir_analytics(args: &Value, state: &McpState) -> Value {
                 },
                 "coverage_curve_points": analytics.coverage_curve.len(),
                 "epoch_stats_count": analytics.epoch_stats.len(),
+                "coverage_series": coverage_series,
+                "epoch_stats": epoch_stats,
+                "top_weights": analytics.top_weights,
             }))
         }
         None => tool_error(&format!("Campaign not found: {campaign_id}")),
     }
 }
 
+fn tool_fresnel_fir_vectors(args: &Value, state: &McpState) -> Value {
+    let campaign_id = match args.get("campaign_id").and_then(|v| v.as_str()) {
+        Some(id) => id,
+        None => return tool_error("Missing required parameter: campaign_id"),
+    };
+
+    let limit = args
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .map(|l| l as usize);
+    let set = match state.manager.get_vectors(campaign_id, limit) {
+        Ok(set) => set,
+        Err(e) => return tool_error(&e.to_string()),
+    };
+    let domain_filter: Option<Vec<&str>> = args
+        .get("domain_filter")
+        .and_then(|v| v.as_array())
+        .map(|a| a.iter().filter_map(|d| d.as_str()).collect());
+
+    let vectors: Vec<Value> = set
+        .vectors
+        .iter()
+        .map(|v| {
+            let assignments: serde_json::Map<String, Value> = v
+                .assignments
+                .iter()
+                .filter(|(name, _)| {
+                    domain_filter
+                        .as_ref()
+                        .is_none_or(|f| f.contains(&name.as_str()))
+                })
+                .map(|(name, value)| (name.clone(), domain_value_to_json(value)))
+                .collect();
+            Value::Object(assignments)
+        })
+        .collect();
+
+    tool_success(json!({
+        "campaign_id": campaign_id,
+        "vectors": vectors,
+        "total_vectors": set.vectors.len(),
+        "exhaustive": set.exhaustive,
+        "unsat_pruned": set.unsat_pruned,
+    }))
+}
+
+fn tool_fresnel_fir_export_sarif(args: &Value, state: &McpState) -> Value {
+    let campaign_id = match args.get("campaign_id").and_then(|v| v.as_str()) {
+        Some(id) => id,
+        None => return tool_error("Missing required parameter: campaign_id"),
+    };
+
+    match state.manager.export_sarif(campaign_id) {
+        Ok(sarif) => tool_success(sarif),
+        Err(e) => tool_error(&e.to_string()),
+    }
+}
+
+fn domain_value_to_json(value: &DomainValue) -> Value {
+    match value {
+        DomainValue::Bool(b) => json!(b),
+        DomainValue::Int(i) => json!(i),
+        DomainValue::Enum(s) => json!(s),
+    }
+}
+
 /// Build a successful MCP tool response.
 fn tool_success(data: Value) -> Value {
     json!({
@@ -10,33 +10,24 @@
 //!
 //! Re-regression priority on campaign start:
 //! 1. Replay all previous finding capsules (confirm fixes, catch regressions)
-//! 2. Explore hot regions with boosted weights
-//! 3. Resume coverage-driven exploration
+//! 2. Explore hot regions with boosted weights (`apply_hot_regions`),
+//!    drawing remembered generator shortcuts first (`ShortcutVectorSource`)
+//! 3. Boost branches on paths that once broke an invariant, so fixed
+//!    properties keep being re-checked (`InvariantRegression`)
+//! 4. Resume coverage-driven exploration
 
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
 
 use serde::{Deserialize, Serialize};
 
-/// A replay capsule — everything needed to reproduce a finding.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ReplayCapsule {
-    /// Hash of the IR that produced this finding.
-    pub ir_hash: String,
-    /// Hash of the WASM module that was under test.
-    pub wasm_hash: String,
-    /// RNG seed used in the campaign.
-    pub seed: u64,
-    /// Description of the finding.
-    pub finding_description: String,
-    /// Action that triggered the finding.
-    pub trigger_action: String,
-    /// Step number in the traversal trace.
-    pub trace_step: u64,
-    /// Model generation at finding time.
-    pub model_generation: u64,
-    /// Input vector assignments (serialized).
-    pub input_vector: HashMap<String, String>,
-}
+use fresnel_fir_explore::solver::{DomainValue, TestVector};
+use fresnel_fir_explore::traversal::signal::Finding;
+use fresnel_fir_explore::traversal::vector_source::VectorSource;
+use fresnel_fir_explore::traversal::weight_table::WeightTable;
+use fresnel_fir_ir::types::{DomainType, FresnelFirIR, InputSpace};
+
+pub use fresnel_fir_explore::traversal::signal::ReplayCapsule;
 
 /// A hot region — a part of the search space that frequently produces findings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,6 +50,33 @@ pub struct LearnedWeight {
     pub weight: f64,
 }
 
+/// An input vector that triggered findings for an action in earlier
+/// campaigns, replayed first on campaign start.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratorShortcut {
+    /// Action the vector was passed to.
+    pub action: String,
+    /// Input vector assignments (serialized).
+    pub vector: HashMap<String, String>,
+    /// Number of findings this vector has triggered.
+    pub hit_count: u32,
+}
+
+/// An invariant property that failed in an earlier campaign.
+///
+/// The invariant analog of a replay capsule: the branches taken on the
+/// paths that broke the property are boosted on every later campaign, so
+/// the property keeps being exercised after it has been fixed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InvariantRegression {
+    /// Name of the `CompiledProperty` that failed.
+    pub property: String,
+    /// Branch IDs on the failing paths, sorted and deduplicated.
+    pub branch_ids: Vec<String>,
+    /// Number of recorded failures.
+    pub failure_count: u32,
+}
+
 /// Cross-campaign memory for a specific IR hash.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CampaignMemory {
@@ -75,6 +93,13 @@ pub struct CampaignMemory {
     pub non_reproduction_counts: HashMap<usize, u32>,
     /// Number of campaigns run against this IR.
     pub campaign_count: u32,
+    /// Input vectors that triggered findings, per action. Memory saved
+    /// before shortcuts were recorded loads with none.
+    #[serde(default)]
+    pub generator_shortcuts: Vec<GeneratorShortcut>,
+    /// Invariants that failed in earlier campaigns.
+    #[serde(default)]
+    pub invariant_regressions: Vec<InvariantRegression>,
 }
 
 /// Configuration for cross-campaign memory behavior.
@@ -92,6 +117,8 @@ pub struct MemoryConfig {
     pub invalidation_threshold: u32,
     /// Boost factor for hot regions on campaign start.
     pub hot_region_boost: f64,
+    /// Weight multiplier for branches on paths that broke an invariant.
+    pub invariant_regression_boost: f64,
 }
 
 impl Default for MemoryConfig {
@@ -101,6 +128,7 @@ impl Default for MemoryConfig {
             aggressive_decay: 0.2,
             invalidation_threshold: 3,
             hot_region_boost: 2.0,
+            invariant_regression_boost: 2.0,
         }
     }
 }
@@ -115,6 +143,30 @@ impl CampaignMemory {
             hot_regions: Vec::new(),
             non_reproduction_counts: HashMap::new(),
             campaign_count: 0,
+            generator_shortcuts: Vec::new(),
+            invariant_regressions: Vec::new(),
+        }
+    }
+
+    /// Create empty memory keyed on the IR's content hash.
+    pub fn for_ir(ir: &FresnelFirIR) -> Self {
+        Self::new(ir.content_hash())
+    }
+
+    /// Whether this memory was learned against the given IR.
+    pub fn applies_to(&self, ir: &FresnelFirIR) -> bool {
+        self.ir_hash == ir.content_hash()
+    }
+
+    /// Deserialize memory from JSON, keeping it only if it was learned
+    /// against `ir`. Memory for a different IR is discarded in favour of
+    /// a fresh, empty memory.
+    pub fn load_for(json: &str, ir: &FresnelFirIR) -> Result<Self, serde_json::Error> {
+        let memory = Self::from_json(json)?;
+        if memory.applies_to(ir) {
+            Ok(memory)
+        } else {
+            Ok(Self::for_ir(ir))
         }
     }
 
@@ -123,6 +175,18 @@ impl CampaignMemory {
         self.replay_capsules.push(capsule);
     }
 
+    /// Record the replay capsule of every finding that carries one, and
+    /// remember each capsule's input vector as a generator shortcut for
+    /// its trigger action.
+    pub fn add_finding_capsules(&mut self, findings: &[Finding]) {
+        for capsule in findings.iter().filter_map(|f| f.capsule.as_ref()) {
+            if !capsule.input_vector.is_empty() {
+                self.record_shortcut(&capsule.trigger_action, capsule.input_vector.clone());
+            }
+            self.replay_capsules.push(capsule.clone());
+        }
+    }
+
     /// Record a hot region.
     pub fn add_hot_region(&mut self, region: HotRegion) {
         // Merge with existing if same branch + state.
@@ -136,6 +200,76 @@ impl CampaignMemory {
         }
     }
 
+    /// Record that `vector` triggered a finding for `action`.
+    pub fn record_shortcut(&mut self, action: &str, vector: HashMap<String, String>) {
+        // Merge with existing if same action + vector.
+        if let Some(existing) = self
+            .generator_shortcuts
+            .iter_mut()
+            .find(|s| s.action == action && s.vector == vector)
+        {
+            existing.hit_count += 1;
+        } else {
+            self.generator_shortcuts.push(GeneratorShortcut {
+                action: action.to_string(),
+                vector,
+                hit_count: 1,
+            });
+        }
+    }
+
+    /// Record that `property` failed on a path through `branch_ids`.
+    pub fn record_invariant_failure(&mut self, property: &str, branch_ids: &[String]) {
+        let index = match self
+            .invariant_regressions
+            .iter()
+            .position(|r| r.property == property)
+        {
+            Some(index) => index,
+            None => {
+                self.invariant_regressions.push(InvariantRegression {
+                    property: property.to_string(),
+                    branch_ids: Vec::new(),
+                    failure_count: 0,
+                });
+                self.invariant_regressions.len() - 1
+            }
+        };
+        let regression = &mut self.invariant_regressions[index];
+        regression.failure_count += 1;
+        regression.branch_ids.extend(branch_ids.iter().cloned());
+        regression.branch_ids.sort();
+        regression.branch_ids.dedup();
+    }
+
+    /// Boost every branch that touched a previously failed invariant, once
+    /// per branch however many properties it touched. Applied to the
+    /// branch defaults at campaign start, so the boost holds in every model
+    /// state without a learned weight of its own.
+    pub fn apply_invariant_regressions(
+        &self,
+        weight_table: &mut WeightTable,
+        config: &MemoryConfig,
+    ) {
+        let mut branch_ids: Vec<&String> = self
+            .invariant_regressions
+            .iter()
+            .flat_map(|r| &r.branch_ids)
+            .collect();
+        branch_ids.sort();
+        branch_ids.dedup();
+        let (min_weight, max_weight) = weight_table.bounds();
+        for branch_id in branch_ids {
+            let default = weight_table
+                .defaults()
+                .get(branch_id)
+                .copied()
+                .unwrap_or(1.0);
+            let boosted = default * config.invariant_regression_boost;
+            weight_table.set_default(branch_id, boosted.clamp(min_weight, max_weight));
+        }
+    }
+
     /// Save current weight table state as learned weights.
     pub fn save_learned_weights(&mut self, weights: Vec<LearnedWeight>) {
         self.learned_weights = weights;
@@ -191,12 +325,17 @@ impl CampaignMemory {
         let mut indexed: Vec<(usize, &ReplayCapsule)> =
             self.replay_capsules.iter().enumerate().collect();
 
-        // Sort by: non-reproduction count (ascending — most reliable first),
-        // then by finding severity (we use trigger_action as proxy).
-        indexed.sort_by(|(idx_a, _), (idx_b, _)| {
-            let count_a = self.non_reproduction_counts.get(idx_a).unwrap_or(&0);
-            let count_b = self.non_reproduction_counts.get(idx_b).unwrap_or(&0);
-            count_a.cmp(count_b)
+        // Sort by: severity (most severe first), then non-reproduction
+        // count (ascending — most reliable first). Remaining ties break on
+        // the capsule's own contents so the order survives a reload.
+        let count = |idx: &usize| self.non_reproduction_counts.get(idx).copied().unwrap_or(0);
+        indexed.sort_by(|(a_idx, a), (b_idx, b)| {
+            a.severity
+                .cmp(&b.severity)
+                .then_with(|| count(a_idx).cmp(&count(b_idx)))
+                .then_with(|| a.trigger_action.cmp(&b.trigger_action))
+                .then(a.trace_step.cmp(&b.trace_step))
+                .then(a.seed.cmp(&b.seed))
         });
 
         indexed
@@ -205,10 +344,17 @@ impl CampaignMemory {
     /// Get hot regions ordered by finding frequency (descending).
     pub fn hot_region_order(&self) -> Vec<&HotRegion> {
         let mut regions: Vec<&HotRegion> = self.hot_regions.iter().collect();
-        regions.sort_by(|a, b| b.finding_count.cmp(&a.finding_count));
+        regions.sort_by_key(|r| Reverse(r.finding_count));
         regions
     }
 
+    /// Get generator shortcuts ordered by hit count (descending).
+    pub fn shortcut_order(&self) -> Vec<&GeneratorShortcut> {
+        let mut shortcuts: Vec<&GeneratorShortcut> = self.generator_shortcuts.iter().collect();
+        shortcuts.sort_by_key(|s| Reverse(s.hit_count));
+        shortcuts
+    }
+
     /// Serialize memory to JSON.
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
@@ -220,9 +366,99 @@ impl CampaignMemory {
     }
 }
 
+/// Boost the starting weight of every hot region in `memory`: the weight
+/// of its branch in its model state is multiplied by its `boost_factor`.
+///
+/// Applied to a fresh table before the first pass (passed to the runner
+/// as `CampaignConfig::initial_weights`), so regions that produced
+/// findings in earlier campaigns are explored preferentially from the
+/// outset.
+pub fn apply_hot_regions(memory: &CampaignMemory, wt: &mut WeightTable) {
+    for region in memory.hot_region_order() {
+        wt.adjust(
+            &region.branch_id,
+            region.model_state_hash,
+            region.boost_factor,
+        );
+    }
+}
+
+/// Vector source that yields remembered generator shortcuts for an action
+/// before falling back to `inner`.
+///
+/// Each shortcut is yielded once, most effective first. Shortcuts naming a
+/// domain the input space lacks, or a value the domain can no longer take,
+/// are dropped.
+pub struct ShortcutVectorSource<V> {
+    shortcuts: HashMap<String, VecDeque<TestVector>>,
+    inner: V,
+}
+
+impl<V: VectorSource> ShortcutVectorSource<V> {
+    pub fn new(memory: &CampaignMemory, inputs: &InputSpace, inner: V) -> Self {
+        let mut shortcuts: HashMap<String, VecDeque<TestVector>> = HashMap::new();
+        for shortcut in memory.shortcut_order() {
+            if let Some(vector) = parse_vector(&shortcut.vector, inputs) {
+                shortcuts
+                    .entry(shortcut.action.clone())
+                    .or_default()
+                    .push_back(vector);
+            }
+        }
+        Self { shortcuts, inner }
+    }
+
+    /// Shortcuts not yet yielded.
+    pub fn pending(&self) -> usize {
+        self.shortcuts.values().map(VecDeque::len).sum()
+    }
+
+    /// The wrapped source.
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+}
+
+impl<V: VectorSource> VectorSource for ShortcutVectorSource<V> {
+    fn next_vector(&mut self, action: &str) -> Option<TestVector> {
+        match self.shortcuts.get_mut(action).and_then(VecDeque::pop_front) {
+            Some(vector) => Some(vector),
+            None => self.inner.next_vector(action),
+        }
+    }
+}
+
+/// Parse serialized assignments back into a vector, typed by `inputs`.
+fn parse_vector(assignments: &HashMap<String, String>, inputs: &InputSpace) -> Option<TestVector> {
+    let mut vector = TestVector::new();
+    for (name, raw) in assignments {
+        let value = match &inputs.domains.get(name)?.domain_type {
+            DomainType::Bool => DomainValue::Bool(raw.parse().ok()?),
+            DomainType::Int { min, max, stride } => {
+                let value: i64 = raw.parse().ok()?;
+                let on_grid =
+                    (i128::from(value) - i128::from(*min)) % i128::from((*stride).max(1)) == 0;
+                if !(min..=max).contains(&&value) || !on_grid {
+                    return None;
+                }
+                DomainValue::Int(value)
+            }
+            DomainType::Enum { values, .. } => {
+                if !values.contains(raw) {
+                    return None;
+                }
+                DomainValue::Enum(raw.clone())
+            }
+        };
+        vector.assignments.insert(name.clone(), value);
+    }
+    Some(vector)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fresnel_fir_explore::traversal::signal::Severity;
 
     fn make_capsule(action: &str) -> ReplayCapsule {
         ReplayCapsule {
@@ -234,6 +470,7 @@ mod tests {
             trace_step: 10,
             model_generation: 5,
             input_vector: HashMap::new(),
+            severity: Severity::Low,
         }
     }
 
@@ -312,6 +549,55 @@ mod tests {
         assert!(!mem.non_reproduction_counts.contains_key(&0));
     }
 
+    #[test]
+    fn test_failed_invariant_boosts_its_branches_next_campaign() {
+        let mut mem = CampaignMemory::new("hash".into());
+        mem.record_invariant_failure(
+            "ownership_isolation",
+            &["publish_path".into(), "read_path".into()],
+        );
+        mem.record_invariant_failure("ownership_isolation", &["publish_path".into()]);
+        assert_eq!(mem.invariant_regressions.len(), 1);
+        assert_eq!(mem.invariant_regressions[0].failure_count, 2);
+
+        let config = MemoryConfig::default();
+        let mut mem = CampaignMemory::from_json(&mem.to_json().unwrap()).unwrap();
+        mem.prepare_new_campaign(&config);
+
+        let mut weights = WeightTable::new();
+        for branch in ["publish_path", "read_path", "delete_path"] {
+            weights.set_default(branch, 25.0);
+        }
+        mem.apply_invariant_regressions(&mut weights, &config);
+
+        for hash in [0, 42] {
+            assert_eq!(weights.get("publish_path", hash), 50.0);
+            assert_eq!(weights.get("read_path", hash), 50.0);
+            assert_eq!(weights.get("delete_path", hash), 25.0);
+        }
+    }
+
+    #[test]
+    fn test_hot_regions_boost_starting_weights() {
+        let mut mem = CampaignMemory::new("hash".into());
+        mem.add_hot_region(HotRegion {
+            branch_id: "publish_path".into(),
+            model_state_hash: 42,
+            finding_count: 3,
+            boost_factor: 2.0,
+        });
+
+        let mut weights = WeightTable::new();
+        weights.set_default("publish_path", 25.0);
+        weights.set_default("read_path", 25.0);
+        apply_hot_regions(&mem, &mut weights);
+
+        assert_eq!(weights.get("publish_path", 42), 50.0);
+        // Other states and branches keep their defaults.
+        assert_eq!(weights.get("publish_path", 7), 25.0);
+        assert_eq!(weights.get("read_path", 42), 25.0);
+    }
+
     #[test]
     fn test_hot_region_merging() {
         let mut mem = CampaignMemory::new("hash".into());
@@ -350,6 +636,54 @@ mod tests {
         assert_eq!(order[1].1.trigger_action, "unreliable");
     }
 
+    #[test]
+    fn test_regression_order_most_severe_first() {
+        let mut mem = CampaignMemory::new("hash".into());
+        for (action, severity) in [
+            ("hang", Severity::Medium),
+            ("crash", Severity::Critical),
+            ("violation", Severity::Critical),
+        ] {
+            mem.add_capsule(ReplayCapsule {
+                severity,
+                ..make_capsule(action)
+            });
+        }
+        // Unreliability only breaks ties within a severity.
+        mem.record_non_reproduction(1);
+
+        let order: Vec<&str> = mem
+            .regression_order()
+            .iter()
+            .map(|(_, c)| c.trigger_action.as_str())
+            .collect();
+        assert_eq!(order, ["violation", "crash", "hang"]);
+    }
+
+    #[test]
+    fn test_regression_order_breaks_ties_deterministically() {
+        let mut mem = CampaignMemory::new("hash".into());
+        for (action, trace_step, seed) in [("write", 3, 9), ("read", 7, 1), ("read", 3, 5)] {
+            mem.add_capsule(ReplayCapsule {
+                trace_step,
+                seed,
+                ..make_capsule(action)
+            });
+        }
+        for idx in 0..3 {
+            mem.record_non_reproduction(idx);
+        }
+
+        let order = |mem: &CampaignMemory| -> Vec<usize> {
+            mem.regression_order().iter().map(|(idx, _)| *idx).collect()
+        };
+        assert_eq!(order(&mem), [2, 1, 0]);
+        assert_eq!(order(&mem), order(&mem));
+
+        let restored = CampaignMemory::from_json(&mem.to_json().unwrap()).unwrap();
+        assert_eq!(order(&restored), [2, 1, 0]);
+    }
+
     #[test]
     fn test_hot_region_order_by_frequency() {
         let mut mem = CampaignMemory::new("hash".into());
@@ -418,4 +752,162 @@ mod tests {
         assert!((mem.learned_weights[0].weight - 51.2).abs() < 0.1);
         assert_eq!(mem.campaign_count, 3);
     }
+
+    #[test]
+    fn test_load_for_discards_memory_of_other_ir() {
+        let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+        let ir = fresnel_fir_ir::parse::parse_ir(json).unwrap();
+
+        let mut mem = CampaignMemory::for_ir(&ir);
+        mem.add_capsule(make_capsule("fn_a"));
+        let saved = mem.to_json().unwrap();
+
+        let loaded = CampaignMemory::load_for(&saved, &ir).unwrap();
+        assert!(loaded.applies_to(&ir));
+        assert_eq!(loaded.replay_capsules.len(), 1);
+
+        let mut other = ir.clone();
+        other.exploration.epoch_size += 1;
+        let fresh = CampaignMemory::load_for(&saved, &other).unwrap();
+        assert_eq!(fresh.ir_hash, other.content_hash());
+        assert!(fresh.replay_capsules.is_empty());
+    }
+
+    #[test]
+    fn test_generator_shortcuts_roundtrip_in_hit_order() {
+        let vector = |amount: &str| HashMap::from([("amount".to_string(), amount.to_string())]);
+        let mut mem = CampaignMemory::new("hash".into());
+        mem.record_shortcut("withdraw", vector("0"));
+        mem.record_shortcut("withdraw", vector("-1"));
+        mem.record_shortcut("withdraw", vector("-1"));
+        mem.record_shortcut("deposit", vector("7"));
+        mem.record_shortcut("withdraw", vector("-1"));
+        assert_eq!(mem.generator_shortcuts.len(), 3);
+
+        let restored = CampaignMemory::from_json(&mem.to_json().unwrap()).unwrap();
+        assert_eq!(restored.generator_shortcuts, mem.generator_shortcuts);
+        let order: Vec<(&str, u32)> = restored
+            .shortcut_order()
+            .iter()
+            .map(|s| (s.vector["amount"].as_str(), s.hit_count))
+            .collect();
+        assert_eq!(order, [("-1", 3), ("0", 1), ("7", 1)]);
+
+        // Memory saved before shortcuts existed still loads.
+        let mut legacy: serde_json::Value = serde_json::from_str(&mem.to_json().unwrap()).unwrap();
+        legacy
+            .as_object_mut()
+            .unwrap()
+            .remove("generator_shortcuts");
+        let legacy = CampaignMemory::from_json(&legacy.to_string()).unwrap();
+        assert!(legacy.generator_shortcuts.is_empty());
+    }
+
+    #[test]
+    fn test_finding_capsules_become_shortcuts() {
+        use fresnel_fir_explore::traversal::signal::{SignalEvent, SignalType};
+
+        let finding = |input_vector: HashMap<String, String>| Finding {
+            id: 0,
+            signal: SignalEvent {
+                thread_id: 0,
+                local_step: 0,
+                model_state_hash: 0,
+                signal_type: SignalType::Crash {
+                    action: "withdraw".into(),
+                    message: "trap".into(),
+                },
+            },
+            trace_indices: vec![],
+            model_generation: 0,
+            severity: Severity::Critical,
+            breaking_trace_index: None,
+            capsule: Some(ReplayCapsule {
+                input_vector,
+                ..make_capsule("withdraw")
+            }),
+            source_span: None,
+        };
+        let vector = HashMap::from([("amount".to_string(), "-1".to_string())]);
+        let mut mem = CampaignMemory::new("hash".into());
+        mem.add_finding_capsules(&[
+            finding(vector.clone()),
+            finding(vector.clone()),
+            finding(HashMap::new()),
+        ]);
+
+        assert_eq!(mem.replay_capsules.len(), 3);
+        assert_eq!(
+            mem.generator_shortcuts,
+            vec![GeneratorShortcut {
+                action: "withdraw".into(),
+                vector,
+                hit_count: 2,
+            }]
+        );
+    }
+
+    /// Input space with a single int domain `amount`.
+    fn amount_inputs(min: i64, max: i64, stride: i64) -> InputSpace {
+        use fresnel_fir_ir::types::{CoverageConfig, Domain};
+
+        InputSpace {
+            domains: HashMap::from([(
+                "amount".to_string(),
+                Domain {
+                    domain_type: DomainType::Int { min, max, stride },
+                },
+            )]),
+            constraints: vec![],
+            forbidden: Vec::new(),
+            coverage: CoverageConfig {
+                targets: vec![],
+                seed: 42,
+                reproducible: true,
+            },
+        }
+    }
+
+    #[test]
+    fn test_shortcut_source_yields_known_vectors_first() {
+        use fresnel_fir_explore::traversal::vector_source::MockVectorSource;
+
+        let inputs = amount_inputs(-5, 5, 1);
+        let vector = |amount: &str| HashMap::from([("amount".to_string(), amount.to_string())]);
+        let mut mem = CampaignMemory::new("hash".into());
+        mem.record_shortcut("withdraw", vector("0"));
+        mem.record_shortcut("withdraw", vector("-1"));
+        mem.record_shortcut("withdraw", vector("-1"));
+        mem.record_shortcut("withdraw", vector("not a number"));
+        mem.record_shortcut("withdraw", vector("6"));
+
+        let mut source = ShortcutVectorSource::new(&mem, &inputs, MockVectorSource::new());
+        assert_eq!(source.pending(), 2);
+        let amount = |v: Option<TestVector>| v.unwrap().assignments.get("amount").cloned();
+        assert_eq!(
+            amount(source.next_vector("withdraw")),
+            Some(DomainValue::Int(-1))
+        );
+        assert_eq!(
+            amount(source.next_vector("withdraw")),
+            Some(DomainValue::Int(0))
+        );
+        // Exhausted shortcuts and other actions fall through to the inner source.
+        assert_eq!(amount(source.next_vector("withdraw")), None);
+        assert_eq!(amount(source.next_vector("deposit")), None);
+        assert_eq!(source.pending(), 0);
+    }
+
+    #[test]
+    fn test_shortcut_int_values_must_lie_on_the_domain_grid() {
+        let inputs = amount_inputs(0, 10, 5);
+        let parsed = |amount: &str| {
+            let vector = HashMap::from([("amount".to_string(), amount.to_string())]);
+            parse_vector(&vector, &inputs).map(|v| v.assignments["amount"].clone())
+        };
+        assert_eq!(parsed("5"), Some(DomainValue::Int(5)));
+        assert_eq!(parsed("3"), None);
+        assert_eq!(parsed("15"), None);
+        assert_eq!(parsed("-5"), None);
+    }
 }
@@ -0,0 +1,251 @@
+//! SARIF export of campaign findings.
+//!
+//! Maps findings to a SARIF 2.1.0 log that CI systems and code scanners
+//! can ingest. Each finding becomes one `result`: the rule id names its
+//! signal type, the level follows its severity, and the location is the
+//! IR element that raised it, given as a logical location path into the
+//! IR document (e.g. `protocols.document_lifecycle.root.children[1]`).
+
+use std::collections::BTreeSet;
+
+use serde_json::{json, Value};
+
+use fresnel_fir_explore::traversal::signal::{Finding, Severity, SignalType};
+use fresnel_fir_ir::types::FresnelFirIR;
+
+/// SARIF version written by `findings_to_sarif`.
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// JSON schema of `SARIF_VERSION` logs.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Build a SARIF log with one run holding a result per finding.
+///
+/// `ir` supplies property descriptions and the content hash recorded on
+/// the run, so results can be traced back to the spec that produced them.
+pub fn findings_to_sarif(findings: &[Finding], ir: &FresnelFirIR) -> Value {
+    let rule_ids: BTreeSet<&str> = findings
+        .iter()
+        .map(|f| rule_id(&f.signal.signal_type))
+        .collect();
+    let rules: Vec<Value> = rule_ids
+        .into_iter()
+        .map(|id| {
+            json!({
+                "id": id,
+                "shortDescription": { "text": rule_description(id) },
+            })
+        })
+        .collect();
+    let results: Vec<Value> = findings.iter().map(|f| finding_result(f, ir)).collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "fresnel-fir",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "properties": { "irHash": ir.content_hash() },
+            "results": results,
+        }]
+    })
+}
+
+fn finding_result(finding: &Finding, ir: &FresnelFirIR) -> Value {
+    let signal_type = &finding.signal.signal_type;
+    let mut result = json!({
+        "ruleId": rule_id(signal_type),
+        "level": level(finding.severity),
+        "message": { "text": message(signal_type, ir) },
+        "partialFingerprints": { "findingSignature/v1": finding.signature() },
+        "properties": {
+            "findingId": finding.id,
+            "threadId": finding.signal.thread_id,
+            "modelGeneration": finding.model_generation,
+        },
+    });
+    if let Some(path) = location_path(finding, ir) {
+        result["locations"] = json!([{
+            "logicalLocations": [{ "fullyQualifiedName": path }]
+        }]);
+    }
+    result
+}
+
+/// Rule id for a signal type; matches the kinds in `Finding::signature`.
+pub(crate) fn rule_id(signal_type: &SignalType) -> &'static str {
+    match signal_type {
+        SignalType::Crash { .. } => "crash",
+        SignalType::PropertyViolation { .. } => "property_violation",
+        SignalType::Discrepancy { .. } => "discrepancy",
+        SignalType::Timeout { .. } => "timeout",
+        SignalType::GuardFailure { .. } => "guard_failure",
+        SignalType::GuardPass { .. } => "guard_pass",
+        SignalType::CoverageDelta { .. } => "coverage_delta",
+        SignalType::CoveragePlateau { .. } => "coverage_plateau",
+        SignalType::LoopLimitConflict { .. } => "loop_limit_conflict",
+        SignalType::ZeroWeightFallback { .. } => "zero_weight_fallback",
+    }
+}
+
+fn rule_description(rule_id: &str) -> &'static str {
+    match rule_id {
+        "crash" => "The DUT panicked or trapped",
+        "property_violation" => "An invariant or temporal property was violated",
+        "discrepancy" => "The DUT disagreed with the model",
+        "timeout" => "A DUT action exceeded its time or fuel budget",
+        "guard_failure" => "A guard prevented a transition",
+        "guard_pass" => "A guard allowed a transition",
+        "coverage_delta" => "New coverage was reached",
+        "coverage_plateau" => "Coverage stopped growing",
+        "loop_limit_conflict" => "A loop limit was below the declared minimum",
+        "zero_weight_fallback" => "Every alternative at a branch had zero weight",
+        _ => "Finding",
+    }
+}
+
+/// SARIF level: critical and high findings fail CI, medium ones warn.
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
+
+fn message(signal_type: &SignalType, ir: &FresnelFirIR) -> String {
+    match signal_type {
+        SignalType::Crash { action, message } => format!("'{action}' crashed: {message}"),
+        SignalType::PropertyViolation { property, details } => {
+            match ir
+                .properties
+                .get(property)
+                .and_then(|p| p.description.as_deref())
+            {
+                Some(description) => format!("{details} ({description})"),
+                None => details.clone(),
+            }
+        }
+        SignalType::Discrepancy {
+            action,
+            model_value,
+            observed_value,
+        } => format!("'{action}' diverged: model {model_value}, observed {observed_value}"),
+        SignalType::Timeout {
+            action,
+            fuel_consumed,
+        } => match fuel_consumed {
+            Some(fuel) => format!("'{action}' timed out after {fuel} fuel"),
+            None => format!("'{action}' timed out"),
+        },
+        other => format!("{other:?}"),
+    }
+}
+
+/// The IR element a finding points at: its source span, or for a property
+/// violation raised without one, the property itself.
+fn location_path(finding: &Finding, ir: &FresnelFirIR) -> Option<String> {
+    if let Some(span) = &finding.source_span {
+        return Some(span.path.clone());
+    }
+    match &finding.signal.signal_type {
+        SignalType::PropertyViolation { property, .. } if ir.properties.contains_key(property) => {
+            Some(format!("properties.{property}"))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fresnel_fir_compiler::graph::SourceSpan;
+    use fresnel_fir_explore::traversal::signal::{classify_severity, SignalEvent};
+    use fresnel_fir_ir::parse::parse_ir;
+
+    fn finding(id: u64, signal_type: SignalType, span: Option<&str>) -> Finding {
+        Finding {
+            id,
+            severity: classify_severity(&signal_type),
+            signal: SignalEvent {
+                thread_id: 0,
+                local_step: id,
+                model_state_hash: 0,
+                signal_type,
+            },
+            trace_indices: vec![],
+            model_generation: 3,
+            breaking_trace_index: None,
+            capsule: None,
+            source_span: span.map(|path| SourceSpan { path: path.into() }),
+        }
+    }
+
+    #[test]
+    fn test_sarif_has_required_keys_and_crash_is_error() {
+        let ir = parse_ir(include_str!(
+            "../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json"
+        ))
+        .unwrap();
+        let property = ir.properties.keys().next().unwrap().clone();
+        let findings = [
+            finding(
+                0,
+                SignalType::Crash {
+                    action: "create_document".into(),
+                    message: "unreachable".into(),
+                },
+                Some("protocols.document_lifecycle.root.children[0]"),
+            ),
+            finding(
+                1,
+                SignalType::PropertyViolation {
+                    property: property.clone(),
+                    details: "violated".into(),
+                },
+                None,
+            ),
+        ];
+
+        let sarif = findings_to_sarif(&findings, &ir);
+        assert_eq!(sarif["version"], "2.1.0");
+        let runs = sarif["runs"].as_array().unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0]["tool"]["driver"]["name"], "fresnel-fir");
+        assert_eq!(runs[0]["properties"]["irHash"], ir.content_hash());
+        let results = runs[0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+
+        let crash = &results[0];
+        assert_eq!(crash["ruleId"], "crash");
+        assert_eq!(crash["level"], "error");
+        assert_eq!(
+            crash["message"]["text"],
+            "'create_document' crashed: unreachable"
+        );
+        assert_eq!(
+            crash["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            "protocols.document_lifecycle.root.children[0]"
+        );
+
+        let violation = &results[1];
+        assert_eq!(violation["ruleId"], "property_violation");
+        assert_eq!(
+            violation["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            format!("properties.{property}")
+        );
+
+        let rule_ids: Vec<&str> = runs[0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(rule_ids, vec!["crash", "property_violation"]);
+    }
+}
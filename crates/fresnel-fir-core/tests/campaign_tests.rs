@@ -1,5 +1,12 @@
-use fresnel_fir_core::analytics::CampaignPhase;
-use fresnel_fir_core::campaign::{CampaignManager, FindingRecord};
+use fresnel_fir_core::analytics::{CampaignAnalytics, CampaignPhase};
+use fresnel_fir_core::campaign::{
+    finding_signature, CampaignError, CampaignManager, FindingRecord, BUDGET_EXTENSION_FINDING_RATE,
+};
+use fresnel_fir_core::limits::StopReason;
+use fresnel_fir_explore::traversal::engine::CoverageReport;
+use fresnel_fir_explore::traversal::runner::{CampaignResult, StopReason as RunStopReason};
+use fresnel_fir_explore::traversal::signal::{Finding, Severity, SignalEvent, SignalType};
+use fresnel_fir_explore::traversal::weight_table::WeightTable;
 
 #[test]
 fn test_new_manager_is_empty() {
@@ -119,6 +126,7 @@ fn test_campaign_findings() {
             action: "create_document".into(),
             details: "WASM trap".into(),
             model_generation: 1,
+            severity: Severity::Critical,
         },
     );
     manager.add_finding(
@@ -130,6 +138,7 @@ fn test_campaign_findings() {
             action: "publish".into(),
             details: "invariant failed".into(),
             model_generation: 2,
+            severity: Severity::Critical,
         },
     );
 
@@ -139,13 +148,63 @@ fn test_campaign_findings() {
     // Incremental: since seqno 0.
     let since = manager.get_findings(&id, Some(0));
     assert_eq!(since.len(), 1);
-    assert_eq!(since[0].seqno, 1);
+    assert_eq!(since[0].first_seqno, 1);
 
     // Campaign state updated.
     let state = manager.get_campaign(&id).unwrap();
     assert_eq!(state.findings_count, 2);
 }
 
+#[test]
+fn test_duplicate_findings_collapse_by_signature() {
+    let manager = CampaignManager::new();
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let id = manager.compile(json).unwrap();
+
+    let finding = |seqno: u64, action: &str, details: String| FindingRecord {
+        id: seqno,
+        seqno,
+        finding_type: "crash".into(),
+        action: action.into(),
+        details,
+        model_generation: seqno,
+        severity: Severity::Critical,
+    };
+    // The same trap at different addresses, reached along different paths.
+    for (seqno, addr) in [(0, 0x1f), (2, 0x2a), (3, 0x40)] {
+        manager.add_finding(
+            &id,
+            finding(seqno, "publish", format!("WASM trap at {addr:#x}")),
+        );
+    }
+    manager.add_finding(&id, finding(1, "archive", "WASM trap at 0x1f".into()));
+
+    let all = manager.get_findings(&id, None);
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].finding.action, "publish");
+    assert_eq!(
+        (all[0].occurrences, all[0].first_seqno, all[0].last_seqno),
+        (3, 0, 3)
+    );
+    assert_eq!(all[1].finding.action, "archive");
+    assert_eq!(
+        (all[1].occurrences, all[1].first_seqno, all[1].last_seqno),
+        (1, 1, 1)
+    );
+    assert_eq!(
+        finding_signature(&finding(0, "publish", "WASM trap at 0x1f".into())),
+        finding_signature(&finding(9, "publish", "WASM  trap at 0xbeef".into()))
+    );
+
+    // Polling only yields findings that are new, not new occurrences.
+    let since = manager.get_findings(&id, Some(0));
+    assert_eq!(since.len(), 1);
+    assert_eq!(since[0].finding.action, "archive");
+
+    // Every occurrence still counts toward the campaign total.
+    assert_eq!(manager.get_campaign(&id).unwrap().findings_count, 4);
+}
+
 #[test]
 fn test_campaign_abort() {
     let manager = CampaignManager::new();
@@ -159,6 +218,42 @@ fn test_campaign_abort() {
     assert_eq!(state.phase, CampaignPhase::Aborted);
 }
 
+#[test]
+fn test_vector_limit_bounds_generation() {
+    let manager = CampaignManager::new();
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let id = manager.compile(json).unwrap();
+
+    let limited = manager.get_vectors(&id, Some(3)).unwrap();
+    assert_eq!(limited.vectors.len(), 3);
+    assert!(!limited.exhaustive);
+
+    let all = manager.get_vectors(&id, None).unwrap();
+    assert_eq!(all.vectors.len(), 288);
+    assert!(all.exhaustive);
+
+    // Served from the exhaustive set from now on.
+    let again = manager.get_vectors(&id, Some(3)).unwrap();
+    assert_eq!(again.vectors, all.vectors[..3]);
+    assert!(!again.exhaustive);
+    assert!(manager.get_vectors(&id, Some(1000)).unwrap().exhaustive);
+}
+
+#[test]
+fn test_abort_cancels_vector_generation() {
+    let manager = CampaignManager::new();
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let id = manager.compile(json).unwrap();
+
+    manager.abort(&id).unwrap();
+    let state = manager.get_campaign(&id).unwrap();
+    assert!(state.cancel.load(std::sync::atomic::Ordering::Relaxed));
+    assert!(matches!(
+        manager.get_vectors(&id, None),
+        Err(CampaignError::InvalidState(_))
+    ));
+}
+
 #[test]
 fn test_campaign_remove() {
     let manager = CampaignManager::new();
@@ -177,3 +272,260 @@ fn test_campaign_not_found_error() {
     let result = manager.abort("nonexistent");
     assert!(result.is_err());
 }
+
+#[test]
+fn test_recompute_budget_extends_on_recent_findings() {
+    let manager = CampaignManager::new();
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let id = manager.compile(json).unwrap();
+    let initial = manager.get_campaign(&id).unwrap().budget;
+
+    // A quiet campaign keeps its budget.
+    let mut quiet = CampaignAnalytics::new();
+    quiet.set_total_steps(initial.min_iterations);
+    quiet.record_finding_rate(0, 0);
+    manager.update_analytics(&id, quiet);
+    let budget = manager.recompute_budget(&id).unwrap();
+    assert_eq!(budget.min_iterations, initial.min_iterations);
+    assert_eq!(budget.extended_at_epoch, None);
+
+    // Findings still arriving in the last epoch, with coverage growing.
+    let steps = 1_000;
+    let mut busy = CampaignAnalytics::new();
+    busy.set_total_steps(900);
+    busy.record_finding_rate(0, 0);
+    busy.set_total_steps(steps);
+    busy.record_finding_rate(1, 10);
+    busy.record_coverage(0, 1, 10);
+    busy.record_coverage(steps, 5, 10);
+    assert!(busy.recent_finding_rate_per_k() > BUDGET_EXTENSION_FINDING_RATE);
+    manager.update_analytics(&id, busy);
+
+    let extended = manager.recompute_budget(&id).unwrap();
+    let base = initial.min_iterations.max(steps);
+    assert_eq!(extended.min_iterations, base + base / 2);
+    assert_eq!(extended.extended_at_epoch, Some(1));
+    assert_eq!(
+        manager.get_campaign(&id).unwrap().budget.min_iterations,
+        extended.min_iterations
+    );
+
+    // The same snapshot does not extend the budget again.
+    let again = manager.recompute_budget(&id).unwrap();
+    assert_eq!(again.min_iterations, extended.min_iterations);
+}
+
+#[test]
+fn test_concurrent_polling_while_phase_advances() {
+    let manager = CampaignManager::new();
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let id = manager.compile(json).unwrap();
+    let total = 100u64;
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let mut seen = 0;
+                while seen < total as usize {
+                    let findings = manager.get_findings(&id, None);
+                    // Appends are atomic: a poll never sees a partial list
+                    // and never goes backwards.
+                    assert!(findings.len() >= seen);
+                    assert!(findings.iter().zip(0..).all(|(f, i)| f.first_seqno == i));
+                    seen = findings.len();
+                    let _ = manager.get_coverage(&id);
+                }
+            });
+        }
+        scope.spawn(|| {
+            manager.set_phase(&id, CampaignPhase::Running).unwrap();
+            for seqno in 0..total {
+                manager.add_finding(
+                    &id,
+                    FindingRecord {
+                        id: seqno,
+                        seqno,
+                        finding_type: "crash".into(),
+                        action: format!("action_{seqno}"),
+                        details: "WASM trap".into(),
+                        model_generation: seqno,
+                        severity: Severity::Critical,
+                    },
+                );
+            }
+            manager.set_phase(&id, CampaignPhase::Complete).unwrap();
+        });
+    });
+
+    let state = manager.get_campaign(&id).unwrap();
+    assert_eq!(state.phase, CampaignPhase::Complete);
+    assert_eq!(state.findings_count as u64, total);
+    assert_eq!(manager.get_findings(&id, None).len() as u64, total);
+    assert_eq!(
+        manager.get_analytics(&id).unwrap().state,
+        CampaignPhase::Complete
+    );
+}
+
+#[test]
+fn test_campaign_stores_run_stop_reason() {
+    let manager = CampaignManager::new();
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let id = manager.compile(json).unwrap();
+    assert_eq!(manager.get_campaign(&id).unwrap().stop_reason, None);
+
+    for (run_reason, stored) in [
+        (RunStopReason::MaxPasses, StopReason::Complete),
+        (RunStopReason::Cancelled, StopReason::UserAborted),
+        (RunStopReason::StopOnFinding, StopReason::StopOnFinding),
+        (RunStopReason::Saturated, StopReason::Saturated),
+    ] {
+        manager.set_stop_reason(&id, run_reason).unwrap();
+        let reason = manager.get_campaign(&id).unwrap().stop_reason.unwrap();
+        assert_eq!(reason, stored);
+        assert_eq!(serde_json::to_value(&reason).unwrap(), reason.as_str());
+    }
+
+    assert!(matches!(
+        manager.set_stop_reason("nonexistent", RunStopReason::MaxPasses),
+        Err(CampaignError::NotFound(_))
+    ));
+}
+
+/// A traversal crash finding in `action`.
+fn crash(id: u64, action: &str, message: &str) -> Finding {
+    Finding {
+        id,
+        signal: SignalEvent {
+            thread_id: 0,
+            local_step: id,
+            model_state_hash: 0,
+            signal_type: SignalType::Crash {
+                action: action.into(),
+                message: message.into(),
+            },
+        },
+        trace_indices: vec![],
+        model_generation: 1,
+        severity: Severity::Critical,
+        breaking_trace_index: None,
+        capsule: None,
+        source_span: None,
+    }
+}
+
+/// A traversal run result with `findings`, ending for `stop_reason`.
+#[allow(deprecated)]
+fn run_result(findings: Vec<Finding>, stop_reason: RunStopReason) -> CampaignResult {
+    CampaignResult {
+        findings,
+        total_actions: 40,
+        passes_completed: 2,
+        unique_nodes_visited: 0,
+        total_guard_failures: 0,
+        coverage: CoverageReport::default(),
+        unmet_min_hits: vec![],
+        cancelled: false,
+        stopped_early: false,
+        stop_finding: None,
+        stop_reason,
+        weight_table: WeightTable::new(),
+    }
+}
+
+#[test]
+fn test_record_run_snapshots_steps_and_top_weights() {
+    let manager = CampaignManager::new();
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let id = manager.compile(json).unwrap();
+
+    let mut result = run_result(vec![], RunStopReason::MaxPasses);
+    result.weight_table.set_default("read_path", 10.0);
+    result.weight_table.set_default("create_path", 50.0);
+    manager.record_run(&id, &result).unwrap();
+    manager.record_run(&id, &result).unwrap();
+
+    let campaign = manager.get_campaign(&id).unwrap();
+    assert_eq!(campaign.steps_executed, 80);
+    assert_eq!(campaign.stop_reason, Some(StopReason::Complete));
+    let analytics = manager.get_analytics(&id).unwrap();
+    assert_eq!(analytics.total_steps, 80);
+    assert_eq!(
+        analytics.top_weights,
+        vec![
+            ("create_path".to_string(), 50.0),
+            ("read_path".to_string(), 10.0)
+        ]
+    );
+    assert!(matches!(
+        manager.record_run("nonexistent", &result),
+        Err(CampaignError::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_record_run_feeds_findings_and_sarif_from_one_path() {
+    let manager = CampaignManager::new();
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let id = manager.compile(json).unwrap();
+
+    let result = run_result(
+        vec![
+            crash(0, "read", "trap at 0x10"),
+            crash(1, "read", "trap at 0x24"),
+            crash(2, "delete", "unreachable"),
+        ],
+        RunStopReason::MaxPasses,
+    );
+    manager.record_run(&id, &result).unwrap();
+
+    assert_eq!(manager.get_campaign(&id).unwrap().findings_count, 3);
+    let findings = manager.get_findings(&id, None);
+    assert_eq!(findings.len(), 2);
+    assert_eq!(findings[0].finding.finding_type, "crash");
+    assert_eq!(findings[0].finding.action, "read");
+    assert_eq!(
+        (
+            findings[0].occurrences,
+            findings[0].first_seqno,
+            findings[0].last_seqno
+        ),
+        (2, 1, 2)
+    );
+    assert_eq!(findings[1].finding.action, "delete");
+
+    let sarif = manager.export_sarif(&id).unwrap();
+    assert_eq!(sarif["runs"][0]["results"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn test_record_run_extends_budget_per_run_epoch() {
+    let manager = CampaignManager::new();
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let id = manager.compile(json).unwrap();
+    let initial = manager.get_campaign(&id).unwrap().budget;
+
+    // A quiet run leaves the budget alone.
+    manager
+        .record_run(&id, &run_result(vec![], RunStopReason::MaxPasses))
+        .unwrap();
+    assert_eq!(
+        manager.get_campaign(&id).unwrap().budget.extended_at_epoch,
+        None
+    );
+
+    // The second run's rate is measured over its own 40 steps, not an
+    // average share of all 80.
+    let busy = run_result(
+        vec![crash(0, "read", "trap at 0x10")],
+        RunStopReason::MaxPasses,
+    );
+    manager.record_run(&id, &busy).unwrap();
+    let analytics = manager.get_analytics(&id).unwrap();
+    assert_eq!(analytics.recent_finding_rate_per_k(), 25.0);
+
+    let budget = manager.get_campaign(&id).unwrap().budget;
+    let base = initial.min_iterations.max(80);
+    assert_eq!(budget.min_iterations, base + base / 4);
+    assert_eq!(budget.extended_at_epoch, Some(1));
+}
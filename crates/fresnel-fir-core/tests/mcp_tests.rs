@@ -53,6 +53,8 @@ fn test_tools_list() {
     assert!(tool_names.contains(&"fresnel_fir_coverage"));
     assert!(tool_names.contains(&"fresnel_fir_abort"));
     assert!(tool_names.contains(&"fresnel_fir_analytics"));
+    assert!(tool_names.contains(&"fresnel_fir_vectors"));
+    assert!(tool_names.contains(&"fresnel_fir_export_sarif"));
 }
 
 #[test]
@@ -79,6 +81,32 @@ fn test_tools_call_fresnel_fir_compile_success() {
     assert_eq!(text["result"], "pass");
     assert!(text["campaign_id"].is_string());
     assert!(text["budget"]["min_iterations"].is_number());
+    let ir = fresnel_fir_ir::parse::parse_ir(ir_json).unwrap();
+    assert_eq!(text["ir_hash"], ir.content_hash());
+    assert_eq!(text["warnings"], serde_json::json!([]));
+}
+
+#[test]
+fn test_tools_call_fresnel_fir_compile_reports_target_warnings() {
+    let state = make_state();
+    let ir_json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json")
+        .replace(r#""over": ["actor_role""#, r#""over": ["actor_rol""#);
+    let req = make_request(
+        "tools/call",
+        serde_json::json!({
+            "name": "fresnel_fir_compile",
+            "arguments": {
+                "ir_json": ir_json
+            }
+        }),
+    );
+    let resp = handle_request(&req, &state);
+
+    let text = parse_tool_response(&resp);
+    assert_eq!(text["result"], "pass");
+    let warnings = text["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].as_str().unwrap().contains("actor_rol"));
 }
 
 #[test]
@@ -239,6 +267,61 @@ fn test_findings_empty() {
     assert_eq!(text["findings"].as_array().unwrap().len(), 0);
 }
 
+#[test]
+fn test_findings_polling_returns_each_finding_once() {
+    use fresnel_fir_explore::traversal::signal::{Finding, Severity, SignalEvent, SignalType};
+
+    let state = McpState::new();
+    let campaign_id = compile_campaign(&state);
+    let crash = |id: u64| Finding {
+        id,
+        signal: SignalEvent {
+            thread_id: 0,
+            local_step: id,
+            model_state_hash: 0,
+            signal_type: SignalType::Crash {
+                action: format!("action_{}", ["a", "b", "c", "d", "e"][id as usize]),
+                message: "trap".into(),
+            },
+        },
+        trace_indices: vec![],
+        model_generation: 1,
+        severity: Severity::Critical,
+        breaking_trace_index: None,
+        capsule: None,
+        source_span: None,
+    };
+    let poll = |since: Option<u64>| {
+        let mut arguments = serde_json::json!({ "campaign_id": campaign_id });
+        if let Some(since) = since {
+            arguments["since_seqno"] = since.into();
+        }
+        let req = make_request(
+            "tools/call",
+            serde_json::json!({ "name": "fresnel_fir_findings", "arguments": arguments }),
+        );
+        parse_tool_response(&handle_request(&req, &state))
+    };
+
+    let mut seen = Vec::new();
+    let mut since = Some(0);
+    for batch in [0..2, 2..2, 2..3, 3..5, 5..5] {
+        for id in batch {
+            state.manager.add_traversal_finding(&campaign_id, crash(id));
+        }
+        let text = poll(since);
+        for finding in text["findings"].as_array().unwrap() {
+            seen.push(finding["action"].as_str().unwrap().to_string());
+        }
+        since = text["next_seqno"].as_u64();
+    }
+
+    assert_eq!(
+        seen,
+        ["action_a", "action_b", "action_c", "action_d", "action_e"]
+    );
+}
+
 #[test]
 fn test_coverage_empty() {
     let state = McpState::new();
@@ -291,6 +374,26 @@ fn test_analytics() {
     assert_eq!(text["campaign_id"], campaign_id);
     assert!(text["summary"]["total_steps"].is_number());
     assert!(text["summary"]["state"].is_string());
+    assert_eq!(text["coverage_series"], serde_json::json!([]));
+    assert_eq!(text["epoch_stats"], serde_json::json!([]));
+}
+
+#[test]
+fn test_analytics_csv_format() {
+    let state = McpState::new();
+    let campaign_id = compile_campaign(&state);
+
+    let req = make_request(
+        "tools/call",
+        serde_json::json!({
+            "name": "fresnel_fir_analytics",
+            "arguments": { "campaign_id": campaign_id, "format": "csv" }
+        }),
+    );
+    let resp = handle_request(&req, &state);
+    let text = parse_tool_response(&resp);
+    assert_eq!(text["coverage_series"], "step,coverage\n");
+    assert!(text["epoch_stats"].as_str().unwrap().starts_with("epoch,"));
 }
 
 #[test]
@@ -338,4 +441,129 @@ fn test_fuzz_lifecycle() {
     let resp = handle_request(&status_req, &state);
     let text = parse_tool_response(&resp);
     assert_eq!(text["state"], "aborted");
+    assert_eq!(text["stop_reason"], "user_aborted");
+}
+
+#[test]
+fn test_tools_call_fresnel_fir_vectors() {
+    let state = make_state();
+    let ir_json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let compile_req = make_request(
+        "tools/call",
+        serde_json::json!({
+            "name": "fresnel_fir_compile",
+            "arguments": { "ir_json": ir_json }
+        }),
+    );
+    let resp = handle_request(&compile_req, &state);
+    let campaign_id = parse_tool_response(&resp)["campaign_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let req = make_request(
+        "tools/call",
+        serde_json::json!({
+            "name": "fresnel_fir_vectors",
+            "arguments": {
+                "campaign_id": campaign_id,
+                "limit": 5,
+                "domain_filter": ["actor_role", "concurrent_actors"]
+            }
+        }),
+    );
+    let resp = handle_request(&req, &state);
+    let text = parse_tool_response(&resp);
+
+    assert_eq!(text["total_vectors"], 5);
+    assert_eq!(text["exhaustive"], false);
+    assert_eq!(text["unsat_pruned"], 0);
+
+    let vectors = text["vectors"].as_array().unwrap();
+    assert_eq!(vectors.len(), 5);
+    for v in vectors {
+        let obj = v.as_object().unwrap();
+        assert_eq!(obj.len(), 2);
+        assert!(obj["actor_role"].is_string());
+        assert!(obj["concurrent_actors"].is_i64());
+    }
+
+    let req = make_request(
+        "tools/call",
+        serde_json::json!({
+            "name": "fresnel_fir_vectors",
+            "arguments": { "campaign_id": campaign_id }
+        }),
+    );
+    let text = parse_tool_response(&handle_request(&req, &state));
+    // 3 roles x 2 auth x 3 visibilities x 2 owner x 8 actors.
+    assert_eq!(text["total_vectors"], 288);
+    assert_eq!(text["exhaustive"], true);
+    assert_eq!(text["vectors"].as_array().unwrap().len(), 288);
+}
+
+#[test]
+fn test_tools_call_fresnel_fir_vectors_unknown_campaign() {
+    let state = make_state();
+    let req = make_request(
+        "tools/call",
+        serde_json::json!({
+            "name": "fresnel_fir_vectors",
+            "arguments": { "campaign_id": "campaign-9999" }
+        }),
+    );
+    let resp = handle_request(&req, &state);
+    assert_eq!(resp["result"]["isError"], true);
+}
+
+#[test]
+fn test_export_sarif() {
+    use fresnel_fir_explore::traversal::signal::{Finding, Severity, SignalEvent, SignalType};
+
+    let state = McpState::new();
+    let campaign_id = compile_campaign(&state);
+    state.manager.add_traversal_finding(
+        &campaign_id,
+        Finding {
+            id: 0,
+            signal: SignalEvent {
+                thread_id: 0,
+                local_step: 4,
+                model_state_hash: 0,
+                signal_type: SignalType::Crash {
+                    action: "create_document".into(),
+                    message: "unreachable".into(),
+                },
+            },
+            trace_indices: vec![],
+            model_generation: 1,
+            severity: Severity::Critical,
+            breaking_trace_index: None,
+            capsule: None,
+            source_span: None,
+        },
+    );
+
+    let req = make_request(
+        "tools/call",
+        serde_json::json!({
+            "name": "fresnel_fir_export_sarif",
+            "arguments": { "campaign_id": campaign_id }
+        }),
+    );
+    let resp = handle_request(&req, &state);
+    let sarif = parse_tool_response(&resp);
+    assert_eq!(sarif["version"], "2.1.0");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["level"], "error");
+
+    let req = make_request(
+        "tools/call",
+        serde_json::json!({
+            "name": "fresnel_fir_export_sarif",
+            "arguments": { "campaign_id": "campaign-9999" }
+        }),
+    );
+    assert_eq!(handle_request(&req, &state)["result"]["isError"], true);
 }
@@ -2,15 +2,26 @@
 //!
 //! Collects signals from traversal threads into fixed-size epochs,
 //! assigns monotonic sequence numbers (sorted by thread_id then local_step),
-//! maps signals to directives, and applies directives at epoch boundaries.
+//! maps signals to directives through a `DirectivePolicy`, and applies
+//! directives at epoch boundaries.
 //!
 //! Same inputs → same directive history regardless of thread scheduling.
 
+use std::collections::BTreeSet;
+
+#[cfg(any(test, feature = "test-util"))]
+use rand::seq::SliceRandom;
+
+#[cfg(any(test, feature = "test-util"))]
+use crate::solver::rng::RngStreams;
 use crate::traversal::signal::{SignalEvent, SignalType};
 use crate::traversal::weight_table::WeightTable;
 
+use super::dead_branch::{DeadBranchTracker, GuardCounts};
 use super::decay::{self, DecayConfig};
 use super::directive::{Directive, DirectiveLog};
+use super::plateau::PlateauDetector;
+use super::policy::{DirectivePolicy, PolicyContext};
 use super::timeout::TimeoutTracker;
 
 /// Configuration for the adaptation coordinator.
@@ -26,8 +37,16 @@ pub struct CoordinatorConfig {
     pub finding_boost: f64,
     /// Force budget when investigating violations.
     pub force_budget: u32,
-    /// Coverage floor threshold (fraction of total weight budget).
+    /// Coverage floor threshold (fraction of total weight budget). Also
+    /// the per-epoch coverage gain below which an epoch counts as flat.
     pub coverage_floor_threshold: f64,
+    /// Consecutive flat epochs before a coverage plateau is signalled.
+    pub plateau_window: usize,
+    /// Consecutive epochs a branch's guard must fail, never having passed
+    /// in any observed state, before it is skipped. 0 disables this.
+    pub dead_branch_epochs: u32,
+    /// TTL in epochs of the skip issued for such a branch.
+    pub dead_branch_skip_ttl: u32,
     /// Decay configuration.
     pub decay: DecayConfig,
 }
@@ -41,17 +60,35 @@ impl Default for CoordinatorConfig {
             finding_boost: 2.0,
             force_budget: 10,
             coverage_floor_threshold: 0.05,
+            plateau_window: 3,
+            dead_branch_epochs: 5,
+            dead_branch_skip_ttl: 50,
             decay: DecayConfig::default(),
         }
     }
 }
 
+/// A skip with an expiry, awaiting restoration of the branch's weight.
+#[derive(Debug, Clone)]
+struct ActiveSkip {
+    branch_id: String,
+    model_state_hash: u64,
+    /// First epoch in which the branch is no longer skipped.
+    expires_at_epoch: u64,
+    /// Weight the branch had before it was first skipped.
+    restore_weight: f64,
+    /// Signal that triggered the skip; logged again on expiry.
+    triggered_by: SignalType,
+}
+
 /// The adaptation coordinator — processes signals and emits directives.
 ///
 /// Invariant: adaptation changes exploration policy, never the spec.
 /// Invariant: adaptation is deterministic given the same signal sequence.
 pub struct Coordinator {
     config: CoordinatorConfig,
+    /// Maps signals to directives.
+    policy: Box<dyn DirectivePolicy>,
     /// Current epoch number.
     epoch: u64,
     /// Signals collected in the current epoch (not yet processed).
@@ -65,18 +102,35 @@ pub struct Coordinator {
     /// Set of branches known to reach uncovered targets.
     /// Used for coverage floor enforcement.
     uncovered_target_branches: Vec<String>,
+    /// Model state hashes seen on incoming signals (plus the default
+    /// state 0). Alt blocks are normalized at every one of them.
+    observed_state_hashes: BTreeSet<u64>,
+    /// Watches epoch coverage snapshots for plateaus.
+    plateau_detector: PlateauDetector,
+    /// Skips with a TTL that have not expired yet.
+    active_skips: Vec<ActiveSkip>,
+    /// Guard pass/fail counts per branch and model state.
+    dead_branches: DeadBranchTracker,
 }
 
 impl Coordinator {
-    pub fn new(config: CoordinatorConfig) -> Self {
+    pub fn new(config: CoordinatorConfig, policy: Box<dyn DirectivePolicy>) -> Self {
+        let plateau_detector =
+            PlateauDetector::new(config.plateau_window, config.coverage_floor_threshold);
+        let dead_branches = DeadBranchTracker::new(config.dead_branch_epochs);
         Self {
             config,
+            policy,
             epoch: 0,
             pending_signals: Vec::new(),
             directive_log: DirectiveLog::new(),
             timeout_tracker: TimeoutTracker::new(),
             signal_seqno: 0,
             uncovered_target_branches: Vec::new(),
+            observed_state_hashes: BTreeSet::from([0]),
+            plateau_detector,
+            active_skips: Vec::new(),
+            dead_branches,
         }
     }
 
@@ -97,6 +151,32 @@ impl Coordinator {
         }
     }
 
+    /// Record the coverage fraction reached at the end of an epoch.
+    ///
+    /// When coverage has stalled for `plateau_window` epochs, a
+    /// `CoveragePlateau` signal is fed in like any other signal; returns
+    /// directives if that completes an epoch.
+    pub fn observe_coverage(
+        &mut self,
+        current_coverage: f64,
+        weight_table: &mut WeightTable,
+        alt_block_branches: &[Vec<String>],
+    ) -> Vec<Directive> {
+        match self.plateau_detector.observe(current_coverage) {
+            Some(signal_type) => self.feed_signal(
+                SignalEvent {
+                    thread_id: 0,
+                    local_step: 0,
+                    model_state_hash: 0,
+                    signal_type,
+                },
+                weight_table,
+                alt_block_branches,
+            ),
+            None => Vec::new(),
+        }
+    }
+
     /// Force-process any remaining signals (e.g., at campaign end).
     pub fn flush(
         &mut self,
@@ -109,6 +189,32 @@ impl Coordinator {
         self.process_epoch(weight_table, alt_block_branches)
     }
 
+    /// Replay a recorded signal sequence, whatever order it arrives in.
+    ///
+    /// The signals are put into total order first — `(thread_id,
+    /// local_step)`, ties broken by the serialized signal — and then fed
+    /// epoch by epoch and flushed, so the returned directive log depends
+    /// only on the recorded signals, never on how threads interleaved them.
+    pub fn replay_signals(
+        &mut self,
+        mut signals: Vec<SignalEvent>,
+        weight_table: &mut WeightTable,
+        alt_block_branches: &[Vec<String>],
+    ) -> DirectiveLog {
+        signals.sort_by_cached_key(|s| {
+            (
+                s.thread_id,
+                s.local_step,
+                serde_json::to_string(&s.signal_type).unwrap_or_default(),
+            )
+        });
+        for signal in signals {
+            self.feed_signal(signal, weight_table, alt_block_branches);
+        }
+        self.flush(weight_table, alt_block_branches);
+        self.directive_log.clone()
+    }
+
     /// Process one epoch: sort signals, map to directives, apply.
     fn process_epoch(
         &mut self,
@@ -124,32 +230,73 @@ impl Coordinator {
         });
 
         // Step 2: Assign monotonic sequence numbers.
+        // Record each signal's model state for normalization, and guard
+        // outcomes for dead-branch detection. Without a branch id the action
+        // stands in for the branch, as in `StandardPolicy`.
         for signal in &signals {
             self.signal_seqno += 1;
-            let _ = signal; // seqno assigned globally, signals just ordered
+            self.observed_state_hashes.insert(signal.model_state_hash);
+            match &signal.signal_type {
+                SignalType::GuardFailure {
+                    branch_id,
+                    action,
+                    model_state_hash,
+                } => {
+                    let branch = if branch_id.is_empty() {
+                        action
+                    } else {
+                        branch_id
+                    };
+                    self.dead_branches.record_failure(
+                        branch,
+                        *model_state_hash,
+                        &signal.signal_type,
+                    );
+                }
+                SignalType::GuardPass {
+                    branch_id,
+                    action,
+                    model_state_hash,
+                } => {
+                    let branch = if branch_id.is_empty() {
+                        action
+                    } else {
+                        branch_id
+                    };
+                    self.dead_branches.record_pass(branch, *model_state_hash);
+                }
+                _ => {}
+            }
         }
 
         // Step 3: Map signals to directives.
         let mut directives = Vec::new();
+        let mut triggers = Vec::new();
         for signal in &signals {
-            let new_directives = self.map_signal_to_directives(&signal.signal_type);
+            let mut ctx = PolicyContext {
+                config: &self.config,
+                uncovered_target_branches: &self.uncovered_target_branches,
+                timeout_tracker: &mut self.timeout_tracker,
+            };
+            let new_directives = self.policy.map_signal(&signal.signal_type, &mut ctx);
             for d in &new_directives {
                 self.directive_log
                     .record(d.clone(), signal.signal_type.clone(), self.epoch);
+                triggers.push(&signal.signal_type);
             }
             directives.extend(new_directives);
         }
 
         // Step 4: Apply per-epoch weight decay.
-        decay::apply_epoch_decay(weight_table, &self.config.decay);
+        decay::apply_epoch_decay(weight_table, &self.config.decay, self.epoch + 1);
 
-        // Step 5: Normalize weights per alt block.
+        // Step 5: Normalize weights per alt block, independently for each
+        // model state hash observed so far.
         for block_branches in alt_block_branches {
             let branch_refs: Vec<&str> = block_branches.iter().map(|s| s.as_str()).collect();
-            // Normalize for each unique model_state_hash we've seen.
-            // For simplicity, normalize at hash 0 (default state).
-            // Full implementation would track all observed hashes.
-            weight_table.normalize(&branch_refs, 0);
+            for &hash in &self.observed_state_hashes {
+                weight_table.normalize(&branch_refs, hash);
+            }
         }
 
         // Step 6: Enforce coverage floor.
@@ -160,102 +307,66 @@ impl Coordinator {
         );
 
         // Step 7: Apply directives to weight table.
-        for directive in &directives {
-            self.apply_directive(directive, weight_table);
+        for (directive, triggered_by) in directives.iter().zip(triggers) {
+            self.apply_directive(directive, triggered_by, weight_table);
         }
 
+        // Step 8: Skip branches whose guard keeps failing and never passed.
+        // Empirical, so the skip expires rather than zeroing the branch.
+        for dead in self.dead_branches.end_epoch() {
+            for model_state_hash in dead.model_state_hashes {
+                let directive = Directive::Skip {
+                    branch_id: dead.branch_id.clone(),
+                    model_state_hash,
+                    remaining: 0,
+                    ttl_epochs: Some(self.config.dead_branch_skip_ttl),
+                };
+                self.directive_log
+                    .record(directive.clone(), dead.triggered_by.clone(), self.epoch);
+                self.apply_directive(&directive, &dead.triggered_by, weight_table);
+                directives.push(directive);
+            }
+        }
+
+        // Step 9: Start the next epoch, lifting skips that expire with it.
         self.epoch += 1;
+        self.timeout_tracker.set_epoch(self.epoch);
+        directives.extend(self.expire_skips(weight_table));
         directives
     }
 
-    /// Map a single signal to zero or more directives.
-    fn map_signal_to_directives(&mut self, signal: &SignalType) -> Vec<Directive> {
-        match signal {
-            SignalType::CoverageDelta { action, .. } => {
-                // Boost weight on the branch that led to new coverage.
-                // We use the action name as a proxy for branch_id here.
-                vec![Directive::AdjustWeight {
-                    branch_id: action.clone(),
-                    model_state_hash: 0,
-                    multiplier: self.config.coverage_boost,
-                }]
-            }
+    /// Restore the pre-skip weight of every skip expiring by the current
+    /// epoch, logging a `SkipExpired` directive for each.
+    fn expire_skips(&mut self, weight_table: &mut WeightTable) -> Vec<Directive> {
+        let epoch = self.epoch;
+        let (expired, active): (Vec<ActiveSkip>, Vec<ActiveSkip>) =
+            std::mem::take(&mut self.active_skips)
+                .into_iter()
+                .partition(|skip| skip.expires_at_epoch <= epoch);
+        self.active_skips = active;
 
-            SignalType::PropertyViolation { property, .. } => {
-                // Force nearby branches for deeper investigation.
-                vec![Directive::Force {
-                    action: property.clone(),
-                    budget: self.config.force_budget,
-                }]
-            }
-
-            SignalType::Discrepancy { action, .. } => {
-                // Force the divergent path + increase loop bounds.
-                vec![Directive::Force {
-                    action: action.clone(),
-                    budget: self.config.force_budget,
-                }]
-            }
-
-            SignalType::Crash { action, .. } => {
-                // Force with boundary values and related inputs.
-                vec![
-                    Directive::Force {
-                        action: action.clone(),
-                        budget: self.config.force_budget * 2,
-                    },
-                    Directive::AdjustWeight {
-                        branch_id: action.clone(),
-                        model_state_hash: 0,
-                        multiplier: self.config.finding_boost,
-                    },
-                ]
-            }
-
-            SignalType::Timeout {
-                action,
-                fuel_consumed,
-            } => {
-                // Two-step timeout response via the tracker.
-                self.timeout_tracker
-                    .handle_timeout(action, *fuel_consumed)
-                    .into_iter()
-                    .collect()
-            }
-
-            SignalType::GuardFailure {
-                branch_id,
-                action,
-                model_state_hash,
-            } => {
-                // State-conditioned decay: "branch B is invalid WHEN model is in state S"
-                let bid = if branch_id.is_empty() {
-                    action
-                } else {
-                    branch_id
-                };
-                vec![Directive::AdjustWeight {
-                    branch_id: bid.clone(),
-                    model_state_hash: *model_state_hash,
-                    multiplier: self.config.guard_failure_decay,
-                }]
-            }
-
-            SignalType::CoveragePlateau { .. } => {
-                // Convert each uncovered target to a Force directive.
-                self.uncovered_target_branches
-                    .iter()
-                    .map(|branch| Directive::Force {
-                        action: branch.clone(),
-                        budget: self.config.force_budget,
-                    })
-                    .collect()
-            }
+        let mut restored = Vec::with_capacity(expired.len());
+        for skip in expired {
+            weight_table.set(&skip.branch_id, skip.model_state_hash, skip.restore_weight);
+            let directive = Directive::SkipExpired {
+                branch_id: skip.branch_id,
+                model_state_hash: skip.model_state_hash,
+                restored_weight: skip.restore_weight,
+            };
+            self.directive_log
+                .record(directive.clone(), skip.triggered_by, epoch);
+            restored.push(directive);
         }
+        restored
     }
 
     /// Apply a single directive to the weight table.
-    fn apply_directive(&self, directive: &Directive, weight_table: &mut WeightTable) {
+    fn apply_directive(
+        &mut self,
+        directive: &Directive,
+        triggered_by: &SignalType,
+        weight_table: &mut WeightTable,
+    ) {
         match directive {
             Directive::AdjustWeight {
                 branch_id,
@@ -275,13 +386,40 @@ impl Coordinator {
             Directive::Skip {
                 branch_id,
                 model_state_hash,
+                ttl_epochs,
                 ..
             } => {
+                if let Some(ttl) = ttl_epochs {
+                    // Suppressed for `ttl` full epochs after this one.
+                    let expires_at_epoch = self.epoch + 1 + u64::from(*ttl);
+                    match self.active_skips.iter_mut().find(|skip| {
+                        skip.branch_id == *branch_id && skip.model_state_hash == *model_state_hash
+                    }) {
+                        // Re-skipped while skipped: keep the original weight.
+                        Some(skip) => {
+                            skip.expires_at_epoch = skip.expires_at_epoch.max(expires_at_epoch);
+                        }
+                        None => self.active_skips.push(ActiveSkip {
+                            branch_id: branch_id.clone(),
+                            model_state_hash: *model_state_hash,
+                            expires_at_epoch,
+                            restore_weight: weight_table.get(branch_id, *model_state_hash),
+                            triggered_by: triggered_by.clone(),
+                        }),
+                    }
+                }
                 // Temporarily set very low weight.
                 weight_table.set(branch_id, *model_state_hash, 0.01);
             }
+            Directive::SkipExpired {
+                branch_id,
+                model_state_hash,
+                restored_weight,
+            } => {
+                weight_table.set(branch_id, *model_state_hash, *restored_weight);
+            }
             // Force and LoopLimit affect the strategy stack, not weight table.
-            // They're handled by the traversal engine when it checks active directives.
+            // The engine hands them to the strategy (see `DirectiveAwareStrategy`).
             Directive::Force { .. } | Directive::LoopLimit { .. } => {}
         }
     }
@@ -291,30 +429,91 @@ impl Coordinator {
         self.uncovered_target_branches = branches;
     }
 
+    /// Branches currently protected by the coverage floor.
+    pub fn uncovered_target_branches(&self) -> &[String] {
+        &self.uncovered_target_branches
+    }
+
     /// Get the directive log for audit/replay.
     pub fn directive_log(&self) -> &DirectiveLog {
         &self.directive_log
     }
 
+    /// The policy mapping signals to directives.
+    pub fn policy(&self) -> &dyn DirectivePolicy {
+        self.policy.as_ref()
+    }
+
     /// Current epoch number.
     pub fn current_epoch(&self) -> u64 {
         self.epoch
     }
 
+    /// Model state hashes observed so far, in ascending order.
+    pub fn observed_state_hashes(&self) -> &BTreeSet<u64> {
+        &self.observed_state_hashes
+    }
+
+    /// Guard outcomes recorded for a branch in one model state.
+    pub fn guard_counts(&self, branch_id: &str, model_state_hash: u64) -> GuardCounts {
+        self.dead_branches.counts(branch_id, model_state_hash)
+    }
+
+    /// Timeout escalation state per action.
+    pub fn timeout_tracker(&self) -> &TimeoutTracker {
+        &self.timeout_tracker
+    }
+
     /// Total signals processed.
     pub fn total_signals_processed(&self) -> u64 {
         self.signal_seqno
     }
 }
 
+/// Replay `signals` in their given order and under `shuffles` seeded
+/// permutations of it, each on a fresh coordinator and a copy of
+/// `weight_table`, and panic unless every replay yields a byte-identical
+/// directive log. Returns the log.
+///
+/// Test-only: compiled for this crate's tests and under the `test-util`
+/// feature.
+#[cfg(any(test, feature = "test-util"))]
+pub fn assert_replay_deterministic(
+    new_coordinator: impl Fn() -> Coordinator,
+    signals: &[SignalEvent],
+    weight_table: &WeightTable,
+    alt_block_branches: &[Vec<String>],
+    shuffles: u64,
+) -> DirectiveLog {
+    let replay = |order: Vec<SignalEvent>| {
+        let mut weights = weight_table.clone();
+        new_coordinator().replay_signals(order, &mut weights, alt_block_branches)
+    };
+    let expected = replay(signals.to_vec());
+    let rendered = format!("{:?}", expected.entries());
+    for seed in 0..shuffles {
+        let mut order = signals.to_vec();
+        order.shuffle(&mut RngStreams::new(seed).stage(0));
+        assert_eq!(
+            format!("{:?}", replay(order).entries()),
+            rendered,
+            "directive log differs under shuffle seed {seed}"
+        );
+    }
+    expected
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapt::policy::StandardPolicy;
+    use crate::traversal::signal::SignalType;
 
     fn make_signal(signal_type: SignalType) -> SignalEvent {
         SignalEvent {
             thread_id: 0,
             local_step: 0,
+            model_state_hash: 0,
             signal_type,
         }
     }
@@ -325,7 +524,7 @@ mod tests {
             epoch_size: 3,
             ..Default::default()
         };
-        let mut coordinator = Coordinator::new(config);
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
         let mut weight_table = WeightTable::new();
 
         let signal = SignalType::CoverageDelta {
@@ -351,7 +550,7 @@ mod tests {
             epoch_size: 100,
             ..Default::default()
         };
-        let mut coordinator = Coordinator::new(config);
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
         let mut weight_table = WeightTable::new();
 
         coordinator.feed_signal(
@@ -374,7 +573,7 @@ mod tests {
             epoch_size: 1,
             ..Default::default()
         };
-        let mut coordinator = Coordinator::new(config);
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
         let mut weight_table = WeightTable::new();
         weight_table.set_default("act", 50.0);
 
@@ -399,7 +598,7 @@ mod tests {
             guard_failure_decay: 0.3,
             ..Default::default()
         };
-        let mut coordinator = Coordinator::new(config);
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
         let mut weight_table = WeightTable::new();
         weight_table.set_default("br", 100.0);
 
@@ -425,7 +624,7 @@ mod tests {
             epoch_size: 1,
             ..Default::default()
         };
-        let mut coordinator = Coordinator::new(config);
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
         let mut weight_table = WeightTable::new();
 
         let directives = coordinator.feed_signal(
@@ -453,7 +652,7 @@ mod tests {
             epoch_size: 1,
             ..Default::default()
         };
-        let mut coordinator = Coordinator::new(config);
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
         coordinator.set_uncovered_target_branches(vec!["target_a".into(), "target_b".into()]);
         let mut weight_table = WeightTable::new();
 
@@ -477,13 +676,41 @@ mod tests {
         assert!(force_actions.contains(&"target_b".to_string()));
     }
 
+    #[test]
+    fn test_flat_coverage_snapshots_force_uncovered_targets() {
+        let config = CoordinatorConfig {
+            epoch_size: 1,
+            plateau_window: 2,
+            ..Default::default()
+        };
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
+        coordinator.set_uncovered_target_branches(vec!["target_a".into()]);
+        let mut weight_table = WeightTable::new();
+
+        assert!(coordinator
+            .observe_coverage(0.6, &mut weight_table, &[])
+            .is_empty());
+        assert!(coordinator
+            .observe_coverage(0.6, &mut weight_table, &[])
+            .is_empty());
+        let directives = coordinator.observe_coverage(0.61, &mut weight_table, &[]);
+
+        assert!(directives
+            .iter()
+            .any(|d| matches!(d, Directive::Force { action, .. } if action == "target_a")));
+        assert!(matches!(
+            coordinator.directive_log().entries()[0].triggered_by,
+            SignalType::CoveragePlateau { .. }
+        ));
+    }
+
     #[test]
     fn test_directive_log_accumulates_across_epochs() {
         let config = CoordinatorConfig {
             epoch_size: 1,
             ..Default::default()
         };
-        let mut coordinator = Coordinator::new(config);
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
         let mut weight_table = WeightTable::new();
 
         coordinator.feed_signal(
@@ -513,7 +740,7 @@ mod tests {
             epoch_size: 3,
             ..Default::default()
         };
-        let mut coordinator = Coordinator::new(config);
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
         let mut weight_table = WeightTable::new();
 
         // Signals from different threads, out of order.
@@ -521,6 +748,7 @@ mod tests {
             SignalEvent {
                 thread_id: 1,
                 local_step: 2,
+                model_state_hash: 0,
                 signal_type: SignalType::CoverageDelta {
                     node_id: 3,
                     action: "c".into(),
@@ -529,6 +757,7 @@ mod tests {
             SignalEvent {
                 thread_id: 0,
                 local_step: 1,
+                model_state_hash: 0,
                 signal_type: SignalType::CoverageDelta {
                     node_id: 1,
                     action: "a".into(),
@@ -537,6 +766,7 @@ mod tests {
             SignalEvent {
                 thread_id: 1,
                 local_step: 1,
+                model_state_hash: 0,
                 signal_type: SignalType::CoverageDelta {
                     node_id: 2,
                     action: "b".into(),
@@ -557,4 +787,370 @@ mod tests {
             assert!(log.entries()[i].seqno > log.entries()[i - 1].seqno);
         }
     }
+
+    /// Treats timeouts like crashes; everything else is standard.
+    struct TimeoutAsCrashPolicy;
+
+    impl DirectivePolicy for TimeoutAsCrashPolicy {
+        fn map_signal(&self, signal: &SignalType, ctx: &mut PolicyContext<'_>) -> Vec<Directive> {
+            match signal {
+                SignalType::Timeout { action, .. } => StandardPolicy.map_signal(
+                    &SignalType::Crash {
+                        action: action.clone(),
+                        message: "timeout".into(),
+                    },
+                    ctx,
+                ),
+                other => StandardPolicy.map_signal(other, ctx),
+            }
+        }
+
+        fn name(&self) -> &str {
+            "timeout-as-crash"
+        }
+    }
+
+    #[test]
+    fn test_custom_policy_treats_timeout_as_finding() {
+        let config = CoordinatorConfig {
+            epoch_size: 1,
+            force_budget: 7,
+            finding_boost: 3.0,
+            ..Default::default()
+        };
+        let mut coordinator = Coordinator::new(config, Box::new(TimeoutAsCrashPolicy));
+        let mut weight_table = WeightTable::new();
+        assert_eq!(coordinator.policy().name(), "timeout-as-crash");
+
+        let directives = coordinator.feed_signal(
+            make_signal(SignalType::Timeout {
+                action: "slow".into(),
+                fuel_consumed: Some(1000),
+            }),
+            &mut weight_table,
+            &[],
+        );
+
+        assert_eq!(
+            directives,
+            vec![
+                Directive::Force {
+                    action: "slow".into(),
+                    budget: 14,
+                },
+                Directive::AdjustWeight {
+                    branch_id: "slow".into(),
+                    model_state_hash: 0,
+                    multiplier: 3.0,
+                },
+            ]
+        );
+        assert_eq!(coordinator.directive_log().len(), 2);
+    }
+
+    #[test]
+    fn test_standard_policy_timeout_schedules_retry() {
+        let config = CoordinatorConfig {
+            epoch_size: 1,
+            ..Default::default()
+        };
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
+        let mut weight_table = WeightTable::new();
+
+        let directives = coordinator.feed_signal(
+            make_signal(SignalType::Timeout {
+                action: "slow".into(),
+                fuel_consumed: Some(1000),
+            }),
+            &mut weight_table,
+            &[],
+        );
+
+        // First timeout only schedules a retry: no finding boost.
+        assert!(!directives
+            .iter()
+            .any(|d| matches!(d, Directive::AdjustWeight { .. })));
+    }
+
+    /// Skips guard-failing branches for two epochs.
+    struct SkipOnGuardFailurePolicy;
+
+    impl DirectivePolicy for SkipOnGuardFailurePolicy {
+        fn map_signal(&self, signal: &SignalType, ctx: &mut PolicyContext<'_>) -> Vec<Directive> {
+            match signal {
+                SignalType::GuardFailure {
+                    branch_id,
+                    model_state_hash,
+                    ..
+                } => vec![Directive::Skip {
+                    branch_id: branch_id.clone(),
+                    model_state_hash: *model_state_hash,
+                    remaining: 0,
+                    ttl_epochs: Some(2),
+                }],
+                other => StandardPolicy.map_signal(other, ctx),
+            }
+        }
+
+        fn name(&self) -> &str {
+            "skip-on-guard-failure"
+        }
+    }
+
+    #[test]
+    fn test_skip_expires_after_ttl() {
+        let config = CoordinatorConfig {
+            epoch_size: 1,
+            decay: DecayConfig {
+                policy: decay::DecayPolicy::None,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut coordinator = Coordinator::new(config, Box::new(SkipOnGuardFailurePolicy));
+        let mut weight_table = WeightTable::new();
+        weight_table.set("br", 0, 40.0);
+        let filler = || {
+            make_signal(SignalType::CoverageDelta {
+                node_id: 1,
+                action: "other".into(),
+            })
+        };
+
+        // Epoch 0 ends with the skip applied.
+        coordinator.feed_signal(
+            make_signal(SignalType::GuardFailure {
+                branch_id: "br".into(),
+                action: "a".into(),
+                model_state_hash: 0,
+            }),
+            &mut weight_table,
+            &[],
+        );
+        assert!(weight_table.get("br", 0) < 1.0);
+
+        // Suppressed throughout epochs 1 and 2.
+        coordinator.feed_signal(filler(), &mut weight_table, &[]);
+        assert_eq!(coordinator.current_epoch(), 2);
+        assert!(weight_table.get("br", 0) < 1.0);
+
+        // Restored as epoch 3 starts.
+        let directives = coordinator.feed_signal(filler(), &mut weight_table, &[]);
+        assert_eq!(coordinator.current_epoch(), 3);
+        assert_eq!(weight_table.get("br", 0), 40.0);
+        assert!(directives
+            .iter()
+            .any(|d| matches!(d, Directive::SkipExpired { branch_id, .. } if branch_id == "br")));
+
+        let logged: Vec<_> = coordinator
+            .directive_log()
+            .entries()
+            .iter()
+            .filter(|e| {
+                matches!(
+                    &e.directive,
+                    Directive::Skip { branch_id, .. } | Directive::SkipExpired { branch_id, .. }
+                        if branch_id == "br"
+                )
+            })
+            .map(|e| e.epoch)
+            .collect();
+        assert_eq!(logged, vec![0, 3]);
+    }
+
+    #[test]
+    fn test_always_failing_branch_is_skipped_after_threshold() {
+        let config = CoordinatorConfig {
+            epoch_size: 1,
+            dead_branch_epochs: 3,
+            dead_branch_skip_ttl: 40,
+            ..Default::default()
+        };
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
+        let mut weight_table = WeightTable::new();
+        let mut feed = |coordinator: &mut Coordinator| {
+            coordinator.feed_signal(
+                make_signal(SignalType::GuardFailure {
+                    branch_id: "dead".into(),
+                    action: "a".into(),
+                    model_state_hash: 0,
+                }),
+                &mut weight_table,
+                &[],
+            )
+        };
+        let is_skip = |d: &Directive| {
+            matches!(
+                d,
+                Directive::Skip { branch_id, ttl_epochs: Some(40), .. } if branch_id == "dead"
+            )
+        };
+
+        for _ in 0..2 {
+            assert!(!feed(&mut coordinator).iter().any(is_skip));
+        }
+        assert!(feed(&mut coordinator).iter().any(is_skip));
+        assert_eq!(
+            coordinator.guard_counts("dead", 0),
+            GuardCounts {
+                passes: 0,
+                failures: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_only_guard_passes_keep_branch_alive() {
+        let config = CoordinatorConfig {
+            epoch_size: 2,
+            dead_branch_epochs: 2,
+            ..Default::default()
+        };
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
+        let mut weight_table = WeightTable::new();
+        let mut feed = |coordinator: &mut Coordinator, action: &str, other: SignalType| {
+            let mut directives = coordinator.feed_signal(
+                make_signal(SignalType::GuardFailure {
+                    branch_id: String::new(),
+                    action: action.into(),
+                    model_state_hash: 0,
+                }),
+                &mut weight_table,
+                &[],
+            );
+            directives.extend(coordinator.feed_signal(make_signal(other), &mut weight_table, &[]));
+            directives
+        };
+        let skips = |directives: &[Directive], branch: &str| {
+            directives
+                .iter()
+                .any(|d| matches!(d, Directive::Skip { branch_id, .. } if branch_id == branch))
+        };
+
+        // New coverage of "a" is not a guard pass, so "a" is still dead.
+        let covered = || SignalType::CoverageDelta {
+            node_id: 1,
+            action: "a".into(),
+        };
+        assert!(!skips(&feed(&mut coordinator, "a", covered()), "a"));
+        assert!(skips(&feed(&mut coordinator, "a", covered()), "a"));
+        assert_eq!(coordinator.guard_counts("a", 0).passes, 0);
+
+        // A guard pass of "b" keeps it alive.
+        let passed = || SignalType::GuardPass {
+            branch_id: String::new(),
+            action: "b".into(),
+            model_state_hash: 5,
+        };
+        for _ in 0..3 {
+            assert!(!skips(&feed(&mut coordinator, "b", passed()), "b"));
+        }
+        assert_eq!(
+            coordinator.guard_counts("b", 5),
+            GuardCounts {
+                passes: 3,
+                failures: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_normalizes_each_observed_state_hash() {
+        let config = CoordinatorConfig {
+            epoch_size: 2,
+            ..Default::default()
+        };
+        let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
+        let mut weight_table = WeightTable::new();
+        weight_table.set("x", 7, 30.0);
+        weight_table.set("y", 7, 10.0);
+        weight_table.set("x", 9, 5.0);
+        weight_table.set("y", 9, 45.0);
+        let blocks = vec![vec!["x".to_string(), "y".to_string()]];
+
+        for (step, hash) in [(0, 7), (1, 9)] {
+            coordinator.feed_signal(
+                SignalEvent {
+                    thread_id: 0,
+                    local_step: step,
+                    model_state_hash: hash,
+                    signal_type: SignalType::CoverageDelta {
+                        node_id: 1,
+                        action: "unrelated".into(),
+                    },
+                },
+                &mut weight_table,
+                &blocks,
+            );
+        }
+
+        assert!(coordinator.observed_state_hashes().contains(&7));
+        assert!(coordinator.observed_state_hashes().contains(&9));
+        for hash in [7, 9] {
+            let total = weight_table.get("x", hash) + weight_table.get("y", hash);
+            assert!((total - 100.0).abs() < 1e-9, "hash {hash} total {total}");
+        }
+        // Each state keeps its own ratio.
+        assert!(weight_table.get("x", 7) > weight_table.get("y", 7));
+        assert!(weight_table.get("x", 9) < weight_table.get("y", 9));
+    }
+
+    #[test]
+    fn test_replay_is_independent_of_arrival_order() {
+        let signal_types = [
+            SignalType::CoverageDelta {
+                node_id: 1,
+                action: "a".into(),
+            },
+            SignalType::GuardFailure {
+                branch_id: "br".into(),
+                action: "b".into(),
+                model_state_hash: 7,
+            },
+            SignalType::Crash {
+                action: "buggy".into(),
+                message: "trap".into(),
+            },
+            SignalType::Timeout {
+                action: "slow".into(),
+                fuel_consumed: Some(1_000),
+            },
+            SignalType::CoverageDelta {
+                node_id: 2,
+                action: "c".into(),
+            },
+        ];
+        let signals: Vec<SignalEvent> = (0..10)
+            .map(|i| SignalEvent {
+                thread_id: i as u32 % 3,
+                local_step: i / 3,
+                model_state_hash: i % 2,
+                signal_type: signal_types[i as usize % signal_types.len()].clone(),
+            })
+            .collect();
+        let mut weight_table = WeightTable::new();
+        weight_table.set_default("br", 100.0);
+        weight_table.set_default("a", 50.0);
+        let alt_blocks = vec![vec!["a".to_string(), "br".to_string()]];
+
+        let log = assert_replay_deterministic(
+            || {
+                Coordinator::new(
+                    CoordinatorConfig {
+                        epoch_size: 3,
+                        ..Default::default()
+                    },
+                    Box::new(StandardPolicy),
+                )
+            },
+            &signals,
+            &weight_table,
+            &alt_blocks,
+            20,
+        );
+
+        // Ten signals in epochs of three: directives span several epochs.
+        let epochs: BTreeSet<u64> = log.entries().iter().map(|e| e.epoch).collect();
+        assert!(epochs.is_superset(&BTreeSet::from([0, 1, 2])));
+    }
 }
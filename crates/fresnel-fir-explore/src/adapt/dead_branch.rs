@@ -0,0 +1,162 @@
+//! Empirical dead-branch detection.
+//!
+//! Counts guard passes and failures per branch and model state. A branch
+//! whose guard has failed in every epoch of a run of `window` epochs,
+//! without ever passing in any observed state, is reported as dead. Unlike
+//! a reachability proof this is only evidence, so the coordinator answers
+//! with a long but expiring skip rather than a permanent zero.
+
+use std::collections::BTreeMap;
+
+use crate::traversal::signal::SignalType;
+
+/// Guard outcomes observed for one branch in one model state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GuardCounts {
+    pub passes: u64,
+    pub failures: u64,
+}
+
+/// A branch found dead at an epoch boundary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadBranch {
+    pub branch_id: String,
+    /// Model states the guard was seen failing in, ascending.
+    pub model_state_hashes: Vec<u64>,
+    /// The latest guard failure, for attribution in the directive log.
+    pub triggered_by: SignalType,
+}
+
+#[derive(Debug, Clone, Default)]
+struct BranchGuardStats {
+    by_state: BTreeMap<u64, GuardCounts>,
+    /// Consecutive epochs with failures and no pass ever recorded.
+    failing_epochs: u32,
+    failed_this_epoch: bool,
+    last_failure: Option<SignalType>,
+}
+
+/// Tracks guard outcomes per `(branch_id, model_state_hash)`.
+#[derive(Debug, Clone)]
+pub struct DeadBranchTracker {
+    /// Consecutive failing epochs before a branch is reported; 0 disables.
+    window: u32,
+    branches: BTreeMap<String, BranchGuardStats>,
+}
+
+impl DeadBranchTracker {
+    pub fn new(window: u32) -> Self {
+        Self {
+            window,
+            branches: BTreeMap::new(),
+        }
+    }
+
+    /// Record a guard failure of `branch_id` in `model_state_hash`.
+    pub fn record_failure(&mut self, branch_id: &str, model_state_hash: u64, signal: &SignalType) {
+        let stats = self.branches.entry(branch_id.to_string()).or_default();
+        stats.by_state.entry(model_state_hash).or_default().failures += 1;
+        stats.failed_this_epoch = true;
+        stats.last_failure = Some(signal.clone());
+    }
+
+    /// Record that `branch_id` got past its guard in `model_state_hash`.
+    pub fn record_pass(&mut self, branch_id: &str, model_state_hash: u64) {
+        let stats = self.branches.entry(branch_id.to_string()).or_default();
+        stats.by_state.entry(model_state_hash).or_default().passes += 1;
+        stats.failing_epochs = 0;
+    }
+
+    /// Outcomes recorded so far for `branch_id` in `model_state_hash`.
+    pub fn counts(&self, branch_id: &str, model_state_hash: u64) -> GuardCounts {
+        self.branches
+            .get(branch_id)
+            .and_then(|stats| stats.by_state.get(&model_state_hash))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Close an epoch and return the branches that just completed `window`
+    /// failing epochs. Their streak starts over, so a branch that keeps
+    /// failing is reported again every `window` epochs.
+    pub fn end_epoch(&mut self) -> Vec<DeadBranch> {
+        let mut dead = Vec::new();
+        for (branch_id, stats) in &mut self.branches {
+            let failed = std::mem::take(&mut stats.failed_this_epoch);
+            if !failed {
+                // A quiet epoch breaks the streak.
+                stats.failing_epochs = 0;
+                continue;
+            }
+            let ever_passed = stats.by_state.values().any(|c| c.passes > 0);
+            if ever_passed || self.window == 0 {
+                continue;
+            }
+            stats.failing_epochs += 1;
+            if stats.failing_epochs < self.window {
+                continue;
+            }
+            stats.failing_epochs = 0;
+            dead.push(DeadBranch {
+                branch_id: branch_id.clone(),
+                model_state_hashes: stats
+                    .by_state
+                    .iter()
+                    .filter(|(_, c)| c.failures > 0)
+                    .map(|(&hash, _)| hash)
+                    .collect(),
+                triggered_by: stats
+                    .last_failure
+                    .clone()
+                    .expect("failed this epoch, so a failure was recorded"),
+            });
+        }
+        dead
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(hash: u64) -> SignalType {
+        SignalType::GuardFailure {
+            branch_id: "br".into(),
+            action: "a".into(),
+            model_state_hash: hash,
+        }
+    }
+
+    #[test]
+    fn test_any_pass_keeps_branch_alive() {
+        let mut tracker = DeadBranchTracker::new(2);
+        tracker.record_failure("br", 1, &failure(1));
+        tracker.record_pass("br", 2);
+        for _ in 0..4 {
+            tracker.record_failure("br", 1, &failure(1));
+            assert!(tracker.end_epoch().is_empty());
+        }
+        assert_eq!(
+            tracker.counts("br", 1),
+            GuardCounts {
+                passes: 0,
+                failures: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_quiet_epoch_resets_streak() {
+        let mut tracker = DeadBranchTracker::new(2);
+        tracker.record_failure("br", 1, &failure(1));
+        assert!(tracker.end_epoch().is_empty());
+        assert!(tracker.end_epoch().is_empty());
+        tracker.record_failure("br", 3, &failure(3));
+        assert!(tracker.end_epoch().is_empty());
+        tracker.record_failure("br", 3, &failure(3));
+        let dead = tracker.end_epoch();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].model_state_hashes, vec![1, 3]);
+        assert_eq!(dead[0].triggered_by, failure(3));
+    }
+}
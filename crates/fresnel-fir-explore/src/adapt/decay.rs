@@ -1,7 +1,7 @@
 //! Per-epoch weight decay, normalization, and coverage floor enforcement.
 //!
 //! Each epoch applies:
-//! 1. Global decay to prevent fixation on explored paths.
+//! 1. Global decay (per `DecayPolicy`) to prevent fixation on explored paths.
 //! 2. Boost on finding-yielding branches proportional to severity.
 //! 3. Normalization of alt-block branches to sum to 100.
 //! 4. Coverage floor enforcement ensuring uncovered targets maintain
@@ -9,12 +9,24 @@
 
 use crate::traversal::weight_table::WeightTable;
 
+/// How state-conditioned weights decay from one epoch to the next.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecayPolicy {
+    /// Weights never decay.
+    None,
+    /// Subtract `rate` from every weight each epoch.
+    Linear { rate: f64 },
+    /// Multiply every weight by `factor` each epoch. Typical: 0.95.
+    Exponential { factor: f64 },
+    /// Multiply every weight by `factor` after every `every_n_epochs`-th epoch.
+    Step { every_n_epochs: u64, factor: f64 },
+}
+
 /// Configuration for per-epoch decay behavior.
 #[derive(Debug, Clone)]
 pub struct DecayConfig {
-    /// Decay factor applied to all state-conditioned weights per epoch.
-    /// Prevents fixation. Typical: 0.95.
-    pub global_decay: f64,
+    /// Decay applied to all state-conditioned weights. Prevents fixation.
+    pub policy: DecayPolicy,
     /// Minimum weight — prevents complete suppression.
     /// Only overridden by provable unreachability.
     pub min_weight: f64,
@@ -23,18 +35,31 @@ pub struct DecayConfig {
 impl Default for DecayConfig {
     fn default() -> Self {
         Self {
-            global_decay: 0.95,
+            policy: DecayPolicy::Exponential { factor: 0.95 },
             min_weight: 0.1,
         }
     }
 }
 
-/// Apply per-epoch decay to all state-conditioned weights.
+/// Apply decay to all state-conditioned weights at the end of `epoch`
+/// (1-based: the first epoch to close is epoch 1).
 ///
 /// After decay, no weight drops below `min_weight` unless it was
 /// already at 0.0 (provably unreachable).
-pub fn apply_epoch_decay(weight_table: &mut WeightTable, config: &DecayConfig) {
-    weight_table.decay_all(config.global_decay);
+pub fn apply_epoch_decay(weight_table: &mut WeightTable, config: &DecayConfig, epoch: u64) {
+    match config.policy {
+        DecayPolicy::None => {}
+        DecayPolicy::Linear { rate } => weight_table.reduce_all(rate, config.min_weight),
+        DecayPolicy::Exponential { factor } => weight_table.decay_all(factor),
+        DecayPolicy::Step {
+            every_n_epochs,
+            factor,
+        } => {
+            if every_n_epochs > 0 && epoch.is_multiple_of(every_n_epochs) {
+                weight_table.decay_all(factor);
+            }
+        }
+    }
     weight_table.clamp_min(config.min_weight);
 }
 
@@ -93,9 +118,10 @@ mod tests {
         apply_epoch_decay(
             &mut wt,
             &DecayConfig {
-                global_decay: 0.9,
+                policy: DecayPolicy::Exponential { factor: 0.9 },
                 min_weight: 0.1,
             },
+            1,
         );
 
         assert!((wt.get("b1", 0) - 90.0).abs() < 0.01);
@@ -110,9 +136,10 @@ mod tests {
         apply_epoch_decay(
             &mut wt,
             &DecayConfig {
-                global_decay: 0.1,
+                policy: DecayPolicy::Exponential { factor: 0.1 },
                 min_weight: 0.1,
             },
+            1,
         );
 
         // 0.2 * 0.1 = 0.02, but clamped to 0.1
@@ -124,18 +151,84 @@ mod tests {
         let mut wt = WeightTable::new();
         wt.set("unreachable", 0, 0.0);
 
-        apply_epoch_decay(
-            &mut wt,
-            &DecayConfig {
-                global_decay: 0.95,
-                min_weight: 0.1,
-            },
-        );
+        apply_epoch_decay(&mut wt, &DecayConfig::default(), 1);
 
         // Zero stays zero (provably unreachable).
         assert_eq!(wt.get("unreachable", 0), 0.0);
     }
 
+    /// Weight of a single branch (initially 100) after each of 5 epochs.
+    fn trajectory(policy: DecayPolicy) -> Vec<f64> {
+        let mut wt = WeightTable::new();
+        wt.set("b", 0, 100.0);
+        let config = DecayConfig {
+            policy,
+            min_weight: 0.1,
+        };
+        (1..=5)
+            .map(|epoch| {
+                apply_epoch_decay(&mut wt, &config, epoch);
+                wt.get("b", 0)
+            })
+            .collect()
+    }
+
+    fn assert_trajectory(actual: Vec<f64>, expected: impl Fn(u64) -> f64) {
+        for (epoch, weight) in (1..=5).zip(actual) {
+            let want = expected(epoch);
+            assert!(
+                (weight - want).abs() < 1e-9,
+                "epoch {epoch}: weight {weight}, expected {want}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_decay_trajectory() {
+        assert_trajectory(trajectory(DecayPolicy::None), |_| 100.0);
+    }
+
+    #[test]
+    fn test_linear_decay_trajectory() {
+        assert_trajectory(trajectory(DecayPolicy::Linear { rate: 15.0 }), |n| {
+            100.0 - 15.0 * n as f64
+        });
+    }
+
+    #[test]
+    fn test_exponential_decay_trajectory() {
+        assert_trajectory(trajectory(DecayPolicy::Exponential { factor: 0.8 }), |n| {
+            100.0 * 0.8f64.powi(n as i32)
+        });
+    }
+
+    #[test]
+    fn test_step_decay_trajectory() {
+        assert_trajectory(
+            trajectory(DecayPolicy::Step {
+                every_n_epochs: 2,
+                factor: 0.5,
+            }),
+            |n| 100.0 * 0.5f64.powi((n / 2) as i32),
+        );
+    }
+
+    #[test]
+    fn test_linear_decay_stops_at_minimum() {
+        let mut wt = WeightTable::new();
+        wt.set("b", 0, 1.0);
+        wt.set("unreachable", 0, 0.0);
+        let config = DecayConfig {
+            policy: DecayPolicy::Linear { rate: 5.0 },
+            min_weight: 0.1,
+        };
+
+        apply_epoch_decay(&mut wt, &config, 1);
+
+        assert!((wt.get("b", 0) - 0.1).abs() < 1e-9);
+        assert_eq!(wt.get("unreachable", 0), 0.0);
+    }
+
     #[test]
     fn test_coverage_floor_boosts_uncovered() {
         let mut wt = WeightTable::new();
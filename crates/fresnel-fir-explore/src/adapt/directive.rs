@@ -27,6 +27,16 @@ pub enum Directive {
         branch_id: String,
         model_state_hash: u64,
         remaining: u32,
+        /// Epochs after which the coordinator restores the branch's
+        /// pre-skip weight. `None` = the skip never expires.
+        ttl_epochs: Option<u32>,
+    },
+    /// Restore a branch's weight once its skip has expired. Emitted by
+    /// the coordinator, never by a policy.
+    SkipExpired {
+        branch_id: String,
+        model_state_hash: u64,
+        restored_weight: f64,
     },
     /// Adjust repeat bounds within declared min/max.
     LoopLimit {
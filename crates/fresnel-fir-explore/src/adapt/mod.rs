@@ -1,5 +1,8 @@
 pub mod coordinator;
+pub mod dead_branch;
 pub mod decay;
 pub mod directive;
+pub mod plateau;
+pub mod policy;
 pub mod reachability;
 pub mod timeout;
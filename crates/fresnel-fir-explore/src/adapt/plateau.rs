@@ -0,0 +1,105 @@
+//! Coverage plateau detection.
+//!
+//! Tracks the coverage fraction reported at each epoch boundary and
+//! emits `CoveragePlateau` once the per-epoch coverage gain has stayed
+//! below a threshold for `window` consecutive epochs. The coordinator
+//! answers a plateau by forcing branches towards uncovered targets.
+
+use std::collections::VecDeque;
+
+use crate::traversal::signal::SignalType;
+
+/// Detects coverage plateaus over a sliding window of epochs.
+#[derive(Debug, Clone)]
+pub struct PlateauDetector {
+    /// Consecutive low-gain epochs needed to declare a plateau.
+    window: usize,
+    /// Per-epoch coverage gain below which an epoch counts as flat.
+    threshold: f64,
+    /// Coverage at the previous epoch boundary.
+    last_coverage: Option<f64>,
+    /// Coverage gains of the most recent epochs, oldest first.
+    deltas: VecDeque<f64>,
+}
+
+impl PlateauDetector {
+    pub fn new(window: usize, threshold: f64) -> Self {
+        Self {
+            window: window.max(1),
+            threshold,
+            last_coverage: None,
+            deltas: VecDeque::new(),
+        }
+    }
+
+    /// Record the coverage fraction at an epoch boundary.
+    ///
+    /// Returns a `CoveragePlateau` signal when the last `window` gains
+    /// were all below the threshold. The window then starts over, so a
+    /// persisting plateau is reported once every `window` epochs.
+    pub fn observe(&mut self, current_coverage: f64) -> Option<SignalType> {
+        let previous = self.last_coverage.replace(current_coverage)?;
+        self.deltas.push_back(current_coverage - previous);
+        if self.deltas.len() > self.window {
+            self.deltas.pop_front();
+        }
+
+        let flat = self.deltas.len() == self.window
+            && self.deltas.iter().all(|&delta| delta < self.threshold);
+        if !flat {
+            return None;
+        }
+
+        let delta_rate = self.deltas.iter().sum::<f64>() / self.window as f64;
+        self.deltas.clear();
+        Some(SignalType::CoveragePlateau {
+            current_coverage,
+            delta_rate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_coverage_fires_after_window() {
+        let mut detector = PlateauDetector::new(3, 0.05);
+
+        // First snapshot is the baseline; the next three fill the window.
+        assert_eq!(detector.observe(0.5), None);
+        assert_eq!(detector.observe(0.5), None);
+        assert_eq!(detector.observe(0.5), None);
+        assert_eq!(
+            detector.observe(0.5),
+            Some(SignalType::CoveragePlateau {
+                current_coverage: 0.5,
+                delta_rate: 0.0,
+            })
+        );
+
+        // The window restarts after firing.
+        assert_eq!(detector.observe(0.5), None);
+    }
+
+    #[test]
+    fn test_growing_coverage_never_fires() {
+        let mut detector = PlateauDetector::new(2, 0.05);
+        for epoch in 0..10 {
+            assert_eq!(detector.observe(epoch as f64 * 0.1), None);
+        }
+    }
+
+    #[test]
+    fn test_one_productive_epoch_resets_plateau() {
+        let mut detector = PlateauDetector::new(3, 0.05);
+        for coverage in [0.2, 0.21, 0.22, 0.4, 0.41, 0.42] {
+            assert_eq!(detector.observe(coverage), None);
+        }
+        assert!(matches!(
+            detector.observe(0.43),
+            Some(SignalType::CoveragePlateau { .. })
+        ));
+    }
+}
@@ -0,0 +1,141 @@
+//! Signal → directive mapping policies.
+//!
+//! The coordinator delegates the decision of how to react to each signal
+//! to a `DirectivePolicy`. `StandardPolicy` is the built-in mapping;
+//! integrators can swap in their own to tune strategy (e.g. treat
+//! timeouts as findings, or disable coverage boosting).
+//!
+//! Policies are pure: the same signal and context must always produce the
+//! same directives. Any mutable exploration state (such as the timeout
+//! two-step protocol) is owned by the coordinator and handed in through
+//! `PolicyContext`, so determinism of the directive history is preserved.
+
+use crate::traversal::signal::SignalType;
+
+use super::coordinator::CoordinatorConfig;
+use super::directive::Directive;
+use super::timeout::TimeoutTracker;
+
+/// Coordinator state visible to a policy while mapping one signal.
+pub struct PolicyContext<'a> {
+    /// Coordinator configuration (boosts, decays, budgets).
+    pub config: &'a CoordinatorConfig,
+    /// Branches known to reach uncovered targets.
+    pub uncovered_target_branches: &'a [String],
+    /// Timeout two-step state per action.
+    pub timeout_tracker: &'a mut TimeoutTracker,
+}
+
+/// Maps a signal to zero or more directives.
+pub trait DirectivePolicy: Send + Sync {
+    /// Map a single signal to directives.
+    fn map_signal(&self, signal: &SignalType, ctx: &mut PolicyContext<'_>) -> Vec<Directive>;
+
+    /// Name of this policy (for tracing).
+    fn name(&self) -> &str;
+}
+
+/// The default signal → directive mapping.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StandardPolicy;
+
+impl DirectivePolicy for StandardPolicy {
+    fn map_signal(&self, signal: &SignalType, ctx: &mut PolicyContext<'_>) -> Vec<Directive> {
+        let config = ctx.config;
+        match signal {
+            SignalType::CoverageDelta { action, .. } => {
+                // Boost weight on the branch that led to new coverage.
+                // We use the action name as a proxy for branch_id here.
+                vec![Directive::AdjustWeight {
+                    branch_id: action.clone(),
+                    model_state_hash: 0,
+                    multiplier: config.coverage_boost,
+                }]
+            }
+
+            SignalType::PropertyViolation { property, .. } => {
+                // Force nearby branches for deeper investigation.
+                vec![Directive::Force {
+                    action: property.clone(),
+                    budget: config.force_budget,
+                }]
+            }
+
+            SignalType::Discrepancy { action, .. } => {
+                // Force the divergent path + increase loop bounds.
+                vec![Directive::Force {
+                    action: action.clone(),
+                    budget: config.force_budget,
+                }]
+            }
+
+            SignalType::Crash { action, .. } => {
+                // Force with boundary values and related inputs.
+                vec![
+                    Directive::Force {
+                        action: action.clone(),
+                        budget: config.force_budget * 2,
+                    },
+                    Directive::AdjustWeight {
+                        branch_id: action.clone(),
+                        model_state_hash: 0,
+                        multiplier: config.finding_boost,
+                    },
+                ]
+            }
+
+            SignalType::Timeout {
+                action,
+                fuel_consumed,
+            } => {
+                // Two-step timeout response via the tracker.
+                ctx.timeout_tracker
+                    .handle_timeout(action, *fuel_consumed)
+                    .into_iter()
+                    .collect()
+            }
+
+            SignalType::GuardFailure {
+                branch_id,
+                action,
+                model_state_hash,
+            } => {
+                // State-conditioned decay: "branch B is invalid WHEN model is in state S"
+                let bid = if branch_id.is_empty() {
+                    action
+                } else {
+                    branch_id
+                };
+                vec![Directive::AdjustWeight {
+                    branch_id: bid.clone(),
+                    model_state_hash: *model_state_hash,
+                    multiplier: config.guard_failure_decay,
+                }]
+            }
+
+            SignalType::CoveragePlateau { .. } => {
+                // Convert each uncovered target to a Force directive.
+                ctx.uncovered_target_branches
+                    .iter()
+                    .map(|branch| Directive::Force {
+                        action: branch.clone(),
+                        budget: config.force_budget,
+                    })
+                    .collect()
+            }
+
+            // Only counted, by the coordinator's dead-branch detection.
+            SignalType::GuardPass { .. } => Vec::new(),
+
+            // The engine already kept the declared minimum; nothing to steer.
+            SignalType::LoopLimitConflict { .. } => Vec::new(),
+
+            // Diagnostic only; the weights themselves are the policy's output.
+            SignalType::ZeroWeightFallback { .. } => Vec::new(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "standard"
+    }
+}
@@ -7,9 +7,13 @@
 //! Weights CAN go to permanent zero IF provably unreachable, with a
 //! proof artifact logged. Zero is reversible on IR recompilation.
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::BTreeSet;
 
-use fresnel_fir_compiler::graph::{GraphNode, NdaGraph, NodeId};
+use fresnel_fir_compiler::graph::{reachable_nodes, GraphNode, NdaGraph, NodeId};
+use fresnel_fir_ir::types::CoverageTarget;
+
+use crate::traversal::engine::CoverageReport;
+use crate::traversal::strategy::reachable_actions;
 
 use super::directive::{Directive, UnreachabilityProof};
 
@@ -26,14 +30,14 @@ pub struct ReachabilityResult {
 ///
 /// Returns all branch IDs that are NOT reachable from the entry node.
 pub fn static_reachability(graph: &NdaGraph) -> ReachabilityResult {
-    let reachable_nodes = bfs_reachable(graph, graph.entry);
+    let reachable_nodes = reachable_nodes(graph, graph.entry);
 
     let mut unreachable = Vec::new();
     let mut reachable = Vec::new();
 
     for (idx, node) in graph.nodes.iter().enumerate() {
         let node_id = idx as NodeId;
-        if let GraphNode::Branch { alternatives } = node {
+        if let GraphNode::Branch { alternatives, .. } = node {
             for alt in alternatives {
                 if reachable_nodes.contains(&node_id) && reachable_nodes.contains(&alt.target) {
                     reachable.push(alt.id.clone());
@@ -58,45 +62,6 @@ pub fn static_reachability(graph: &NdaGraph) -> ReachabilityResult {
     }
 }
 
-/// BFS from a source node, returns all reachable node IDs.
-fn bfs_reachable(graph: &NdaGraph, start: NodeId) -> HashSet<NodeId> {
-    let mut visited = HashSet::new();
-    let mut queue = VecDeque::new();
-
-    visited.insert(start);
-    queue.push_back(start);
-
-    while let Some(current) = queue.pop_front() {
-        // Follow edges.
-        for &(from, to) in &graph.edges {
-            if from == current && !visited.contains(&to) {
-                visited.insert(to);
-                queue.push_back(to);
-            }
-        }
-
-        // Follow branch targets.
-        if let Some(GraphNode::Branch { alternatives }) = graph.nodes.get(current as usize) {
-            for alt in alternatives {
-                if !visited.contains(&alt.target) {
-                    visited.insert(alt.target);
-                    queue.push_back(alt.target);
-                }
-            }
-        }
-
-        // Follow loop body entry.
-        if let Some(GraphNode::LoopEntry { body_start, .. }) = graph.nodes.get(current as usize) {
-            if !visited.contains(body_start) {
-                visited.insert(*body_start);
-                queue.push_back(*body_start);
-            }
-        }
-    }
-
-    visited
-}
-
 /// Generate PermanentZero directives for provably unreachable branches.
 pub fn generate_unreachability_directives(graph: &NdaGraph) -> Vec<Directive> {
     let result = static_reachability(graph);
@@ -108,13 +73,55 @@ pub fn generate_unreachability_directives(graph: &NdaGraph) -> Vec<Directive> {
         .collect()
 }
 
+/// Branches reachable from the graph entry that the coverage floor should
+/// protect: those `coverage` has never selected, plus those leading to the
+/// action of a `MinHits` target in `targets` that is still short of hits.
+///
+/// A branch leads to an action when the action is reachable from its target
+/// but not from every sibling alternative, so choosing it matters.
+pub fn uncovered_target_branches(
+    graph: &NdaGraph,
+    coverage: &CoverageReport,
+    targets: &[CoverageTarget],
+) -> Vec<String> {
+    let unmet: BTreeSet<String> = coverage
+        .unmet_min_hits(targets)
+        .into_iter()
+        .map(|unmet| unmet.action)
+        .collect();
+    let mut leading = BTreeSet::new();
+    if !unmet.is_empty() {
+        for node in &graph.nodes {
+            if let GraphNode::Branch { alternatives, .. } = node {
+                let reached: Vec<BTreeSet<String>> = alternatives
+                    .iter()
+                    .map(|alt| reachable_actions(graph, alt.target))
+                    .collect();
+                for (alt, actions) in alternatives.iter().zip(&reached) {
+                    if unmet.iter().any(|action| {
+                        actions.contains(action) && reached.iter().any(|r| !r.contains(action))
+                    }) {
+                        leading.insert(alt.id.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    static_reachability(graph)
+        .reachable
+        .into_iter()
+        .filter(|branch| !coverage.branch_counts.contains_key(branch) || leading.contains(branch))
+        .collect()
+}
+
 /// Check if a specific branch is reachable from the graph entry.
 pub fn is_branch_reachable(graph: &NdaGraph, branch_id: &str) -> bool {
-    let reachable_nodes = bfs_reachable(graph, graph.entry);
+    let reachable_nodes = reachable_nodes(graph, graph.entry);
 
     for (idx, node) in graph.nodes.iter().enumerate() {
         let node_id = idx as NodeId;
-        if let GraphNode::Branch { alternatives } = node {
+        if let GraphNode::Branch { alternatives, .. } = node {
             for alt in alternatives {
                 if alt.id == branch_id {
                     return reachable_nodes.contains(&node_id)
@@ -138,10 +145,12 @@ mod tests {
         let a = graph.add_node(GraphNode::Terminal {
             action: "a".into(),
             guard: None,
+            source_span: None,
         });
         let b = graph.add_node(GraphNode::Terminal {
             action: "b".into(),
             guard: None,
+            source_span: None,
         });
 
         let branch = graph.add_node(GraphNode::Branch {
@@ -159,6 +168,7 @@ mod tests {
                     guard: None,
                 },
             ],
+            source_span: None,
         });
         graph.add_edge(graph.entry, branch);
         graph.add_edge(a, graph.exit);
@@ -175,10 +185,12 @@ mod tests {
         let a = graph.add_node(GraphNode::Terminal {
             action: "a".into(),
             guard: None,
+            source_span: None,
         });
         let orphan = graph.add_node(GraphNode::Terminal {
             action: "orphan".into(),
             guard: None,
+            source_span: None,
         });
 
         // Branch node that's connected, but one target is an orphan.
@@ -197,6 +209,7 @@ mod tests {
                     guard: None,
                 },
             ],
+            source_span: None,
         });
         graph.add_edge(graph.entry, branch);
         graph.add_edge(a, graph.exit);
@@ -217,6 +230,7 @@ mod tests {
         let connected = graph.add_node(GraphNode::Terminal {
             action: "connected".into(),
             guard: None,
+            source_span: None,
         });
         graph.add_edge(graph.entry, connected);
         graph.add_edge(connected, graph.exit);
@@ -225,6 +239,7 @@ mod tests {
         let orphan_target = graph.add_node(GraphNode::Terminal {
             action: "orphan_target".into(),
             guard: None,
+            source_span: None,
         });
         let _orphan_branch = graph.add_node(GraphNode::Branch {
             alternatives: vec![BranchEdge {
@@ -233,6 +248,7 @@ mod tests {
                 target: orphan_target,
                 guard: None,
             }],
+            source_span: None,
         });
         // No edges to orphan_branch from anywhere reachable.
 
@@ -251,6 +267,7 @@ mod tests {
         let t = graph.add_node(GraphNode::Terminal {
             action: "t".into(),
             guard: None,
+            source_span: None,
         });
         graph.add_edge(graph.entry, t);
         graph.add_edge(t, graph.exit);
@@ -259,6 +276,7 @@ mod tests {
         let orphan_target = graph.add_node(GraphNode::Terminal {
             action: "dead".into(),
             guard: None,
+            source_span: None,
         });
         let _orphan = graph.add_node(GraphNode::Branch {
             alternatives: vec![BranchEdge {
@@ -267,6 +285,7 @@ mod tests {
                 target: orphan_target,
                 guard: None,
             }],
+            source_span: None,
         });
 
         let directives = generate_unreachability_directives(&graph);
@@ -283,6 +302,7 @@ mod tests {
         let a = graph.add_node(GraphNode::Terminal {
             action: "a".into(),
             guard: None,
+            source_span: None,
         });
         let branch = graph.add_node(GraphNode::Branch {
             alternatives: vec![BranchEdge {
@@ -291,6 +311,7 @@ mod tests {
                 target: a,
                 guard: None,
             }],
+            source_span: None,
         });
         graph.add_edge(graph.entry, branch);
         graph.add_edge(a, graph.exit);
@@ -305,6 +326,7 @@ mod tests {
         let body = graph.add_node(GraphNode::Terminal {
             action: "body".into(),
             guard: None,
+            source_span: None,
         });
         let loop_exit = graph.add_node(GraphNode::LoopExit);
         let loop_entry = graph.add_node(GraphNode::LoopEntry {
@@ -317,6 +339,7 @@ mod tests {
         let inner_a = graph.add_node(GraphNode::Terminal {
             action: "inner".into(),
             guard: None,
+            source_span: None,
         });
         let inner_branch = graph.add_node(GraphNode::Branch {
             alternatives: vec![BranchEdge {
@@ -325,6 +348,7 @@ mod tests {
                 target: inner_a,
                 guard: None,
             }],
+            source_span: None,
         });
         graph.add_edge(body, inner_branch);
         graph.add_edge(inner_a, loop_exit);
@@ -335,4 +359,58 @@ mod tests {
 
         assert!(is_branch_reachable(&graph, "inner_branch"));
     }
+
+    #[test]
+    fn test_branches_leading_to_unmet_min_hits_stay_targets() {
+        let mut graph = NdaGraph::new();
+        let common = graph.add_node(GraphNode::Terminal {
+            action: "common".into(),
+            guard: None,
+            source_span: None,
+        });
+        let rare = graph.add_node(GraphNode::Terminal {
+            action: "rare".into(),
+            guard: None,
+            source_span: None,
+        });
+        let branch = graph.add_node(GraphNode::Branch {
+            alternatives: vec![
+                BranchEdge {
+                    id: "common_path".into(),
+                    weight: 50.0,
+                    target: common,
+                    guard: None,
+                },
+                BranchEdge {
+                    id: "rare_path".into(),
+                    weight: 50.0,
+                    target: rare,
+                    guard: None,
+                },
+            ],
+            source_span: None,
+        });
+        graph.add_edge(graph.entry, branch);
+        graph.add_edge(common, graph.exit);
+        graph.add_edge(rare, graph.exit);
+
+        // Both branches have been selected, but "rare" ran only once.
+        let mut coverage = CoverageReport::default();
+        coverage.branch_counts.insert("common_path".into(), 9);
+        coverage.branch_counts.insert("rare_path".into(), 1);
+        coverage.action_counts.insert("common".into(), 9);
+        coverage.action_counts.insert("rare".into(), 1);
+
+        let targets = |count| {
+            vec![CoverageTarget::MinHits {
+                action: "rare".into(),
+                count,
+            }]
+        };
+        assert_eq!(
+            uncovered_target_branches(&graph, &coverage, &targets(5)),
+            ["rare_path"]
+        );
+        assert!(uncovered_target_branches(&graph, &coverage, &targets(1)).is_empty());
+    }
 }
@@ -12,21 +12,34 @@ use std::collections::HashMap;
 
 use super::directive::Directive;
 
-/// State of a timeout-tracked action.
+/// Internal state of a timeout-tracked action.
 #[derive(Debug, Clone, PartialEq)]
-enum TimeoutState {
+enum Strike {
     /// Retry scheduled — waiting for retry result.
-    RetryScheduled { reduced_fuel: u64 },
+    RetryScheduled { reduced_fuel: u64, since_epoch: u64 },
     /// Retry also timed out — skip this action.
     PermanentSkip { skip_remaining: u32 },
 }
 
+/// Escalation state of an action, as reported to analytics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutState {
+    /// No outstanding timeout.
+    None,
+    /// Timed out once in `since_epoch`; the next timeout escalates.
+    FirstStrike { since_epoch: u64 },
+    /// Timed out again on retry and is being skipped.
+    Escalated,
+}
+
 /// Tracks timeout two-step state per action.
 #[derive(Debug, Clone)]
 pub struct TimeoutTracker {
-    states: HashMap<String, TimeoutState>,
+    states: HashMap<String, Strike>,
     /// Default skip budget when permanently skipping.
     default_skip_budget: u32,
+    /// Epoch that new first strikes are attributed to.
+    epoch: u64,
 }
 
 /// Result of processing a timeout event.
@@ -47,9 +60,15 @@ impl TimeoutTracker {
         Self {
             states: HashMap::new(),
             default_skip_budget: 50,
+            epoch: 0,
         }
     }
 
+    /// Set the epoch that subsequent first strikes are recorded in.
+    pub fn set_epoch(&mut self, epoch: u64) {
+        self.epoch = epoch;
+    }
+
     /// Handle a timeout event for an action.
     /// Returns directives to apply based on the two-step protocol.
     pub fn handle_timeout(
@@ -65,19 +84,20 @@ impl TimeoutTracker {
                 let reduced = fuel_consumed.map(|f| f / 2).unwrap_or(500_000);
                 self.states.insert(
                     action.to_string(),
-                    TimeoutState::RetryScheduled {
+                    Strike::RetryScheduled {
                         reduced_fuel: reduced,
+                        since_epoch: self.epoch,
                     },
                 );
                 // No directive yet — the caller should retry with reduced fuel.
                 None
             }
 
-            Some(TimeoutState::RetryScheduled { .. }) => {
+            Some(Strike::RetryScheduled { .. }) => {
                 // Retry also timed out → permanent skip.
                 self.states.insert(
                     action.to_string(),
-                    TimeoutState::PermanentSkip {
+                    Strike::PermanentSkip {
                         skip_remaining: self.default_skip_budget,
                     },
                 );
@@ -86,15 +106,16 @@ impl TimeoutTracker {
                     branch_id: action.to_string(),
                     model_state_hash: 0,
                     remaining: self.default_skip_budget,
+                    ttl_epochs: None,
                 })
             }
 
-            Some(TimeoutState::PermanentSkip { skip_remaining }) => {
+            Some(Strike::PermanentSkip { skip_remaining }) => {
                 // Already skipping — decrement remaining.
                 if skip_remaining > 0 {
                     self.states.insert(
                         action.to_string(),
-                        TimeoutState::PermanentSkip {
+                        Strike::PermanentSkip {
                             skip_remaining: skip_remaining.saturating_sub(1),
                         },
                     );
@@ -116,17 +137,36 @@ impl TimeoutTracker {
     /// Check if an action is in retry state (should be retried with reduced fuel).
     pub fn needs_retry(&self, action: &str) -> Option<u64> {
         match self.states.get(action) {
-            Some(TimeoutState::RetryScheduled { reduced_fuel }) => Some(*reduced_fuel),
+            Some(Strike::RetryScheduled { reduced_fuel, .. }) => Some(*reduced_fuel),
             _ => None,
         }
     }
 
     /// Check if an action is permanently skipped.
     pub fn is_skipped(&self, action: &str) -> bool {
-        matches!(
-            self.states.get(action),
-            Some(TimeoutState::PermanentSkip { .. })
-        )
+        matches!(self.states.get(action), Some(Strike::PermanentSkip { .. }))
+    }
+
+    /// Escalation state of `action`.
+    pub fn state_for(&self, action: &str) -> TimeoutState {
+        match self.states.get(action) {
+            None => TimeoutState::None,
+            Some(Strike::RetryScheduled { since_epoch, .. }) => TimeoutState::FirstStrike {
+                since_epoch: *since_epoch,
+            },
+            Some(Strike::PermanentSkip { .. }) => TimeoutState::Escalated,
+        }
+    }
+
+    /// Escalation state of every tracked action, sorted by action name.
+    pub fn all_states(&self) -> Vec<(String, TimeoutState)> {
+        let mut states: Vec<(String, TimeoutState)> = self
+            .states
+            .keys()
+            .map(|action| (action.clone(), self.state_for(action)))
+            .collect();
+        states.sort_by(|a, b| a.0.cmp(&b.0));
+        states
     }
 
     /// Number of actions currently tracked.
@@ -168,6 +208,27 @@ mod tests {
         assert!(tracker.is_skipped("slow_fn"));
     }
 
+    #[test]
+    fn test_reported_state_escalates_on_second_timeout() {
+        let mut tracker = TimeoutTracker::new();
+        assert_eq!(tracker.state_for("slow_fn"), TimeoutState::None);
+
+        tracker.set_epoch(3);
+        tracker.handle_timeout("slow_fn", Some(1_000_000));
+        assert_eq!(
+            tracker.state_for("slow_fn"),
+            TimeoutState::FirstStrike { since_epoch: 3 }
+        );
+
+        tracker.set_epoch(4);
+        tracker.handle_timeout("slow_fn", Some(500_000));
+        assert_eq!(tracker.state_for("slow_fn"), TimeoutState::Escalated);
+        assert_eq!(
+            tracker.all_states(),
+            vec![("slow_fn".to_string(), TimeoutState::Escalated)]
+        );
+    }
+
     #[test]
     fn test_retry_success_clears_state() {
         let mut tracker = TimeoutTracker::new();
@@ -185,6 +246,7 @@ mod tests {
         let mut tracker = TimeoutTracker {
             states: HashMap::new(),
             default_skip_budget: 2,
+            epoch: 0,
         };
 
         // First timeout.
@@ -0,0 +1,44 @@
+//! Crate-level error type for the explore entry points.
+//!
+//! Modules keep their own granular errors (`EncodingError`,
+//! `ConstraintError`, `SearchError`); `run_pipeline`,
+//! `coverage_driven_generation` and `run_campaign` flatten them into
+//! `ExploreError` so callers match one enum.
+
+use crate::solver::constraint::ConstraintError;
+use crate::solver::domain::EncodingError;
+use crate::solver::search::SearchError;
+use crate::solver::TestVector;
+
+/// Errors from the top-level explore entry points.
+#[derive(Debug, thiserror::Error)]
+pub enum ExploreError {
+    #[error("domain encoding error: {0}")]
+    Encoding(#[from] EncodingError),
+
+    #[error("constraint encoding error: {0}")]
+    Constraint(#[from] ConstraintError),
+
+    #[error("solver error: {0}")]
+    Solver(String),
+
+    /// The run was cancelled before it produced a result.
+    #[error("cancelled")]
+    Cancelled,
+
+    /// The run ran past its timeout. `partial` holds the vectors found
+    /// before the deadline.
+    #[error("timed out ({} vectors found before the deadline)", .partial.len())]
+    Timeout { partial: Vec<TestVector> },
+}
+
+impl From<SearchError> for ExploreError {
+    fn from(err: SearchError) -> Self {
+        match err {
+            SearchError::Encoding(e) => ExploreError::Encoding(e),
+            SearchError::Constraint(e) => ExploreError::Constraint(e),
+            SearchError::Solver(message) => ExploreError::Solver(message),
+            SearchError::Timeout { partial } => ExploreError::Timeout { partial },
+        }
+    }
+}
@@ -1,3 +1,6 @@
 pub mod adapt;
+pub mod error;
 pub mod solver;
 pub mod traversal;
+
+pub use error::ExploreError;
@@ -7,13 +7,37 @@
 //! - `and(A, B, ...)` — conjunction
 //! - `or(A, B, ...)` — disjunction
 //! - `not(A)` — negation
+//! - `xor(A, B, ...)` — odd parity (exactly one of two operands for N = 2)
+//! - `iff(A, B)` — biconditional
+//! - `lt/lte/gt/gte(domain_var, value)` — order comparison, for int domains
+//!   and for `ordered` enums, by declared position; `not` flips it to the
+//!   complementary range
+//! - `lt/lte/gt/gte(sum(a, b, ...), value)` — comparison of a total over int
+//!   domains and int literals, encoded by blocking each violating combination
+//! - `in(domain_var, [v1, v2, ...])` — membership, one clause over the value
+//!   literals; `not_in` forbids each listed value
+//!
+//! A bare bool domain name is an atomic proposition (the domain is true).
+//!
+//! The input space's `forbidden` combinations are encoded alongside the
+//! constraints, each as one blocking clause.
 //!
 //! These are compiled into CNF clauses suitable for the SAT solver.
+//! Shapes without a direct CNF form (nested `or`/`not`, parity chains)
+//! go through a Tseitin transformation: each sub-expression gets an
+//! auxiliary variable allocated from the encoded space's `next_var`,
+//! tied to its operands by the standard gate clauses. Auxiliary
+//! variables belong to no domain, so `decode_model` never reports them.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 
 use fresnel_fir_ir::expr::{Expr, Literal, OpKind};
 use varisat::Lit;
 
-use super::domain::{lit_for_not_value, lit_for_value, EncodedInputSpace, Encoding};
+use super::domain::{
+    lit_for_not_value, lit_for_value, EncodedDomain, EncodedInputSpace, Encoding, ValueKind,
+};
 use super::DomainValue;
 
 /// Errors during constraint encoding.
@@ -29,31 +53,63 @@ pub enum ConstraintError {
     InvalidValue { domain: String, value: String },
 }
 
+/// Maximum number of value combinations a `sum` comparison may enumerate.
+const MAX_SUM_COMBINATIONS: usize = 65_536;
+
 /// Result of encoding a constraint expression: a set of CNF clauses.
 /// Each inner Vec<Lit> is a disjunctive clause; the set is conjunctive (AND of ORs).
 pub type CnfClauses = Vec<Vec<Lit>>;
 
-/// Encode all IR constraints into SAT clauses.
+/// Encode all IR constraints, plus the space's forbidden combinations, into
+/// SAT clauses.
+///
+/// Takes the space mutably so auxiliary variables can be allocated.
 pub fn encode_constraints(
     constraints: &[fresnel_fir_ir::types::InputConstraint],
-    encoded_space: &EncodedInputSpace,
+    encoded_space: &mut EncodedInputSpace,
 ) -> Result<CnfClauses, ConstraintError> {
     let mut all_clauses = Vec::new();
     for constraint in constraints {
         let clauses = encode_expr(&constraint.rule, encoded_space)?;
         all_clauses.extend(clauses);
     }
+    for combination in &encoded_space.forbidden {
+        all_clauses.push(encode_forbidden(combination, encoded_space)?);
+    }
     Ok(all_clauses)
 }
 
+/// Encode a forbidden combination as one blocking clause: at least one of
+/// its domains takes a value other than the listed one.
+fn encode_forbidden(
+    combination: &[(String, Literal)],
+    space: &EncodedInputSpace,
+) -> Result<Vec<Lit>, ConstraintError> {
+    let mut clause = Vec::new();
+    for (domain_name, value) in combination {
+        let enc = space
+            .domains
+            .get(domain_name)
+            .ok_or_else(|| ConstraintError::UnknownDomain(domain_name.clone()))?;
+        let invalid = || ConstraintError::InvalidValue {
+            domain: domain_name.clone(),
+            value: format!("{:?}", value),
+        };
+        let domain_val = literal_to_domain_value(value, &enc.encoding).map_err(|_| invalid())?;
+        clause.extend(lit_for_not_value(enc, &domain_val).ok_or_else(invalid)?);
+    }
+    Ok(clause)
+}
+
 /// Encode a single expression into CNF clauses.
 ///
-/// The encoding uses Tseitin-like transformation where possible:
+/// Direct encodings are used where possible:
 /// - Atomic propositions (eq, neq) become unit or short clauses.
 /// - `and(A, B)` concatenates the clauses of A and B.
 /// - `implies(A, B)` becomes `or(not(A), B)`.
-/// - `or(A, B)` and `not(A)` require auxiliary handling.
-fn encode_expr(expr: &Expr, space: &EncodedInputSpace) -> Result<CnfClauses, ConstraintError> {
+/// - `or(A, B)`, `not(A)`, `xor` and `iff` name complex operands with
+///   Tseitin variables (see `gate_lit`).
+fn encode_expr(expr: &Expr, space: &mut EncodedInputSpace) -> Result<CnfClauses, ConstraintError> {
     match expr {
         // eq(domain_var_name, literal_value)
         // Encoded as: the SAT literal for that value must be true.
@@ -100,13 +156,78 @@ fn encode_expr(expr: &Expr, space: &EncodedInputSpace) -> Result<CnfClauses, Con
             args,
         } if args.len() == 1 => encode_not(&args[0], space),
 
+        // xor(A, B, ...) => odd parity.
+        Expr::Op {
+            op: OpKind::Xor,
+            args,
+        } if args.len() >= 2 => encode_xor(args, space),
+
+        // iff(A, B) => both true or both false.
+        Expr::Op {
+            op: OpKind::Iff,
+            args,
+        } if args.len() == 2 => {
+            let mut clauses = Vec::new();
+            let a = gate_lit(&args[0], space, &mut clauses)?;
+            let b = gate_lit(&args[1], space, &mut clauses)?;
+            clauses.push(vec![!a, b]);
+            clauses.push(vec![a, !b]);
+            Ok(clauses)
+        }
+
+        // A comparison over `sum` terms forbids each violating combination.
+        Expr::Op {
+            op: op @ (OpKind::Lt | OpKind::Lte | OpKind::Gt | OpKind::Gte),
+            args,
+        } if args.len() == 2 && args.iter().any(is_sum) => {
+            encode_sum_compare(op, &args[0], &args[1], space)
+        }
+
+        // lt/lte/gt/gte(domain_var, value) => one of the variants on the
+        // satisfying side of `value` in the domain's ordinal view.
+        Expr::Op {
+            op: op @ (OpKind::Lt | OpKind::Lte | OpKind::Gt | OpKind::Gte),
+            args,
+        } if args.len() == 2 => Ok(vec![compare_lits(op, &args[0], &args[1], space)?]),
+
+        // in(domain_var, [v1, v2, ...]) => one clause over the value literals.
+        Expr::Op {
+            op: OpKind::In,
+            args,
+        } => {
+            let mut clauses = Vec::new();
+            let lits = member_lits(args, space, &mut clauses)?;
+            clauses.push(lits);
+            Ok(clauses)
+        }
+
+        // not_in(domain_var, [v1, v2, ...]) => every listed value is false.
+        Expr::Op {
+            op: OpKind::NotIn,
+            args,
+        } => {
+            let mut clauses = Vec::new();
+            let lits = member_lits(args, space, &mut clauses)?;
+            clauses.extend(lits.into_iter().map(|l| vec![!l]));
+            Ok(clauses)
+        }
+
+        // Bare bool domain name => that domain is true.
+        Expr::Literal(Literal::String(name)) => match space.domains.get(name) {
+            Some(EncodedDomain {
+                encoding: Encoding::Bool { var },
+                ..
+            }) => Ok(vec![vec![var.positive()]]),
+            _ => Err(ConstraintError::UnsupportedExpr(expr.to_infix())),
+        },
+
         // Literal true is trivially satisfied (no clauses needed).
         Expr::Literal(Literal::Bool(true)) => Ok(vec![]),
 
         // Literal false is unsatisfiable (empty clause).
         Expr::Literal(Literal::Bool(false)) => Ok(vec![vec![]]),
 
-        other => Err(ConstraintError::UnsupportedExpr(format!("{:?}", other))),
+        other => Err(ConstraintError::UnsupportedExpr(other.to_infix())),
     }
 }
 
@@ -116,7 +237,7 @@ fn encode_expr(expr: &Expr, space: &EncodedInputSpace) -> Result<CnfClauses, Con
 fn encode_eq(
     lhs: &Expr,
     rhs: &Expr,
-    space: &EncodedInputSpace,
+    space: &mut EncodedInputSpace,
     negate: bool,
 ) -> Result<CnfClauses, ConstraintError> {
     // Try both orderings: (domain_name, value) or (value, domain_name).
@@ -128,14 +249,16 @@ fn encode_eq(
 
         let domain_val = literal_to_domain_value(&value, &enc.encoding)?;
 
-        let lit = if negate {
-            lit_for_not_value(enc, &domain_val)
+        // eq is a conjunction of unit clauses, neq the single clause
+        // negating it.
+        let clauses = if negate {
+            lit_for_not_value(enc, &domain_val).map(|clause| vec![clause])
         } else {
-            lit_for_value(enc, &domain_val)
+            lit_for_value(enc, &domain_val).map(|lits| lits.into_iter().map(|l| vec![l]).collect())
         };
 
-        match lit {
-            Some(l) => Ok(vec![vec![l]]),
+        match clauses {
+            Some(clauses) => Ok(clauses),
             None => Err(ConstraintError::InvalidValue {
                 domain: domain_name,
                 value: format!("{:?}", value),
@@ -175,6 +298,301 @@ fn extract_domain_value_pair(
     None
 }
 
+/// Literals of the variants satisfying `op(lhs, rhs)`, one side being an
+/// int domain with an int literal, or a domain with an ordinal view and one
+/// of its variants. The comparison holds exactly when one of the literals
+/// is true.
+fn compare_lits(
+    op: &OpKind,
+    lhs: &Expr,
+    rhs: &Expr,
+    space: &EncodedInputSpace,
+) -> Result<Vec<Lit>, ConstraintError> {
+    let (domain_name, value) = extract_domain_value_pair(lhs, rhs, space).ok_or_else(|| {
+        ConstraintError::UnsupportedExpr(
+            "comparison must be between a domain variable and a literal".to_string(),
+        )
+    })?;
+    let domain_first = matches!(lhs, Expr::Literal(Literal::String(n)) if *n == domain_name);
+
+    if let Literal::Int(pivot) = value {
+        return Ok(int_variants(&domain_name, space)?
+            .into_iter()
+            .filter(|&(v, _)| compare_holds(op, domain_first, v.cmp(&pivot)))
+            .map(|(_, lit)| lit)
+            .collect());
+    }
+
+    let order = space.ordinals.get(&domain_name).ok_or_else(|| {
+        ConstraintError::UnsupportedExpr(format!(
+            "domain '{domain_name}' has no ordering to compare against"
+        ))
+    })?;
+    let rank = |label: &str| order.iter().position(|v| v == label);
+    let pivot = match &value {
+        Literal::String(label) => rank(label),
+        _ => None,
+    }
+    .ok_or_else(|| ConstraintError::InvalidValue {
+        domain: domain_name.clone(),
+        value: format!("{:?}", value),
+    })?;
+
+    let holds = |r: usize| compare_holds(op, domain_first, r.cmp(&pivot));
+
+    match &space.domains[&domain_name].encoding {
+        Encoding::OneHot { variants, .. } => Ok(variants
+            .iter()
+            .filter(|(label, _)| rank(label).is_some_and(holds))
+            .map(|(_, var)| var.positive())
+            .collect()),
+        // Labels follow the ordinal view, so the satisfying ranks are a
+        // prefix or a suffix and one threshold bounds them.
+        Encoding::Order { labels, thresholds } => {
+            let satisfied: Vec<bool> = (0..labels.len()).map(holds).collect();
+            Ok(match satisfied.iter().position(|&s| s != satisfied[0]) {
+                None if satisfied[0] => vec![thresholds[0].positive(), thresholds[0].negative()],
+                None => vec![],
+                Some(boundary) if satisfied[0] => vec![thresholds[boundary - 1].negative()],
+                Some(boundary) => vec![thresholds[boundary - 1].positive()],
+            })
+        }
+        Encoding::Bool { .. } => Err(ConstraintError::UnsupportedExpr(format!(
+            "cannot order bool domain '{domain_name}'"
+        ))),
+    }
+}
+
+/// One literal per value listed in `in(domain_var, [values...])`, true
+/// exactly when the domain takes that value. Values spanning several
+/// literals (order encodings) are named by an and-gate appended to `out`.
+fn member_lits(
+    args: &[Expr],
+    space: &mut EncodedInputSpace,
+    out: &mut CnfClauses,
+) -> Result<Vec<Lit>, ConstraintError> {
+    let (domain_name, values) = match args.split_first() {
+        Some((Expr::Literal(Literal::String(name)), values)) if !values.is_empty() => {
+            (name, values)
+        }
+        _ => {
+            return Err(ConstraintError::UnsupportedExpr(
+                "membership must test a domain variable against a value list".to_string(),
+            ))
+        }
+    };
+    let enc = space
+        .domains
+        .get(domain_name)
+        .ok_or_else(|| ConstraintError::UnknownDomain(domain_name.clone()))?;
+
+    let mut per_value = Vec::with_capacity(values.len());
+    for value in values {
+        let value = expr_to_literal(value).ok_or_else(|| {
+            ConstraintError::UnsupportedExpr(format!(
+                "membership value must be a literal, got {}",
+                value.to_infix()
+            ))
+        })?;
+        let invalid = || ConstraintError::InvalidValue {
+            domain: domain_name.clone(),
+            value: format!("{:?}", value),
+        };
+        let domain_val = literal_to_domain_value(&value, &enc.encoding).map_err(|_| invalid())?;
+        per_value.push(lit_for_value(enc, &domain_val).ok_or_else(invalid)?);
+    }
+
+    Ok(per_value
+        .into_iter()
+        .map(|lits| match lits.as_slice() {
+            [lit] => *lit,
+            _ => and_gate(&lits, space, out),
+        })
+        .collect())
+}
+
+/// Whether `op` holds given how the domain's value orders against the
+/// literal. `op(value, domain)` reads the comparison from the other side.
+fn compare_holds(op: &OpKind, domain_first: bool, ordering: Ordering) -> bool {
+    let ordering = if domain_first {
+        ordering
+    } else {
+        ordering.reverse()
+    };
+    match op {
+        OpKind::Lt => ordering.is_lt(),
+        OpKind::Lte => ordering.is_le(),
+        OpKind::Gt => ordering.is_gt(),
+        _ => ordering.is_ge(),
+    }
+}
+
+/// The comparison that holds exactly when `op` does not.
+fn complement_compare(op: &OpKind) -> Option<OpKind> {
+    match op {
+        OpKind::Lt => Some(OpKind::Gte),
+        OpKind::Lte => Some(OpKind::Gt),
+        OpKind::Gt => Some(OpKind::Lte),
+        OpKind::Gte => Some(OpKind::Lt),
+        OpKind::In => Some(OpKind::NotIn),
+        OpKind::NotIn => Some(OpKind::In),
+        _ => None,
+    }
+}
+
+fn is_sum(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Op {
+            op: OpKind::Sum | OpKind::Sub,
+            ..
+        }
+    )
+}
+
+/// Encode a comparison with a `sum` or `sub` operand as blocking clauses.
+///
+/// Both sides are flattened into `sum(coefficient * domain) + constant`,
+/// then every combination of the domains' values is evaluated. Each
+/// combination violating the comparison gets a clause forbidding that
+/// product of one-hot literals. The enumeration is bounded by
+/// `MAX_SUM_COMBINATIONS`.
+fn encode_sum_compare(
+    op: &OpKind,
+    lhs: &Expr,
+    rhs: &Expr,
+    space: &EncodedInputSpace,
+) -> Result<CnfClauses, ConstraintError> {
+    let mut coefficients = BTreeMap::new();
+    let mut constant = 0;
+    linear_terms(lhs, 1, space, &mut coefficients, &mut constant)?;
+    linear_terms(rhs, -1, space, &mut coefficients, &mut constant)?;
+
+    let mut axes = Vec::new();
+    for (name, coefficient) in coefficients {
+        if coefficient != 0 {
+            axes.push((coefficient, int_variants(&name, space)?));
+        }
+    }
+    let combinations = axes
+        .iter()
+        .try_fold(1usize, |n, (_, values)| n.checked_mul(values.len()))
+        .filter(|&n| n <= MAX_SUM_COMBINATIONS)
+        .ok_or_else(|| {
+            ConstraintError::UnsupportedExpr(format!(
+                "sum comparison spans more than {MAX_SUM_COMBINATIONS} value combinations"
+            ))
+        })?;
+
+    // `lhs - rhs` against zero.
+    let holds = |diff: i64| match op {
+        OpKind::Lt => diff < 0,
+        OpKind::Lte => diff <= 0,
+        OpKind::Gt => diff > 0,
+        _ => diff >= 0,
+    };
+
+    let mut clauses = Vec::new();
+    let mut picks = vec![0; axes.len()];
+    for _ in 0..combinations {
+        let diff = axes
+            .iter()
+            .zip(&picks)
+            .try_fold(constant, |diff, ((coefficient, values), &i)| {
+                coefficient
+                    .checked_mul(values[i].0)
+                    .and_then(|term| diff.checked_add(term))
+            })
+            .ok_or_else(sum_overflow)?;
+        if !holds(diff) {
+            clauses.push(
+                axes.iter()
+                    .zip(&picks)
+                    .map(|((_, values), &i)| !values[i].1)
+                    .collect(),
+            );
+        }
+        for (pick, (_, values)) in picks.iter_mut().zip(&axes).rev() {
+            *pick += 1;
+            if *pick < values.len() {
+                break;
+            }
+            *pick = 0;
+        }
+    }
+    Ok(clauses)
+}
+
+/// Accumulate `sign * expr` into per-domain coefficients and a constant.
+fn linear_terms(
+    expr: &Expr,
+    sign: i64,
+    space: &EncodedInputSpace,
+    coefficients: &mut BTreeMap<String, i64>,
+    constant: &mut i64,
+) -> Result<(), ConstraintError> {
+    match expr {
+        Expr::Op {
+            op: OpKind::Sum,
+            args,
+        } => {
+            for arg in args {
+                linear_terms(arg, sign, space, coefficients, constant)?;
+            }
+            Ok(())
+        }
+        Expr::Op {
+            op: OpKind::Sub,
+            args,
+        } if args.len() == 2 => {
+            linear_terms(&args[0], sign, space, coefficients, constant)?;
+            linear_terms(&args[1], -sign, space, coefficients, constant)
+        }
+        Expr::Literal(Literal::Int(i)) => {
+            *constant = sign
+                .checked_mul(*i)
+                .and_then(|term| constant.checked_add(term))
+                .ok_or_else(sum_overflow)?;
+            Ok(())
+        }
+        Expr::Literal(Literal::String(name)) => {
+            if !space.domains.contains_key(name) {
+                return Err(ConstraintError::UnknownDomain(name.clone()));
+            }
+            *coefficients.entry(name.clone()).or_insert(0) += sign;
+            Ok(())
+        }
+        other => Err(ConstraintError::UnsupportedExpr(format!(
+            "sum operand must be an int domain or int literal, got {}",
+            other.to_infix()
+        ))),
+    }
+}
+
+fn sum_overflow() -> ConstraintError {
+    ConstraintError::UnsupportedExpr("sum comparison overflows a 64-bit integer".to_string())
+}
+
+/// The values of an int domain with their one-hot literals.
+fn int_variants(name: &str, space: &EncodedInputSpace) -> Result<Vec<(i64, Lit)>, ConstraintError> {
+    let not_int = || ConstraintError::UnsupportedExpr(format!("'{name}' is not an int domain"));
+    match &space.domains[name].encoding {
+        Encoding::OneHot {
+            kind: ValueKind::Int,
+            variants,
+        } => variants
+            .iter()
+            .map(|(label, var)| {
+                label
+                    .parse::<i64>()
+                    .map(|value| (value, var.positive()))
+                    .map_err(|_| not_int())
+            })
+            .collect(),
+        Encoding::OneHot { .. } | Encoding::Bool { .. } | Encoding::Order { .. } => Err(not_int()),
+    }
+}
+
 /// Convert an Expr to a Literal if possible.
 fn expr_to_literal(expr: &Expr) -> Option<Literal> {
     match expr {
@@ -193,6 +611,7 @@ fn literal_to_domain_value(
         (Literal::String(s), Encoding::OneHot { .. }) => Ok(DomainValue::Enum(s.clone())),
         (Literal::Int(i), Encoding::OneHot { .. }) => Ok(DomainValue::Int(*i)),
         (Literal::Bool(b), Encoding::OneHot { .. }) => Ok(DomainValue::Bool(*b)),
+        (Literal::String(s), Encoding::Order { .. }) => Ok(DomainValue::Enum(s.clone())),
         _ => Err(ConstraintError::UnsupportedExpr(format!(
             "cannot convert literal {:?} for encoding {:?}",
             lit, encoding
@@ -202,43 +621,27 @@ fn literal_to_domain_value(
 
 /// Encode `implies(A, B)`.
 ///
-/// For atomic A (produces a single unit clause [lit_a]):
-///   implies(A, B) = not(A) OR B
-///   For each clause `c` in B, we add `(!lit_a) ++ c`.
-///
-/// For conjunctive A (produces multiple unit clauses):
-///   implies(A1 AND A2 AND ..., B) = not(A1) OR not(A2) OR ... OR B
-///   For each clause `c` in B, we add `(!a1 OR !a2 OR ... OR c)`.
+/// implies(A1 AND A2 AND ..., B) = not(A1) OR not(A2) OR ... OR B, so for
+/// each clause `c` in B we add `(!a1 OR !a2 OR ... OR c)`, where `ai` is
+/// the literal of conjunct `Ai` from `conjunct_lits`.
 fn encode_implies(
     antecedent: &Expr,
     consequent: &Expr,
-    space: &EncodedInputSpace,
+    space: &mut EncodedInputSpace,
 ) -> Result<CnfClauses, ConstraintError> {
-    let ante_clauses = encode_expr(antecedent, space)?;
     let cons_clauses = encode_expr(consequent, space)?;
-
-    // Collect all antecedent unit literals (negated for implication).
-    let mut ante_negated_lits: Vec<Lit> = Vec::new();
-    for clause in &ante_clauses {
-        if clause.len() == 1 {
-            ante_negated_lits.push(!clause[0]);
-        } else {
-            // Non-unit antecedent clause: more complex encoding needed.
-            // For now, we handle the common case where antecedent is a conjunction
-            // of atomic propositions (each producing a unit clause).
-            return Err(ConstraintError::UnsupportedExpr(
-                "implies with non-atomic antecedent clause is not yet supported".to_string(),
-            ));
-        }
-    }
-
     if cons_clauses.is_empty() {
         // Consequent is trivially true: implication is trivially true.
         return Ok(vec![]);
     }
 
-    // For each consequent clause, prepend the negated antecedent literals.
     let mut result = Vec::new();
+    let ante_negated_lits: Vec<Lit> = conjunct_lits(antecedent, space, &mut result)?
+        .into_iter()
+        .map(|l| !l)
+        .collect();
+
+    // For each consequent clause, prepend the negated antecedent literals.
     for cons_clause in &cons_clauses {
         let mut new_clause = ante_negated_lits.clone();
         new_clause.extend_from_slice(cons_clause);
@@ -250,81 +653,310 @@ fn encode_implies(
 
 /// Encode `or(A, B, ...)`.
 ///
-/// When each sub-expression produces only unit clauses, we can combine
-/// them into a single disjunctive clause. For more complex cases,
-/// we use auxiliary variables (Tseitin transformation).
-fn encode_or(args: &[Expr], space: &EncodedInputSpace) -> Result<CnfClauses, ConstraintError> {
-    // Collect the encoding of each argument.
-    let mut arg_clauses: Vec<CnfClauses> = Vec::new();
-    for arg in args {
-        arg_clauses.push(encode_expr(arg, space)?);
-    }
+/// Each disjunct contributes one literal to a single clause: atomic
+/// propositions directly, anything else through a Tseitin variable.
+/// This stays linear in size, unlike distributing the clause sets.
+fn encode_or(args: &[Expr], space: &mut EncodedInputSpace) -> Result<CnfClauses, ConstraintError> {
+    let mut gate_clauses = Vec::new();
+    let mut disjunction = Vec::new();
 
-    // Simple case: each argument produces exactly one unit clause.
-    let all_unit = arg_clauses
-        .iter()
-        .all(|cs| cs.len() == 1 && cs[0].len() == 1);
-    if all_unit {
-        let combined: Vec<Lit> = arg_clauses.iter().map(|cs| cs[0][0]).collect();
-        return Ok(vec![combined]);
-    }
-
-    // General case: at least one argument has multiple clauses.
-    // For `or(A, B)` where A = {c1 AND c2} and B = {d1 AND d2}:
-    // We need: for every combination (one clause from A, one clause from B),
-    // create a clause that is their union.
-    // This can be exponential, but for small constraint sets it's fine.
-    let mut result: Vec<Vec<Lit>> = vec![vec![]];
-    for arg_cs in &arg_clauses {
-        if arg_cs.is_empty() {
+    for arg in args {
+        match arg {
             // This disjunct is trivially true => entire OR is true.
-            return Ok(vec![]);
-        }
-        let mut new_result = Vec::new();
-        for existing in &result {
-            for clause in arg_cs {
-                let mut combined = existing.clone();
-                combined.extend_from_slice(clause);
-                new_result.push(combined);
-            }
+            Expr::Literal(Literal::Bool(true)) => return Ok(vec![]),
+            Expr::Literal(Literal::Bool(false)) => {}
+            _ => disjunction.push(gate_lit(arg, space, &mut gate_clauses)?),
         }
-        result = new_result;
     }
 
-    Ok(result)
+    gate_clauses.push(disjunction);
+    Ok(gate_clauses)
 }
 
 /// Encode `not(A)`.
 ///
-/// For atomic A (unit clause [lit]): not(A) = [!lit].
-/// For conjunctions: not(A AND B) = or(not(A), not(B)) — De Morgan.
-fn encode_not(expr: &Expr, space: &EncodedInputSpace) -> Result<CnfClauses, ConstraintError> {
+/// For comparisons: the complementary range, e.g. not(lt) = gte.
+/// Otherwise De Morgan over A's conjuncts: not(A1 AND A2 AND ...) =
+/// (!a1 OR !a2 OR ...), where `ai` is the literal of conjunct `Ai` from
+/// `conjunct_lits`. not(true) has no conjuncts and is the empty clause.
+fn encode_not(expr: &Expr, space: &mut EncodedInputSpace) -> Result<CnfClauses, ConstraintError> {
+    // not(lt(x, 4)) is gte(x, 4): encode the complementary range directly.
+    if let Expr::Op { op, args } = expr {
+        if let Some(complement) = complement_compare(op) {
+            let flipped = Expr::Op {
+                op: complement,
+                args: args.clone(),
+            };
+            return encode_expr(&flipped, space);
+        }
+    }
+
+    let mut clauses = Vec::new();
+    let negated: Vec<Lit> = conjunct_lits(expr, space, &mut clauses)?
+        .into_iter()
+        .map(|l| !l)
+        .collect();
+    clauses.push(negated);
+    Ok(clauses)
+}
+
+/// Literals whose conjunction is equivalent to `expr`, each operand
+/// encoded exactly once. `and` is flattened, `true` contributes nothing,
+/// and an atomic proposition yields the literals of its unit clauses
+/// directly. Anything else is one `gate_lit`, whose gate clauses are
+/// appended to `out`.
+fn conjunct_lits(
+    expr: &Expr,
+    space: &mut EncodedInputSpace,
+    out: &mut CnfClauses,
+) -> Result<Vec<Lit>, ConstraintError> {
+    match expr {
+        Expr::Op {
+            op: OpKind::And,
+            args,
+        } => {
+            let mut lits = Vec::new();
+            for arg in args {
+                lits.extend(conjunct_lits(arg, space, out)?);
+            }
+            Ok(lits)
+        }
+
+        Expr::Literal(Literal::Bool(true)) => Ok(vec![]),
+
+        Expr::Op {
+            op: OpKind::Eq | OpKind::Neq,
+            args,
+        } if args.len() == 2 => {
+            let clauses = encode_expr(expr, space)?;
+            if clauses.iter().all(|c| c.len() == 1) {
+                Ok(clauses.iter().map(|c| c[0]).collect())
+            } else {
+                Ok(vec![clauses_lit(expr, &clauses, space, out)?])
+            }
+        }
+
+        _ => Ok(vec![gate_lit(expr, space, out)?]),
+    }
+}
+
+/// Encode an atomic proposition to its literal. Equality under order
+/// encoding is a conjunction of unit clauses (its negation one clause of
+/// several literals), named by a gate.
+fn atomic_lit(
+    expr: &Expr,
+    space: &mut EncodedInputSpace,
+    out: &mut CnfClauses,
+) -> Result<Lit, ConstraintError> {
     let clauses = encode_expr(expr, space)?;
+    clauses_lit(expr, &clauses, space, out)
+}
 
-    if clauses.is_empty() {
-        // not(true) = false => empty clause (unsatisfiable).
-        return Ok(vec![vec![]]);
+/// Name the already-encoded clauses of atomic proposition `expr` by one
+/// literal.
+fn clauses_lit(
+    expr: &Expr,
+    clauses: &CnfClauses,
+    space: &mut EncodedInputSpace,
+    out: &mut CnfClauses,
+) -> Result<Lit, ConstraintError> {
+    if clauses.len() == 1 && clauses[0].len() == 1 {
+        Ok(clauses[0][0])
+    } else if clauses.len() > 1 && clauses.iter().all(|c| c.len() == 1) {
+        let lits: Vec<Lit> = clauses.iter().map(|c| c[0]).collect();
+        Ok(and_gate(&lits, space, out))
+    } else if clauses.len() == 1 {
+        Ok(or_gate(&clauses[0], space, out))
+    } else {
+        Err(ConstraintError::UnsupportedExpr(format!(
+            "expected an atomic proposition, got {}",
+            expr.to_infix()
+        )))
     }
+}
 
-    // For unit clauses, negate each literal.
-    // For a conjunction of unit clauses (AND of lits): not(l1 AND l2) = (!l1 OR !l2).
-    let all_unit = clauses.iter().all(|c| c.len() == 1);
-    if all_unit {
-        // De Morgan: not(l1 AND l2 AND ...) = (!l1 OR !l2 OR ...)
-        let negated: Vec<Lit> = clauses.iter().map(|c| !c[0]).collect();
-        return Ok(vec![negated]);
+/// Tseitin transformation: return a literal equivalent to `expr`.
+///
+/// Atomic propositions map to their own literal. Compound expressions get
+/// a fresh auxiliary variable `t` and the gate clauses making `t` true
+/// exactly when the expression is, appended to `out`.
+fn gate_lit(
+    expr: &Expr,
+    space: &mut EncodedInputSpace,
+    out: &mut CnfClauses,
+) -> Result<Lit, ConstraintError> {
+    match expr {
+        Expr::Literal(Literal::Bool(b)) => {
+            let t = space.fresh_var().positive();
+            out.push(vec![if *b { t } else { !t }]);
+            Ok(t)
+        }
+
+        Expr::Op {
+            op: OpKind::Not,
+            args,
+        } if args.len() == 1 => Ok(!gate_lit(&args[0], space, out)?),
+
+        Expr::Op {
+            op: OpKind::And,
+            args,
+        } => {
+            let lits = gate_lits(args, space, out)?;
+            Ok(and_gate(&lits, space, out))
+        }
+
+        Expr::Op {
+            op: OpKind::Or,
+            args,
+        } => {
+            let lits = gate_lits(args, space, out)?;
+            Ok(or_gate(&lits, space, out))
+        }
+
+        // A sum comparison holds when none of its blocking clauses is violated.
+        Expr::Op {
+            op: op @ (OpKind::Lt | OpKind::Lte | OpKind::Gt | OpKind::Gte),
+            args,
+        } if args.len() == 2 && args.iter().any(is_sum) => {
+            let blocking = encode_sum_compare(op, &args[0], &args[1], space)?;
+            let lits: Vec<Lit> = blocking
+                .iter()
+                .map(|clause| or_gate(clause, space, out))
+                .collect();
+            Ok(and_gate(&lits, space, out))
+        }
+
+        // A comparison is the disjunction of the variants satisfying it.
+        Expr::Op {
+            op: op @ (OpKind::Lt | OpKind::Lte | OpKind::Gt | OpKind::Gte),
+            args,
+        } if args.len() == 2 => {
+            let lits = compare_lits(op, &args[0], &args[1], space)?;
+            Ok(or_gate(&lits, space, out))
+        }
+
+        // Membership is the disjunction of the listed values.
+        Expr::Op {
+            op: op @ (OpKind::In | OpKind::NotIn),
+            args,
+        } => {
+            let lits = member_lits(args, space, out)?;
+            let t = or_gate(&lits, space, out);
+            Ok(if *op == OpKind::In { t } else { !t })
+        }
+
+        // t <-> (!a OR b).
+        Expr::Op {
+            op: OpKind::Implies,
+            args,
+        } if args.len() == 2 => {
+            let a = gate_lit(&args[0], space, out)?;
+            let b = gate_lit(&args[1], space, out)?;
+            let t = space.fresh_var().positive();
+            out.push(vec![t, a]);
+            out.push(vec![t, !b]);
+            out.push(vec![!t, !a, b]);
+            Ok(t)
+        }
+
+        Expr::Op {
+            op: OpKind::Xor,
+            args,
+        } if args.len() >= 2 => {
+            let lits = gate_lits(args, space, out)?;
+            let mut acc = lits[0];
+            for &x in &lits[1..] {
+                acc = xor_gate(acc, x, space, out);
+            }
+            Ok(acc)
+        }
+
+        // a <-> b is the negation of a XOR b.
+        Expr::Op {
+            op: OpKind::Iff,
+            args,
+        } if args.len() == 2 => {
+            let a = gate_lit(&args[0], space, out)?;
+            let b = gate_lit(&args[1], space, out)?;
+            Ok(!xor_gate(a, b, space, out))
+        }
+
+        _ => atomic_lit(expr, space, out),
     }
+}
 
-    Err(ConstraintError::UnsupportedExpr(
-        "not() over complex (non-unit) clauses is not yet supported".to_string(),
-    ))
+/// `gate_lit` over each operand, in order.
+fn gate_lits(
+    args: &[Expr],
+    space: &mut EncodedInputSpace,
+    out: &mut CnfClauses,
+) -> Result<Vec<Lit>, ConstraintError> {
+    let mut lits = Vec::with_capacity(args.len());
+    for arg in args {
+        lits.push(gate_lit(arg, space, out)?);
+    }
+    Ok(lits)
+}
+
+/// Fresh `t <-> (l1 AND ... AND ln)`: (!t OR li) for each i, (t OR !l1 OR ... OR !ln).
+fn and_gate(lits: &[Lit], space: &mut EncodedInputSpace, out: &mut CnfClauses) -> Lit {
+    let t = space.fresh_var().positive();
+    let mut back = vec![t];
+    for &l in lits {
+        out.push(vec![!t, l]);
+        back.push(!l);
+    }
+    out.push(back);
+    t
+}
+
+/// Fresh `t <-> (l1 OR ... OR ln)`: (t OR !li) for each i, (!t OR l1 OR ... OR ln).
+fn or_gate(lits: &[Lit], space: &mut EncodedInputSpace, out: &mut CnfClauses) -> Lit {
+    let t = space.fresh_var().positive();
+    let mut back = vec![!t];
+    for &l in lits {
+        out.push(vec![t, !l]);
+        back.push(l);
+    }
+    out.push(back);
+    t
+}
+
+/// Fresh `t <-> (a XOR b)`, as the four standard gate clauses.
+fn xor_gate(a: Lit, b: Lit, space: &mut EncodedInputSpace, out: &mut CnfClauses) -> Lit {
+    let t = space.fresh_var().positive();
+    out.push(vec![!t, a, b]);
+    out.push(vec![!t, !a, !b]);
+    out.push(vec![t, !a, b]);
+    out.push(vec![t, a, !b]);
+    t
+}
+
+/// Encode `xor(A, B, ...)` as odd parity.
+///
+/// Two operands use the direct two-clause form `(a OR b) AND (!a OR !b)`.
+/// More operands chain XOR gates over auxiliary variables, finishing with
+/// the two-clause form on the last operand.
+fn encode_xor(args: &[Expr], space: &mut EncodedInputSpace) -> Result<CnfClauses, ConstraintError> {
+    let mut clauses = Vec::new();
+    let lits = gate_lits(args, space, &mut clauses)?;
+
+    let mut acc = lits[0];
+    for &x in &lits[1..lits.len() - 1] {
+        acc = xor_gate(acc, x, space, &mut clauses);
+    }
+
+    let last = lits[lits.len() - 1];
+    clauses.push(vec![acc, last]);
+    clauses.push(vec![!acc, !last]);
+    Ok(clauses)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use fresnel_fir_ir::types::*;
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
     use varisat::{solver::Solver, ExtendFormula};
 
     use crate::solver::domain::{decode_model, encode_input_space};
@@ -336,6 +968,7 @@ mod tests {
         InputSpace {
             domains,
             constraints,
+            forbidden: Vec::new(),
             coverage: CoverageConfig {
                 targets: vec![],
                 seed: 42,
@@ -347,12 +980,13 @@ mod tests {
     fn make_solver_with_space(
         input_space: &InputSpace,
     ) -> (Solver<'_>, super::super::domain::EncodedInputSpace) {
-        let encoded = encode_input_space(input_space).unwrap();
+        let mut encoded = encode_input_space(input_space).unwrap();
         let mut solver = Solver::new();
         for clause in &encoded.structural_clauses {
             solver.add_clause(clause);
         }
-        let constraint_clauses = encode_constraints(&input_space.constraints, &encoded).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
         for clause in &constraint_clauses {
             solver.add_clause(clause);
         }
@@ -368,6 +1002,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -400,6 +1035,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -443,6 +1079,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -508,6 +1145,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -566,6 +1204,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -620,6 +1259,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -634,6 +1274,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["private".into(), "shared".into(), "public".into()],
+                    ordered: false,
                 },
             },
         );
@@ -646,7 +1287,11 @@ mod tests {
         domains.insert(
             "concurrent_actors".to_string(),
             Domain {
-                domain_type: DomainType::Int { min: 1, max: 8 },
+                domain_type: DomainType::Int {
+                    min: 1,
+                    max: 8,
+                    stride: 1,
+                },
             },
         );
 
@@ -693,6 +1338,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -726,4 +1372,787 @@ mod tests {
         // Should be UNSAT.
         assert!(!solver.solve().unwrap());
     }
+
+    fn bool_domains(names: &[&str]) -> HashMap<String, Domain> {
+        names
+            .iter()
+            .map(|n| {
+                (
+                    n.to_string(),
+                    Domain {
+                        domain_type: DomainType::Bool,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn op(op: OpKind, args: Vec<Expr>) -> Expr {
+        Expr::Op { op, args }
+    }
+
+    fn name(n: &str) -> Expr {
+        Expr::Literal(Literal::String(n.into()))
+    }
+
+    #[test]
+    fn test_xor_two_bools() {
+        let constraints = vec![InputConstraint {
+            name: "exclusive".to_string(),
+            rule: op(OpKind::Xor, vec![name("flagA"), name("flagB")]),
+        }];
+        let input_space =
+            make_input_space_with_constraints(bool_domains(&["flagA", "flagB"]), constraints);
+
+        let vectors = crate::solver::search::solve_input_space(&input_space, 0).unwrap();
+        assert_eq!(vectors.len(), 2);
+        for v in &vectors {
+            assert_ne!(v.assignments["flagA"], v.assignments["flagB"]);
+        }
+    }
+
+    #[test]
+    fn test_iff_two_bools() {
+        let constraints = vec![InputConstraint {
+            name: "same".to_string(),
+            rule: op(OpKind::Iff, vec![name("flagA"), name("flagB")]),
+        }];
+        let input_space =
+            make_input_space_with_constraints(bool_domains(&["flagA", "flagB"]), constraints);
+
+        let vectors = crate::solver::search::solve_input_space(&input_space, 0).unwrap();
+        assert_eq!(vectors.len(), 2);
+        for v in &vectors {
+            assert_eq!(v.assignments["flagA"], v.assignments["flagB"]);
+        }
+    }
+
+    #[test]
+    fn test_xor_odd_parity_over_three() {
+        let constraints = vec![InputConstraint {
+            name: "odd".to_string(),
+            rule: op(OpKind::Xor, vec![name("a"), name("b"), name("c")]),
+        }];
+        let input_space =
+            make_input_space_with_constraints(bool_domains(&["a", "b", "c"]), constraints);
+
+        // 4 of the 8 assignments have an odd number of true values.
+        let vectors = crate::solver::search::solve_input_space(&input_space, 0).unwrap();
+        assert_eq!(vectors.len(), 4);
+        for v in &vectors {
+            let trues = v
+                .assignments
+                .values()
+                .filter(|x| **x == DomainValue::Bool(true))
+                .count();
+            assert_eq!(trues % 2, 1);
+        }
+    }
+
+    #[test]
+    fn test_xor_non_atomic_operand() {
+        // xor(and(a, b), c): exactly one of (a AND b) and c holds.
+        let constraints = vec![InputConstraint {
+            name: "complex".to_string(),
+            rule: op(
+                OpKind::Xor,
+                vec![op(OpKind::And, vec![name("a"), name("b")]), name("c")],
+            ),
+        }];
+        let input_space =
+            make_input_space_with_constraints(bool_domains(&["a", "b", "c"]), constraints);
+
+        let vectors = crate::solver::search::solve_input_space(&input_space, 0).unwrap();
+        assert_eq!(vectors.len(), 4);
+        for v in &vectors {
+            let both = v.assignments["a"] == DomainValue::Bool(true)
+                && v.assignments["b"] == DomainValue::Bool(true);
+            assert_ne!(both, v.assignments["c"] == DomainValue::Bool(true));
+        }
+    }
+
+    /// Reference evaluator for constraint expressions over a full assignment.
+    fn eval_reference(expr: &Expr, assignment: &BTreeMap<String, DomainValue>) -> bool {
+        match expr {
+            Expr::Literal(Literal::Bool(b)) => *b,
+            Expr::Literal(Literal::String(n)) => assignment[n] == DomainValue::Bool(true),
+            Expr::Op { op, args } => match op {
+                OpKind::Eq | OpKind::Neq => {
+                    let Expr::Literal(Literal::String(n)) = &args[0] else {
+                        panic!("expected domain name");
+                    };
+                    let expected = match &args[1] {
+                        Expr::Literal(Literal::Bool(b)) => DomainValue::Bool(*b),
+                        Expr::Literal(Literal::String(s)) => DomainValue::Enum(s.clone()),
+                        other => panic!("unexpected value {other:?}"),
+                    };
+                    (assignment[n] == expected) == (*op == OpKind::Eq)
+                }
+                OpKind::Not => !eval_reference(&args[0], assignment),
+                OpKind::And => args.iter().all(|a| eval_reference(a, assignment)),
+                OpKind::Or => args.iter().any(|a| eval_reference(a, assignment)),
+                OpKind::Implies => {
+                    !eval_reference(&args[0], assignment) || eval_reference(&args[1], assignment)
+                }
+                OpKind::Xor => {
+                    args.iter()
+                        .filter(|a| eval_reference(a, assignment))
+                        .count()
+                        % 2
+                        == 1
+                }
+                OpKind::Iff => {
+                    eval_reference(&args[0], assignment) == eval_reference(&args[1], assignment)
+                }
+                other => panic!("unexpected operator {other:?}"),
+            },
+            other => panic!("unexpected expression {other:?}"),
+        }
+    }
+
+    fn eq(domain: &str, value: Literal) -> Expr {
+        op(OpKind::Eq, vec![name(domain), Expr::Literal(value)])
+    }
+
+    /// Enumerate the encoded solution set and compare it with brute force
+    /// over every assignment of the role x vis x flag space.
+    fn assert_matches_reference(rule: Expr) {
+        let mut domains = bool_domains(&["flag"]);
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "vis".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["private".into(), "public".into()],
+                    ordered: false,
+                },
+            },
+        );
+        let constraints = vec![InputConstraint {
+            name: "nested".to_string(),
+            rule: rule.clone(),
+        }];
+        let input_space = make_input_space_with_constraints(domains, constraints);
+
+        let solved: BTreeSet<_> = crate::solver::search::solve_input_space(&input_space, 0)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.assignments)
+            .collect();
+
+        let mut expected = BTreeSet::new();
+        for role in ["admin", "member", "guest"] {
+            for vis in ["private", "public"] {
+                for flag in [false, true] {
+                    let assignment = BTreeMap::from([
+                        ("flag".to_string(), DomainValue::Bool(flag)),
+                        ("role".to_string(), DomainValue::Enum(role.into())),
+                        ("vis".to_string(), DomainValue::Enum(vis.into())),
+                    ]);
+                    if eval_reference(&rule, &assignment) {
+                        expected.insert(assignment);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(solved, expected);
+    }
+
+    #[test]
+    fn test_tseitin_nested_not_or_matches_reference() {
+        // not(or(and(eq(role, admin), eq(vis, public)), not(eq(flag, true))))
+        let rule = op(
+            OpKind::Not,
+            vec![op(
+                OpKind::Or,
+                vec![
+                    op(
+                        OpKind::And,
+                        vec![
+                            eq("role", Literal::String("admin".into())),
+                            eq("vis", Literal::String("public".into())),
+                        ],
+                    ),
+                    op(OpKind::Not, vec![eq("flag", Literal::Bool(true))]),
+                ],
+            )],
+        );
+        assert_matches_reference(rule);
+    }
+
+    #[test]
+    fn test_tseitin_mixed_connectives_match_reference() {
+        // or(implies(or(eq(role, guest), flag), eq(vis, private)),
+        //    iff(not(eq(role, member)), and(flag, eq(vis, public))))
+        let rule = op(
+            OpKind::Or,
+            vec![
+                op(
+                    OpKind::Implies,
+                    vec![
+                        op(
+                            OpKind::Or,
+                            vec![eq("role", Literal::String("guest".into())), name("flag")],
+                        ),
+                        eq("vis", Literal::String("private".into())),
+                    ],
+                ),
+                op(
+                    OpKind::Iff,
+                    vec![
+                        op(
+                            OpKind::Not,
+                            vec![eq("role", Literal::String("member".into()))],
+                        ),
+                        op(
+                            OpKind::And,
+                            vec![name("flag"), eq("vis", Literal::String("public".into()))],
+                        ),
+                    ],
+                ),
+            ],
+        );
+        assert_matches_reference(rule);
+    }
+
+    #[test]
+    fn test_compound_operands_encode_once() {
+        let mut domains = bool_domains(&["flag"]);
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        let input_space = make_input_space_with_constraints(domains, vec![]);
+        // or(and(eq(role, guest), flag), eq(role, member)): two gates.
+        let nested = || {
+            op(
+                OpKind::Or,
+                vec![
+                    op(
+                        OpKind::And,
+                        vec![eq("role", Literal::String("guest".into())), name("flag")],
+                    ),
+                    eq("role", Literal::String("member".into())),
+                ],
+            )
+        };
+
+        // Each compound operand is encoded once: only its own gates are
+        // allocated, and the emitted clauses use every one of them.
+        for rule in [
+            op(
+                OpKind::Implies,
+                vec![nested(), eq("role", Literal::String("admin".into()))],
+            ),
+            op(OpKind::Not, vec![nested()]),
+        ] {
+            let mut encoded = encode_input_space(&input_space).unwrap();
+            let first_aux = encoded.next_var;
+            let clauses = encode_expr(&rule, &mut encoded).unwrap();
+            let used: BTreeSet<usize> = clauses.iter().flatten().map(|l| l.var().index()).collect();
+            assert_eq!(encoded.next_var - first_aux, 2, "{}", rule.to_infix());
+            assert!((first_aux..encoded.next_var).all(|v| used.contains(&v)));
+        }
+    }
+
+    /// Visibility values solving `rule` over an enum domain declared
+    /// ordered as `private < shared < public`.
+    fn solve_visibility(rule: Expr) -> BTreeSet<DomainValue> {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "visibility".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["private".into(), "shared".into(), "public".into()],
+                    ordered: true,
+                },
+            },
+        );
+        let input_space = make_input_space_with_constraints(
+            domains,
+            vec![InputConstraint {
+                name: "rule".to_string(),
+                rule,
+            }],
+        );
+
+        crate::solver::search::solve_input_space(&input_space, 0)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.assignments["visibility"].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_in_matches_hand_written_or() {
+        let solve_roles = |rule: Expr| -> BTreeSet<DomainValue> {
+            let mut domains = HashMap::new();
+            domains.insert(
+                "role".to_string(),
+                Domain {
+                    domain_type: DomainType::Enum {
+                        values: vec!["admin".into(), "member".into(), "guest".into()],
+                        ordered: false,
+                    },
+                },
+            );
+            let input_space = make_input_space_with_constraints(
+                domains,
+                vec![InputConstraint {
+                    name: "rule".to_string(),
+                    rule,
+                }],
+            );
+            crate::solver::search::solve_input_space(&input_space, 0)
+                .unwrap()
+                .into_iter()
+                .map(|v| v.assignments["role"].clone())
+                .collect()
+        };
+        let member = || {
+            op(
+                OpKind::In,
+                vec![name("role"), name("admin"), name("member")],
+            )
+        };
+
+        let via_in = solve_roles(member());
+        assert_eq!(
+            via_in,
+            BTreeSet::from([
+                DomainValue::Enum("admin".into()),
+                DomainValue::Enum("member".into()),
+            ])
+        );
+        let via_or = solve_roles(op(
+            OpKind::Or,
+            vec![
+                eq("role", Literal::String("admin".into())),
+                eq("role", Literal::String("member".into())),
+            ],
+        ));
+        assert_eq!(via_in, via_or);
+
+        let guest_only = BTreeSet::from([DomainValue::Enum("guest".into())]);
+        assert_eq!(
+            solve_roles(op(
+                OpKind::NotIn,
+                vec![name("role"), name("admin"), name("member")]
+            )),
+            guest_only
+        );
+        assert_eq!(solve_roles(op(OpKind::Not, vec![member()])), guest_only);
+    }
+
+    #[test]
+    fn test_in_rejects_value_outside_domain() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        let input_space = make_input_space_with_constraints(domains, vec![]);
+        let mut encoded = encode_input_space(&input_space).unwrap();
+        let rule = op(OpKind::In, vec![name("role"), name("admin"), name("owner")]);
+        assert!(matches!(
+            encode_expr(&rule, &mut encoded),
+            Err(ConstraintError::InvalidValue { value, .. }) if value.contains("owner")
+        ));
+    }
+
+    #[test]
+    fn test_gte_resolves_against_ordered_enum() {
+        let gte = op(OpKind::Gte, vec![name("visibility"), name("shared")]);
+        assert_eq!(
+            solve_visibility(gte.clone()),
+            BTreeSet::from([
+                DomainValue::Enum("shared".into()),
+                DomainValue::Enum("public".into()),
+            ])
+        );
+
+        // Comparisons compose like any other proposition.
+        assert_eq!(
+            solve_visibility(op(OpKind::Not, vec![gte])),
+            BTreeSet::from([DomainValue::Enum("private".into())])
+        );
+    }
+
+    /// Visibility values solving `rule`, and the total clause count, with
+    /// `private < shared < public` either declared `ordered` (order
+    /// encoding) or left unordered (one-hot encoding).
+    fn solve_visibility_encoded(rule: Expr, ordered: bool) -> (BTreeSet<DomainValue>, usize) {
+        let domains = HashMap::from([(
+            "visibility".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["private".into(), "shared".into(), "public".into()],
+                    ordered,
+                },
+            },
+        )]);
+        let input_space = make_input_space_with_constraints(
+            domains,
+            vec![InputConstraint {
+                name: "rule".to_string(),
+                rule,
+            }],
+        );
+
+        let mut encoded = encode_input_space(&input_space).unwrap();
+        let clauses = encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+        let solutions = crate::solver::search::solve_input_space(&input_space, 0)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.assignments["visibility"].clone())
+            .collect();
+        (solutions, encoded.structural_clauses.len() + clauses.len())
+    }
+
+    #[test]
+    fn test_order_encoding_matches_one_hot_with_fewer_clauses() {
+        let gte = op(OpKind::Gte, vec![name("visibility"), name("shared")]);
+        // An unordered enum cannot be compared, so spell the range out.
+        let range = op(
+            OpKind::In,
+            vec![name("visibility"), name("shared"), name("public")],
+        );
+        let (order_solutions, order_clauses) = solve_visibility_encoded(gte, true);
+        let (one_hot_solutions, one_hot_clauses) = solve_visibility_encoded(range, false);
+        assert_eq!(order_solutions, one_hot_solutions);
+        assert_eq!(
+            order_solutions,
+            BTreeSet::from([
+                DomainValue::Enum("shared".into()),
+                DomainValue::Enum("public".into()),
+            ])
+        );
+        assert!(
+            order_clauses < one_hot_clauses,
+            "order {order_clauses} vs one-hot {one_hot_clauses}"
+        );
+
+        // Equality with an inner variant takes two threshold literals, and
+        // still composes under negation and inside gates.
+        let shared = || op(OpKind::Eq, vec![name("visibility"), name("shared")]);
+        let lt_shared = op(OpKind::Lt, vec![name("visibility"), name("shared")]);
+        let private = op(OpKind::Eq, vec![name("visibility"), name("private")]);
+        for (ordered_rule, one_hot_rule) in [
+            (shared(), shared()),
+            (
+                op(OpKind::Not, vec![shared()]),
+                op(OpKind::Not, vec![shared()]),
+            ),
+            (
+                op(OpKind::Or, vec![shared(), lt_shared]),
+                op(OpKind::Or, vec![shared(), private]),
+            ),
+        ] {
+            assert_eq!(
+                solve_visibility_encoded(ordered_rule, true).0,
+                solve_visibility_encoded(one_hot_rule, false).0
+            );
+        }
+    }
+
+    /// (a, b) pairs solving `rule` over two `Int{0,8}` domains.
+    fn solve_int_pairs(rule: Expr) -> BTreeSet<(i64, i64)> {
+        let int_domain = || Domain {
+            domain_type: DomainType::Int {
+                min: 0,
+                max: 8,
+                stride: 1,
+            },
+        };
+        let domains = HashMap::from([
+            ("a".to_string(), int_domain()),
+            ("b".to_string(), int_domain()),
+        ]);
+        let input_space = make_input_space_with_constraints(
+            domains,
+            vec![InputConstraint {
+                name: "rule".to_string(),
+                rule,
+            }],
+        );
+
+        crate::solver::search::solve_input_space(&input_space, 0)
+            .unwrap()
+            .into_iter()
+            .map(|v| match (&v.assignments["a"], &v.assignments["b"]) {
+                (DomainValue::Int(a), DomainValue::Int(b)) => (*a, *b),
+                other => panic!("expected int values, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sum_lte_matches_hand_computed_pairs() {
+        let sum = op(OpKind::Sum, vec![name("a"), name("b")]);
+        let lte = op(OpKind::Lte, vec![sum, Expr::Literal(Literal::Int(4))]);
+
+        let mut expected = BTreeSet::new();
+        for a in 0..=4 {
+            for b in 0..=4 - a {
+                expected.insert((a, b));
+            }
+        }
+        assert_eq!(expected.len(), 15);
+        assert_eq!(solve_int_pairs(lte.clone()), expected);
+
+        // Nested under `not`, the complement is every other pair.
+        let complement: BTreeSet<_> = (0..=8)
+            .flat_map(|a| (0..=8).map(move |b| (a, b)))
+            .filter(|pair| !expected.contains(pair))
+            .collect();
+        assert_eq!(solve_int_pairs(op(OpKind::Not, vec![lte])), complement);
+    }
+
+    #[test]
+    fn test_sum_over_non_int_domain_is_unsupported() {
+        let mut encoded = encode_input_space(&make_input_space_with_constraints(
+            bool_domains(&["flag"]),
+            vec![],
+        ))
+        .unwrap();
+        let rule = op(
+            OpKind::Lte,
+            vec![
+                op(
+                    OpKind::Sum,
+                    vec![name("flag"), Expr::Literal(Literal::Int(1))],
+                ),
+                Expr::Literal(Literal::Int(1)),
+            ],
+        );
+        let err = encode_constraints(
+            &[InputConstraint {
+                name: "rule".to_string(),
+                rule,
+            }],
+            &mut encoded,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ConstraintError::UnsupportedExpr(_)));
+    }
+
+    #[test]
+    fn test_overflowing_sum_is_unsupported() {
+        let domains = HashMap::from([(
+            "a".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: 0,
+                    max: 8,
+                    stride: 1,
+                },
+            },
+        )]);
+        let zero = || Expr::Literal(Literal::Int(0));
+        for lhs in [
+            op(
+                OpKind::Sum,
+                vec![name("a"), Expr::Literal(Literal::Int(i64::MAX))],
+            ),
+            op(
+                OpKind::Sub,
+                vec![zero(), Expr::Literal(Literal::Int(i64::MIN))],
+            ),
+        ] {
+            let mut encoded =
+                encode_input_space(&make_input_space_with_constraints(domains.clone(), vec![]))
+                    .unwrap();
+            let err = encode_constraints(
+                &[InputConstraint {
+                    name: "rule".to_string(),
+                    rule: op(OpKind::Lte, vec![lhs, zero()]),
+                }],
+                &mut encoded,
+            )
+            .unwrap_err();
+            assert!(matches!(err, ConstraintError::UnsupportedExpr(_)));
+        }
+    }
+
+    /// Values of an `Int{1,5}` domain `count` satisfying `rule`.
+    fn solve_count(rule: Expr) -> BTreeSet<i64> {
+        let domains = HashMap::from([(
+            "count".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: 1,
+                    max: 5,
+                    stride: 1,
+                },
+            },
+        )]);
+        let input_space = make_input_space_with_constraints(
+            domains,
+            vec![InputConstraint {
+                name: "rule".to_string(),
+                rule,
+            }],
+        );
+
+        crate::solver::search::solve_input_space(&input_space, 0)
+            .unwrap()
+            .into_iter()
+            .map(|v| match v.assignments["count"] {
+                DomainValue::Int(count) => count,
+                ref other => panic!("expected an int value, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_not_comparison_encodes_complementary_range() {
+        let two = || Expr::Literal(Literal::Int(2));
+        let not_lte = op(
+            OpKind::Not,
+            vec![op(OpKind::Lte, vec![name("count"), two()])],
+        );
+        let gt = op(OpKind::Gt, vec![name("count"), two()]);
+
+        assert_eq!(solve_count(not_lte.clone()), BTreeSet::from([3, 4, 5]));
+        assert_eq!(solve_count(not_lte), solve_count(gt));
+
+        // Literal on the left: not(2 < count) is count <= 2.
+        let not_lt_flipped = op(
+            OpKind::Not,
+            vec![op(OpKind::Lt, vec![two(), name("count")])],
+        );
+        assert_eq!(solve_count(not_lt_flipped), BTreeSet::from([1, 2]));
+    }
+
+    fn role_visibility_space(forbidden: Vec<Vec<(String, Literal)>>) -> InputSpace {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "vis".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["private".into(), "public".into()],
+                    ordered: false,
+                },
+            },
+        );
+        let mut input_space = make_input_space_with_constraints(domains, vec![]);
+        input_space.forbidden = forbidden;
+        input_space
+    }
+
+    fn pair(domain: &str, value: &str) -> (String, Literal) {
+        (domain.to_string(), Literal::String(value.to_string()))
+    }
+
+    #[test]
+    fn test_forbidden_combination_is_never_generated() {
+        let input_space =
+            role_visibility_space(vec![vec![pair("role", "guest"), pair("vis", "public")]]);
+        let mut encoded = encode_input_space(&input_space).unwrap();
+        let clauses = encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+        assert_eq!(clauses.len(), 1, "one blocking clause per combination");
+
+        let pairs: BTreeSet<(String, String)> =
+            crate::solver::search::solve_input_space(&input_space, 0)
+                .unwrap()
+                .into_iter()
+                .map(|v| {
+                    (
+                        v.assignments["role"].to_string(),
+                        v.assignments["vis"].to_string(),
+                    )
+                })
+                .collect();
+        let expected = [
+            ("admin", "private"),
+            ("admin", "public"),
+            ("guest", "private"),
+        ]
+        .into_iter()
+        .map(|(r, v)| (r.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn test_forbidden_combination_must_name_existing_values() {
+        let unknown_domain = role_visibility_space(vec![vec![pair("tier", "gold")]]);
+        let mut encoded = encode_input_space(&unknown_domain).unwrap();
+        assert!(matches!(
+            encode_constraints(&[], &mut encoded),
+            Err(ConstraintError::UnknownDomain(d)) if d == "tier"
+        ));
+
+        let unknown_value = role_visibility_space(vec![vec![pair("role", "root")]]);
+        let mut encoded = encode_input_space(&unknown_value).unwrap();
+        assert!(matches!(
+            encode_constraints(&[], &mut encoded),
+            Err(ConstraintError::InvalidValue { domain, .. }) if domain == "role"
+        ));
+    }
+
+    #[test]
+    fn test_merged_overlay_space_solves() {
+        let mut base = role_visibility_space(vec![]);
+        let overlay = make_input_space_with_constraints(
+            HashMap::from([(
+                "tier".to_string(),
+                Domain {
+                    domain_type: DomainType::Enum {
+                        values: vec!["free".into(), "paid".into()],
+                        ordered: false,
+                    },
+                },
+            )]),
+            vec![InputConstraint {
+                name: "guests_are_free".to_string(),
+                rule: op(
+                    OpKind::Implies,
+                    vec![
+                        op(OpKind::Eq, vec![name("role"), name("guest")]),
+                        op(OpKind::Eq, vec![name("tier"), name("free")]),
+                    ],
+                ),
+            }],
+        );
+        base.merge(&overlay).unwrap();
+        assert_eq!(base.domains.len(), 3);
+        assert_eq!(base.constraints.len(), 1);
+
+        let vectors = crate::solver::search::solve_input_space(&base, 0).unwrap();
+        // 2 * 2 * 2 combinations minus the two paid guests.
+        assert_eq!(vectors.len(), 6);
+        assert!(vectors.iter().all(|v| {
+            v.assignments["role"].to_string() != "guest"
+                || v.assignments["tier"].to_string() == "free"
+        }));
+    }
 }
@@ -2,17 +2,24 @@
 //!
 //! Generates test vectors targeting specific coverage points:
 //! - **all-pairs**: For N variables, ensure every pair of values is covered.
-//! - **boundary**: Boundary values for integer domains (min, max, min+1, max-1).
+//! - **boundary**: Boundary values for integer domains (min, max, min+1, max-1,
+//!   plus -1, 0, 1 when the range crosses zero) and enum domains (first and
+//!   last variants, plus inner neighbours if ordered).
 //! - **each-transition**: Each transition in a state machine (delegated to traversal).
 
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use fresnel_fir_ir::types::{CoverageTarget, DomainType, InputSpace};
 
 use super::constraint::{encode_constraints, CnfClauses};
 use super::domain::{encode_input_space, lit_for_value, EncodedInputSpace};
-use super::search::{find_many, SearchError};
+use super::search::{find_many, find_one_with_assumptions, SatResult, SearchError, SolverContext};
 use super::{DomainValue, TestVector};
+use crate::error::ExploreError;
 
 /// A coverage point — a specific combination that must be exercised.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -39,6 +46,20 @@ pub struct CoverageResult {
     pub uncoverable: HashSet<CoveragePoint>,
     /// Total coverage points targeted.
     pub total_targets: usize,
+    /// Whether generation stopped early on cancellation. Targets not yet
+    /// tried are in neither `covered` nor `uncoverable`.
+    pub cancelled: bool,
+}
+
+/// Options for `coverage_driven_generation_with`.
+#[derive(Debug, Clone, Default)]
+pub struct GenerationConfig {
+    /// Targets are tried in descending priority; unlisted targets rank 0
+    /// and keep their declaration order among equals.
+    pub priority: HashMap<CoveragePoint, u32>,
+    /// Cancellation token, checked after each target is tried. Once set,
+    /// generation stops and returns the vectors found so far.
+    pub cancel: Option<Arc<AtomicBool>>,
 }
 
 /// Generate all-pairs coverage targets for the given variables.
@@ -78,41 +99,47 @@ pub fn boundary_targets(
     let mut targets = Vec::new();
 
     // Add explicit boundary values from the IR.
-    for val in explicit_values {
-        if let Some(i) = val.as_i64() {
-            targets.push(CoveragePoint::Boundary {
-                var: domain_name.to_string(),
-                value: DomainValue::Int(i),
-            });
-        } else if let Some(s) = val.as_str() {
-            targets.push(CoveragePoint::Boundary {
-                var: domain_name.to_string(),
-                value: DomainValue::Enum(s.to_string()),
-            });
-        } else if let Some(b) = val.as_bool() {
-            targets.push(CoveragePoint::Boundary {
-                var: domain_name.to_string(),
-                value: DomainValue::Bool(b),
-            });
-        }
+    for value in explicit_values.iter().filter_map(explicit_value) {
+        targets.push(CoveragePoint::Boundary {
+            var: domain_name.to_string(),
+            value,
+        });
     }
 
-    // For integer domains, also add automatic boundary values.
+    // Automatic boundary values from the domain itself.
     if let Some(domain) = input_space.domains.get(domain_name) {
-        if let DomainType::Int { min, max } = &domain.domain_type {
-            let mut auto = vec![*min, *max];
-            if max - min > 1 {
-                auto.push(min + 1);
-                auto.push(max - 1);
-            }
-            for val in auto {
-                let point = CoveragePoint::Boundary {
-                    var: domain_name.to_string(),
-                    value: DomainValue::Int(val),
-                };
-                if !targets.contains(&point) {
-                    targets.push(point);
+        let auto = match &domain.domain_type {
+            DomainType::Int { min, max, stride } => {
+                let (min, stride) = (*min, (*stride).max(1));
+                // Highest value on the stride grid.
+                let top = min + (max - min) / stride * stride;
+                let mut auto = vec![min, top];
+                if top - min > stride {
+                    auto.push(min + stride);
+                    auto.push(top - stride);
+                }
+                // Zero crossing: sign bugs hide around -1, 0, 1.
+                if min < 0 && 0 < *max {
+                    auto.extend([-1, 0, 1]);
                 }
+                auto.into_iter()
+                    .filter(|v| (v - min) % stride == 0)
+                    .map(DomainValue::Int)
+                    .collect()
+            }
+            DomainType::Enum { values, ordered } => enum_boundaries(values, *ordered)
+                .into_iter()
+                .map(|v| DomainValue::Enum(v.clone()))
+                .collect(),
+            DomainType::Bool => vec![],
+        };
+        for value in auto {
+            let point = CoveragePoint::Boundary {
+                var: domain_name.to_string(),
+                value,
+            };
+            if !targets.contains(&point) {
+                targets.push(point);
             }
         }
     }
@@ -120,7 +147,37 @@ pub fn boundary_targets(
     targets
 }
 
+/// Boundary variants of an enum domain: the first and last declared
+/// variants, plus their inner neighbours when the declared order is
+/// meaningful (the enum analogue of `min+1` / `max-1`).
+fn enum_boundaries(values: &[String], ordered: bool) -> Vec<&String> {
+    let mut picks = Vec::new();
+    if let (Some(first), Some(last)) = (values.first(), values.last()) {
+        picks.push(first);
+        picks.push(last);
+        if ordered && values.len() > 2 {
+            picks.push(&values[1]);
+            picks.push(&values[values.len() - 2]);
+        }
+    }
+    picks
+}
+
+/// Interpret an explicit boundary value from the IR.
+fn explicit_value(val: &serde_json::Value) -> Option<DomainValue> {
+    if let Some(i) = val.as_i64() {
+        Some(DomainValue::Int(i))
+    } else if let Some(s) = val.as_str() {
+        Some(DomainValue::Enum(s.to_string()))
+    } else {
+        val.as_bool().map(DomainValue::Bool)
+    }
+}
+
 /// Extract all coverage targets from an InputSpace's coverage config.
+///
+/// Targets listed more than once (repeated or overlapping declarations)
+/// are kept only at their first occurrence.
 pub fn extract_targets(input_space: &InputSpace) -> Vec<CoveragePoint> {
     let mut targets = Vec::new();
 
@@ -132,70 +189,193 @@ pub fn extract_targets(input_space: &InputSpace) -> Vec<CoveragePoint> {
             CoverageTarget::Boundary { domain, values } => {
                 targets.extend(boundary_targets(input_space, domain, values));
             }
-            CoverageTarget::EachTransition { .. } => {
-                // Transition coverage is delegated to the traversal engine.
-                // The solver doesn't handle it directly.
+            CoverageTarget::EachTransition { .. } | CoverageTarget::MinHits { .. } => {
+                // Transition and hit-count coverage are delegated to the
+                // traversal engine. The solver doesn't handle them directly.
             }
         }
     }
 
+    let mut seen = HashSet::new();
+    targets.retain(|t| seen.insert(t.clone()));
     targets
 }
 
-/// Check which coverage points a set of vectors covers.
-pub fn check_coverage(vectors: &[TestVector], targets: &[CoveragePoint]) -> HashSet<CoveragePoint> {
-    let mut covered = HashSet::new();
+/// A problem with a declared coverage target.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TargetWarning {
+    /// The target references a domain the input space does not declare.
+    UnknownDomain { domain: String },
+    /// An explicit boundary value is not a value of its domain.
+    ValueOutOfRange {
+        domain: String,
+        value: serde_json::Value,
+    },
+}
+
+impl fmt::Display for TargetWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetWarning::UnknownDomain { domain } => {
+                write!(f, "coverage target references unknown domain '{domain}'")
+            }
+            TargetWarning::ValueOutOfRange { domain, value } => {
+                write!(f, "boundary value {value} is outside domain '{domain}'")
+            }
+        }
+    }
+}
+
+/// Check the declared coverage targets against the input domains.
+///
+/// Flags targets over undeclared domains and explicit boundary values
+/// that their domain cannot take. Such targets are silently dropped or
+/// uncoverable during generation.
+pub fn validate_targets(input_space: &InputSpace) -> Vec<TargetWarning> {
+    let mut warnings = Vec::new();
 
-    for target in targets {
+    for target in &input_space.coverage.targets {
         match target {
-            CoveragePoint::Pair {
-                var1,
-                val1,
-                var2,
-                val2,
-            } => {
-                if vectors.iter().any(|v| {
-                    v.assignments.get(var1.as_str()) == Some(val1)
-                        && v.assignments.get(var2.as_str()) == Some(val2)
-                }) {
-                    covered.insert(target.clone());
+            CoverageTarget::AllPairs { over } => {
+                for var in over {
+                    if !input_space.domains.contains_key(var) {
+                        warnings.push(TargetWarning::UnknownDomain {
+                            domain: var.clone(),
+                        });
+                    }
                 }
             }
-            CoveragePoint::Boundary { var, value } => {
-                if vectors
-                    .iter()
-                    .any(|v| v.assignments.get(var.as_str()) == Some(value))
-                {
-                    covered.insert(target.clone());
+            CoverageTarget::Boundary { domain, values } => {
+                if !input_space.domains.contains_key(domain) {
+                    warnings.push(TargetWarning::UnknownDomain {
+                        domain: domain.clone(),
+                    });
+                    continue;
+                }
+                let domain_type = &input_space.domains[domain].domain_type;
+                for value in values {
+                    let in_range =
+                        explicit_value(value).is_some_and(|v| domain_contains(domain_type, &v));
+                    if !in_range {
+                        warnings.push(TargetWarning::ValueOutOfRange {
+                            domain: domain.clone(),
+                            value: value.clone(),
+                        });
+                    }
                 }
             }
+            CoverageTarget::EachTransition { .. } | CoverageTarget::MinHits { .. } => {}
         }
     }
 
-    covered
+    warnings
+}
+
+/// Check which coverage points a set of vectors covers.
+pub fn check_coverage(vectors: &[TestVector], targets: &[CoveragePoint]) -> HashSet<CoveragePoint> {
+    targets
+        .iter()
+        .filter(|target| vectors.iter().any(|v| covers(v, target)))
+        .cloned()
+        .collect()
+}
+
+/// Whether a single vector exercises a coverage point.
+fn covers(vector: &TestVector, point: &CoveragePoint) -> bool {
+    match point {
+        CoveragePoint::Pair {
+            var1,
+            val1,
+            var2,
+            val2,
+        } => {
+            vector.assignments.get(var1.as_str()) == Some(val1)
+                && vector.assignments.get(var2.as_str()) == Some(val2)
+        }
+        CoveragePoint::Boundary { var, value } => {
+            vector.assignments.get(var.as_str()) == Some(value)
+        }
+    }
+}
+
+/// Reduce a vector set to a smaller one with identical coverage.
+///
+/// Greedy set cover: repeatedly keep the vector hitting the most
+/// still-uncovered points (earliest wins ties) until every point the
+/// input covers is covered again. Vectors adding nothing are dropped.
+pub fn minimize_vector_set(vectors: &[TestVector], targets: &[CoveragePoint]) -> Vec<TestVector> {
+    let hits: Vec<HashSet<&CoveragePoint>> = vectors
+        .iter()
+        .map(|v| targets.iter().filter(|t| covers(v, t)).collect())
+        .collect();
+
+    let mut uncovered: HashSet<&CoveragePoint> = hits.iter().flatten().copied().collect();
+    let mut picked = vec![false; vectors.len()];
+    let mut result = Vec::new();
+
+    while !uncovered.is_empty() {
+        let mut best: Option<(usize, usize)> = None;
+        for (i, h) in hits.iter().enumerate() {
+            if picked[i] {
+                continue;
+            }
+            let gain = h.iter().filter(|p| uncovered.contains(*p)).count();
+            if gain > 0 && best.is_none_or(|(_, g)| gain > g) {
+                best = Some((i, gain));
+            }
+        }
+
+        let Some((i, _)) = best else {
+            break;
+        };
+        picked[i] = true;
+        for p in &hits[i] {
+            uncovered.remove(p);
+        }
+        result.push(vectors[i].clone());
+    }
+
+    result
 }
 
 /// Generate vectors to cover specific uncovered points.
 ///
 /// For each uncovered point, generates a vector that satisfies
 /// the point's requirements plus all domain/constraint constraints.
+/// One solver is shared by all points, each solved under the point's
+/// literals as assumptions.
 pub fn generate_for_targets(
     encoded: &EncodedInputSpace,
     constraint_clauses: &CnfClauses,
     uncovered: &[CoveragePoint],
 ) -> Result<Vec<TestVector>, SearchError> {
+    let mut ctx = SolverContext::new(encoded, constraint_clauses);
     let mut result = Vec::new();
 
     for point in uncovered {
-        let extra_clauses = point_to_clauses(point, encoded)?;
-        let vectors = find_many(encoded, constraint_clauses, &extra_clauses, 1)?;
-        result.extend(vectors);
+        if let Some(vector) = cover_point(&mut ctx, encoded, point)? {
+            result.push(vector);
+        }
     }
 
     Ok(result)
 }
 
+/// Find a vector exercising `point`, or `None` if it is uncoverable.
+fn cover_point(
+    ctx: &mut SolverContext<'_>,
+    encoded: &EncodedInputSpace,
+    point: &CoveragePoint,
+) -> Result<Option<TestVector>, SearchError> {
+    let assumptions = point_to_clauses(point, encoded)?.concat();
+    match find_one_with_assumptions(ctx, &assumptions)? {
+        SatResult::Sat(vector) => Ok(Some(vector)),
+        SatResult::Unsat => Ok(None),
+    }
+}
+
 /// Convert a coverage point into extra SAT clauses that force it.
+/// Every clause is a unit clause, so the literals double as assumptions.
 fn point_to_clauses(
     point: &CoveragePoint,
     encoded: &EncodedInputSpace,
@@ -216,26 +396,25 @@ fn point_to_clauses(
                 SearchError::Solver(format!("unknown domain '{var2}' in coverage target"))
             })?;
 
-            let lit1 = lit_for_value(enc1, val1).ok_or_else(|| {
+            let lits1 = lit_for_value(enc1, val1).ok_or_else(|| {
                 SearchError::Solver(format!("no SAT literal for {val1} in {var1}"))
             })?;
-            let lit2 = lit_for_value(enc2, val2).ok_or_else(|| {
+            let lits2 = lit_for_value(enc2, val2).ok_or_else(|| {
                 SearchError::Solver(format!("no SAT literal for {val2} in {var2}"))
             })?;
 
-            clauses.push(vec![lit1]);
-            clauses.push(vec![lit2]);
+            clauses.extend(lits1.into_iter().chain(lits2).map(|lit| vec![lit]));
         }
         CoveragePoint::Boundary { var, value } => {
             let enc = encoded.domains.get(var).ok_or_else(|| {
                 SearchError::Solver(format!("unknown domain '{var}' in coverage target"))
             })?;
 
-            let lit = lit_for_value(enc, value).ok_or_else(|| {
+            let lits = lit_for_value(enc, value).ok_or_else(|| {
                 SearchError::Solver(format!("no SAT literal for {value} in {var}"))
             })?;
 
-            clauses.push(vec![lit]);
+            clauses.extend(lits.into_iter().map(|lit| vec![lit]));
         }
     }
 
@@ -249,10 +428,22 @@ fn point_to_clauses(
 /// 3. Check which targets are covered.
 /// 4. For uncovered targets, generate targeted vectors.
 /// 5. Return combined vectors + coverage report.
-pub fn coverage_driven_generation(input_space: &InputSpace) -> Result<CoverageResult, SearchError> {
-    let encoded = encode_input_space(input_space)?;
-    let constraint_clauses = encode_constraints(&input_space.constraints, &encoded)?;
-    let targets = extract_targets(input_space);
+pub fn coverage_driven_generation(
+    input_space: &InputSpace,
+) -> Result<CoverageResult, ExploreError> {
+    coverage_driven_generation_with(input_space, &GenerationConfig::default())
+}
+
+/// `coverage_driven_generation` with targets ordered by `config.priority`,
+/// so a cancelled run has covered the highest-priority targets first.
+pub fn coverage_driven_generation_with(
+    input_space: &InputSpace,
+    config: &GenerationConfig,
+) -> Result<CoverageResult, ExploreError> {
+    let mut encoded = encode_input_space(input_space)?;
+    let constraint_clauses = encode_constraints(&input_space.constraints, &mut encoded)?;
+    let mut targets = extract_targets(input_space);
+    targets.sort_by_key(|t| Reverse(config.priority.get(t).copied().unwrap_or(0)));
 
     if targets.is_empty() {
         // No coverage targets — just solve for all vectors.
@@ -262,20 +453,30 @@ pub fn coverage_driven_generation(input_space: &InputSpace) -> Result<CoverageRe
             covered: HashSet::new(),
             uncoverable: HashSet::new(),
             total_targets: 0,
+            cancelled: false,
         });
     }
 
     // First pass: generate targeted vectors for each coverage point.
+    let mut ctx = SolverContext::new(&encoded, &constraint_clauses);
     let mut vectors = Vec::new();
     let mut uncoverable = HashSet::new();
+    let mut cancelled = false;
 
     for target in &targets {
-        let extra = point_to_clauses(target, &encoded)?;
-        let found = find_many(&encoded, &constraint_clauses, &extra, 1)?;
-        if found.is_empty() {
-            uncoverable.insert(target.clone());
-        } else {
-            vectors.extend(found);
+        match cover_point(&mut ctx, &encoded, target)? {
+            Some(vector) => vectors.push(vector),
+            None => {
+                uncoverable.insert(target.clone());
+            }
+        }
+        if config
+            .cancel
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed))
+        {
+            cancelled = true;
+            break;
         }
     }
 
@@ -290,19 +491,37 @@ pub fn coverage_driven_generation(input_space: &InputSpace) -> Result<CoverageRe
         covered,
         uncoverable,
         total_targets: targets.len(),
+        cancelled,
     })
 }
 
+/// Whether a domain can take `value`. Int domains are checked
+/// arithmetically against their stride grid, never enumerated.
+fn domain_contains(domain_type: &DomainType, value: &DomainValue) -> bool {
+    match (domain_type, value) {
+        (DomainType::Bool, DomainValue::Bool(_)) => true,
+        (DomainType::Enum { values, .. }, DomainValue::Enum(v)) => values.contains(v),
+        (DomainType::Int { min, max, stride }, DomainValue::Int(v)) => {
+            (min..=max).contains(&v)
+                && (i128::from(*v) - i128::from(*min)) % i128::from((*stride).max(1)) == 0
+        }
+        _ => false,
+    }
+}
+
 /// Get all possible values for a domain variable from the InputSpace.
 fn domain_values(input_space: &InputSpace, var: &str) -> Vec<DomainValue> {
     if let Some(domain) = input_space.domains.get(var) {
         match &domain.domain_type {
             DomainType::Bool => vec![DomainValue::Bool(false), DomainValue::Bool(true)],
-            DomainType::Enum { values } => values
+            DomainType::Enum { values, .. } => values
                 .iter()
                 .map(|v| DomainValue::Enum(v.clone()))
                 .collect(),
-            DomainType::Int { min, max } => (*min..=*max).map(DomainValue::Int).collect(),
+            DomainType::Int { min, max, stride } => (*min..=*max)
+                .step_by((*stride).max(1) as usize)
+                .map(DomainValue::Int)
+                .collect(),
         }
     } else {
         vec![]
@@ -323,6 +542,7 @@ mod tests {
         InputSpace {
             domains,
             constraints,
+            forbidden: Vec::new(),
             coverage: CoverageConfig {
                 targets: coverage_targets,
                 seed: 42,
@@ -339,6 +559,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -347,6 +568,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["private".into(), "public".into()],
+                    ordered: false,
                 },
             },
         );
@@ -375,7 +597,11 @@ mod tests {
         domains.insert(
             "count".to_string(),
             Domain {
-                domain_type: DomainType::Int { min: 1, max: 8 },
+                domain_type: DomainType::Int {
+                    min: 1,
+                    max: 8,
+                    stride: 1,
+                },
             },
         );
 
@@ -397,6 +623,255 @@ mod tests {
         assert_eq!(targets.len(), 4);
     }
 
+    #[test]
+    fn test_boundary_targets_zero_crossing() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "delta".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: -5,
+                    max: 5,
+                    stride: 1,
+                },
+            },
+        );
+
+        let input_space = make_input_space(domains, vec![], vec![]);
+        let targets = boundary_targets(&input_space, "delta", &[]);
+
+        let values: HashSet<i64> = targets
+            .iter()
+            .map(|t| match t {
+                CoveragePoint::Boundary {
+                    value: DomainValue::Int(i),
+                    ..
+                } => *i,
+                other => panic!("unexpected target {other:?}"),
+            })
+            .collect();
+        assert_eq!(values, HashSet::from([-5, -4, -1, 0, 1, 4, 5]));
+        assert_eq!(targets.len(), 7);
+    }
+
+    #[test]
+    fn test_boundary_targets_respect_stride() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "offset".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: -16,
+                    max: 30,
+                    stride: 8,
+                },
+            },
+        );
+
+        let input_space = make_input_space(domains, vec![], vec![]);
+        let targets = boundary_targets(&input_space, "offset", &[]);
+
+        // Grid is -16, -8, 0, 8, 16, 24: ends and their neighbours, plus
+        // 0 as the only zero-crossing value on the grid.
+        let values: HashSet<i64> = targets
+            .iter()
+            .map(|t| match t {
+                CoveragePoint::Boundary {
+                    value: DomainValue::Int(i),
+                    ..
+                } => *i,
+                other => panic!("unexpected target {other:?}"),
+            })
+            .collect();
+        assert_eq!(values, HashSet::from([-16, -8, 0, 16, 24]));
+        assert_eq!(targets.len(), 5);
+    }
+
+    #[test]
+    fn test_boundary_targets_ordered_enum() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "vis".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["private".into(), "shared".into(), "public".into()],
+                    ordered: true,
+                },
+            },
+        );
+
+        let input_space = make_input_space(domains, vec![], vec![]);
+        let targets = boundary_targets(&input_space, "vis", &[]);
+
+        let boundary = |v: &str| CoveragePoint::Boundary {
+            var: "vis".into(),
+            value: DomainValue::Enum(v.into()),
+        };
+        assert!(targets.contains(&boundary("private")));
+        assert!(targets.contains(&boundary("public")));
+        // The single inner variant neighbours both ends; it appears once.
+        assert!(targets.contains(&boundary("shared")));
+        assert_eq!(targets.len(), 3);
+    }
+
+    #[test]
+    fn test_boundary_targets_unordered_enum_ends_only() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+
+        let input_space = make_input_space(domains, vec![], vec![]);
+        let targets = boundary_targets(&input_space, "role", &[]);
+
+        assert_eq!(
+            targets,
+            vec![
+                CoveragePoint::Boundary {
+                    var: "role".into(),
+                    value: DomainValue::Enum("admin".into()),
+                },
+                CoveragePoint::Boundary {
+                    var: "role".into(),
+                    value: DomainValue::Enum("guest".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_targets_deduplicates() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "count".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: 1,
+                    max: 8,
+                    stride: 1,
+                },
+            },
+        );
+        let boundary = CoverageTarget::Boundary {
+            domain: "count".into(),
+            values: vec![serde_json::json!(1), serde_json::json!(8)],
+        };
+
+        let input_space = make_input_space(domains, vec![], vec![boundary.clone(), boundary]);
+        let targets = extract_targets(&input_space);
+
+        // 1, 8, 2, 7 once each, despite the duplicate declaration.
+        assert_eq!(targets.len(), 4);
+        let unique: HashSet<&CoveragePoint> = targets.iter().collect();
+        assert_eq!(unique.len(), targets.len());
+    }
+
+    #[test]
+    fn test_validate_targets_flags_unknown_domains_and_values() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "count".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: 1,
+                    max: 8,
+                    stride: 1,
+                },
+            },
+        );
+
+        let input_space = make_input_space(
+            domains,
+            vec![],
+            vec![
+                CoverageTarget::AllPairs {
+                    over: vec!["role".into(), "cuont".into()],
+                },
+                CoverageTarget::Boundary {
+                    domain: "count".into(),
+                    values: vec![
+                        serde_json::json!(8),
+                        serde_json::json!(9),
+                        serde_json::json!("admin"),
+                    ],
+                },
+            ],
+        );
+
+        let warnings = validate_targets(&input_space);
+        assert_eq!(
+            warnings,
+            vec![
+                TargetWarning::UnknownDomain {
+                    domain: "cuont".into()
+                },
+                TargetWarning::ValueOutOfRange {
+                    domain: "count".into(),
+                    value: serde_json::json!(9),
+                },
+                TargetWarning::ValueOutOfRange {
+                    domain: "count".into(),
+                    value: serde_json::json!("admin"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_targets_checks_int_stride_without_enumerating() {
+        let domains = HashMap::from([(
+            "amount".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: -5,
+                    max: i64::MAX,
+                    stride: 5,
+                },
+            },
+        )]);
+        let values = [-5, 0, 1_000_000_000_000, -10, 7, i64::MAX];
+        let input_space = make_input_space(
+            domains,
+            vec![],
+            vec![CoverageTarget::Boundary {
+                domain: "amount".into(),
+                values: values.iter().map(|v| serde_json::json!(v)).collect(),
+            }],
+        );
+
+        let flagged: Vec<serde_json::Value> = validate_targets(&input_space)
+            .into_iter()
+            .map(|w| match w {
+                TargetWarning::ValueOutOfRange { value, .. } => value,
+                other => panic!("unexpected warning {other:?}"),
+            })
+            .collect();
+        // i64::MAX + 5 is not a multiple of 5.
+        assert_eq!(
+            flagged,
+            vec![
+                serde_json::json!(-10),
+                serde_json::json!(7),
+                serde_json::json!(i64::MAX)
+            ]
+        );
+    }
+
     #[test]
     fn test_check_coverage() {
         let mut v1 = TestVector::new();
@@ -444,6 +919,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -452,6 +928,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["private".into(), "shared".into(), "public".into()],
+                    ordered: false,
                 },
             },
         );
@@ -475,13 +952,61 @@ mod tests {
         assert!(result.uncoverable.is_empty());
     }
 
+    #[test]
+    fn test_priority_pair_covered_first_when_cancelled() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "vis".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["private".into(), "public".into()],
+                    ordered: false,
+                },
+            },
+        );
+        let coverage_targets = vec![CoverageTarget::AllPairs {
+            over: vec!["role".into(), "vis".into()],
+        }];
+        let input_space = make_input_space(domains, vec![], coverage_targets);
+
+        let guest_public = CoveragePoint::Pair {
+            var1: "role".into(),
+            val1: DomainValue::Enum("guest".into()),
+            var2: "vis".into(),
+            val2: DomainValue::Enum("public".into()),
+        };
+        let config = GenerationConfig {
+            priority: HashMap::from([(guest_public.clone(), 10)]),
+            cancel: Some(Arc::new(AtomicBool::new(true))),
+        };
+        let result = coverage_driven_generation_with(&input_space, &config).unwrap();
+
+        assert!(result.cancelled);
+        assert_eq!(result.total_targets, 6);
+        assert_eq!(result.vectors.len(), 1);
+        assert_eq!(result.covered, HashSet::from([guest_public]));
+    }
+
     #[test]
     fn test_coverage_driven_generation_boundary() {
         let mut domains = HashMap::new();
         domains.insert(
             "count".to_string(),
             Domain {
-                domain_type: DomainType::Int { min: 1, max: 8 },
+                domain_type: DomainType::Int {
+                    min: 1,
+                    max: 8,
+                    stride: 1,
+                },
             },
         );
 
@@ -502,10 +1027,9 @@ mod tests {
         assert_eq!(result.covered.len(), result.total_targets);
     }
 
-    #[test]
-    fn test_coverage_with_constraint_makes_pair_uncoverable() {
-        // role=guest and auth=true should be uncoverable
-        // if constraint says implies(guest, auth=false).
+    /// role x auth all-pairs, where implies(guest, auth=false) makes
+    /// guest+true uncoverable.
+    fn guest_not_auth_input_space() -> InputSpace {
         use fresnel_fir_ir::expr::{Expr, Literal, OpKind};
 
         let mut domains = HashMap::new();
@@ -514,6 +1038,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -551,7 +1076,14 @@ mod tests {
             over: vec!["role".into(), "auth".into()],
         }];
 
-        let input_space = make_input_space(domains, constraints, coverage_targets);
+        make_input_space(domains, constraints, coverage_targets)
+    }
+
+    #[test]
+    fn test_coverage_with_constraint_makes_pair_uncoverable() {
+        // role=guest and auth=true should be uncoverable
+        // if constraint says implies(guest, auth=false).
+        let input_space = guest_not_auth_input_space();
         let result = coverage_driven_generation(&input_space).unwrap();
 
         // 4 pairs total: admin+true, admin+false, guest+true, guest+false
@@ -566,4 +1098,85 @@ mod tests {
         }));
         assert_eq!(result.covered.len(), 3);
     }
+
+    #[test]
+    fn test_assumption_path_matches_fresh_solver_per_point() {
+        let input_space = guest_not_auth_input_space();
+        let mut encoded = encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+        let targets = extract_targets(&input_space);
+
+        // Old path: a fresh solver per point, forced by unit clauses.
+        let mut fresh_vectors = Vec::new();
+        let mut fresh_uncoverable = HashSet::new();
+        for target in &targets {
+            let extra = point_to_clauses(target, &encoded).unwrap();
+            let found = find_many(&encoded, &constraint_clauses, &extra, 1).unwrap();
+            if found.is_empty() {
+                fresh_uncoverable.insert(target.clone());
+            }
+            fresh_vectors.extend(found);
+        }
+
+        let result = coverage_driven_generation(&input_space).unwrap();
+        assert_eq!(result.uncoverable, fresh_uncoverable);
+        assert_eq!(result.covered, check_coverage(&fresh_vectors, &targets));
+
+        let uncovered: Vec<CoveragePoint> = targets
+            .iter()
+            .filter(|t| !fresh_uncoverable.contains(t))
+            .cloned()
+            .collect();
+        let generated = generate_for_targets(&encoded, &constraint_clauses, &uncovered).unwrap();
+        assert_eq!(generated.len(), uncovered.len());
+        assert_eq!(
+            check_coverage(&generated, &uncovered).len(),
+            uncovered.len()
+        );
+    }
+
+    #[test]
+    fn test_minimize_vector_set_preserves_coverage() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "vis".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["private".into(), "shared".into(), "public".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "owner".to_string(),
+            Domain {
+                domain_type: DomainType::Bool,
+            },
+        );
+
+        let coverage_targets = vec![CoverageTarget::AllPairs {
+            over: vec!["role".into(), "vis".into(), "owner".into()],
+        }];
+
+        let input_space = make_input_space(domains, vec![], coverage_targets);
+        let result = coverage_driven_generation(&input_space).unwrap();
+        let targets = extract_targets(&input_space);
+
+        let minimized = minimize_vector_set(&result.vectors, &targets);
+
+        assert!(minimized.len() < 21);
+        assert!(minimized.len() <= result.vectors.len());
+        assert_eq!(check_coverage(&minimized, &targets), result.covered);
+        assert_eq!(check_coverage(&minimized, &targets).len(), 21);
+    }
 }
@@ -4,11 +4,16 @@
 //! - **Bool**: 1 SAT variable. `true` = domain true, `false` = domain false.
 //! - **Enum**: One-hot encoding. N SAT variables for N variants.
 //!   Exactly-one constraint added (at-least-one + pairwise at-most-one).
-//! - **Int [min, max]**: Treated as enum over the range `min..=max`.
+//! - **Ordered enum** (2+ variants): Order encoding. N-1 threshold
+//!   variables, the i-th meaning "at least the (i+1)-th variant" in
+//!   declared order, chained by monotonicity clauses. Range
+//!   comparisons become single literals; equality takes up to two.
+//! - **Int [min, max]**: Treated as enum over `min, min+stride, ..., <=max`.
 //!   Range must be bounded and reasonably small (max 1024 values).
 
 use std::collections::BTreeMap;
 
+use fresnel_fir_ir::expr::Literal;
 use fresnel_fir_ir::types::{Domain, DomainType, InputSpace};
 #[cfg(test)]
 use varisat::ExtendFormula;
@@ -35,9 +40,41 @@ pub enum Encoding {
     Bool { var: Var },
     /// One-hot: one SAT variable per value.
     OneHot {
+        /// What the labels stand for, so decoding never guesses from them.
+        kind: ValueKind,
         /// Ordered list of (value_label, SAT_variable).
         variants: Vec<(String, Var)>,
     },
+    /// Order (unary) encoding: `thresholds[i]` is true iff the value is at
+    /// least `labels[i + 1]`, so `labels.len() - 1` variables in all.
+    Order {
+        /// Variant labels, lowest first.
+        labels: Vec<String>,
+        thresholds: Vec<Var>,
+    },
+}
+
+/// What the variant labels of a one-hot encoding stand for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// Decimal labels of an int domain.
+    Int,
+    /// Enum variant names, even ones that look like numbers.
+    Enum,
+}
+
+impl ValueKind {
+    /// The domain value a variant label of this kind denotes.
+    pub fn decode(self, label: &str) -> DomainValue {
+        match self {
+            ValueKind::Int => DomainValue::Int(
+                label
+                    .parse()
+                    .expect("int variant labels are formatted integers"),
+            ),
+            ValueKind::Enum => DomainValue::Enum(label.to_string()),
+        }
+    }
 }
 
 /// All encoded domains plus their structural constraints (exactly-one for enums).
@@ -45,10 +82,30 @@ pub enum Encoding {
 pub struct EncodedInputSpace {
     /// Domain name -> encoding.
     pub domains: BTreeMap<String, EncodedDomain>,
-    /// Structural clauses (exactly-one constraints for one-hot encodings).
+    /// Structural clauses (exactly-one constraints for one-hot encodings,
+    /// monotonicity for order encodings).
     pub structural_clauses: Vec<Vec<Lit>>,
     /// Next free variable index.
     pub next_var: usize,
+    /// Ordinal views: ordered enum domain name -> variant labels in
+    /// declared order, lowest first. Comparison constraints over an enum
+    /// domain resolve against its view.
+    pub ordinals: BTreeMap<String, Vec<String>>,
+    /// Forbidden value combinations from the input space, blocked by
+    /// `encode_constraints`.
+    pub forbidden: Vec<Vec<(String, Literal)>>,
+}
+
+impl EncodedInputSpace {
+    /// Allocate a fresh auxiliary SAT variable past all domain variables.
+    ///
+    /// Auxiliary variables are never part of a domain, so `decode_model`
+    /// and blocking clauses ignore them.
+    pub fn fresh_var(&mut self) -> Var {
+        let var = Var::from_index(self.next_var);
+        self.next_var += 1;
+        var
+    }
 }
 
 /// Errors during domain encoding.
@@ -60,6 +117,9 @@ pub enum EncodingError {
     #[error("integer domain '{name}' range too large: {size} values (max {MAX_INT_RANGE})")]
     IntRangeTooLarge { name: String, size: i64 },
 
+    #[error("integer domain '{name}' has non-positive stride {stride}")]
+    InvalidStride { name: String, stride: i64 },
+
     #[error("enum domain '{name}' has no values")]
     EmptyEnum { name: String },
 }
@@ -87,9 +147,27 @@ pub fn encode_input_space(input_space: &InputSpace) -> Result<EncodedInputSpace,
         domains,
         structural_clauses,
         next_var,
+        ordinals: ordinal_views(input_space),
+        forbidden: input_space.forbidden.clone(),
     })
 }
 
+/// Collect the ordinal view of each ordered enum domain: its variants in
+/// declared order.
+fn ordinal_views(input_space: &InputSpace) -> BTreeMap<String, Vec<String>> {
+    input_space
+        .domains
+        .iter()
+        .filter_map(|(name, domain)| match &domain.domain_type {
+            DomainType::Enum {
+                values,
+                ordered: true,
+            } => Some((name.clone(), values.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Encode a single domain variable.
 fn encode_domain(
     name: &str,
@@ -104,12 +182,36 @@ fn encode_domain(
             Encoding::Bool { var }
         }
 
-        DomainType::Enum { values } => {
-            if values.is_empty() {
-                return Err(EncodingError::EmptyEnum {
-                    name: name.to_string(),
-                });
+        DomainType::Enum { values, .. } if values.is_empty() => {
+            return Err(EncodingError::EmptyEnum {
+                name: name.to_string(),
+            });
+        }
+
+        // Ordered enums get the order encoding, in declared order so
+        // comparisons always select a contiguous run of variants.
+        DomainType::Enum {
+            values,
+            ordered: true,
+        } if values.len() > 1 => {
+            let labels = values.clone();
+            let thresholds: Vec<Var> = (1..labels.len())
+                .map(|_| {
+                    let var = Var::from_index(*next_var);
+                    *next_var += 1;
+                    var
+                })
+                .collect();
+
+            // Monotonicity: at least variant i+1 implies at least variant i.
+            for pair in thresholds.windows(2) {
+                clauses.push(vec![pair[1].negative(), pair[0].positive()]);
             }
+
+            Encoding::Order { labels, thresholds }
+        }
+
+        DomainType::Enum { values, .. } => {
             let variants: Vec<(String, Var)> = values
                 .iter()
                 .map(|v| {
@@ -131,11 +233,20 @@ fn encode_domain(
                 }
             }
 
-            Encoding::OneHot { variants }
+            Encoding::OneHot {
+                kind: ValueKind::Enum,
+                variants,
+            }
         }
 
-        DomainType::Int { min, max } => {
-            let (min, max) = (*min, *max);
+        DomainType::Int { min, max, stride } => {
+            let (min, max, stride) = (*min, *max, *stride);
+            if stride <= 0 {
+                return Err(EncodingError::InvalidStride {
+                    name: name.to_string(),
+                    stride,
+                });
+            }
             if min > max {
                 return Err(EncodingError::EmptyIntRange {
                     name: name.to_string(),
@@ -143,7 +254,7 @@ fn encode_domain(
                     max,
                 });
             }
-            let size = max - min + 1;
+            let size = (max - min) / stride + 1;
             if size > MAX_INT_RANGE {
                 return Err(EncodingError::IntRangeTooLarge {
                     name: name.to_string(),
@@ -151,8 +262,9 @@ fn encode_domain(
                 });
             }
 
-            // Encode as one-hot over the integer range.
+            // Encode as one-hot over the values on the stride grid.
             let variants: Vec<(String, Var)> = (min..=max)
+                .step_by(stride as usize)
                 .map(|i| {
                     let var = Var::from_index(*next_var);
                     *next_var += 1;
@@ -170,7 +282,10 @@ fn encode_domain(
                 }
             }
 
-            Encoding::OneHot { variants }
+            Encoding::OneHot {
+                kind: ValueKind::Int,
+                variants,
+            }
         }
     };
 
@@ -211,54 +326,76 @@ fn decode_single(
             let is_true = var_assignment.get(&var.index()).copied().unwrap_or(false);
             Some(DomainValue::Bool(is_true))
         }
-        Encoding::OneHot { variants } => {
+        Encoding::OneHot { kind, variants } => {
             // Find which variant is true.
-            for (label, var) in variants {
-                if var_assignment.get(&var.index()).copied().unwrap_or(false) {
-                    // Determine if this is an int or enum domain.
-                    // Try parsing as int first.
-                    if let Ok(i) = label.parse::<i64>() {
-                        return Some(DomainValue::Int(i));
-                    }
-                    return Some(DomainValue::Enum(label.clone()));
-                }
-            }
             // Fallback: if no variant is true in the model, pick the first one.
             // This shouldn't happen with correct exactly-one constraints.
-            let label = &variants[0].0;
-            if let Ok(i) = label.parse::<i64>() {
-                Some(DomainValue::Int(i))
-            } else {
-                Some(DomainValue::Enum(label.clone()))
-            }
+            let (label, _) = variants
+                .iter()
+                .find(|(_, var)| var_assignment.get(&var.index()).copied().unwrap_or(false))
+                .unwrap_or(&variants[0]);
+            Some(kind.decode(label))
+        }
+        Encoding::Order { labels, thresholds } => {
+            // The value sits just below the first threshold not reached.
+            let rank = thresholds
+                .iter()
+                .position(|var| !var_assignment.get(&var.index()).copied().unwrap_or(false))
+                .unwrap_or(thresholds.len());
+            Some(DomainValue::Enum(labels[rank].clone()))
         }
     }
 }
 
-/// Get the SAT literal for a specific domain value.
+/// Get the SAT literals whose conjunction holds exactly when the domain
+/// takes a specific value: one for bool and one-hot domains, at most two
+/// (`>= value` and `< next`) under order encoding.
 /// Returns `None` if the value doesn't exist in the domain.
-pub fn lit_for_value(encoded: &EncodedDomain, value: &DomainValue) -> Option<Lit> {
+pub fn lit_for_value(encoded: &EncodedDomain, value: &DomainValue) -> Option<Vec<Lit>> {
     match (&encoded.encoding, value) {
-        (Encoding::Bool { var }, DomainValue::Bool(true)) => Some(var.positive()),
-        (Encoding::Bool { var }, DomainValue::Bool(false)) => Some(var.negative()),
-        (Encoding::OneHot { variants }, DomainValue::Enum(s)) => variants
+        (Encoding::Bool { var }, DomainValue::Bool(true)) => Some(vec![var.positive()]),
+        (Encoding::Bool { var }, DomainValue::Bool(false)) => Some(vec![var.negative()]),
+        (
+            Encoding::OneHot {
+                kind: ValueKind::Enum,
+                variants,
+            },
+            DomainValue::Enum(s),
+        ) => variants
             .iter()
             .find(|(label, _)| label == s)
-            .map(|(_, var)| var.positive()),
-        (Encoding::OneHot { variants }, DomainValue::Int(i)) => {
+            .map(|(_, var)| vec![var.positive()]),
+        (
+            Encoding::OneHot {
+                kind: ValueKind::Int,
+                variants,
+            },
+            DomainValue::Int(i),
+        ) => {
             let label = i.to_string();
             variants
                 .iter()
                 .find(|(l, _)| *l == label)
-                .map(|(_, var)| var.positive())
+                .map(|(_, var)| vec![var.positive()])
+        }
+        (Encoding::Order { labels, thresholds }, DomainValue::Enum(s)) => {
+            let rank = labels.iter().position(|label| label == s)?;
+            let mut lits = Vec::with_capacity(2);
+            if rank > 0 {
+                lits.push(thresholds[rank - 1].positive());
+            }
+            if let Some(next) = thresholds.get(rank) {
+                lits.push(next.negative());
+            }
+            Some(lits)
         }
         _ => None,
     }
 }
 
-/// Get the SAT literal that forces a domain to NOT take a specific value.
-pub fn lit_for_not_value(encoded: &EncodedDomain, value: &DomainValue) -> Option<Lit> {
-    lit_for_value(encoded, value).map(|l| !l)
+/// Get the clause that forces a domain to NOT take a specific value.
+pub fn lit_for_not_value(encoded: &EncodedDomain, value: &DomainValue) -> Option<Vec<Lit>> {
+    lit_for_value(encoded, value).map(|lits| lits.into_iter().map(|l| !l).collect())
 }
 
 #[cfg(test)]
@@ -272,6 +409,7 @@ mod tests {
         InputSpace {
             domains,
             constraints: vec![],
+            forbidden: Vec::new(),
             coverage: CoverageConfig {
                 targets: vec![],
                 seed: 42,
@@ -307,6 +445,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -315,7 +454,7 @@ mod tests {
 
         let role = &encoded.domains["role"];
         match &role.encoding {
-            Encoding::OneHot { variants } => {
+            Encoding::OneHot { variants, .. } => {
                 assert_eq!(variants.len(), 3);
                 assert_eq!(variants[0].0, "admin");
                 assert_eq!(variants[1].0, "member");
@@ -333,7 +472,11 @@ mod tests {
         domains.insert(
             "count".to_string(),
             Domain {
-                domain_type: DomainType::Int { min: 1, max: 4 },
+                domain_type: DomainType::Int {
+                    min: 1,
+                    max: 4,
+                    stride: 1,
+                },
             },
         );
         let input_space = make_input_space(domains);
@@ -341,7 +484,7 @@ mod tests {
 
         let count = &encoded.domains["count"];
         match &count.encoding {
-            Encoding::OneHot { variants } => {
+            Encoding::OneHot { variants, .. } => {
                 assert_eq!(variants.len(), 4); // 1, 2, 3, 4
                 assert_eq!(variants[0].0, "1");
                 assert_eq!(variants[3].0, "4");
@@ -385,6 +528,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -414,7 +558,11 @@ mod tests {
         domains.insert(
             "count".to_string(),
             Domain {
-                domain_type: DomainType::Int { min: 1, max: 8 },
+                domain_type: DomainType::Int {
+                    min: 1,
+                    max: 8,
+                    stride: 1,
+                },
             },
         );
         let input_space = make_input_space(domains);
@@ -445,6 +593,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -457,7 +606,11 @@ mod tests {
         domains.insert(
             "count".to_string(),
             Domain {
-                domain_type: DomainType::Int { min: 1, max: 3 },
+                domain_type: DomainType::Int {
+                    min: 1,
+                    max: 3,
+                    stride: 1,
+                },
             },
         );
         let input_space = make_input_space(domains);
@@ -483,7 +636,10 @@ mod tests {
         domains.insert(
             "bad".to_string(),
             Domain {
-                domain_type: DomainType::Enum { values: vec![] },
+                domain_type: DomainType::Enum {
+                    values: vec![],
+                    ordered: false,
+                },
             },
         );
         let input_space = make_input_space(domains);
@@ -497,7 +653,11 @@ mod tests {
         domains.insert(
             "bad".to_string(),
             Domain {
-                domain_type: DomainType::Int { min: 10, max: 5 },
+                domain_type: DomainType::Int {
+                    min: 10,
+                    max: 5,
+                    stride: 1,
+                },
             },
         );
         let input_space = make_input_space(domains);
@@ -505,6 +665,85 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_int_stride_encodes_grid_only() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "size".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: 0,
+                    max: 30,
+                    stride: 8,
+                },
+            },
+        );
+        let input_space = make_input_space(domains);
+        let encoded = encode_input_space(&input_space).unwrap();
+        let size = &encoded.domains["size"];
+
+        match &size.encoding {
+            Encoding::OneHot { variants, .. } => {
+                let labels: Vec<&str> = variants.iter().map(|(l, _)| l.as_str()).collect();
+                assert_eq!(labels, vec!["0", "8", "16", "24"]);
+            }
+            _ => panic!("expected one-hot encoding"),
+        }
+        assert!(lit_for_value(size, &DomainValue::Int(16)).is_some());
+        assert!(lit_for_value(size, &DomainValue::Int(12)).is_none());
+        assert!(lit_for_value(size, &DomainValue::Int(30)).is_none());
+    }
+
+    #[test]
+    fn test_non_positive_stride_rejected() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "bad".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: 0,
+                    max: 8,
+                    stride: 0,
+                },
+            },
+        );
+        let input_space = make_input_space(domains);
+        assert!(matches!(
+            encode_input_space(&input_space),
+            Err(EncodingError::InvalidStride { stride: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_numeric_enum_variant_decodes_as_enum() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "code".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["7".into(), "42".into()],
+                    ordered: false,
+                },
+            },
+        );
+        let input_space = make_input_space(domains);
+        let encoded = encode_input_space(&input_space).unwrap();
+        let code = &encoded.domains["code"];
+        assert!(lit_for_value(code, &DomainValue::Int(42)).is_none());
+
+        let mut solver = Solver::new();
+        for clause in &encoded.structural_clauses {
+            solver.add_clause(clause);
+        }
+        let forty_two = lit_for_value(code, &DomainValue::Enum("42".into())).unwrap();
+        solver.assume(&forty_two);
+        assert!(solver.solve().unwrap());
+
+        let decoded = decode_model(&encoded, &solver.model().unwrap());
+        assert_eq!(decoded["code"], DomainValue::Enum("42".into()));
+        assert_ne!(decoded["code"], DomainValue::Int(42));
+    }
+
     #[test]
     fn test_lit_for_value_enum() {
         let mut domains = HashMap::new();
@@ -513,6 +752,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -522,7 +762,7 @@ mod tests {
 
         let lit = lit_for_value(role_enc, &DomainValue::Enum("admin".into()));
         assert!(lit.is_some());
-        assert!(lit.unwrap().is_positive());
+        assert!(lit.unwrap()[0].is_positive());
 
         let lit_bad = lit_for_value(role_enc, &DomainValue::Enum("nonexistent".into()));
         assert!(lit_bad.is_none());
@@ -543,10 +783,62 @@ mod tests {
 
         let lit_true = lit_for_value(flag_enc, &DomainValue::Bool(true));
         assert!(lit_true.is_some());
-        assert!(lit_true.unwrap().is_positive());
+        assert!(lit_true.unwrap()[0].is_positive());
 
         let lit_false = lit_for_value(flag_enc, &DomainValue::Bool(false));
         assert!(lit_false.is_some());
-        assert!(lit_false.unwrap().is_negative());
+        assert!(lit_false.unwrap()[0].is_negative());
+    }
+
+    #[test]
+    fn test_ordered_enum_uses_order_encoding() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "visibility".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["private".into(), "shared".into(), "public".into()],
+                    ordered: true,
+                },
+            },
+        );
+        let input_space = make_input_space(domains);
+        let encoded = encode_input_space(&input_space).unwrap();
+        let vis = &encoded.domains["visibility"];
+
+        let thresholds = match &vis.encoding {
+            Encoding::Order { labels, thresholds } => {
+                assert_eq!(labels, &["private", "shared", "public"]);
+                thresholds.clone()
+            }
+            other => panic!("expected order encoding, got {other:?}"),
+        };
+        assert_eq!(thresholds.len(), 2);
+        // One monotonicity clause: public -> at least shared.
+        assert_eq!(
+            encoded.structural_clauses,
+            vec![vec![thresholds[1].negative(), thresholds[0].positive()]]
+        );
+
+        let lits = |v: &str| lit_for_value(vis, &DomainValue::Enum(v.into())).unwrap();
+        assert_eq!(lits("private"), vec![thresholds[0].negative()]);
+        assert_eq!(
+            lits("shared"),
+            vec![thresholds[0].positive(), thresholds[1].negative()]
+        );
+        assert_eq!(lits("public"), vec![thresholds[1].positive()]);
+
+        // Every consistent threshold assignment decodes to its variant.
+        for (reached, expected) in [(0, "private"), (1, "shared"), (2, "public")] {
+            let model: Vec<Lit> = thresholds
+                .iter()
+                .enumerate()
+                .map(|(i, var)| var.lit(i < reached))
+                .collect();
+            assert_eq!(
+                decode_model(&encoded, &model)["visibility"],
+                DomainValue::Enum(expected.into())
+            );
+        }
     }
 }
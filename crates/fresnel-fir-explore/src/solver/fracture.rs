@@ -9,6 +9,8 @@
 
 use std::collections::BTreeMap;
 
+use varisat::Lit;
+
 use super::constraint::CnfClauses;
 use super::domain::{lit_for_value, EncodedInputSpace, Encoding};
 use super::search::{find_many, is_sat, SearchError};
@@ -25,6 +27,19 @@ pub struct Subspace {
     pub stage_id: u64,
 }
 
+impl Subspace {
+    /// The fixing literals, for solving this subspace under assumptions.
+    ///
+    /// Fixings produced by `fracture_by_variable` are always unit clauses.
+    pub fn assumptions(&self) -> Vec<Lit> {
+        self.fixing_clauses
+            .iter()
+            .filter(|c| c.len() == 1)
+            .map(|c| c[0])
+            .collect()
+    }
+}
+
 /// Result of fracturing and solving a subspace.
 #[derive(Debug)]
 pub enum SubspaceResult {
@@ -57,7 +72,7 @@ pub fn fracture_by_variable(
     let mut subspaces = Vec::new();
 
     for (i, value) in values.iter().enumerate() {
-        let lit = lit_for_value(domain_enc, value).ok_or_else(|| {
+        let lits = lit_for_value(domain_enc, value).ok_or_else(|| {
             SearchError::Solver(format!(
                 "no SAT literal for value {value} in domain {variable}"
             ))
@@ -67,7 +82,7 @@ pub fn fracture_by_variable(
         fixed.insert(variable.to_string(), value.clone());
 
         let mut fixing_clauses = base_clauses.clone();
-        fixing_clauses.push(vec![lit]);
+        fixing_clauses.extend(lits.into_iter().map(|lit| vec![lit]));
 
         let stage_id = base_stage_id * 1000 + i as u64;
 
@@ -85,15 +100,13 @@ pub fn fracture_by_variable(
 fn domain_values(encoding: &Encoding) -> Vec<DomainValue> {
     match encoding {
         Encoding::Bool { .. } => vec![DomainValue::Bool(false), DomainValue::Bool(true)],
-        Encoding::OneHot { variants } => variants
+        Encoding::OneHot { kind, variants } => variants
             .iter()
-            .map(|(label, _)| {
-                if let Ok(i) = label.parse::<i64>() {
-                    DomainValue::Int(i)
-                } else {
-                    DomainValue::Enum(label.clone())
-                }
-            })
+            .map(|(label, _)| kind.decode(label))
+            .collect(),
+        Encoding::Order { labels, .. } => labels
+            .iter()
+            .map(|label| DomainValue::Enum(label.clone()))
             .collect(),
     }
 }
@@ -282,6 +295,7 @@ mod tests {
         InputSpace {
             domains,
             constraints,
+            forbidden: Vec::new(),
             coverage: CoverageConfig {
                 targets: vec![],
                 seed: 42,
@@ -318,6 +332,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -354,6 +369,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -388,8 +404,9 @@ mod tests {
         }];
 
         let input_space = make_input_space(domains, constraints);
-        let encoded = encode_input_space(&input_space).unwrap();
-        let constraint_clauses = encode_constraints(&input_space.constraints, &encoded).unwrap();
+        let mut encoded = encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
 
         let results = fracture_and_solve(
             &encoded,
@@ -422,6 +439,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -438,8 +456,9 @@ mod tests {
         }];
 
         let input_space = make_input_space(domains, constraints);
-        let encoded = encode_input_space(&input_space).unwrap();
-        let constraint_clauses = encode_constraints(&input_space.constraints, &encoded).unwrap();
+        let mut encoded = encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
 
         let results = fracture_and_solve(
             &encoded,
@@ -474,6 +493,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -485,8 +505,9 @@ mod tests {
         );
 
         let input_space = make_input_space(domains, vec![]);
-        let encoded = encode_input_space(&input_space).unwrap();
-        let constraint_clauses = encode_constraints(&input_space.constraints, &encoded).unwrap();
+        let mut encoded = encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
 
         let vectors = hierarchical_fracture(
             &encoded,
@@ -514,6 +535,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -548,8 +570,9 @@ mod tests {
         }];
 
         let input_space = make_input_space(domains, constraints);
-        let encoded = encode_input_space(&input_space).unwrap();
-        let constraint_clauses = encode_constraints(&input_space.constraints, &encoded).unwrap();
+        let mut encoded = encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
 
         let vectors = hierarchical_fracture(
             &encoded,
@@ -576,6 +599,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
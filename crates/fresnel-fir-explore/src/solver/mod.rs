@@ -9,9 +9,11 @@ pub mod search;
 
 use std::collections::BTreeMap;
 
+use serde::{Deserialize, Serialize};
+
 /// A concrete assignment of values to input domain variables.
 /// Uses BTreeMap for deterministic ordering and Hash support.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TestVector {
     /// Variable name -> assigned value (sorted for determinism)
     pub assignments: BTreeMap<String, DomainValue>,
@@ -32,7 +34,11 @@ impl Default for TestVector {
 }
 
 /// A concrete value from a domain.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+///
+/// Serializes with its type tag, e.g. `{"type":"int","value":1}`, so an
+/// `Int` and an `Enum` that display the same stay distinct on round-trip.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
 pub enum DomainValue {
     Bool(bool),
     Int(i64),
@@ -48,3 +54,47 @@ impl std::fmt::Display for DomainValue {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_value_json_keeps_type_tag() {
+        let values = [
+            (DomainValue::Bool(true), r#"{"type":"bool","value":true}"#),
+            (DomainValue::Int(1), r#"{"type":"int","value":1}"#),
+            (
+                DomainValue::Enum("1".into()),
+                r#"{"type":"enum","value":"1"}"#,
+            ),
+        ];
+        for (value, json) in &values {
+            assert_eq!(serde_json::to_string(value).unwrap(), *json);
+            let back: DomainValue = serde_json::from_str(json).unwrap();
+            assert_eq!(&back, value);
+        }
+
+        let int: DomainValue = serde_json::from_str(values[1].1).unwrap();
+        let enum_: DomainValue = serde_json::from_str(values[2].1).unwrap();
+        assert_eq!(int.to_string(), enum_.to_string());
+        assert_ne!(int, enum_);
+    }
+
+    #[test]
+    fn test_vector_json_round_trip() {
+        let mut vector = TestVector::new();
+        for (name, value) in [
+            ("count", DomainValue::Int(1)),
+            ("label", DomainValue::Enum("1".into())),
+            ("flag", DomainValue::Bool(false)),
+        ] {
+            vector.assignments.insert(name.to_string(), value);
+        }
+
+        let json = serde_json::to_string(&vector).unwrap();
+        let back: TestVector = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, vector);
+        assert_ne!(back.assignments["count"], back.assignments["label"]);
+    }
+}
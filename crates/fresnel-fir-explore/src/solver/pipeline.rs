@@ -1,26 +1,44 @@
-//! Parallel fracture/solve/abort pipeline.
+//! Fracture/solve/abort pipeline.
 //!
 //! The full pipeline from the patents:
 //! 1. Encode input space domains as SAT variables
 //! 2. Encode constraints as SAT clauses
 //! 3. Fracture by first variable -> subspaces
-//! 4. Solve subspaces in parallel (rayon)
+//! 4. Solve subspaces
 //! 5. Abort UNSAT subspaces immediately
 //! 6. Search for unique vectors in SAT subspaces
 //! 7. Hierarchical: fracture further if coverage insufficient
 //! 8. Collect all vectors into the pool
-
+//!
+//! `run_pipeline` walks the fracture tree sequentially on one thread,
+//! solving every subspace under assumptions on a shared `SolverContext`.
+//! It no longer solves siblings in parallel: it trades wall-clock
+//! parallelism for encoding the base clauses once. Callers with many leaf
+//! subspaces and spare cores should use `run_pipeline_parallel_leaves`,
+//! which solves leaves concurrently with rayon on fresh solvers.
+
+use std::collections::hash_map::RandomState;
 use std::collections::{BTreeMap, HashSet};
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use rand::seq::SliceRandom;
 use rayon::prelude::*;
 
-use fresnel_fir_ir::types::InputSpace;
+use fresnel_fir_ir::types::{DomainType, InputSpace};
 
 use super::constraint::{encode_constraints, CnfClauses};
-use super::domain::{encode_input_space, EncodedInputSpace};
+use super::domain::{encode_input_space, lit_for_value, EncodedInputSpace};
 use super::fracture::{fracture_by_variable, Subspace};
-use super::search::{find_many, is_sat, SearchError};
+use super::rng::stage_rng;
+use super::search::{
+    find_many_counted, fresh_clause_count, is_sat_with_config, SearchConfig, SearchError,
+    SearchStats, SolverContext,
+};
 use super::{DomainValue, TestVector};
+use crate::error::ExploreError;
 
 /// Configuration for the pipeline.
 #[derive(Debug, Clone)]
@@ -32,7 +50,74 @@ pub struct PipelineConfig {
     pub max_vectors_per_leaf: usize,
     /// Variables to fracture by, in order.
     /// If empty, just solve the whole space.
-    pub fracture_variables: Vec<String>,
+    pub fracture_variables: FractureOrder,
+    /// Cancellation token, checked between subspaces. Once set, the run
+    /// stops early and returns the vectors found so far.
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// Wall-clock budget for each solver query (SAT check or leaf search).
+    /// A subspace whose check times out is treated as unknown, never
+    /// pruned as UNSAT. `None` = unbounded.
+    pub solver_timeout: Option<Duration>,
+}
+
+impl Default for PipelineConfig {
+    /// Seed 0, exhaustive leaves, automatic fracture order, no
+    /// cancellation or timeout.
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Auto,
+            cancel: None,
+            solver_timeout: None,
+        }
+    }
+}
+
+/// How the pipeline picks the variables to fracture by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FractureOrder {
+    /// Fracture by exactly these variables, in this order.
+    Manual(Vec<String>),
+    /// Fracture by every domain, smallest first (see `auto_fracture_order`).
+    Auto,
+}
+
+impl FractureOrder {
+    /// Resolve to a concrete variable order for the given input space.
+    pub fn resolve(&self, input_space: &InputSpace) -> Vec<String> {
+        match self {
+            FractureOrder::Manual(vars) => vars.clone(),
+            FractureOrder::Auto => auto_fracture_order(input_space),
+        }
+    }
+}
+
+/// Order domains by ascending value count (ties broken by name).
+///
+/// Fracturing by the smallest domains first prunes UNSAT subspaces
+/// at the shallowest depth, cutting the number of SAT checks.
+pub fn auto_fracture_order(input_space: &InputSpace) -> Vec<String> {
+    let mut sized: Vec<(u64, &String)> = input_space
+        .domains
+        .iter()
+        .map(|(name, d)| {
+            let size = match &d.domain_type {
+                DomainType::Bool => 2,
+                DomainType::Enum { values, .. } => values.len() as u64,
+                DomainType::Int { min, max, stride } => {
+                    if max >= min {
+                        ((max - min) as u64 / (*stride).max(1) as u64).saturating_add(1)
+                    } else {
+                        0
+                    }
+                }
+            };
+            (size, name)
+        })
+        .collect();
+    sized.sort();
+    sized.into_iter().map(|(_, name)| name.clone()).collect()
 }
 
 /// Result of running the full pipeline.
@@ -44,49 +129,251 @@ pub struct PipelineResult {
     pub sat_count: usize,
     /// Number of subspaces that were UNSAT (aborted).
     pub unsat_count: usize,
+    /// Number of leaf subspaces whose SAT check hit the solver timeout.
+    /// They are neither searched nor counted as UNSAT.
+    pub unknown_count: usize,
+    /// Total clauses inserted into SAT solvers across the run. Counted by
+    /// the shared `SolverContext` in `run_pipeline`; computed from
+    /// `fresh_clause_count` in `run_pipeline_parallel_leaves`, whose fresh
+    /// solvers are not instrumented.
+    pub clause_insertions: usize,
+    /// Number of subspace SAT checks performed.
+    pub sat_checks: usize,
+    /// Whether the run was cancelled before finishing (vectors are partial).
+    pub cancelled: bool,
+    /// Solver effort of every searched leaf, in search order.
+    pub leaf_stats: Vec<LeafStats>,
+    /// Sum of `leaf_stats`.
+    pub search_stats: SearchStats,
 }
 
-/// Run the full parallel fracture/solve/abort pipeline.
+/// Solver effort spent searching one leaf subspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeafStats {
+    /// The fracture-variable assignment that defines the leaf
+    /// (empty when the whole space is searched unfractured).
+    pub fixed: BTreeMap<String, DomainValue>,
+    /// Effort of the leaf's vector search.
+    pub stats: SearchStats,
+}
+
+/// Sum the effort of all leaves.
+fn total_stats(leaf_stats: &[LeafStats]) -> SearchStats {
+    let mut total = SearchStats::default();
+    for leaf in leaf_stats {
+        total.accumulate(&leaf.stats);
+    }
+    total
+}
+
+/// Progress of a parallel pipeline run, reported as each leaf completes.
 ///
-/// This is the top-level entry point for vector generation.
+/// Counts are cumulative and never decrease between reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PipelineProgress {
+    /// Leaf subspaces to solve (after pruning during collection).
+    pub leaves_total: usize,
+    /// Leaf subspaces solved so far.
+    pub leaves_done: usize,
+    /// SAT leaves so far.
+    pub sat_so_far: usize,
+    /// UNSAT subspaces so far, including those pruned during collection.
+    /// Leaves whose check timed out count towards neither.
+    pub unsat_so_far: usize,
+    /// Vectors found so far (before deduplication).
+    pub vectors_so_far: usize,
+}
+
+/// Classification of a subspace SAT check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Check {
+    Sat,
+    Unsat,
+    /// The check hit the solver timeout; the subspace must not be pruned.
+    Unknown,
+}
+
+impl Check {
+    fn from_result(result: Result<bool, SearchError>) -> Result<Self, SearchError> {
+        match result {
+            Ok(true) => Ok(Check::Sat),
+            Ok(false) => Ok(Check::Unsat),
+            Err(SearchError::Timeout { .. }) => Ok(Check::Unknown),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Vectors from a search, keeping the partial results of a timeout.
+/// The flag is set if the search timed out.
+fn leaf_search(
+    result: Result<Vec<TestVector>, SearchError>,
+) -> Result<(Vec<TestVector>, bool), SearchError> {
+    match result {
+        Ok(vectors) => Ok((vectors, false)),
+        Err(SearchError::Timeout { partial }) => Ok((partial, true)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether a cancellation token has been set.
+fn is_cancelled(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|c| c.load(Ordering::Relaxed))
+}
+
+/// Cancellation state threaded through the sequential fracture walk.
+struct WalkControl<'a> {
+    cancel: Option<&'a AtomicBool>,
+    /// Called after each leaf subspace is solved.
+    after_leaf: &'a mut dyn FnMut(),
+    cancelled: bool,
+}
+
+impl WalkControl<'_> {
+    /// Check the token, latching cancellation once observed.
+    fn should_stop(&mut self) -> bool {
+        if !self.cancelled && is_cancelled(self.cancel) {
+            self.cancelled = true;
+        }
+        self.cancelled
+    }
+}
+
+/// Run the full fracture/solve/abort pipeline.
+///
+/// This is the top-level entry point for vector generation. A single
+/// `SolverContext` holds the base clauses; every subspace is solved
+/// under its fixing literals as assumptions instead of a fresh solver.
 pub fn run_pipeline(
     input_space: &InputSpace,
     config: &PipelineConfig,
-) -> Result<PipelineResult, SearchError> {
-    let encoded = encode_input_space(input_space)?;
-    let constraint_clauses = encode_constraints(&input_space.constraints, &encoded)?;
+) -> Result<PipelineResult, ExploreError> {
+    Ok(run_pipeline_observed(
+        input_space,
+        config,
+        &BTreeMap::new(),
+        &mut || {},
+    )?)
+}
+
+/// Run the pipeline with some domains pinned to fixed values, for a
+/// focused sub-campaign around a known-interesting point such as a
+/// finding's input vector.
+///
+/// Each pinned value is added as unit clauses (see `lit_for_value`) and
+/// pinned domains are dropped from the fracture order, so generation
+/// only varies the unpinned domains.
+pub fn run_pipeline_focused(
+    input_space: &InputSpace,
+    config: &PipelineConfig,
+    pinned: &BTreeMap<String, DomainValue>,
+) -> Result<PipelineResult, ExploreError> {
+    Ok(run_pipeline_observed(
+        input_space,
+        config,
+        pinned,
+        &mut || {},
+    )?)
+}
+
+/// Unit clauses fixing every pinned domain to its value.
+fn pinning_clauses(
+    encoded: &EncodedInputSpace,
+    pinned: &BTreeMap<String, DomainValue>,
+) -> Result<CnfClauses, SearchError> {
+    let mut clauses = CnfClauses::new();
+    for (name, value) in pinned {
+        let enc = encoded.domains.get(name).ok_or_else(|| {
+            SearchError::Solver(format!("unknown domain '{name}' in pinned values"))
+        })?;
+        let lits = lit_for_value(enc, value)
+            .ok_or_else(|| SearchError::Solver(format!("no SAT literal for {value} in {name}")))?;
+        clauses.extend(lits.into_iter().map(|lit| vec![lit]));
+    }
+    Ok(clauses)
+}
 
-    if config.fracture_variables.is_empty() {
+/// `run_pipeline` over `pinned` values (see `run_pipeline_focused`), with
+/// a hook invoked after each leaf subspace is solved.
+fn run_pipeline_observed(
+    input_space: &InputSpace,
+    config: &PipelineConfig,
+    pinned: &BTreeMap<String, DomainValue>,
+    after_leaf: &mut dyn FnMut(),
+) -> Result<PipelineResult, SearchError> {
+    let mut control = WalkControl {
+        cancel: config.cancel.as_deref(),
+        after_leaf,
+        cancelled: false,
+    };
+    let mut encoded = encode_input_space(input_space)?;
+    let mut constraint_clauses = encode_constraints(&input_space.constraints, &mut encoded)?;
+    constraint_clauses.extend(pinning_clauses(&encoded, pinned)?);
+    let mut ctx = SolverContext::with_timeout(&encoded, &constraint_clauses, config.solver_timeout);
+    let mut fracture_variables = config.fracture_variables.resolve(input_space);
+    fracture_variables.retain(|variable| !pinned.contains_key(variable));
+
+    if fracture_variables.is_empty() {
+        if control.should_stop() {
+            return Ok(PipelineResult {
+                vectors: vec![],
+                sat_count: 0,
+                unsat_count: 0,
+                unknown_count: 0,
+                clause_insertions: ctx.clause_insertions(),
+                sat_checks: 0,
+                cancelled: true,
+                leaf_stats: vec![],
+                search_stats: SearchStats::default(),
+            });
+        }
         // No fracturing — solve the whole space directly.
-        let vectors = find_many(
-            &encoded,
-            &constraint_clauses,
-            &vec![],
-            config.max_vectors_per_leaf,
-        )?;
+        let mut stats = SearchStats::default();
+        let (vectors, timed_out) =
+            leaf_search(ctx.find_many_under_counted(&[], config.max_vectors_per_leaf, &mut stats))?;
+        let empty = vectors.is_empty();
         return Ok(PipelineResult {
-            sat_count: if vectors.is_empty() { 0 } else { 1 },
-            unsat_count: if vectors.is_empty() { 1 } else { 0 },
+            sat_count: usize::from(!empty),
+            unsat_count: usize::from(empty && !timed_out),
+            unknown_count: usize::from(empty && timed_out),
             vectors,
+            clause_insertions: ctx.clause_insertions(),
+            sat_checks: 0,
+            cancelled: false,
+            leaf_stats: vec![LeafStats {
+                fixed: BTreeMap::new(),
+                stats,
+            }],
+            search_stats: stats,
         });
     }
 
     let mut all_vectors = Vec::new();
     let mut sat_count = 0usize;
     let mut unsat_count = 0usize;
+    let mut unknown_count = 0usize;
+    let mut sat_checks = 0usize;
+    let mut leaf_stats = Vec::new();
 
-    parallel_fracture_recursive(
+    fracture_recursive(
+        &mut ctx,
         &encoded,
-        &constraint_clauses,
-        &config.fracture_variables,
-        0,
-        &BTreeMap::new(),
-        &vec![],
+        &fracture_variables,
         0,
+        &Subspace {
+            fixed: BTreeMap::new(),
+            fixing_clauses: vec![],
+            stage_id: 0,
+        },
+        config.seed,
         config.max_vectors_per_leaf,
         &mut all_vectors,
         &mut sat_count,
         &mut unsat_count,
+        &mut unknown_count,
+        &mut sat_checks,
+        &mut leaf_stats,
+        &mut control,
     )?;
 
     // Deduplicate vectors.
@@ -97,80 +384,110 @@ pub fn run_pipeline(
         vectors: all_vectors,
         sat_count,
         unsat_count,
+        unknown_count,
+        clause_insertions: ctx.clause_insertions(),
+        sat_checks,
+        cancelled: control.cancelled,
+        search_stats: total_stats(&leaf_stats),
+        leaf_stats,
     })
 }
 
-/// Recursive parallel fracture/solve.
+/// Recursive fracture/solve over a shared solver context.
 ///
-/// At each depth, fractures by the current variable, then uses rayon
-/// to solve all subspaces in parallel. UNSAT subspaces are aborted.
-/// SAT subspaces are either recursed into (if more variables remain)
-/// or searched for vectors (leaf level).
+/// At each depth, fractures by the current variable and checks every
+/// subspace under assumptions. UNSAT subspaces are aborted. SAT subspaces
+/// are either recursed into (if more variables remain) or searched for
+/// vectors (leaf level); timed-out checks are recursed into as well, but
+/// not searched. Cancellation is checked before every subspace. Each
+/// searched leaf's solver effort is appended to `leaf_stats`.
+///
+/// Sibling subspaces are visited in an order shuffled with the stage RNG
+/// for `seed`, as in `collect_leaves`.
 #[allow(clippy::too_many_arguments)]
-fn parallel_fracture_recursive(
+fn fracture_recursive(
+    ctx: &mut SolverContext<'_>,
     encoded: &EncodedInputSpace,
-    constraint_clauses: &CnfClauses,
     variables: &[String],
     depth: usize,
-    fixed: &BTreeMap<String, DomainValue>,
-    base_clauses: &CnfClauses,
-    stage_id: u64,
+    parent: &Subspace,
+    seed: u64,
     max_vectors_per_leaf: usize,
     results: &mut Vec<TestVector>,
     sat_count: &mut usize,
     unsat_count: &mut usize,
+    unknown_count: &mut usize,
+    sat_checks: &mut usize,
+    leaf_stats: &mut Vec<LeafStats>,
+    control: &mut WalkControl<'_>,
 ) -> Result<(), SearchError> {
+    if control.should_stop() {
+        return Ok(());
+    }
+
     if depth >= variables.len() {
         // Leaf level: solve for vectors.
-        if is_sat(encoded, constraint_clauses, base_clauses)? {
-            *sat_count += 1;
-            let vectors = find_many(
-                encoded,
-                constraint_clauses,
-                base_clauses,
-                max_vectors_per_leaf,
-            )?;
-            results.extend(vectors);
-        } else {
-            *unsat_count += 1;
+        let assumptions = parent.assumptions();
+        *sat_checks += 1;
+        match Check::from_result(ctx.is_sat_under(&assumptions))? {
+            Check::Sat => {
+                *sat_count += 1;
+                let mut stats = SearchStats::default();
+                let search =
+                    ctx.find_many_under_counted(&assumptions, max_vectors_per_leaf, &mut stats);
+                results.extend(leaf_search(search)?.0);
+                leaf_stats.push(LeafStats {
+                    fixed: parent.fixed.clone(),
+                    stats,
+                });
+            }
+            Check::Unsat => *unsat_count += 1,
+            Check::Unknown => *unknown_count += 1,
         }
+        (control.after_leaf)();
         return Ok(());
     }
 
     let variable = &variables[depth];
-    let subspaces = fracture_by_variable(encoded, variable, fixed, base_clauses, stage_id)?;
-
-    // Parallel SAT check across all subspaces.
-    let sat_results: Vec<(usize, bool)> = subspaces
-        .par_iter()
-        .enumerate()
-        .map(|(i, subspace)| {
-            let sat =
-                is_sat(encoded, constraint_clauses, &subspace.fixing_clauses).unwrap_or(false);
-            (i, sat)
-        })
-        .collect();
+    let mut subspaces = fracture_by_variable(
+        encoded,
+        variable,
+        &parent.fixed,
+        &parent.fixing_clauses,
+        parent.stage_id,
+    )?;
+    subspaces.shuffle(&mut stage_rng(seed, parent.stage_id));
+
+    // SAT check all siblings first so UNSAT subspaces abort before any search.
+    let mut sat_results = Vec::with_capacity(subspaces.len());
+    for subspace in &subspaces {
+        sat_results.push(Check::from_result(
+            ctx.is_sat_under(&subspace.assumptions()),
+        )?);
+    }
+    *sat_checks += subspaces.len();
 
-    // Process results: abort UNSAT, recurse into SAT.
-    for (i, is_satisfiable) in sat_results {
-        if !is_satisfiable {
+    for (subspace, check) in subspaces.iter().zip(sat_results) {
+        if check == Check::Unsat {
             *unsat_count += 1;
             continue; // Abort UNSAT subspace.
         }
 
-        let subspace = &subspaces[i];
-        parallel_fracture_recursive(
+        fracture_recursive(
+            ctx,
             encoded,
-            constraint_clauses,
             variables,
             depth + 1,
-            &subspace.fixed,
-            &subspace.fixing_clauses,
-            subspace.stage_id,
+            subspace,
+            seed,
             max_vectors_per_leaf,
             results,
             sat_count,
             unsat_count,
+            unknown_count,
+            sat_checks,
+            leaf_stats,
+            control,
         )?;
     }
 
@@ -181,89 +498,234 @@ fn parallel_fracture_recursive(
 ///
 /// Like `run_pipeline`, but at the leaf level, solves all SAT subspaces
 /// concurrently using rayon. Better for workloads with many leaf subspaces.
+/// Each check builds a fresh solver, so clause insertions are much higher;
+/// they are computed with `fresh_clause_count`, not measured.
+/// Once cancelled, leaves not yet started are skipped. Each leaf drops
+/// vectors already found by another leaf as it finishes, so only the first
+/// sighting of a vector is kept.
+///
+/// `progress`, if given, is called from the rayon workers after each leaf.
+/// Reports are serialized, so successive counts are monotonic.
 pub fn run_pipeline_parallel_leaves(
     input_space: &InputSpace,
     config: &PipelineConfig,
+    progress: Option<&(dyn Fn(PipelineProgress) + Sync)>,
 ) -> Result<PipelineResult, SearchError> {
-    let encoded = encode_input_space(input_space)?;
-    let constraint_clauses = encode_constraints(&input_space.constraints, &encoded)?;
-
-    if config.fracture_variables.is_empty() {
-        let vectors = find_many(
+    let mut encoded = encode_input_space(input_space)?;
+    let constraint_clauses = encode_constraints(&input_space.constraints, &mut encoded)?;
+    let fracture_variables = config.fracture_variables.resolve(input_space);
+    let cancel = config.cancel.as_deref();
+
+    if fracture_variables.is_empty() {
+        if is_cancelled(cancel) {
+            return Ok(PipelineResult {
+                vectors: vec![],
+                sat_count: 0,
+                unsat_count: 0,
+                unknown_count: 0,
+                clause_insertions: 0,
+                sat_checks: 0,
+                cancelled: true,
+                leaf_stats: vec![],
+                search_stats: SearchStats::default(),
+            });
+        }
+        let search = SearchConfig {
+            max_vectors: config.max_vectors_per_leaf,
+            extra_clauses: vec![],
+            timeout: config.solver_timeout,
+        };
+        let mut stats = SearchStats::default();
+        let (vectors, timed_out) = leaf_search(find_many_counted(
             &encoded,
             &constraint_clauses,
-            &vec![],
-            config.max_vectors_per_leaf,
-        )?;
+            &search,
+            &mut stats,
+        ))?;
+        let empty = vectors.is_empty();
         return Ok(PipelineResult {
-            sat_count: if vectors.is_empty() { 0 } else { 1 },
-            unsat_count: if vectors.is_empty() { 1 } else { 0 },
+            sat_count: usize::from(!empty),
+            unsat_count: usize::from(empty && !timed_out),
+            unknown_count: usize::from(empty && timed_out),
+            clause_insertions: fresh_clause_count(&encoded, &constraint_clauses, &vec![])
+                + vectors.len(),
             vectors,
+            sat_checks: 0,
+            cancelled: false,
+            leaf_stats: vec![LeafStats {
+                fixed: BTreeMap::new(),
+                stats,
+            }],
+            search_stats: stats,
         });
     }
 
     // Collect all leaf subspaces first.
     let mut leaves = Vec::new();
     let mut pruned_count = 0usize;
+    let mut clause_insertions = 0usize;
+    let mut sat_checks = 0usize;
     collect_leaves(
         &encoded,
         &constraint_clauses,
-        &config.fracture_variables,
+        &fracture_variables,
         0,
         &BTreeMap::new(),
         &vec![],
         0,
+        config.seed,
         &mut leaves,
         &mut pruned_count,
+        &mut clause_insertions,
+        &mut sat_checks,
+        cancel,
+        config.solver_timeout,
+        &|search| is_sat_with_config(&encoded, &constraint_clauses, search),
     )?;
 
-    // Solve all leaves in parallel.
-    let leaf_results: Vec<Result<(Vec<TestVector>, bool), SearchError>> = leaves
+    // Leaf collection stops early once cancelled; every leaf is then skipped.
+    let mut cancelled = is_cancelled(cancel);
+
+    let reported = Mutex::new(PipelineProgress {
+        leaves_total: leaves.len(),
+        unsat_so_far: pruned_count,
+        ..Default::default()
+    });
+    let report = |check: Check, vectors: usize| {
+        if let Some(callback) = progress {
+            let mut p = reported.lock().expect("progress lock poisoned");
+            p.leaves_done += 1;
+            match check {
+                Check::Sat => p.sat_so_far += 1,
+                Check::Unsat => p.unsat_so_far += 1,
+                Check::Unknown => {}
+            }
+            p.vectors_so_far += vectors;
+            callback(*p);
+        }
+    };
+
+    let seen = ShardedVectorSet::new();
+
+    // Solve all leaves in parallel. `None` marks a leaf skipped after cancellation;
+    // only searched (SAT) leaves carry stats.
+    type LeafResult =
+        Result<Option<(Vec<TestVector>, Check, usize, Option<SearchStats>)>, SearchError>;
+    let leaf_results: Vec<LeafResult> = leaves
         .par_iter()
         .map(|subspace| {
-            if !is_sat(&encoded, &constraint_clauses, &subspace.fixing_clauses)? {
-                return Ok((vec![], false));
+            if is_cancelled(cancel) {
+                return Ok(None);
+            }
+            let fresh = fresh_clause_count(&encoded, &constraint_clauses, &subspace.fixing_clauses);
+            let search = SearchConfig {
+                max_vectors: config.max_vectors_per_leaf,
+                extra_clauses: subspace.fixing_clauses.clone(),
+                timeout: config.solver_timeout,
+            };
+            let check =
+                Check::from_result(is_sat_with_config(&encoded, &constraint_clauses, &search))?;
+            if check != Check::Sat {
+                report(check, 0);
+                return Ok(Some((vec![], check, fresh, None)));
             }
-            let vectors = find_many(
+            let mut stats = SearchStats::default();
+            let (mut vectors, _) = leaf_search(find_many_counted(
                 &encoded,
                 &constraint_clauses,
-                &subspace.fixing_clauses,
-                config.max_vectors_per_leaf,
-            )?;
-            Ok((vectors, true))
+                &search,
+                &mut stats,
+            ))?;
+            report(Check::Sat, vectors.len());
+            // One solver for the SAT check, one for the search plus its blocking clauses.
+            let inserted = 2 * fresh + vectors.len();
+            vectors.retain(|v| seen.insert(v));
+            Ok(Some((vectors, Check::Sat, inserted, Some(stats))))
         })
         .collect();
 
     let mut all_vectors = Vec::new();
     let mut sat_count = 0;
     let mut unsat_count = 0;
+    let mut unknown_count = 0;
 
-    for result in leaf_results {
-        let (vectors, is_sat_result) = result?;
-        if is_sat_result {
-            sat_count += 1;
-            all_vectors.extend(vectors);
-        } else {
-            unsat_count += 1;
+    let mut leaf_stats = Vec::new();
+
+    for (subspace, result) in leaves.iter().zip(leaf_results) {
+        let Some((vectors, check, inserted, stats)) = result? else {
+            cancelled = true;
+            continue;
+        };
+        if let Some(stats) = stats {
+            leaf_stats.push(LeafStats {
+                fixed: subspace.fixed.clone(),
+                stats,
+            });
+        }
+        sat_checks += 1;
+        clause_insertions += inserted;
+        match check {
+            Check::Sat => {
+                sat_count += 1;
+                all_vectors.extend(vectors);
+            }
+            Check::Unsat => unsat_count += 1,
+            Check::Unknown => unknown_count += 1,
         }
     }
 
     // Include pruned subspaces in the UNSAT count.
     unsat_count += pruned_count;
 
-    // Deduplicate.
-    let mut seen = HashSet::new();
-    all_vectors.retain(|v| seen.insert(v.clone()));
-
     Ok(PipelineResult {
         vectors: all_vectors,
         sat_count,
         unsat_count,
+        unknown_count,
+        clause_insertions,
+        sat_checks,
+        cancelled,
+        search_stats: total_stats(&leaf_stats),
+        leaf_stats,
     })
 }
 
+/// Number of lock shards in a `ShardedVectorSet`.
+const DEDUP_SHARDS: usize = 16;
+
+/// Set of vectors seen across concurrently solved leaves, sharded by hash
+/// so workers inserting different vectors rarely wait on the same lock.
+struct ShardedVectorSet {
+    shards: Vec<Mutex<HashSet<TestVector>>>,
+    hasher: RandomState,
+}
+
+impl ShardedVectorSet {
+    fn new() -> Self {
+        Self {
+            shards: (0..DEDUP_SHARDS).map(|_| Mutex::default()).collect(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Record `vector`, returning whether it had not been seen before.
+    fn insert(&self, vector: &TestVector) -> bool {
+        let shard = self.hasher.hash_one(vector) as usize % self.shards.len();
+        self.shards[shard]
+            .lock()
+            .expect("dedup lock poisoned")
+            .insert(vector.clone())
+    }
+}
+
 /// Recursively collect all leaf subspaces without solving them.
-/// Tracks how many subspaces were pruned as UNSAT during collection.
+/// Tracks how many subspaces were pruned as UNSAT during collection,
+/// plus the SAT checks made and the clauses their fresh solvers inserted.
+/// Subspaces whose check times out are kept, not pruned; any other
+/// `check` error aborts the collection.
+///
+/// Sibling subspaces are shuffled with the stage RNG for `seed`, so the
+/// seed decides which leaves solve first without changing the leaf set.
 #[allow(clippy::too_many_arguments)]
 fn collect_leaves(
     encoded: &EncodedInputSpace,
@@ -273,9 +735,19 @@ fn collect_leaves(
     fixed: &BTreeMap<String, DomainValue>,
     base_clauses: &CnfClauses,
     stage_id: u64,
+    seed: u64,
     leaves: &mut Vec<Subspace>,
     pruned_count: &mut usize,
+    clause_insertions: &mut usize,
+    sat_check_count: &mut usize,
+    cancel: Option<&AtomicBool>,
+    timeout: Option<Duration>,
+    check: &(dyn Fn(&SearchConfig) -> Result<bool, SearchError> + Sync),
 ) -> Result<(), SearchError> {
+    if is_cancelled(cancel) {
+        return Ok(());
+    }
+
     if depth >= variables.len() {
         leaves.push(Subspace {
             fixed: fixed.clone(),
@@ -286,13 +758,27 @@ fn collect_leaves(
     }
 
     let variable = &variables[depth];
-    let subspaces = fracture_by_variable(encoded, variable, fixed, base_clauses, stage_id)?;
+    let mut subspaces = fracture_by_variable(encoded, variable, fixed, base_clauses, stage_id)?;
+    subspaces.shuffle(&mut stage_rng(seed, stage_id));
 
     // Quick parallel SAT check to prune early.
-    let sat_checks: Vec<bool> = subspaces
+    let sat_checks = subspaces
         .par_iter()
-        .map(|s| is_sat(encoded, constraint_clauses, &s.fixing_clauses).unwrap_or(false))
-        .collect();
+        .map(|s| {
+            let search = SearchConfig {
+                extra_clauses: s.fixing_clauses.clone(),
+                timeout,
+                ..Default::default()
+            };
+            Check::from_result(check(&search)).map(|c| c != Check::Unsat)
+        })
+        .collect::<Result<Vec<bool>, SearchError>>()?;
+
+    *sat_check_count += subspaces.len();
+    for subspace in &subspaces {
+        *clause_insertions +=
+            fresh_clause_count(encoded, constraint_clauses, &subspace.fixing_clauses);
+    }
 
     for (i, subspace) in subspaces.iter().enumerate() {
         if !sat_checks[i] {
@@ -307,8 +793,14 @@ fn collect_leaves(
             &subspace.fixed,
             &subspace.fixing_clauses,
             subspace.stage_id,
+            seed,
             leaves,
             pruned_count,
+            clause_insertions,
+            sat_check_count,
+            cancel,
+            timeout,
+            check,
         )?;
     }
 
@@ -329,6 +821,7 @@ mod tests {
         InputSpace {
             domains,
             constraints,
+            forbidden: Vec::new(),
             coverage: CoverageConfig {
                 targets: vec![],
                 seed: 42,
@@ -351,13 +844,44 @@ mod tests {
         let config = PipelineConfig {
             seed: 42,
             max_vectors_per_leaf: 0,
-            fracture_variables: vec![],
+            fracture_variables: FractureOrder::Manual(vec![]),
+            ..Default::default()
         };
 
         let result = run_pipeline(&input_space, &config).unwrap();
         assert_eq!(result.vectors.len(), 2);
     }
 
+    #[test]
+    fn test_constraint_failure_surfaces_as_explore_error() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "flag".to_string(),
+            Domain {
+                domain_type: DomainType::Bool,
+            },
+        );
+        let mut input_space = make_input_space(domains, vec![]);
+        input_space.forbidden = vec![vec![("tier".to_string(), Literal::String("gold".into()))]];
+
+        let config = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Manual(vec![]),
+            ..Default::default()
+        };
+
+        let err = run_pipeline(&input_space, &config).unwrap_err();
+        let ExploreError::Constraint(inner) = &err else {
+            panic!("expected a constraint error, got {err:?}");
+        };
+        assert_eq!(
+            inner.to_string(),
+            "unknown domain variable 'tier' in constraint"
+        );
+        assert!(err.to_string().ends_with(&inner.to_string()));
+    }
+
     #[test]
     fn test_pipeline_single_fracture() {
         let mut domains = HashMap::new();
@@ -366,6 +890,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -380,7 +905,8 @@ mod tests {
         let config = PipelineConfig {
             seed: 42,
             max_vectors_per_leaf: 0,
-            fracture_variables: vec!["role".into()],
+            fracture_variables: FractureOrder::Manual(vec!["role".into()]),
+            ..Default::default()
         };
 
         let result = run_pipeline(&input_space, &config).unwrap();
@@ -389,6 +915,56 @@ mod tests {
         assert_eq!(result.unsat_count, 0);
     }
 
+    #[test]
+    fn test_pipeline_aggregates_leaf_stats() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "auth".to_string(),
+            Domain {
+                domain_type: DomainType::Bool,
+            },
+        );
+        let input_space = make_input_space(domains, vec![]);
+
+        let config = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Manual(vec!["role".into()]),
+            ..Default::default()
+        };
+
+        for result in [
+            run_pipeline(&input_space, &config).unwrap(),
+            run_pipeline_parallel_leaves(&input_space, &config, None).unwrap(),
+        ] {
+            // One searched leaf per role, each holding both auth values.
+            assert_eq!(result.leaf_stats.len(), 3);
+            for leaf in &result.leaf_stats {
+                assert!(leaf.fixed.contains_key("role"));
+                assert_eq!(leaf.stats.vectors_found, 2);
+                assert!(leaf.stats.solve_calls >= 3);
+            }
+            assert_eq!(result.search_stats.vectors_found, 6);
+            assert_eq!(
+                result.search_stats.solve_calls,
+                result
+                    .leaf_stats
+                    .iter()
+                    .map(|leaf| leaf.stats.solve_calls)
+                    .sum::<u64>()
+            );
+        }
+    }
+
     #[test]
     fn test_pipeline_with_constraint_and_abort() {
         // role = admin forced -> guest subspace is UNSAT
@@ -398,6 +974,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -418,7 +995,8 @@ mod tests {
         let config = PipelineConfig {
             seed: 42,
             max_vectors_per_leaf: 0,
-            fracture_variables: vec!["role".into()],
+            fracture_variables: FractureOrder::Manual(vec!["role".into()]),
+            ..Default::default()
         };
 
         let result = run_pipeline(&input_space, &config).unwrap();
@@ -435,6 +1013,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -449,6 +1028,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["private".into(), "public".into()],
+                    ordered: false,
                 },
             },
         );
@@ -458,7 +1038,12 @@ mod tests {
         let config = PipelineConfig {
             seed: 42,
             max_vectors_per_leaf: 0,
-            fracture_variables: vec!["role".into(), "auth".into(), "vis".into()],
+            fracture_variables: FractureOrder::Manual(vec![
+                "role".into(),
+                "auth".into(),
+                "vis".into(),
+            ]),
+            ..Default::default()
         };
 
         let result = run_pipeline(&input_space, &config).unwrap();
@@ -474,6 +1059,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -512,10 +1098,11 @@ mod tests {
         let config = PipelineConfig {
             seed: 42,
             max_vectors_per_leaf: 0,
-            fracture_variables: vec!["role".into(), "auth".into()],
+            fracture_variables: FractureOrder::Manual(vec!["role".into(), "auth".into()]),
+            ..Default::default()
         };
 
-        let result = run_pipeline_parallel_leaves(&input_space, &config).unwrap();
+        let result = run_pipeline_parallel_leaves(&input_space, &config, None).unwrap();
         // admin+true, admin+false, member+true, member+false, guest+false = 5
         // guest+true is UNSAT
         assert_eq!(result.vectors.len(), 5);
@@ -523,6 +1110,48 @@ mod tests {
         assert_eq!(result.sat_count, 5);
     }
 
+    #[test]
+    fn test_seed_shuffles_leaf_order_not_vector_set() {
+        let mut domains = HashMap::new();
+        for name in ["role", "tier"] {
+            domains.insert(
+                name.to_string(),
+                Domain {
+                    domain_type: DomainType::Enum {
+                        values: (0..6).map(|i| format!("{name}_{i}")).collect(),
+                        ordered: false,
+                    },
+                },
+            );
+        }
+        let input_space = make_input_space(domains, vec![]);
+
+        let config = |seed| PipelineConfig {
+            seed,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Manual(vec!["role".into(), "tier".into()]),
+            ..Default::default()
+        };
+        let sequential = |seed| run_pipeline(&input_space, &config(seed)).unwrap().vectors;
+        let parallel = |seed| {
+            run_pipeline_parallel_leaves(&input_space, &config(seed), None)
+                .unwrap()
+                .vectors
+        };
+
+        for run in [&sequential as &dyn Fn(u64) -> Vec<TestVector>, &parallel] {
+            let first = run(1);
+            let second = run(2);
+            assert_eq!(first.len(), 36);
+            assert_eq!(run(1), first, "same seed, same order");
+
+            let set1: HashSet<_> = first.iter().collect();
+            let set2: HashSet<_> = second.iter().collect();
+            assert_eq!(set1, set2);
+            assert_ne!(first[..10], second[..10]);
+        }
+    }
+
     #[test]
     fn test_pipeline_reproduces_same_vectors() {
         let mut domains = HashMap::new();
@@ -531,6 +1160,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -546,7 +1176,8 @@ mod tests {
         let config = PipelineConfig {
             seed: 42,
             max_vectors_per_leaf: 0,
-            fracture_variables: vec!["role".into()],
+            fracture_variables: FractureOrder::Manual(vec!["role".into()]),
+            ..Default::default()
         };
 
         let result1 = run_pipeline(&input_space, &config).unwrap();
@@ -558,15 +1189,15 @@ mod tests {
         assert_eq!(set1, set2);
     }
 
-    #[test]
-    fn test_pipeline_design_doc_example() {
-        // Full example from the design doc inputs section.
+    /// Full example from the design doc inputs section.
+    fn design_doc_input_space() -> InputSpace {
         let mut domains = HashMap::new();
         domains.insert(
             "actor_role".to_string(),
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -581,6 +1212,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["private".into(), "shared".into(), "public".into()],
+                    ordered: false,
                 },
             },
         );
@@ -593,7 +1225,11 @@ mod tests {
         domains.insert(
             "concurrent_actors".to_string(),
             Domain {
-                domain_type: DomainType::Int { min: 1, max: 8 },
+                domain_type: DomainType::Int {
+                    min: 1,
+                    max: 8,
+                    stride: 1,
+                },
             },
         );
 
@@ -620,15 +1256,24 @@ mod tests {
             },
         }];
 
-        let input_space = make_input_space(domains, constraints);
+        make_input_space(domains, constraints)
+    }
+
+    #[test]
+    fn test_pipeline_design_doc_example() {
+        let input_space = design_doc_input_space();
 
         let config = PipelineConfig {
             seed: 42,
             max_vectors_per_leaf: 0,
-            fracture_variables: vec!["actor_role".into(), "doc_visibility".into()],
+            fracture_variables: FractureOrder::Manual(vec![
+                "actor_role".into(),
+                "doc_visibility".into(),
+            ]),
+            ..Default::default()
         };
 
-        let result = run_pipeline_parallel_leaves(&input_space, &config).unwrap();
+        let result = run_pipeline_parallel_leaves(&input_space, &config, None).unwrap();
 
         // 3 roles x 3 vis = 9 leaf subspaces, all SAT (constraint is trivially true).
         // Each leaf has 2 auth x 2 owner x 8 concurrent_actors = 32 vectors.
@@ -641,4 +1286,317 @@ mod tests {
             assert_eq!(v.assignments.len(), 5);
         }
     }
+
+    #[test]
+    fn test_focused_pipeline_only_varies_unpinned_domains() {
+        let input_space = design_doc_input_space();
+        let config = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Manual(vec![
+                "actor_role".into(),
+                "doc_visibility".into(),
+            ]),
+            ..Default::default()
+        };
+        let pinned =
+            BTreeMap::from([("actor_role".to_string(), DomainValue::Enum("guest".into()))]);
+
+        let result = run_pipeline_focused(&input_space, &config, &pinned).unwrap();
+
+        // 2 auth x 3 vis x 2 owner x 8 concurrent_actors = 96 vectors.
+        assert_eq!(result.vectors.len(), 96);
+        assert!(result
+            .vectors
+            .iter()
+            .all(|v| v.assignments["actor_role"] == DomainValue::Enum("guest".into())));
+        let distinct = |domain: &str| {
+            result
+                .vectors
+                .iter()
+                .map(|v| v.assignments[domain].clone())
+                .collect::<HashSet<_>>()
+                .len()
+        };
+        assert_eq!(distinct("actor_authenticated"), 2);
+        assert_eq!(distinct("doc_visibility"), 3);
+        assert_eq!(distinct("actor_is_owner"), 2);
+        assert_eq!(distinct("concurrent_actors"), 8);
+    }
+
+    #[test]
+    fn test_parallel_dedup_matches_serial_dedup() {
+        let input_space = design_doc_input_space();
+        let config = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Manual(vec![
+                "actor_role".into(),
+                "actor_is_owner".into(),
+            ]),
+            ..Default::default()
+        };
+
+        let parallel = run_pipeline_parallel_leaves(&input_space, &config, None).unwrap();
+        let serial = run_pipeline(&input_space, &config).unwrap();
+
+        let parallel_set: HashSet<_> = parallel.vectors.iter().collect();
+        let serial_set: HashSet<_> = serial.vectors.iter().collect();
+        assert_eq!(
+            parallel_set.len(),
+            parallel.vectors.len(),
+            "no duplicates kept"
+        );
+        assert_eq!(parallel_set, serial_set);
+    }
+
+    #[test]
+    fn test_find_projected_design_doc_role_visibility() {
+        let input_space = design_doc_input_space();
+        let mut encoded = encode_input_space(&input_space).unwrap();
+        let constraints = encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+        let projection = vec!["actor_role".to_string(), "doc_visibility".to_string()];
+
+        let projected =
+            super::super::search::find_projected(&encoded, &constraints, &vec![], &projection)
+                .unwrap();
+
+        // 3 roles x 3 visibilities, instead of the 288 full vectors.
+        let distinct: HashSet<_> = projected.iter().collect();
+        assert_eq!(projected.len(), 9);
+        assert_eq!(distinct.len(), 9);
+        for combination in &projected {
+            assert_eq!(
+                combination.keys().collect::<Vec<_>>(),
+                projection.iter().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_incremental_pipeline_matches_fresh_solvers() {
+        let input_space = design_doc_input_space();
+        let config = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Manual(vec![
+                "actor_role".into(),
+                "doc_visibility".into(),
+            ]),
+            ..Default::default()
+        };
+
+        let incremental = run_pipeline(&input_space, &config).unwrap();
+        let fresh = run_pipeline_parallel_leaves(&input_space, &config, None).unwrap();
+
+        let set1: HashSet<_> = incremental.vectors.iter().collect();
+        let set2: HashSet<_> = fresh.vectors.iter().collect();
+        assert_eq!(set1, set2);
+        assert_eq!(incremental.vectors.len(), 288);
+        assert!(
+            incremental.clause_insertions < fresh.clause_insertions,
+            "incremental {} vs fresh {}",
+            incremental.clause_insertions,
+            fresh.clause_insertions
+        );
+    }
+
+    #[test]
+    fn test_cancel_after_first_leaf_returns_partial() {
+        let input_space = design_doc_input_space();
+        let token = Arc::new(AtomicBool::new(false));
+        let config = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Manual(vec![
+                "actor_role".into(),
+                "doc_visibility".into(),
+            ]),
+            cancel: Some(Arc::clone(&token)),
+            solver_timeout: None,
+        };
+
+        let full = run_pipeline(&input_space, &config).unwrap();
+        assert!(!full.cancelled);
+
+        let mut leaves_solved = 0;
+        let partial = run_pipeline_observed(&input_space, &config, &BTreeMap::new(), &mut || {
+            leaves_solved += 1;
+            token.store(true, Ordering::Relaxed);
+        })
+        .unwrap();
+
+        assert!(partial.cancelled);
+        assert_eq!(leaves_solved, 1);
+        assert_eq!(partial.sat_count, 1);
+        assert!(!partial.vectors.is_empty());
+        assert!(partial.vectors.len() < full.vectors.len());
+    }
+
+    #[test]
+    fn test_cancelled_parallel_pipeline_skips_leaves() {
+        let input_space = design_doc_input_space();
+        let config = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Manual(vec!["actor_role".into()]),
+            cancel: Some(Arc::new(AtomicBool::new(true))),
+            solver_timeout: None,
+        };
+
+        let result = run_pipeline_parallel_leaves(&input_space, &config, None).unwrap();
+        assert!(result.cancelled);
+        assert!(result.vectors.is_empty());
+        assert_eq!(result.sat_count, 0);
+    }
+
+    #[test]
+    fn test_timed_out_subspaces_are_unknown_not_unsat() {
+        let input_space = design_doc_input_space();
+        let config = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Manual(vec![
+                "actor_role".into(),
+                "doc_visibility".into(),
+            ]),
+            cancel: None,
+            solver_timeout: Some(Duration::ZERO),
+        };
+
+        // Every check times out: nothing may be pruned, so all
+        // 3 roles x 3 visibilities reach the leaf level as unknown.
+        let result = run_pipeline(&input_space, &config).unwrap();
+        assert_eq!(result.unknown_count, 9);
+        assert_eq!(result.unsat_count, 0);
+        assert_eq!(result.sat_count, 0);
+
+        let parallel = run_pipeline_parallel_leaves(&input_space, &config, None).unwrap();
+        assert_eq!(parallel.unknown_count, 9);
+        assert_eq!(parallel.unsat_count, 0);
+        assert_eq!(parallel.sat_count, 0);
+    }
+
+    #[test]
+    fn test_leaf_check_errors_are_not_pruned_as_unsat() {
+        let input_space = design_doc_input_space();
+        let mut encoded = encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+        let mut leaves = Vec::new();
+        let (mut pruned, mut insertions, mut checks) = (0, 0, 0);
+
+        let result = collect_leaves(
+            &encoded,
+            &constraint_clauses,
+            &["actor_role".into(), "doc_visibility".into()],
+            0,
+            &BTreeMap::new(),
+            &vec![],
+            0,
+            42,
+            &mut leaves,
+            &mut pruned,
+            &mut insertions,
+            &mut checks,
+            None,
+            None,
+            &|_| Err(SearchError::Solver("boom".into())),
+        );
+
+        assert!(matches!(result, Err(SearchError::Solver(ref m)) if m == "boom"));
+        assert_eq!(pruned, 0);
+        assert!(leaves.is_empty());
+    }
+
+    #[test]
+    fn test_parallel_progress_reaches_total() {
+        let input_space = design_doc_input_space();
+        let config = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 4,
+            fracture_variables: FractureOrder::Manual(vec![
+                "actor_role".into(),
+                "doc_visibility".into(),
+            ]),
+            ..Default::default()
+        };
+
+        let reports = Mutex::new(Vec::new());
+        let record = |p: PipelineProgress| reports.lock().unwrap().push(p);
+        let result = run_pipeline_parallel_leaves(&input_space, &config, Some(&record)).unwrap();
+        let reports = reports.into_inner().unwrap();
+
+        // 3 roles x 3 visibilities, none pruned.
+        assert_eq!(reports.len(), 9);
+        let last = reports.last().unwrap();
+        assert_eq!(last.leaves_done, last.leaves_total);
+        assert_eq!(last.sat_so_far, result.sat_count);
+        assert_eq!(last.vectors_so_far, result.vectors.len());
+        for pair in reports.windows(2) {
+            assert_eq!(pair[1].leaves_done, pair[0].leaves_done + 1);
+            assert!(pair[1].vectors_so_far >= pair[0].vectors_so_far);
+        }
+    }
+
+    #[test]
+    fn test_auto_fracture_order_smallest_first() {
+        let order = auto_fracture_order(&design_doc_input_space());
+        assert_eq!(
+            order,
+            vec![
+                "actor_authenticated",
+                "actor_is_owner",
+                "actor_role",
+                "doc_visibility",
+                "concurrent_actors",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_auto_fracture_order_prunes_earlier() {
+        let auto = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Auto,
+            ..Default::default()
+        };
+        let result = run_pipeline(&design_doc_input_space(), &auto).unwrap();
+        assert_eq!(result.vectors.len(), 288);
+
+        // Heavily constrained variant: both bools pinned to false.
+        let mut input_space = design_doc_input_space();
+        for name in ["actor_authenticated", "actor_is_owner"] {
+            input_space.constraints.push(InputConstraint {
+                name: format!("{name}_false"),
+                rule: Expr::Op {
+                    op: OpKind::Eq,
+                    args: vec![
+                        Expr::Literal(Literal::String(name.into())),
+                        Expr::Literal(Literal::Bool(false)),
+                    ],
+                },
+            });
+        }
+
+        // Same variables, largest domains first.
+        let mut largest_first = auto_fracture_order(&input_space);
+        largest_first.reverse();
+        let manual = PipelineConfig {
+            seed: 42,
+            max_vectors_per_leaf: 0,
+            fracture_variables: FractureOrder::Manual(largest_first),
+            ..Default::default()
+        };
+
+        let auto_result = run_pipeline(&input_space, &auto).unwrap();
+        let manual_result = run_pipeline(&input_space, &manual).unwrap();
+
+        let set1: HashSet<_> = auto_result.vectors.iter().collect();
+        let set2: HashSet<_> = manual_result.vectors.iter().collect();
+        assert_eq!(set1, set2);
+        assert_eq!(auto_result.vectors.len(), 72);
+        assert!(auto_result.sat_checks < manual_result.sat_checks);
+    }
 }
@@ -5,19 +5,36 @@
 //! can draw vectors without blocking the solver (producer).
 //!
 //! Uses crossbeam's lock-free ArrayQueue for bounded, wait-free
-//! concurrent access.
+//! concurrent access. `PriorityVectorPool` layers priority bands on top,
+//! so vectors covering still-uncovered targets are drawn first.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use crossbeam::queue::ArrayQueue;
 
-use super::coverage::CoveragePoint;
+use super::coverage::{check_coverage, CoveragePoint};
 use super::TestVector;
 
 /// Default capacity per target queue.
 const DEFAULT_QUEUE_CAPACITY: usize = 256;
 
+/// Default number of priority bands in a `PriorityVectorPool`.
+const DEFAULT_PRIORITY_BANDS: usize = 4;
+
+/// Smallest general capacity `VectorPoolStats::recommend_capacity` suggests.
+const MIN_RECOMMENDED_CAPACITY: usize = 16;
+
+/// Largest general capacity `VectorPoolStats::recommend_capacity` suggests.
+const MAX_RECOMMENDED_CAPACITY: usize = DEFAULT_QUEUE_CAPACITY * 256;
+
+/// Consumption rate at or above which consumers are draining the pool
+/// faster than it is refilled.
+const HIGH_CONSUMPTION_RATE: f64 = 0.9;
+
+/// Consumption rate at or below which most pushed vectors sit unused.
+const LOW_CONSUMPTION_RATE: f64 = 0.25;
+
 /// A lockfree pool of pre-generated test vectors.
 ///
 /// Organized into:
@@ -33,6 +50,66 @@ pub struct VectorPool {
     pushed: std::sync::atomic::AtomicUsize,
     /// Stats: total vectors popped.
     popped: std::sync::atomic::AtomicUsize,
+    /// Pushes, pops and empty pops since the current window started.
+    window: WindowCounters,
+}
+
+/// Counters reset by `VectorPool::take_window_stats`.
+#[derive(Debug, Default)]
+struct WindowCounters {
+    pushed: std::sync::atomic::AtomicUsize,
+    popped: std::sync::atomic::AtomicUsize,
+    starved: std::sync::atomic::AtomicUsize,
+}
+
+/// Pool traffic over one window, for tuning capacity between solve batches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VectorPoolStats {
+    /// Vectors pushed during the window.
+    pub pushed: usize,
+    /// Vectors popped during the window.
+    pub popped: usize,
+    /// Pops during the window that found the pool empty.
+    pub starved: usize,
+    /// Capacity of the general queue.
+    pub capacity: usize,
+    /// Vectors in the general queue when the window closed.
+    pub len: usize,
+}
+
+impl VectorPoolStats {
+    /// Popped over pushed vectors in the window: above 1.0 the pool is
+    /// draining, below it filling. Infinite if vectors were popped but
+    /// none pushed, 0.0 for an idle window.
+    pub fn consumption_rate(&self) -> f64 {
+        match (self.pushed, self.popped) {
+            (0, 0) => 0.0,
+            (0, _) => f64::INFINITY,
+            (pushed, popped) => popped as f64 / pushed as f64,
+        }
+    }
+
+    /// Suggested general capacity for the next window.
+    ///
+    /// Doubles when consumers starved or drained nearly everything pushed,
+    /// so the solver can run further ahead; halves when most pushed
+    /// vectors went unused and the queue is less than half full. Stays
+    /// within `[MIN_RECOMMENDED_CAPACITY, MAX_RECOMMENDED_CAPACITY]`
+    /// unless the current capacity is already outside it.
+    pub fn recommend_capacity(&self) -> usize {
+        let rate = self.consumption_rate();
+        if self.starved > 0 || rate >= HIGH_CONSUMPTION_RATE {
+            (self.capacity * 2)
+                .min(MAX_RECOMMENDED_CAPACITY)
+                .max(self.capacity)
+        } else if self.pushed > 0 && rate <= LOW_CONSUMPTION_RATE && self.len < self.capacity / 2 {
+            (self.capacity / 2)
+                .max(MIN_RECOMMENDED_CAPACITY)
+                .min(self.capacity)
+        } else {
+            self.capacity
+        }
+    }
 }
 
 /// A hashable key for coverage points (used as HashMap key).
@@ -47,6 +124,7 @@ impl VectorPool {
             targeted: HashMap::new(),
             pushed: std::sync::atomic::AtomicUsize::new(0),
             popped: std::sync::atomic::AtomicUsize::new(0),
+            window: WindowCounters::default(),
         }
     }
 
@@ -55,6 +133,11 @@ impl VectorPool {
         Self::new(DEFAULT_QUEUE_CAPACITY * 4)
     }
 
+    /// Capacity of the general queue.
+    pub fn general_capacity(&self) -> usize {
+        self.general.capacity()
+    }
+
     /// Register a coverage target with its own queue.
     pub fn register_target(&mut self, target: CoveragePoint) {
         let key = CoveragePointKey(target);
@@ -68,8 +151,7 @@ impl VectorPool {
     pub fn push_general(&self, vector: TestVector) -> bool {
         match self.general.push(vector) {
             Ok(()) => {
-                self.pushed
-                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                self.record_push();
                 true
             }
             Err(_) => false,
@@ -83,8 +165,7 @@ impl VectorPool {
         if let Some(queue) = self.targeted.get(&key) {
             match queue.push(vector) {
                 Ok(()) => {
-                    self.pushed
-                        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    self.record_push();
                     true
                 }
                 Err(rejected) => {
@@ -101,7 +182,10 @@ impl VectorPool {
     pub fn pop_general(&self) -> Option<TestVector> {
         let result = self.general.pop();
         if result.is_some() {
-            self.popped
+            self.record_pop();
+        } else {
+            self.window
+                .starved
                 .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         }
         result
@@ -113,8 +197,7 @@ impl VectorPool {
         let key = CoveragePointKey(target.clone());
         if let Some(queue) = self.targeted.get(&key) {
             if let Some(v) = queue.pop() {
-                self.popped
-                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                self.record_pop();
                 return Some(v);
             }
         }
@@ -174,6 +257,151 @@ impl VectorPool {
     pub fn general_handle(&self) -> Arc<ArrayQueue<TestVector>> {
         Arc::clone(&self.general)
     }
+
+    /// Close the current stats window, returning its traffic, and start
+    /// a new one. Pops through a `general_handle` are not counted.
+    pub fn take_window_stats(&self) -> VectorPoolStats {
+        let take = |counter: &std::sync::atomic::AtomicUsize| {
+            counter.swap(0, std::sync::atomic::Ordering::Relaxed)
+        };
+        VectorPoolStats {
+            pushed: take(&self.window.pushed),
+            popped: take(&self.window.popped),
+            starved: take(&self.window.starved),
+            capacity: self.general.capacity(),
+            len: self.general.len(),
+        }
+    }
+
+    /// Replace the general queue with one of `new_capacity` (at least 1),
+    /// moving the queued vectors across in order. Vectors that do not fit
+    /// are returned. Per-target queues keep their capacity.
+    ///
+    /// Handles from `general_handle` still point at the old queue; take a
+    /// new handle after rebuilding.
+    pub fn rebuild_with_capacity(&mut self, new_capacity: usize) -> Vec<TestVector> {
+        let queue = ArrayQueue::new(new_capacity.max(1));
+        let mut overflow = Vec::new();
+        while let Some(vector) = self.general.pop() {
+            if let Err(rejected) = queue.push(vector) {
+                overflow.push(rejected);
+            }
+        }
+        self.general = Arc::new(queue);
+        overflow
+    }
+
+    fn record_push(&self) {
+        self.pushed
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.window
+            .pushed
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn record_pop(&self) {
+        self.popped
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.window
+            .popped
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// A lockfree pool that hands out higher-priority vectors first.
+///
+/// Each priority band is its own `ArrayQueue`, FIFO and lock-free; `pop`
+/// scans from the highest band down and takes from the first non-empty
+/// one. Use `VectorPool` when draw order doesn't matter.
+#[derive(Debug)]
+pub struct PriorityVectorPool {
+    /// Bands indexed by priority; the last band is drained first.
+    bands: Vec<ArrayQueue<TestVector>>,
+    /// Stats: total vectors pushed.
+    pushed: std::sync::atomic::AtomicUsize,
+    /// Stats: total vectors popped.
+    popped: std::sync::atomic::AtomicUsize,
+}
+
+impl PriorityVectorPool {
+    /// Create a pool with `bands` priority levels (at least one), each
+    /// holding up to `band_capacity` vectors.
+    pub fn new(bands: usize, band_capacity: usize) -> Self {
+        Self {
+            bands: (0..bands.max(1))
+                .map(|_| ArrayQueue::new(band_capacity))
+                .collect(),
+            pushed: std::sync::atomic::AtomicUsize::new(0),
+            popped: std::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    /// Create a pool with default band count and capacity.
+    pub fn with_defaults() -> Self {
+        Self::new(DEFAULT_PRIORITY_BANDS, DEFAULT_QUEUE_CAPACITY * 4)
+    }
+
+    /// Highest priority accepted; larger priorities are clamped to it.
+    pub fn max_priority(&self) -> usize {
+        self.bands.len() - 1
+    }
+
+    /// Push a vector at `priority` (0 = lowest).
+    /// Returns false if that band is full.
+    pub fn push(&self, priority: usize, vector: TestVector) -> bool {
+        let band = &self.bands[priority.min(self.max_priority())];
+        match band.push(vector) {
+            Ok(()) => {
+                self.pushed
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Push a vector at the highest priority if it covers any of
+    /// `uncovered`, at the lowest otherwise.
+    pub fn push_by_coverage(&self, vector: TestVector, uncovered: &[CoveragePoint]) -> bool {
+        let covers = !check_coverage(std::slice::from_ref(&vector), uncovered).is_empty();
+        let priority = if covers { self.max_priority() } else { 0 };
+        self.push(priority, vector)
+    }
+
+    /// Pop a vector from the highest-priority non-empty band.
+    pub fn pop(&self) -> Option<TestVector> {
+        let result = self.bands.iter().rev().find_map(|band| band.pop());
+        if result.is_some() {
+            self.popped
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// Get the number of vectors in a priority band.
+    pub fn band_len(&self, priority: usize) -> usize {
+        self.bands.get(priority).map(|b| b.len()).unwrap_or(0)
+    }
+
+    /// Get the number of vectors across all bands.
+    pub fn len(&self) -> usize {
+        self.bands.iter().map(|b| b.len()).sum()
+    }
+
+    /// Check if every band is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bands.iter().all(|b| b.is_empty())
+    }
+
+    /// Get total vectors pushed (cumulative).
+    pub fn total_pushed(&self) -> usize {
+        self.pushed.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Get total vectors popped (cumulative).
+    pub fn total_popped(&self) -> usize {
+        self.popped.load(std::sync::atomic::Ordering::Relaxed)
+    }
 }
 
 #[cfg(test)]
@@ -286,6 +514,35 @@ mod tests {
         assert_eq!(pool.total_popped(), 1);
     }
 
+    #[test]
+    fn test_high_pop_pressure_recommends_larger_capacity() {
+        let mut pool = VectorPool::new(8);
+        for round in 0..3 {
+            for i in 0..8 {
+                assert!(pool.push_general(make_vector(&format!("r{round}_{i}"), true)));
+            }
+            while pool.pop_general().is_some() {}
+        }
+        let stats = pool.take_window_stats();
+        assert_eq!((stats.pushed, stats.popped), (24, 24));
+        assert_eq!(stats.starved, 3);
+        assert_eq!(stats.consumption_rate(), 1.0);
+        assert!(stats.recommend_capacity() > pool.general_capacity());
+
+        // The next window starts from zero.
+        assert_eq!(pool.take_window_stats().pushed, 0);
+
+        pool.push_many(vec![
+            make_vector("admin", true),
+            make_vector("guest", false),
+        ]);
+        let overflow = pool.rebuild_with_capacity(stats.recommend_capacity());
+        assert!(overflow.is_empty());
+        assert_eq!(pool.general_capacity(), 16);
+        assert_eq!(pool.pop_general(), Some(make_vector("admin", true)));
+        assert_eq!(pool.general_len(), 1);
+    }
+
     #[test]
     fn test_concurrent_pop() {
         let pool = VectorPool::new(100);
@@ -368,4 +625,66 @@ mod tests {
         // At least 48 consumed (4 threads x 12).
         assert!(total >= 48);
     }
+
+    #[test]
+    fn test_priority_pool_pops_by_coverage_band() {
+        let target = CoveragePoint::Boundary {
+            var: "role".into(),
+            value: DomainValue::Enum("admin".into()),
+        };
+        let pool = PriorityVectorPool::new(2, 8);
+
+        assert!(pool.push_by_coverage(make_vector("guest", true), std::slice::from_ref(&target)));
+        assert!(pool.push_by_coverage(make_vector("admin", true), &[target]));
+        assert_eq!(pool.band_len(0), 1);
+        assert_eq!(pool.band_len(1), 1);
+
+        assert_eq!(pool.pop(), Some(make_vector("admin", true)));
+        assert_eq!(pool.pop(), Some(make_vector("guest", true)));
+        assert!(pool.pop().is_none());
+        assert_eq!(pool.total_popped(), 2);
+    }
+
+    #[test]
+    fn test_priority_pool_drains_high_before_low_under_contention() {
+        let pool = Arc::new(PriorityVectorPool::new(3, 100));
+        for i in 0..40 {
+            let (priority, role) = if i % 2 == 0 {
+                (2, format!("high_{i}"))
+            } else {
+                (0, format!("low_{i}"))
+            };
+            assert!(pool.push(priority, make_vector(&role, true)));
+        }
+
+        let consumers: Vec<_> = (0..4)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || {
+                    let mut drawn = Vec::new();
+                    while let Some(v) = pool.pop() {
+                        let DomainValue::Enum(role) = &v.assignments["role"] else {
+                            unreachable!("role is an enum value");
+                        };
+                        drawn.push(role.starts_with("high"));
+                    }
+                    drawn
+                })
+            })
+            .collect();
+
+        let mut total = 0;
+        for consumer in consumers {
+            let drawn = consumer.join().unwrap();
+            // Nothing is pushed while draining, so once any thread sees the
+            // high band empty it stays empty: no thread draws high after low.
+            assert!(
+                drawn.windows(2).all(|w| w[0] || !w[1]),
+                "low-priority vector drawn before a high-priority one: {drawn:?}"
+            );
+            total += drawn.len();
+        }
+        assert_eq!(total, 40);
+        assert!(pool.is_empty());
+    }
 }
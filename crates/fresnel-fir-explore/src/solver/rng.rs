@@ -1,15 +1,64 @@
-//! Per-stage RNG seeding with ChaCha8.
+//! Reproducible RNG streams with ChaCha8.
 //!
-//! Each fracture stage gets its own ChaCha8Rng seeded from
-//! `(global_seed + stage_id)`. Same seed -> same vectors, always.
+//! Every random choice in a run draws from a stream derived from the
+//! run's master seed by `RngStreams`: fracture stages are keyed by
+//! `stage_id`, traversal threads by `thread_id`. A stream depends only on
+//! the seed and its key, never on which worker asks for it or when, so
+//! results do not change with the thread count or scheduling. Do not seed
+//! RNGs any other way; `clippy.toml` rejects entropy-seeded ones and
+//! `seed_from_u64`.
 
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
 
+/// Key space of stage streams.
+const STAGE_DOMAIN: u64 = 0;
+/// Key space of thread streams.
+const THREAD_DOMAIN: u64 = 1;
+
+/// Derives independent child RNG streams from one master seed.
+///
+/// The master seed and the key space go into the ChaCha key and the
+/// stage or thread id selects the ChaCha stream, so no two
+/// `(seed, kind, id)` triples share a stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RngStreams {
+    master_seed: u64,
+}
+
+impl RngStreams {
+    pub fn new(master_seed: u64) -> Self {
+        Self { master_seed }
+    }
+
+    pub fn master_seed(&self) -> u64 {
+        self.master_seed
+    }
+
+    /// Stream for the fracture stage `stage_id`.
+    pub fn stage(&self, stage_id: u64) -> ChaCha8Rng {
+        self.stream(STAGE_DOMAIN, stage_id)
+    }
+
+    /// Stream for the traversal thread `thread_id`.
+    pub fn thread(&self, thread_id: u32) -> ChaCha8Rng {
+        self.stream(THREAD_DOMAIN, u64::from(thread_id))
+    }
+
+    fn stream(&self, domain: u64, id: u64) -> ChaCha8Rng {
+        let mut key = [0u8; 32];
+        key[..8].copy_from_slice(&self.master_seed.to_le_bytes());
+        key[8..16].copy_from_slice(&domain.to_le_bytes());
+        let mut rng = ChaCha8Rng::from_seed(key);
+        rng.set_stream(id);
+        rng
+    }
+}
+
 /// Create a deterministic RNG for a given global seed and stage ID.
+/// Shorthand for `RngStreams::new(global_seed).stage(stage_id)`.
 pub fn stage_rng(global_seed: u64, stage_id: u64) -> ChaCha8Rng {
-    let combined = global_seed.wrapping_add(stage_id);
-    ChaCha8Rng::seed_from_u64(combined)
+    RngStreams::new(global_seed).stage(stage_id)
 }
 
 #[cfg(test)]
@@ -49,4 +98,39 @@ mod tests {
 
         assert_ne!(val1, val2);
     }
+
+    #[test]
+    fn test_stage_and_thread_streams_are_distinct() {
+        let streams = RngStreams::new(42);
+        let stage: u64 = streams.stage(1).gen();
+        let thread: u64 = streams.thread(1).gen();
+        let next_seed: u64 = RngStreams::new(43).stage(0).gen();
+
+        assert_ne!(stage, thread);
+        assert_ne!(stage, next_seed);
+    }
+
+    #[test]
+    fn test_stage_streams_independent_of_thread_count() {
+        use rayon::prelude::*;
+
+        let streams = RngStreams::new(7);
+        let draw = |threads: usize| -> Vec<Vec<u64>> {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| {
+                    (0..64u64)
+                        .into_par_iter()
+                        .map(|stage_id| {
+                            let mut rng = streams.stage(stage_id);
+                            (0..4).map(|_| rng.gen()).collect()
+                        })
+                        .collect()
+                })
+        };
+
+        assert_eq!(draw(1), draw(4));
+    }
 }
@@ -2,16 +2,24 @@
 //!
 //! Given encoded domains + constraints, finds satisfying test vectors.
 //! Supports finding a single solution, multiple unique solutions,
-//! and bounded search with a maximum count.
+//! and bounded search with a maximum count or wall-clock timeout.
 
-use std::collections::HashSet;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 
-use varisat::{solver::Solver, ExtendFormula, Lit, Var};
+use rayon::prelude::*;
+use varisat::solver::{Solver, SolverError};
+use varisat::{ExtendFormula, Lit, ProofFormat, Var};
 
 use super::constraint::{encode_constraints, CnfClauses};
-use super::domain::{decode_model, EncodedInputSpace, Encoding};
-use super::TestVector;
-use fresnel_fir_ir::types::InputSpace;
+use super::domain::{decode_model, lit_for_value, EncodedInputSpace, Encoding};
+use super::{DomainValue, TestVector};
+use fresnel_fir_compiler::compile::{compile_with_input_check, CompileError, CompiledIR};
+use fresnel_fir_ir::types::{FresnelFirIR, InputConstraint, InputSpace};
 
 /// Errors during search.
 #[derive(Debug, thiserror::Error)]
@@ -24,6 +32,11 @@ pub enum SearchError {
 
     #[error("solver error: {0}")]
     Solver(String),
+
+    /// The search ran past its timeout. `partial` holds the vectors found
+    /// before the deadline (always empty for single-solution queries).
+    #[error("solver timed out ({} vectors found before the deadline)", .partial.len())]
+    Timeout { partial: Vec<TestVector> },
 }
 
 /// Result of a satisfiability check.
@@ -43,6 +56,78 @@ pub struct SearchConfig {
     /// Additional clauses to add beyond structural + constraint clauses.
     /// Used by fracture to fix variables.
     pub extra_clauses: CnfClauses,
+    /// Wall-clock budget for the whole search. `None` = unbounded.
+    pub timeout: Option<Duration>,
+}
+
+/// Solver effort spent by a search.
+///
+/// varisat 0.2 does not expose its conflict or decision counters, so
+/// those stay 0 with the current backend; `solve_calls`, `vectors_found`
+/// and `wall_time` are always measured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Calls to the SAT solver, including the final UNSAT one.
+    pub solve_calls: u64,
+    /// CDCL conflicts, where the solver reports them.
+    pub conflicts: u64,
+    /// Branching decisions, where the solver reports them.
+    pub decisions: u64,
+    /// Unique vectors found.
+    pub vectors_found: usize,
+    /// Wall-clock time spent searching.
+    pub wall_time: Duration,
+}
+
+impl SearchStats {
+    /// Add another search's effort to this one.
+    pub fn accumulate(&mut self, other: &SearchStats) {
+        self.solve_calls += other.solve_calls;
+        self.conflicts += other.conflicts;
+        self.decisions += other.decisions;
+        self.vectors_found += other.vectors_found;
+        self.wall_time += other.wall_time;
+    }
+}
+
+/// A solve deadline shared with the solver's proof sink.
+type Deadline = Rc<Cell<Option<Instant>>>;
+
+fn deadline_passed(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|d| Instant::now() >= d)
+}
+
+/// Proof sink that fails once the deadline has passed.
+///
+/// varisat has no public interrupt hook, but `solve()` stops as soon as
+/// writing its proof fails, and proof steps are emitted throughout the
+/// CDCL search. Failing the write therefore interrupts a long solve.
+/// The solver is unusable afterwards and must be rebuilt.
+struct DeadlineSink(Deadline);
+
+impl io::Write for DeadlineSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if deadline_passed(self.0.get()) {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "solver deadline exceeded",
+            ));
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Map a solve error, reporting deadline interrupts as timeouts.
+fn solve_error(e: SolverError, deadline: Option<Instant>, partial: Vec<TestVector>) -> SearchError {
+    if deadline_passed(deadline) {
+        SearchError::Timeout { partial }
+    } else {
+        SearchError::Solver(e.to_string())
+    }
 }
 
 /// Collect all SAT variables used in the encoding.
@@ -51,11 +136,12 @@ fn all_domain_vars(encoded: &EncodedInputSpace) -> Vec<Var> {
     for enc in encoded.domains.values() {
         match &enc.encoding {
             Encoding::Bool { var } => vars.push(*var),
-            Encoding::OneHot { variants } => {
+            Encoding::OneHot { variants, .. } => {
                 for (_, var) in variants {
                     vars.push(*var);
                 }
             }
+            Encoding::Order { thresholds, .. } => vars.extend(thresholds),
         }
     }
     vars
@@ -77,13 +163,20 @@ fn domain_blocking_clause(encoded: &EncodedInputSpace, model: &[Lit]) -> Vec<Lit
 }
 
 /// Initialize a solver with all domain variables registered and all clauses added.
+/// With a `deadline`, solving is interrupted once it passes.
 fn init_solver<'a>(
     encoded: &EncodedInputSpace,
     constraint_clauses: &CnfClauses,
     extra_clauses: &CnfClauses,
+    deadline: Option<Deadline>,
 ) -> Solver<'a> {
     let mut solver = Solver::new();
 
+    // The proof sink must be installed before any clause is added.
+    if let Some(deadline) = deadline {
+        solver.write_proof(DeadlineSink(deadline), ProofFormat::Varisat);
+    }
+
     // Ensure all domain variables are known to the solver by adding
     // tautological clauses [v, !v] for each variable. This guarantees
     // the solver tracks all variables even if no real clause mentions them.
@@ -109,13 +202,313 @@ fn init_solver<'a>(
     solver
 }
 
+/// Number of clauses `init_solver` inserts into a fresh solver, computed
+/// from the encoding rather than counted on a live solver.
+pub fn fresh_clause_count(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    extra_clauses: &CnfClauses,
+) -> usize {
+    all_domain_vars(encoded).len()
+        + encoded.structural_clauses.len()
+        + constraint_clauses.len()
+        + extra_clauses.len()
+}
+
+/// Render the structural, constraint and extra clauses as DIMACS CNF.
+///
+/// A leading comment block maps each domain variable (1-indexed) to its
+/// `domain` or `domain=value` label; auxiliary Tseitin variables are
+/// counted in the header but not labelled.
+pub fn to_dimacs(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    extra_clauses: &CnfClauses,
+) -> String {
+    let clauses: Vec<&Vec<Lit>> = encoded
+        .structural_clauses
+        .iter()
+        .chain(constraint_clauses)
+        .chain(extra_clauses)
+        .collect();
+    let var_count = clauses
+        .iter()
+        .flat_map(|clause| clause.iter())
+        .map(|lit| lit.var().index() + 1)
+        .fold(encoded.next_var, usize::max);
+
+    let mut out = String::new();
+    for (name, enc) in &encoded.domains {
+        match &enc.encoding {
+            Encoding::Bool { var } => {
+                out.push_str(&format!("c {} {name}\n", var.to_dimacs()));
+            }
+            Encoding::OneHot { variants, .. } => {
+                for (label, var) in variants {
+                    out.push_str(&format!("c {} {name}={label}\n", var.to_dimacs()));
+                }
+            }
+            Encoding::Order { labels, thresholds } => {
+                for (label, var) in labels[1..].iter().zip(thresholds) {
+                    out.push_str(&format!("c {} {name}>={label}\n", var.to_dimacs()));
+                }
+            }
+        }
+    }
+    out.push_str(&format!("p cnf {var_count} {}\n", clauses.len()));
+    for clause in clauses {
+        for lit in clause {
+            out.push_str(&format!("{} ", lit.to_dimacs()));
+        }
+        out.push_str("0\n");
+    }
+    out
+}
+
+/// A reusable solver holding the structural and constraint clauses once.
+///
+/// Subspaces are solved under assumptions (e.g. fracture fixings) instead
+/// of rebuilding a fresh solver per query. Blocking clauses added while
+/// enumerating are guarded by a per-call selector literal, so they never
+/// leak into later queries.
+pub struct SolverContext<'e> {
+    encoded: &'e EncodedInputSpace,
+    constraint_clauses: &'e CnfClauses,
+    solver: Solver<'static>,
+    clause_insertions: usize,
+    timeout: Option<Duration>,
+    deadline: Option<Deadline>,
+}
+
+impl<'e> SolverContext<'e> {
+    /// Build a context from the encoded space and its constraint clauses.
+    pub fn new(encoded: &'e EncodedInputSpace, constraint_clauses: &'e CnfClauses) -> Self {
+        Self::with_timeout(encoded, constraint_clauses, None)
+    }
+
+    /// Like `new`, but each query fails with `SearchError::Timeout` once
+    /// it runs longer than `timeout`. The underlying solver is rebuilt
+    /// after a timeout, so the context stays usable.
+    pub fn with_timeout(
+        encoded: &'e EncodedInputSpace,
+        constraint_clauses: &'e CnfClauses,
+        timeout: Option<Duration>,
+    ) -> Self {
+        let deadline = timeout.map(|_| Rc::new(Cell::new(None)));
+        Self {
+            encoded,
+            constraint_clauses,
+            solver: init_solver(encoded, constraint_clauses, &vec![], deadline.clone()),
+            clause_insertions: fresh_clause_count(encoded, constraint_clauses, &vec![]),
+            timeout,
+            deadline,
+        }
+    }
+
+    /// Start the clock for one query; returns its deadline.
+    fn arm(&mut self) -> Option<Instant> {
+        let deadline = self.timeout.map(|t| Instant::now() + t);
+        if let Some(cell) = &self.deadline {
+            cell.set(deadline);
+        }
+        deadline
+    }
+
+    /// Replace the solver after an interrupted solve left it unusable.
+    fn rebuild(&mut self) {
+        self.solver = init_solver(
+            self.encoded,
+            self.constraint_clauses,
+            &vec![],
+            self.deadline.clone(),
+        );
+        self.clause_insertions +=
+            fresh_clause_count(self.encoded, self.constraint_clauses, &vec![]);
+    }
+
+    /// Map a solve error, rebuilding the solver if it was interrupted.
+    fn fail(
+        &mut self,
+        e: SolverError,
+        deadline: Option<Instant>,
+        partial: Vec<TestVector>,
+    ) -> SearchError {
+        let err = solve_error(e, deadline, partial);
+        if matches!(err, SearchError::Timeout { .. }) {
+            self.rebuild();
+        }
+        err
+    }
+
+    /// Total clauses inserted into the underlying solver so far.
+    pub fn clause_insertions(&self) -> usize {
+        self.clause_insertions
+    }
+
+    /// Check satisfiability with the given literals assumed true.
+    pub fn is_sat_under(&mut self, assumptions: &[Lit]) -> Result<bool, SearchError> {
+        match self.find_one_under(assumptions)? {
+            SatResult::Sat(_) => Ok(true),
+            SatResult::Unsat => Ok(false),
+        }
+    }
+
+    /// Find a single satisfying assignment with the given literals assumed true.
+    pub fn find_one_under(&mut self, assumptions: &[Lit]) -> Result<SatResult, SearchError> {
+        let deadline = self.arm();
+        if deadline_passed(deadline) {
+            return Err(SearchError::Timeout { partial: vec![] });
+        }
+        self.solver.assume(assumptions);
+        match self.solver.solve() {
+            Ok(true) => {
+                let model = self
+                    .solver
+                    .model()
+                    .ok_or_else(|| SearchError::Solver("SAT but no model returned".to_string()))?;
+                let assignments = decode_model(self.encoded, &model);
+                Ok(SatResult::Sat(TestVector { assignments }))
+            }
+            Ok(false) => Ok(SatResult::Unsat),
+            Err(e) => Err(self.fail(e, deadline, vec![])),
+        }
+    }
+
+    /// Find multiple unique assignments with the given literals assumed true.
+    ///
+    /// Same semantics as [`find_many`]. Blocking clauses are conditioned on
+    /// a fresh selector variable which is disabled once the search ends.
+    pub fn find_many_under(
+        &mut self,
+        assumptions: &[Lit],
+        max_vectors: usize,
+    ) -> Result<Vec<TestVector>, SearchError> {
+        self.find_many_under_counted(assumptions, max_vectors, &mut SearchStats::default())
+    }
+
+    /// `find_many_under`, accumulating solver effort into `stats` (also
+    /// when the search fails or times out).
+    pub(super) fn find_many_under_counted(
+        &mut self,
+        assumptions: &[Lit],
+        max_vectors: usize,
+        stats: &mut SearchStats,
+    ) -> Result<Vec<TestVector>, SearchError> {
+        let started = Instant::now();
+        let result = self.find_many_under_until(assumptions, max_vectors, stats);
+        stats.wall_time += started.elapsed();
+        result
+    }
+
+    fn find_many_under_until(
+        &mut self,
+        assumptions: &[Lit],
+        max_vectors: usize,
+        stats: &mut SearchStats,
+    ) -> Result<Vec<TestVector>, SearchError> {
+        let deadline = self.arm();
+        let selector = self.solver.new_var();
+        let mut scoped: Vec<Lit> = assumptions.to_vec();
+        scoped.push(selector.positive());
+
+        let mut vectors = Vec::new();
+        let mut seen = HashSet::new();
+
+        loop {
+            if max_vectors > 0 && vectors.len() >= max_vectors {
+                break;
+            }
+            if deadline_passed(deadline) {
+                self.rebuild();
+                return Err(SearchError::Timeout { partial: vectors });
+            }
+
+            self.solver.assume(&scoped);
+            stats.solve_calls += 1;
+            match self.solver.solve() {
+                Ok(true) => {
+                    let model = self.solver.model().ok_or_else(|| {
+                        SearchError::Solver("SAT but no model returned".to_string())
+                    })?;
+                    let vector = TestVector {
+                        assignments: decode_model(self.encoded, &model),
+                    };
+                    if seen.insert(vector.clone()) {
+                        vectors.push(vector);
+                        stats.vectors_found += 1;
+                    }
+
+                    let mut blocking = domain_blocking_clause(self.encoded, &model);
+                    if blocking.is_empty() {
+                        break;
+                    }
+                    blocking.push(selector.negative());
+                    self.solver.add_clause(&blocking);
+                    self.clause_insertions += 1;
+                }
+                Ok(false) => break,
+                Err(e) => return Err(self.fail(e, deadline, vectors)),
+            }
+        }
+
+        // Retire this search's blocking clauses.
+        self.solver.add_clause(&[selector.negative()]);
+        self.clause_insertions += 1;
+
+        // Clauses added outside `solve()` write proof steps too; if the
+        // deadline passed meanwhile, the solver may be poisoned.
+        if deadline_passed(deadline) {
+            self.rebuild();
+        }
+
+        Ok(vectors)
+    }
+}
+
+/// Find a single satisfying assignment on a persistent solver, with the
+/// given literals assumed true.
+///
+/// Equivalent to `find_one` with each assumption added as a unit clause,
+/// but reuses `ctx` instead of building a fresh solver per query.
+pub fn find_one_with_assumptions(
+    ctx: &mut SolverContext<'_>,
+    assumptions: &[Lit],
+) -> Result<SatResult, SearchError> {
+    ctx.find_one_under(assumptions)
+}
+
 /// Find a single satisfying assignment for the given input space.
 pub fn find_one(
     encoded: &EncodedInputSpace,
     constraint_clauses: &CnfClauses,
     extra_clauses: &CnfClauses,
 ) -> Result<SatResult, SearchError> {
-    let mut solver = init_solver(encoded, constraint_clauses, extra_clauses);
+    find_one_until(encoded, constraint_clauses, extra_clauses, None)
+}
+
+/// Like `find_one`, with extra clauses and timeout taken from `config`
+/// (`max_vectors` is ignored). Fails with `SearchError::Timeout` once the
+/// timeout is exceeded.
+pub fn find_one_with_config(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    config: &SearchConfig,
+) -> Result<SatResult, SearchError> {
+    let deadline = config.timeout.map(|t| Instant::now() + t);
+    find_one_until(encoded, constraint_clauses, &config.extra_clauses, deadline)
+}
+
+fn find_one_until(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    extra_clauses: &CnfClauses,
+    deadline: Option<Instant>,
+) -> Result<SatResult, SearchError> {
+    if deadline_passed(deadline) {
+        return Err(SearchError::Timeout { partial: vec![] });
+    }
+    let sink = deadline.map(|d| Rc::new(Cell::new(Some(d))));
+    let mut solver = init_solver(encoded, constraint_clauses, extra_clauses, sink);
 
     match solver.solve() {
         Ok(true) => {
@@ -126,7 +519,7 @@ pub fn find_one(
             Ok(SatResult::Sat(TestVector { assignments }))
         }
         Ok(false) => Ok(SatResult::Unsat),
-        Err(e) => Err(SearchError::Solver(e.to_string())),
+        Err(e) => Err(solve_error(e, deadline, vec![])),
     }
 }
 
@@ -142,16 +535,143 @@ pub fn find_many(
     extra_clauses: &CnfClauses,
     max_vectors: usize,
 ) -> Result<Vec<TestVector>, SearchError> {
-    let mut solver = init_solver(encoded, constraint_clauses, extra_clauses);
+    find_many_until(
+        encoded,
+        constraint_clauses,
+        extra_clauses,
+        max_vectors,
+        None,
+        &mut SearchStats::default(),
+    )
+}
+
+/// Like `find_many`, with all parameters taken from `config`. Once the
+/// timeout is exceeded, fails with `SearchError::Timeout` carrying the
+/// vectors found so far.
+pub fn find_many_with_config(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    config: &SearchConfig,
+) -> Result<Vec<TestVector>, SearchError> {
+    find_many_counted(
+        encoded,
+        constraint_clauses,
+        config,
+        &mut SearchStats::default(),
+    )
+}
+
+/// Like `find_many_with_config`, also reporting the solver effort spent.
+pub fn find_many_with_stats(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    config: &SearchConfig,
+) -> Result<(Vec<TestVector>, SearchStats), SearchError> {
+    let mut stats = SearchStats::default();
+    let vectors = find_many_counted(encoded, constraint_clauses, config, &mut stats)?;
+    Ok((vectors, stats))
+}
+
+/// `find_many_with_config`, accumulating solver effort into `stats`
+/// (also when the search fails or times out).
+pub(super) fn find_many_counted(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    config: &SearchConfig,
+    stats: &mut SearchStats,
+) -> Result<Vec<TestVector>, SearchError> {
+    let started = Instant::now();
+    let deadline = config.timeout.map(|t| started + t);
+    let result = find_many_until(
+        encoded,
+        constraint_clauses,
+        &config.extra_clauses,
+        config.max_vectors,
+        deadline,
+        stats,
+    );
+    stats.wall_time += started.elapsed();
+    result
+}
 
+fn find_many_until(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    extra_clauses: &CnfClauses,
+    max_vectors: usize,
+    deadline: Option<Instant>,
+    stats: &mut SearchStats,
+) -> Result<Vec<TestVector>, SearchError> {
     let mut vectors = Vec::new();
+    let result = for_each_solution_until(
+        encoded,
+        constraint_clauses,
+        extra_clauses,
+        max_vectors,
+        deadline,
+        stats,
+        &mut |vector| {
+            vectors.push(vector);
+            ControlFlow::Continue(())
+        },
+    );
+    match result {
+        Ok(_) => Ok(vectors),
+        Err(SearchError::Timeout { .. }) => Err(SearchError::Timeout { partial: vectors }),
+        Err(e) => Err(e),
+    }
+}
+
+/// Stream unique satisfying assignments to `on_solution` as they are found.
+///
+/// Same enumeration as [`find_many`], without buffering the vectors: the
+/// search stops after `max_vectors` solutions (0 = all), when the space is
+/// exhausted, or as soon as `on_solution` returns `ControlFlow::Break`.
+/// Returns the number of vectors delivered.
+pub fn for_each_solution(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    extra_clauses: &CnfClauses,
+    max_vectors: usize,
+    on_solution: &mut dyn FnMut(TestVector) -> ControlFlow<()>,
+) -> Result<usize, SearchError> {
+    for_each_solution_until(
+        encoded,
+        constraint_clauses,
+        extra_clauses,
+        max_vectors,
+        None,
+        &mut SearchStats::default(),
+        on_solution,
+    )
+}
+
+/// `for_each_solution` with a deadline. A timeout is reported with an
+/// empty `partial`: the vectors found went to `on_solution`.
+fn for_each_solution_until(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    extra_clauses: &CnfClauses,
+    max_vectors: usize,
+    deadline: Option<Instant>,
+    stats: &mut SearchStats,
+    on_solution: &mut dyn FnMut(TestVector) -> ControlFlow<()>,
+) -> Result<usize, SearchError> {
+    let sink = deadline.map(|d| Rc::new(Cell::new(Some(d))));
+    let mut solver = init_solver(encoded, constraint_clauses, extra_clauses, sink);
+
+    let mut delivered = 0;
     let mut seen = HashSet::new();
 
     loop {
-        if max_vectors > 0 && vectors.len() >= max_vectors {
+        if max_vectors > 0 && delivered >= max_vectors {
             break;
         }
+        if deadline_passed(deadline) {
+            return Err(SearchError::Timeout { partial: vec![] });
+        }
 
+        stats.solve_calls += 1;
         match solver.solve() {
             Ok(true) => {
                 let model = solver
@@ -162,7 +682,11 @@ pub fn find_many(
 
                 // Check uniqueness via hash.
                 if seen.insert(vector.clone()) {
-                    vectors.push(vector);
+                    delivered += 1;
+                    stats.vectors_found += 1;
+                    if on_solution(vector).is_break() {
+                        break;
+                    }
                 }
 
                 // Add blocking clause — only for domain-relevant variables.
@@ -173,6 +697,188 @@ pub fn find_many(
                 solver.add_clause(&blocking);
             }
             Ok(false) => break, // UNSAT — no more solutions.
+            Err(e) => return Err(solve_error(e, deadline, vec![])),
+        }
+    }
+
+    Ok(delivered)
+}
+
+/// Find the distinct assignments of the `projection` domains across all
+/// solutions.
+///
+/// After each solution only the projection variables are blocked, so every
+/// projected combination costs one solve rather than one per full vector
+/// that extends it.
+pub fn find_projected(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    extra_clauses: &CnfClauses,
+    projection: &[String],
+) -> Result<Vec<BTreeMap<String, DomainValue>>, SearchError> {
+    let mut projected_vars = HashSet::new();
+    for name in projection {
+        let enc = encoded
+            .domains
+            .get(name)
+            .ok_or_else(|| SearchError::Solver(format!("unknown domain '{name}' in projection")))?;
+        match &enc.encoding {
+            Encoding::Bool { var } => {
+                projected_vars.insert(var.index());
+            }
+            Encoding::OneHot { variants, .. } => {
+                projected_vars.extend(variants.iter().map(|(_, var)| var.index()));
+            }
+            Encoding::Order { thresholds, .. } => {
+                projected_vars.extend(thresholds.iter().map(|var| var.index()));
+            }
+        }
+    }
+
+    let mut solver = init_solver(encoded, constraint_clauses, extra_clauses, None);
+    let mut combinations = Vec::new();
+
+    loop {
+        match solver.solve() {
+            Ok(true) => {
+                let model = solver
+                    .model()
+                    .ok_or_else(|| SearchError::Solver("SAT but no model returned".to_string()))?;
+                let mut assignments = decode_model(encoded, &model);
+                assignments.retain(|name, _| projection.contains(name));
+                combinations.push(assignments);
+
+                let blocking: Vec<Lit> = model
+                    .iter()
+                    .filter(|l| projected_vars.contains(&l.var().index()))
+                    .map(|l| !*l)
+                    .collect();
+                if blocking.is_empty() {
+                    break; // Empty projection: one (empty) combination.
+                }
+                solver.add_clause(&blocking);
+            }
+            Ok(false) => break,
+            Err(e) => return Err(SearchError::Solver(e.to_string())),
+        }
+    }
+
+    Ok(combinations)
+}
+
+/// Find unique satisfying assignments, preferring high-weight values.
+///
+/// Weights are relative to a neutral 1.0: values above it are preferred
+/// (heaviest first), values below it are avoided (lightest first).
+///
+/// varisat 0.2 exposes no phase or polarity API, so the bias is applied
+/// with assumptions instead, like `find_many_warm`'s hint: for each
+/// vector, preferences are assumed one at a time and kept only while the
+/// space stays SAT. A preference that conflicts with the constraints or
+/// with an earlier, heavier preference is dropped for that vector. This
+/// costs one extra solve per preference per vector, but every returned
+/// vector is still a satisfying assignment, and the result is
+/// deterministic for a fixed weight map.
+pub fn find_weighted(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    extra_clauses: &CnfClauses,
+    count: usize,
+    value_weights: &HashMap<(String, DomainValue), f64>,
+) -> Result<Vec<TestVector>, SearchError> {
+    let preferences = weighted_preferences(encoded, value_weights)?;
+    let mut solver = init_solver(encoded, constraint_clauses, extra_clauses, None);
+    let mut vectors = Vec::new();
+
+    while count == 0 || vectors.len() < count {
+        let mut assumptions: Vec<Lit> = Vec::new();
+        for group in &preferences {
+            let kept = assumptions.len();
+            assumptions.extend(group);
+            solver.assume(&assumptions);
+            match solver.solve() {
+                Ok(true) => {}
+                Ok(false) => {
+                    assumptions.truncate(kept);
+                }
+                Err(e) => return Err(SearchError::Solver(e.to_string())),
+            }
+        }
+
+        solver.assume(&assumptions);
+        match solver.solve() {
+            Ok(true) => {
+                let model = solver
+                    .model()
+                    .ok_or_else(|| SearchError::Solver("SAT but no model returned".to_string()))?;
+                vectors.push(TestVector {
+                    assignments: decode_model(encoded, &model),
+                });
+
+                let blocking = domain_blocking_clause(encoded, &model);
+                if blocking.is_empty() {
+                    break;
+                }
+                solver.add_clause(&blocking);
+            }
+            Ok(false) => break,
+            Err(e) => return Err(SearchError::Solver(e.to_string())),
+        }
+    }
+
+    Ok(vectors)
+}
+
+/// Like `find_many`, but starts the search from a previous solution.
+///
+/// varisat 0.2 exposes no phase API, so the `hint` vector's value
+/// literals are assumed for the first solve instead: if the hint is
+/// satisfiable it is returned first, otherwise the search starts cold.
+/// Either way every later solve is unassumed and blocking clauses are
+/// added as usual, so the hint only changes the order of the results.
+/// Hint values that are not in the encoded space are ignored.
+pub fn find_many_warm(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    extra_clauses: &CnfClauses,
+    count: usize,
+    hint: &TestVector,
+) -> Result<Vec<TestVector>, SearchError> {
+    let mut hint_lits: Vec<Lit> = hint
+        .assignments
+        .iter()
+        .filter_map(|(name, value)| lit_for_value(encoded.domains.get(name)?, value))
+        .flatten()
+        .collect();
+    let mut solver = init_solver(encoded, constraint_clauses, extra_clauses, None);
+    let mut vectors = Vec::new();
+
+    while count == 0 || vectors.len() < count {
+        let warm = !hint_lits.is_empty();
+        solver.assume(&hint_lits);
+        // Read the model before resetting assumptions: `assume` clears it.
+        let result = solver.solve().map(|sat| sat.then(|| solver.model()));
+        if warm {
+            hint_lits.clear();
+            solver.assume(&[]);
+        }
+        match result {
+            Ok(Some(model)) => {
+                let model = model
+                    .ok_or_else(|| SearchError::Solver("SAT but no model returned".to_string()))?;
+                vectors.push(TestVector {
+                    assignments: decode_model(encoded, &model),
+                });
+
+                let blocking = domain_blocking_clause(encoded, &model);
+                if blocking.is_empty() {
+                    break;
+                }
+                solver.add_clause(&blocking);
+            }
+            // The hint is unsatisfiable here: retry without it.
+            Ok(None) if warm => {}
+            Ok(None) => break,
             Err(e) => return Err(SearchError::Solver(e.to_string())),
         }
     }
@@ -180,6 +886,45 @@ pub fn find_many(
     Ok(vectors)
 }
 
+/// Turn value weights into an ordered list of literal groups to try
+/// assuming, each group all at once. A preferred value is one group of
+/// all its literals. An avoided value gets one group per negated literal,
+/// since ruling out any of them avoids it.
+fn weighted_preferences(
+    encoded: &EncodedInputSpace,
+    value_weights: &HashMap<(String, DomainValue), f64>,
+) -> Result<Vec<Vec<Lit>>, SearchError> {
+    let mut entries: Vec<(&(String, DomainValue), f64)> = value_weights
+        .iter()
+        .filter(|(_, w)| **w != 1.0)
+        .map(|(k, w)| (k, *w))
+        .collect();
+
+    // Preferred values (heaviest first), then avoided values (lightest first).
+    // Ties break on (domain, value) so HashMap order never leaks through.
+    entries.sort_by(|(ka, wa), (kb, wb)| {
+        let rank = |w: f64| if w > 1.0 { (0, -w) } else { (1, w) };
+        let (ra, xa) = rank(*wa);
+        let (rb, xb) = rank(*wb);
+        ra.cmp(&rb).then(xa.total_cmp(&xb)).then_with(|| ka.cmp(kb))
+    });
+
+    let mut groups = Vec::new();
+    for ((name, value), w) in entries {
+        let enc = encoded.domains.get(name).ok_or_else(|| {
+            SearchError::Solver(format!("unknown domain '{name}' in value weights"))
+        })?;
+        let lits = lit_for_value(enc, value)
+            .ok_or_else(|| SearchError::Solver(format!("no SAT literal for {value} in {name}")))?;
+        if w > 1.0 {
+            groups.push(lits);
+        } else {
+            groups.extend(lits.into_iter().map(|lit| vec![!lit]));
+        }
+    }
+    Ok(groups)
+}
+
 /// Check if the given encoded space (with constraints + extras) is satisfiable.
 pub fn is_sat(
     encoded: &EncodedInputSpace,
@@ -192,21 +937,91 @@ pub fn is_sat(
     }
 }
 
+/// Like `is_sat`, with extra clauses and timeout taken from `config`.
+pub fn is_sat_with_config(
+    encoded: &EncodedInputSpace,
+    constraint_clauses: &CnfClauses,
+    config: &SearchConfig,
+) -> Result<bool, SearchError> {
+    match find_one_with_config(encoded, constraint_clauses, config)? {
+        SatResult::Sat(_) => Ok(true),
+        SatResult::Unsat => Ok(false),
+    }
+}
+
+/// Check each assumption set against a context's constraint base, in
+/// order. Sets are solved in parallel, each worker on its own solver over
+/// the shared clauses, so `ctx` itself is left untouched. The context's
+/// timeout does not apply.
+pub fn is_sat_batch(
+    ctx: &SolverContext<'_>,
+    assumption_sets: &[Vec<Lit>],
+) -> Result<Vec<bool>, SearchError> {
+    // The context holds a non-Send deadline cell; share only its clauses.
+    let (encoded, constraint_clauses) = (ctx.encoded, ctx.constraint_clauses);
+    assumption_sets
+        .par_iter()
+        .map_init(
+            || init_solver(encoded, constraint_clauses, &vec![], None),
+            |solver, assumptions| {
+                solver.assume(assumptions);
+                solver
+                    .solve()
+                    .map_err(|e| SearchError::Solver(e.to_string()))
+            },
+        )
+        .collect()
+}
+
 /// Convenience: encode + find all unique vectors from an InputSpace.
 pub fn solve_input_space(
     input_space: &InputSpace,
     max_vectors: usize,
 ) -> Result<Vec<TestVector>, SearchError> {
-    let encoded = super::domain::encode_input_space(input_space)?;
-    let constraint_clauses = encode_constraints(&input_space.constraints, &encoded)?;
+    let mut encoded = super::domain::encode_input_space(input_space)?;
+    let constraint_clauses = encode_constraints(&input_space.constraints, &mut encoded)?;
     find_many(&encoded, &constraint_clauses, &vec![], max_vectors)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use fresnel_fir_ir::expr::{Expr, Literal, OpKind};
-    use fresnel_fir_ir::types::*;
+/// Names of a minimal set of input constraints that cannot hold together,
+/// or `None` if the constraints are satisfiable.
+///
+/// Found by deletion: each constraint is dropped in turn and left out if
+/// the rest are still UNSAT, so every name in the core is needed.
+pub fn unsat_core(input_space: &InputSpace) -> Result<Option<Vec<String>>, SearchError> {
+    let unsat = |constraints: &[InputConstraint]| -> Result<bool, SearchError> {
+        let mut encoded = super::domain::encode_input_space(input_space)?;
+        let constraint_clauses = encode_constraints(constraints, &mut encoded)?;
+        Ok(!is_sat(&encoded, &constraint_clauses, &Vec::new())?)
+    };
+
+    let mut core = input_space.constraints.clone();
+    if !unsat(&core)? {
+        return Ok(None);
+    }
+    let mut i = 0;
+    while i < core.len() {
+        let removed = core.remove(i);
+        if !unsat(&core)? {
+            core.insert(i, removed);
+            i += 1;
+        }
+    }
+    Ok(Some(core.into_iter().map(|c| c.name).collect()))
+}
+
+/// Compile `ir`, warning with an unsat core when no input vector satisfies
+/// its input constraints. This is the default way to compile; constraints
+/// the encoder cannot handle fail with `CompileError::InputSpace`.
+pub fn compile_checked(ir: &FresnelFirIR) -> Result<CompiledIR, CompileError> {
+    compile_with_input_check(ir, &|inputs| unsat_core(inputs).map_err(|e| e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fresnel_fir_ir::expr::{Expr, Literal, OpKind};
+    use fresnel_fir_ir::types::*;
     use std::collections::HashMap;
 
     use crate::solver::DomainValue;
@@ -218,6 +1033,7 @@ mod tests {
         InputSpace {
             domains,
             constraints,
+            forbidden: Vec::new(),
             coverage: CoverageConfig {
                 targets: vec![],
                 seed: 42,
@@ -266,6 +1082,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -280,6 +1097,33 @@ mod tests {
         assert!(values.contains(&DomainValue::Enum("guest".into())));
     }
 
+    #[test]
+    fn test_find_all_int_with_stride() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "size".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: 0,
+                    max: 32,
+                    stride: 8,
+                },
+            },
+        );
+        let input_space = make_input_space(domains, vec![]);
+        let vectors = solve_input_space(&input_space, 0).unwrap();
+
+        let mut values: Vec<i64> = vectors
+            .iter()
+            .map(|v| match v.assignments["size"] {
+                DomainValue::Int(i) => i,
+                ref other => panic!("expected int, got {other:?}"),
+            })
+            .collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 8, 16, 24, 32]);
+    }
+
     #[test]
     fn test_find_all_with_constraint() {
         // 2 roles x 2 bools = 4, minus guest+true = 3.
@@ -289,6 +1133,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -341,6 +1186,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -356,6 +1202,111 @@ mod tests {
         assert_eq!(vectors.len(), 3);
     }
 
+    #[test]
+    fn test_find_many_with_stats_counts_solve_calls() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        let input_space = make_input_space(domains, vec![]);
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+
+        let (vectors, stats) =
+            find_many_with_stats(&encoded, &constraint_clauses, &SearchConfig::default()).unwrap();
+        assert_eq!(vectors.len(), 3);
+        assert_eq!(stats.vectors_found, 3);
+        // One solve per solution plus the final UNSAT.
+        assert!(
+            stats.solve_calls >= 4,
+            "solve_calls = {}",
+            stats.solve_calls
+        );
+    }
+
+    #[test]
+    fn test_to_dimacs_header_matches_body() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "flag".to_string(),
+            Domain {
+                domain_type: DomainType::Bool,
+            },
+        );
+        let role_is = |value: &str| Expr::Op {
+            op: OpKind::Eq,
+            args: vec![
+                Expr::Literal(Literal::String("role".into())),
+                Expr::Literal(Literal::String(value.into())),
+            ],
+        };
+        // or(and(role == admin, flag), role == guest) needs a Tseitin variable.
+        let constraints = vec![InputConstraint {
+            name: "admin_flagged_or_guest".to_string(),
+            rule: Expr::Op {
+                op: OpKind::Or,
+                args: vec![
+                    Expr::Op {
+                        op: OpKind::And,
+                        args: vec![
+                            role_is("admin"),
+                            Expr::Literal(Literal::String("flag".into())),
+                        ],
+                    },
+                    role_is("guest"),
+                ],
+            },
+        }];
+        let input_space = make_input_space(domains, constraints);
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+        let flag = match &encoded.domains["flag"].encoding {
+            Encoding::Bool { var } => *var,
+            other => panic!("unexpected encoding {other:?}"),
+        };
+        let extra = vec![vec![flag.positive()]];
+
+        let dimacs = to_dimacs(&encoded, &constraint_clauses, &extra);
+
+        let mut lines = dimacs.lines().filter(|l| !l.starts_with('c'));
+        let header: Vec<&str> = lines.next().unwrap().split_whitespace().collect();
+        assert_eq!(header[..2], ["p", "cnf"]);
+        let vars: usize = header[2].parse().unwrap();
+        let clause_count: usize = header[3].parse().unwrap();
+
+        let body: Vec<Vec<isize>> = lines
+            .map(|l| l.split_whitespace().map(|t| t.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(
+            clause_count,
+            encoded.structural_clauses.len() + constraint_clauses.len() + extra.len()
+        );
+        assert_eq!(body.len(), clause_count);
+        assert!(body.iter().all(|clause| clause.last() == Some(&0)));
+
+        let max_var = body.iter().flatten().map(|lit| lit.unsigned_abs()).max();
+        assert_eq!(max_var, Some(vars));
+        assert_eq!(vars, encoded.next_var);
+        assert!(dimacs.contains("role=guest\n"));
+    }
+
     #[test]
     fn test_uniqueness_guaranteed() {
         let mut domains = HashMap::new();
@@ -364,6 +1315,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -392,6 +1344,7 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
@@ -434,8 +1387,9 @@ mod tests {
             },
         );
         let input_space = make_input_space(domains, vec![]);
-        let encoded = super::super::domain::encode_input_space(&input_space).unwrap();
-        let constraint_clauses = encode_constraints(&input_space.constraints, &encoded).unwrap();
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
 
         assert!(is_sat(&encoded, &constraint_clauses, &vec![]).unwrap());
     }
@@ -448,19 +1402,21 @@ mod tests {
             Domain {
                 domain_type: DomainType::Enum {
                     values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
                 },
             },
         );
         let input_space = make_input_space(domains, vec![]);
-        let encoded = super::super::domain::encode_input_space(&input_space).unwrap();
-        let constraint_clauses = encode_constraints(&input_space.constraints, &encoded).unwrap();
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
 
         // Force role = "admin" via extra clause.
         let admin_lit = super::super::domain::lit_for_value(
             &encoded.domains["role"],
             &DomainValue::Enum("admin".into()),
         )
-        .unwrap();
+        .unwrap()[0];
         let extra = vec![vec![admin_lit]];
 
         let result = find_one(&encoded, &constraint_clauses, &extra).unwrap();
@@ -471,4 +1427,453 @@ mod tests {
             SatResult::Unsat => panic!("expected SAT"),
         }
     }
+
+    #[test]
+    fn test_is_sat_batch_matches_individual_fracture_checks() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "auth".to_string(),
+            Domain {
+                domain_type: DomainType::Bool,
+            },
+        );
+        let constraints = vec![InputConstraint {
+            name: "no_guest".to_string(),
+            rule: Expr::Op {
+                op: OpKind::Neq,
+                args: vec![
+                    Expr::Literal(Literal::String("role".into())),
+                    Expr::Literal(Literal::String("guest".into())),
+                ],
+            },
+        }];
+        let input_space = make_input_space(domains, constraints);
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+        let subspaces = crate::solver::fracture::fracture_by_variable(
+            &encoded,
+            "role",
+            &BTreeMap::new(),
+            &vec![],
+            1,
+        )
+        .unwrap();
+
+        // Fixings are unit clauses, so each subspace is one assumption set.
+        let assumption_sets: Vec<Vec<Lit>> = subspaces
+            .iter()
+            .map(|s| s.fixing_clauses.iter().flatten().copied().collect())
+            .collect();
+        let ctx = SolverContext::new(&encoded, &constraint_clauses);
+        let batch = is_sat_batch(&ctx, &assumption_sets).unwrap();
+
+        let individual: Vec<bool> = subspaces
+            .iter()
+            .map(|s| is_sat(&encoded, &constraint_clauses, &s.fixing_clauses).unwrap())
+            .collect();
+        assert_eq!(batch, individual);
+        assert_eq!(batch, [true, true, false]);
+    }
+
+    #[test]
+    fn test_solver_context_under_assumptions() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "auth".to_string(),
+            Domain {
+                domain_type: DomainType::Bool,
+            },
+        );
+        let input_space = make_input_space(domains, vec![]);
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+        let mut ctx = SolverContext::new(&encoded, &constraint_clauses);
+        let base = ctx.clause_insertions();
+
+        let admin = super::super::domain::lit_for_value(
+            &encoded.domains["role"],
+            &DomainValue::Enum("admin".into()),
+        )
+        .unwrap()[0];
+        let guest = super::super::domain::lit_for_value(
+            &encoded.domains["role"],
+            &DomainValue::Enum("guest".into()),
+        )
+        .unwrap()[0];
+
+        assert!(ctx.is_sat_under(&[admin]).unwrap());
+        assert!(!ctx.is_sat_under(&[admin, guest]).unwrap());
+
+        let admins = ctx.find_many_under(&[admin], 0).unwrap();
+        assert_eq!(admins.len(), 2);
+        assert!(admins
+            .iter()
+            .all(|v| v.assignments["role"] == DomainValue::Enum("admin".into())));
+
+        // Blocking clauses from the previous search must not leak.
+        assert_eq!(ctx.find_many_under(&[admin], 0).unwrap().len(), 2);
+        assert_eq!(ctx.find_many_under(&[], 0).unwrap().len(), 6);
+
+        // Only blocking and selector clauses were added after construction.
+        assert_eq!(ctx.clause_insertions() - base, (2 + 1) * 2 + (6 + 1));
+    }
+
+    #[test]
+    fn test_find_weighted_prefers_heavy_value() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "auth".to_string(),
+            Domain {
+                domain_type: DomainType::Bool,
+            },
+        );
+        let input_space = make_input_space(domains, vec![]);
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+
+        let mut weights = HashMap::new();
+        weights.insert(
+            ("role".to_string(), DomainValue::Enum("guest".into())),
+            10.0,
+        );
+
+        let vectors = find_weighted(&encoded, &constraint_clauses, &vec![], 0, &weights).unwrap();
+        assert_eq!(vectors.len(), 6);
+        assert_eq!(
+            vectors[0].assignments["role"],
+            DomainValue::Enum("guest".into())
+        );
+        assert_eq!(
+            vectors[1].assignments["role"],
+            DomainValue::Enum("guest".into())
+        );
+
+        // Same weights, same order.
+        let again = find_weighted(&encoded, &constraint_clauses, &vec![], 0, &weights).unwrap();
+        assert_eq!(vectors, again);
+    }
+
+    #[test]
+    fn test_find_weighted_drops_conflicting_preference() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "auth".to_string(),
+            Domain {
+                domain_type: DomainType::Bool,
+            },
+        );
+        let input_space = make_input_space(domains, vec![]);
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+
+        // `admin` conflicts with the heavier `guest` and is dropped; the
+        // lighter `auth` preference after it still applies.
+        let mut weights = HashMap::new();
+        weights.insert(
+            ("role".to_string(), DomainValue::Enum("guest".into())),
+            10.0,
+        );
+        weights.insert(("role".to_string(), DomainValue::Enum("admin".into())), 5.0);
+        weights.insert(("auth".to_string(), DomainValue::Bool(true)), 2.0);
+
+        let vectors = find_weighted(&encoded, &constraint_clauses, &vec![], 1, &weights).unwrap();
+        assert_eq!(vectors.len(), 1);
+        assert_eq!(
+            vectors[0].assignments["role"],
+            DomainValue::Enum("guest".into())
+        );
+        assert_eq!(vectors[0].assignments["auth"], DomainValue::Bool(true));
+    }
+
+    #[test]
+    fn test_find_weighted_respects_constraints() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        let constraints = vec![InputConstraint {
+            name: "no_guest".to_string(),
+            rule: Expr::Op {
+                op: OpKind::Neq,
+                args: vec![
+                    Expr::Literal(Literal::String("role".into())),
+                    Expr::Literal(Literal::String("guest".into())),
+                ],
+            },
+        }];
+        let input_space = make_input_space(domains, constraints);
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+
+        let mut weights = HashMap::new();
+        weights.insert(
+            ("role".to_string(), DomainValue::Enum("guest".into())),
+            10.0,
+        );
+
+        let vectors = find_weighted(&encoded, &constraint_clauses, &vec![], 1, &weights).unwrap();
+        assert_eq!(vectors.len(), 1);
+        assert_eq!(
+            vectors[0].assignments["role"],
+            DomainValue::Enum("admin".into())
+        );
+    }
+
+    #[test]
+    fn test_find_many_warm_matches_cold_and_leads_with_hint() {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "size".to_string(),
+            Domain {
+                domain_type: DomainType::Int {
+                    min: 0,
+                    max: 32,
+                    stride: 8,
+                },
+            },
+        );
+        let input_space = make_input_space(domains, vec![]);
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraints = encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+
+        let cold = find_many(&encoded, &constraints, &vec![], 0).unwrap();
+        let hint = cold.last().unwrap().clone();
+        let warm = find_many_warm(&encoded, &constraints, &vec![], 0, &hint).unwrap();
+
+        assert_eq!(warm[0], hint);
+        let as_set = |vs: &[TestVector]| vs.iter().cloned().collect::<HashSet<_>>();
+        assert_eq!(warm.len(), cold.len());
+        assert_eq!(as_set(&warm), as_set(&cold));
+
+        // A hint outside the space leaves the results complete.
+        let stray = TestVector {
+            assignments: BTreeMap::from([("role".to_string(), DomainValue::Enum("root".into()))]),
+        };
+        let unhinted = find_many_warm(&encoded, &constraints, &vec![], 0, &stray).unwrap();
+        assert_eq!(as_set(&unhinted), as_set(&cold));
+    }
+
+    fn bool_enum_space() -> (EncodedInputSpace, CnfClauses) {
+        let mut domains = HashMap::new();
+        domains.insert(
+            "role".to_string(),
+            Domain {
+                domain_type: DomainType::Enum {
+                    values: vec!["admin".into(), "member".into(), "guest".into()],
+                    ordered: false,
+                },
+            },
+        );
+        domains.insert(
+            "flag".to_string(),
+            Domain {
+                domain_type: DomainType::Bool,
+            },
+        );
+        let input_space = make_input_space(domains, vec![]);
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraints = encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+        (encoded, constraints)
+    }
+
+    #[test]
+    fn test_for_each_solution_counts_all() {
+        let (encoded, constraints) = bool_enum_space();
+        let mut seen = HashSet::new();
+        let delivered = for_each_solution(&encoded, &constraints, &vec![], 0, &mut |vector| {
+            seen.insert(vector);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        assert_eq!(delivered, 6);
+        assert_eq!(seen.len(), 6);
+        let all = find_many(&encoded, &constraints, &vec![], 0).unwrap();
+        assert_eq!(seen, all.into_iter().collect::<HashSet<_>>());
+    }
+
+    #[test]
+    fn test_for_each_solution_stops_on_break() {
+        let (encoded, constraints) = bool_enum_space();
+        let mut calls = 0;
+        let delivered = for_each_solution(&encoded, &constraints, &vec![], 0, &mut |_| {
+            calls += 1;
+            if calls == 3 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+
+        assert_eq!(calls, 3);
+        assert_eq!(delivered, 3);
+    }
+
+    fn large_int_space() -> InputSpace {
+        let mut domains = HashMap::new();
+        for name in ["x", "y", "z"] {
+            domains.insert(
+                name.to_string(),
+                Domain {
+                    domain_type: DomainType::Int {
+                        min: 0,
+                        max: 99,
+                        stride: 1,
+                    },
+                },
+            );
+        }
+        make_input_space(domains, vec![])
+    }
+
+    #[test]
+    fn test_find_many_timeout_returns_partial() {
+        let input_space = large_int_space();
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+
+        // 10^6 solutions cannot be enumerated in 50ms.
+        let config = SearchConfig {
+            max_vectors: 0,
+            extra_clauses: vec![],
+            timeout: Some(Duration::from_millis(50)),
+        };
+        let started = Instant::now();
+        let result = find_many_with_config(&encoded, &constraint_clauses, &config);
+
+        assert!(started.elapsed() < Duration::from_secs(10));
+        match result {
+            Err(SearchError::Timeout { partial }) => {
+                assert!(partial.len() < 1_000_000);
+                let unique: HashSet<_> = partial.iter().collect();
+                assert_eq!(unique.len(), partial.len());
+            }
+            other => panic!("expected timeout, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_zero_timeout_is_sat_times_out() {
+        let input_space = large_int_space();
+        let mut encoded = super::super::domain::encode_input_space(&input_space).unwrap();
+        let constraint_clauses =
+            encode_constraints(&input_space.constraints, &mut encoded).unwrap();
+
+        let config = SearchConfig {
+            timeout: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        assert!(matches!(
+            is_sat_with_config(&encoded, &constraint_clauses, &config),
+            Err(SearchError::Timeout { .. })
+        ));
+
+        // Without a timeout the same space is SAT.
+        let unbounded = SearchConfig::default();
+        assert!(is_sat_with_config(&encoded, &constraint_clauses, &unbounded).unwrap());
+    }
+
+    #[test]
+    fn test_compile_checked_warns_on_contradictory_inputs() {
+        use fresnel_fir_compiler::compile::CompileWarning;
+
+        let json = r#"{
+            "entities": {},
+            "refinements": {},
+            "functions": {},
+            "protocols": {},
+            "effects": {},
+            "properties": {},
+            "generators": {},
+            "exploration": { "weights": { "scope": "per_alt_branch_and_model_state", "initial": "from_protocol", "decay": "per_epoch" }, "directives_allowed": [], "adaptation_signals": [], "strategy": { "initial": "pseudo_random_traversal", "fallback": "targeted_on_violation" }, "epoch_size": 100, "coverage_floor_threshold": 0.05, "concurrency": { "mode": "deterministic_interleaving", "threads": 4 } },
+            "inputs": {
+                "domains": {
+                    "role": { "type": "enum", "values": ["admin", "guest"] },
+                    "flag": { "type": "bool" }
+                },
+                "constraints": [
+                    { "name": "flag_set", "rule": "flag" },
+                    { "name": "admin_and_guest", "rule": ["and", ["eq", "role", "admin"], ["eq", "role", "guest"]] }
+                ],
+                "coverage": { "targets": [], "seed": 42, "reproducible": true }
+            },
+            "bindings": { "runtime": "wasm", "entry": "main.wasm", "actions": {}, "event_hooks": { "mode": "function_intercept", "observe": [], "capture": [] } }
+        }"#;
+        let mut ir = fresnel_fir_ir::parse::parse_ir(json).unwrap();
+
+        let compiled = compile_checked(&ir).unwrap();
+        assert_eq!(
+            compiled.warnings,
+            vec![CompileWarning::EmptyInputSpace {
+                unsat_core: vec!["admin_and_guest".to_string()]
+            }]
+        );
+
+        // Dropping the contradiction leaves a satisfiable space.
+        ir.inputs.constraints.retain(|c| c.name == "flag_set");
+        assert!(compile_checked(&ir).unwrap().warnings.is_empty());
+
+        // A constraint the encoder rejects is an error, not a pass.
+        ir.inputs.constraints[0].rule = Expr::Literal(Literal::String("nope".into()));
+        let err = compile_checked(&ir).unwrap_err();
+        assert!(matches!(err, CompileError::InputSpace(ref m) if m.contains("nope")));
+    }
 }
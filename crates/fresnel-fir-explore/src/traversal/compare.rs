@@ -0,0 +1,158 @@
+//! Campaign comparison.
+//!
+//! Diffs two campaign results, e.g. before and after a spec change or
+//! under two strategies. Findings are matched by signature and actions by
+//! name, so neither the order of findings nor the pass they occurred in
+//! affects the diff, and swapping the two runs swaps the `a`/`b` sides.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::runner::CampaignResult;
+
+/// Execution counts of one action in both runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionCoverageDelta {
+    pub action: String,
+    pub count_a: u64,
+    pub count_b: u64,
+}
+
+/// Differences between two campaign results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CampaignDiff {
+    /// Finding signatures reported by `a` but not `b`, sorted.
+    pub only_in_a: Vec<String>,
+    /// Finding signatures reported by `b` but not `a`, sorted.
+    pub only_in_b: Vec<String>,
+    /// Actions whose execution counts differ, sorted by action.
+    pub coverage_delta: Vec<ActionCoverageDelta>,
+    /// `b.total_actions - a.total_actions`.
+    pub total_actions_delta: i64,
+}
+
+impl CampaignDiff {
+    /// Whether the two runs found the same findings and covered the same
+    /// actions equally often.
+    pub fn is_empty(&self) -> bool {
+        self.only_in_a.is_empty()
+            && self.only_in_b.is_empty()
+            && self.coverage_delta.is_empty()
+            && self.total_actions_delta == 0
+    }
+}
+
+/// Diff campaign `a` against campaign `b`.
+pub fn compare_campaigns(a: &CampaignResult, b: &CampaignResult) -> CampaignDiff {
+    let signatures = |result: &CampaignResult| -> BTreeSet<String> {
+        result.findings.iter().map(|f| f.signature()).collect()
+    };
+    let (sigs_a, sigs_b) = (signatures(a), signatures(b));
+
+    let mut counts: BTreeMap<&str, (u64, u64)> = BTreeMap::new();
+    for (action, &count) in &a.coverage.action_counts {
+        counts.entry(action.as_str()).or_default().0 = count;
+    }
+    for (action, &count) in &b.coverage.action_counts {
+        counts.entry(action.as_str()).or_default().1 = count;
+    }
+
+    CampaignDiff {
+        only_in_a: sigs_a.difference(&sigs_b).cloned().collect(),
+        only_in_b: sigs_b.difference(&sigs_a).cloned().collect(),
+        coverage_delta: counts
+            .into_iter()
+            .filter(|(_, (count_a, count_b))| count_a != count_b)
+            .map(|(action, (count_a, count_b))| ActionCoverageDelta {
+                action: action.to_string(),
+                count_a,
+                count_b,
+            })
+            .collect(),
+        total_actions_delta: b.total_actions as i64 - a.total_actions as i64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traversal::engine::CoverageReport;
+    use crate::traversal::runner::StopReason;
+    use crate::traversal::signal::{Finding, Severity, SignalEvent, SignalType};
+    use crate::traversal::weight_table::WeightTable;
+
+    fn crash(id: u64, action: &str, message: &str) -> Finding {
+        Finding {
+            id,
+            signal: SignalEvent {
+                thread_id: 0,
+                local_step: id,
+                model_state_hash: 0,
+                signal_type: SignalType::Crash {
+                    action: action.into(),
+                    message: message.into(),
+                },
+            },
+            trace_indices: vec![],
+            model_generation: 0,
+            severity: Severity::Critical,
+            breaking_trace_index: None,
+            capsule: None,
+            source_span: None,
+        }
+    }
+
+    #[allow(deprecated)]
+    fn result(findings: Vec<Finding>, action_counts: &[(&str, u64)]) -> CampaignResult {
+        let mut coverage = CoverageReport::default();
+        for &(action, count) in action_counts {
+            coverage.action_counts.insert(action.into(), count);
+        }
+        CampaignResult {
+            findings,
+            total_actions: action_counts.iter().map(|(_, count)| count).sum(),
+            passes_completed: 1,
+            unique_nodes_visited: 0,
+            total_guard_failures: 0,
+            coverage,
+            unmet_min_hits: vec![],
+            cancelled: false,
+            stopped_early: false,
+            stop_finding: None,
+            stop_reason: StopReason::MaxPasses,
+            weight_table: WeightTable::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_extra_crash_and_action() {
+        let a = result(
+            vec![crash(0, "read", "trap at 0x10")],
+            &[("create", 3), ("read", 2)],
+        );
+        let b = result(
+            vec![
+                crash(0, "delete", "unreachable"),
+                crash(1, "read", "trap at 0x2f"),
+            ],
+            &[("create", 3), ("read", 2), ("delete", 1)],
+        );
+
+        let diff = compare_campaigns(&a, &b);
+        assert!(diff.only_in_a.is_empty());
+        assert_eq!(diff.only_in_b, vec!["crash|delete|unreachable".to_string()]);
+        assert_eq!(
+            diff.coverage_delta,
+            vec![ActionCoverageDelta {
+                action: "delete".into(),
+                count_a: 0,
+                count_b: 1,
+            }]
+        );
+        assert_eq!(diff.total_actions_delta, 1);
+
+        let reversed = compare_campaigns(&b, &a);
+        assert_eq!(reversed.only_in_a, diff.only_in_b);
+        assert_eq!(reversed.total_actions_delta, -1);
+        assert!(compare_campaigns(&a, &a).is_empty());
+    }
+}
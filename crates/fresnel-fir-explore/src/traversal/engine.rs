@@ -1,18 +1,27 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 
-use fresnel_fir_compiler::graph::{GraphNode, NdaGraph, NodeId};
-use fresnel_fir_ir::types::FresnelFirIR;
+use fresnel_fir_compiler::graph::{BranchEdge, GraphNode, NdaGraph, NodeId};
+use fresnel_fir_compiler::predicate::CompiledExpr;
+use fresnel_fir_ir::types::{ActionBinding, ArgType, CoverageTarget, FresnelFirIR};
 use fresnel_fir_model::effect::apply_effect;
-use fresnel_fir_model::invariant::{check_invariants, CompiledProperty};
+use fresnel_fir_model::invariant::{check_invariants, CompiledProperty, Violation};
 use fresnel_fir_model::state::{InstanceId, ModelState, Value};
+use fresnel_fir_vif::adapter::WasmArg;
+use serde::{Deserialize, Serialize, Serializer};
 
-use super::signal::{Finding, SignalEvent, SignalType};
+use super::signal::{classify_severity, Finding, ReplayCapsule, SignalEvent, SignalType};
 use super::strategy::StrategyStack;
 use super::trace::{TraceStepKind, TraversalTrace};
 use super::vector_source::VectorSource;
-use super::weight_table::WeightTable;
+use super::weight_table::{WeightTable, ZERO_WEIGHT_EPSILON};
+use crate::adapt::directive::Directive;
 use crate::solver::{DomainValue, TestVector};
 
+/// Binding under which guards see the drawn input vector, e.g.
+/// `["field", "$input", "amount"]`. The `$` keeps it apart from entity
+/// names, so an entity called `input` is never shadowed.
+pub const INPUT_BINDING: &str = "$input";
+
 /// Result of executing a single DUT action.
 #[derive(Debug)]
 pub struct ActionOutcome {
@@ -32,6 +41,12 @@ pub struct ActionOutcome {
 /// and with a real WASM sandbox for production use.
 pub trait ActionExecutor {
     fn execute(&mut self, action: &str, vector: Option<&TestVector>) -> ActionOutcome;
+
+    /// Signals the executor itself detected during the last `execute`
+    /// call. The engine reports each one as a finding. None by default.
+    fn take_signals(&mut self) -> Vec<SignalType> {
+        Vec::new()
+    }
 }
 
 /// Model-only executor — no DUT calls, just returns success.
@@ -49,15 +64,151 @@ impl ActionExecutor for ModelOnlyExecutor {
     }
 }
 
+/// Nondeterminism detector — runs every action `repeats` times against
+/// the inner executor with the same vector.
+///
+/// If the repeats disagree on `return_value` or `trapped`, the DUT is
+/// nondeterministic and a `Discrepancy` signal is raised as a finding.
+/// The first run's outcome is the one returned to the engine.
+///
+/// Actions with an IR effect (a create or a field set) run only once:
+/// repeating them would change DUT state the model never sees, and the
+/// second run would legitimately differ from the first.
+pub struct RepeatExecutor<E: ActionExecutor> {
+    pub inner: E,
+    /// Executions per action (values below 1 are treated as 1).
+    pub repeats: u32,
+    /// Actions that mutate state, never repeated.
+    effectful: HashSet<String>,
+    detected: Vec<SignalType>,
+}
+
+impl<E: ActionExecutor> RepeatExecutor<E> {
+    pub fn new(inner: E, repeats: u32, ir: &FresnelFirIR) -> Self {
+        let effectful = ir
+            .effects
+            .iter()
+            .filter(|(_, effect)| effect.creates.is_some() || !effect.sets.is_empty())
+            .map(|(action, _)| action.clone())
+            .collect();
+        Self {
+            inner,
+            repeats,
+            effectful,
+            detected: Vec::new(),
+        }
+    }
+}
+
+/// One-line description of a finding's signal for its replay capsule.
+fn describe_signal(signal_type: &SignalType) -> String {
+    match signal_type {
+        SignalType::Crash { action, message } => format!("crash in {action}: {message}"),
+        SignalType::PropertyViolation { property, details } => {
+            format!("property {property} violated: {details}")
+        }
+        SignalType::Discrepancy {
+            action,
+            model_value,
+            observed_value,
+        } => format!("discrepancy in {action}: model {model_value}, observed {observed_value}"),
+        other => format!("{other:?}"),
+    }
+}
+
+fn describe_outcome(outcome: &ActionOutcome) -> String {
+    format!(
+        "return_value={:?} trapped={}",
+        outcome.return_value, outcome.trapped
+    )
+}
+
+impl<E: ActionExecutor> ActionExecutor for RepeatExecutor<E> {
+    fn execute(&mut self, action: &str, vector: Option<&TestVector>) -> ActionOutcome {
+        let first = self.inner.execute(action, vector);
+        self.detected.extend(self.inner.take_signals());
+        if self.effectful.contains(action) {
+            return first;
+        }
+        for _ in 1..self.repeats.max(1) {
+            let again = self.inner.execute(action, vector);
+            self.detected.extend(self.inner.take_signals());
+            if again.return_value != first.return_value || again.trapped != first.trapped {
+                self.detected.push(SignalType::Discrepancy {
+                    action: action.to_string(),
+                    model_value: describe_outcome(&first),
+                    observed_value: describe_outcome(&again),
+                });
+                break;
+            }
+        }
+        first
+    }
+
+    fn take_signals(&mut self) -> Vec<SignalType> {
+        std::mem::take(&mut self.detected)
+    }
+}
+
 /// Sandbox executor — calls into a real WASM sandbox via the verification adapter.
+///
+/// Each call runs under the action's entry in `fuel_limits`, or under
+/// `default_fuel` (the instance's configured budget unless overridden) when
+/// the action isn't listed, so a runaway action exhausts its own budget.
 pub struct SandboxExecutor<'a> {
     pub instance: &'a mut fresnel_fir_sandbox::sandbox::SandboxInstance,
     pub adapter: &'a fresnel_fir_vif::adapter::VerificationAdapter,
+    pub fuel_limits: HashMap<String, u64>,
+    pub default_fuel: Option<u64>,
+}
+
+impl<'a> SandboxExecutor<'a> {
+    pub fn new(
+        instance: &'a mut fresnel_fir_sandbox::sandbox::SandboxInstance,
+        adapter: &'a fresnel_fir_vif::adapter::VerificationAdapter,
+    ) -> Self {
+        let default_fuel = instance.fuel_budget();
+        Self {
+            instance,
+            adapter,
+            fuel_limits: HashMap::new(),
+            default_fuel,
+        }
+    }
+
+    /// Run `action` under its own fuel budget.
+    pub fn with_fuel_limit(mut self, action: &str, fuel: u64) -> Self {
+        self.fuel_limits.insert(action.to_string(), fuel);
+        self
+    }
+
+    /// Campaign-wide budget for actions without their own limit.
+    pub fn with_default_fuel(mut self, fuel: u64) -> Self {
+        self.default_fuel = Some(fuel);
+        self
+    }
 }
 
 impl<'a> ActionExecutor for SandboxExecutor<'a> {
     fn execute(&mut self, action: &str, vector: Option<&TestVector>) -> ActionOutcome {
-        let args = vector_to_i32_args(vector);
+        if let Some(fuel) = self.fuel_limits.get(action).copied().or(self.default_fuel) {
+            self.instance.set_fuel_budget(fuel);
+        }
+        // Without a binding the adapter reports the missing binding itself.
+        let args = match self.adapter.action_binding(action) {
+            Some(binding) => match vector_to_args(vector, binding) {
+                Ok(args) => args,
+                Err(error) => {
+                    return ActionOutcome {
+                        return_value: None,
+                        trapped: false,
+                        fuel_consumed: None,
+                        error: Some(format!("cannot call '{action}': {error}")),
+                    }
+                }
+            },
+            None => Vec::new(),
+        };
         let result = self.adapter.execute_action(self.instance, action, &args);
         ActionOutcome {
             return_value: result.return_value,
@@ -69,24 +220,66 @@ impl<'a> ActionExecutor for SandboxExecutor<'a> {
 }
 
 /// Result of a single traversal pass through the graph.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TraversalResult {
     pub findings: Vec<Finding>,
     pub signals: Vec<SignalEvent>,
     pub actions_executed: u64,
     pub guards_failed: u64,
+    /// Guards actually evaluated; cached results are not counted.
+    #[serde(default)]
+    pub guard_evaluations: u64,
     pub nodes_visited: u64,
     pub coverage: CoverageReport,
     pub trace: TraversalTrace,
 }
 
+impl TraversalResult {
+    /// Serialize the result to JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserialize a result from JSON.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
 /// Coverage information from a traversal run.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct CoverageReport {
     /// Actions executed and their counts.
+    #[serde(serialize_with = "serialize_sorted")]
     pub action_counts: HashMap<String, u64>,
     /// Branch IDs selected and their counts.
+    #[serde(serialize_with = "serialize_sorted")]
     pub branch_counts: HashMap<String, u64>,
+    /// Actions that were reached and had their guard evaluated, but never
+    /// got past it. Disjoint from `action_counts`: these are gaps in the
+    /// inputs or model state, not in graph reachability.
+    #[serde(default, serialize_with = "serialize_sorted_set")]
+    pub guard_never_passed: HashSet<String>,
+}
+
+/// Serialize a count map in key order, so the same counts always produce
+/// the same JSON.
+fn serialize_sorted<S: Serializer>(
+    counts: &HashMap<String, u64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    counts
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
+/// Serialize a name set in order, like [`serialize_sorted`].
+fn serialize_sorted_set<S: Serializer>(
+    names: &HashSet<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    names.iter().collect::<BTreeSet<_>>().serialize(serializer)
 }
 
 impl CoverageReport {
@@ -97,6 +290,169 @@ impl CoverageReport {
     pub fn total_actions(&self) -> u64 {
         self.action_counts.values().sum()
     }
+
+    /// Add another report's counts into this one.
+    pub fn merge(&mut self, other: &CoverageReport) {
+        for (action, count) in &other.action_counts {
+            *self.action_counts.entry(action.clone()).or_insert(0) += count;
+        }
+        for (branch, count) in &other.branch_counts {
+            *self.branch_counts.entry(branch.clone()).or_insert(0) += count;
+        }
+        // An action blocked in one report but executed in the other did
+        // get past its guard.
+        self.guard_never_passed
+            .extend(other.guard_never_passed.iter().cloned());
+        let action_counts = &self.action_counts;
+        self.guard_never_passed
+            .retain(|action| !action_counts.contains_key(action));
+    }
+
+    /// `MinHits` targets whose action has run fewer than the required
+    /// number of times, in declaration order. Other targets are ignored.
+    pub fn unmet_min_hits(&self, targets: &[CoverageTarget]) -> Vec<UnmetMinHits> {
+        targets
+            .iter()
+            .filter_map(|target| match target {
+                CoverageTarget::MinHits { action, count } => {
+                    let hits = self.action_counts.get(action).copied().unwrap_or(0);
+                    (hits < *count).then(|| UnmetMinHits {
+                        action: action.clone(),
+                        hits,
+                        required: *count,
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Sum several reports, e.g. one per parallel engine.
+    pub fn merge_all(reports: &[CoverageReport]) -> CoverageReport {
+        let mut merged = CoverageReport::default();
+        for report in reports {
+            merged.merge(report);
+        }
+        merged
+    }
+}
+
+/// A `MinHits` coverage target not yet met by a [`CoverageReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmetMinHits {
+    pub action: String,
+    /// Executions recorded so far.
+    pub hits: u64,
+    /// Executions the target asks for.
+    pub required: u64,
+}
+
+/// Nodes and actions covered so far in a campaign, across passes.
+///
+/// Handed to each pass's engine so `CoverageDelta` fires only on the first
+/// coverage in the whole campaign rather than once per pass.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CampaignCoverage {
+    pub nodes: HashSet<NodeId>,
+    pub actions: HashSet<String>,
+}
+
+/// Order in which the engine's object stack yields pending nodes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraversalOrder {
+    /// LIFO: follow one path to its end before backtracking.
+    #[default]
+    DepthFirst,
+    /// FIFO: explore successors level by level.
+    BreadthFirst,
+}
+
+/// Progress of one traversal pass, detached from the engine.
+///
+/// Lets a pass be advanced in slices by successive engines, e.g. when
+/// several logical threads interleave over one shared model.
+#[derive(Debug)]
+pub struct PassState {
+    object_stack: VecDeque<NodeId>,
+    trace: TraversalTrace,
+    signals: Vec<SignalEvent>,
+    findings: Vec<Finding>,
+    coverage: CoverageReport,
+    visited_nodes: HashSet<NodeId>,
+    step_counter: u64,
+    finding_counter: u64,
+    actions_executed: u64,
+    guards_failed: u64,
+    guard_evaluations: u64,
+    /// Trace index of the action that broke each currently violated
+    /// invariant.
+    invariant_broken_at: HashMap<String, usize>,
+}
+
+impl PassState {
+    fn new(entry: NodeId) -> Self {
+        Self {
+            object_stack: VecDeque::from([entry]),
+            trace: TraversalTrace::new(),
+            signals: Vec::new(),
+            findings: Vec::new(),
+            coverage: CoverageReport::default(),
+            visited_nodes: HashSet::new(),
+            step_counter: 0,
+            finding_counter: 0,
+            actions_executed: 0,
+            guards_failed: 0,
+            guard_evaluations: 0,
+            invariant_broken_at: HashMap::new(),
+        }
+    }
+
+    /// Whether the pass has no pending nodes left.
+    pub fn is_finished(&self) -> bool {
+        self.object_stack.is_empty()
+    }
+
+    /// The trace recorded so far.
+    pub fn trace(&self) -> &TraversalTrace {
+        &self.trace
+    }
+
+    /// Close the pass and collect its results.
+    pub fn into_result(self) -> TraversalResult {
+        TraversalResult {
+            findings: self.findings,
+            signals: self.signals,
+            actions_executed: self.actions_executed,
+            guards_failed: self.guards_failed,
+            guard_evaluations: self.guard_evaluations,
+            nodes_visited: self.visited_nodes.len() as u64,
+            coverage: self.coverage,
+            trace: self.trace,
+        }
+    }
+}
+
+/// Guard results for the current model generation.
+///
+/// A guard's result depends only on the model, the bound actor and the
+/// drawn inputs, and every model mutation bumps the generation, so a result
+/// stays valid until the generation moves on.
+#[derive(Debug, Default)]
+struct GuardCache {
+    generation: u64,
+    /// (guarded node, drawn inputs) -> guard passed.
+    results: HashMap<(NodeId, Option<TestVector>), bool>,
+}
+
+/// Campaign identity stamped into the replay capsule of each finding.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayContext {
+    /// RNG seed of the campaign.
+    pub seed: u64,
+    /// Content hash of the IR under test.
+    pub ir_hash: String,
+    /// Hash of the WASM module under test; empty for model-only runs.
+    pub wasm_hash: String,
 }
 
 /// The traversal engine — walks an NDA graph, executing actions.
@@ -114,15 +470,15 @@ pub struct TraversalEngine<'a, V: VectorSource, E: ActionExecutor> {
     strategy_stack: &'a mut StrategyStack,
     vector_source: &'a mut V,
     weight_table: &'a mut WeightTable,
-    trace: TraversalTrace,
-    signals: Vec<SignalEvent>,
-    findings: Vec<Finding>,
-    coverage: CoverageReport,
-    visited_nodes: std::collections::HashSet<NodeId>,
-    step_counter: u64,
-    finding_counter: u64,
-    actions_executed: u64,
-    guards_failed: u64,
+    order: TraversalOrder,
+    thread_id: u32,
+    vector_retries: u32,
+    /// Campaign-wide coverage; without it a pass counts as a campaign.
+    covered: Option<&'a mut CampaignCoverage>,
+    guard_cache: GuardCache,
+    /// Without it findings carry no replay capsule.
+    replay: Option<ReplayContext>,
+    pass: PassState,
 }
 
 impl<'a, V: VectorSource, E: ActionExecutor> TraversalEngine<'a, V, E> {
@@ -148,84 +504,182 @@ impl<'a, V: VectorSource, E: ActionExecutor> TraversalEngine<'a, V, E> {
             strategy_stack,
             vector_source,
             weight_table,
-            trace: TraversalTrace::new(),
-            signals: Vec::new(),
-            findings: Vec::new(),
-            coverage: CoverageReport::default(),
-            visited_nodes: std::collections::HashSet::new(),
-            step_counter: 0,
-            finding_counter: 0,
-            actions_executed: 0,
-            guards_failed: 0,
+            order: TraversalOrder::default(),
+            thread_id: 0,
+            vector_retries: 0,
+            covered: None,
+            guard_cache: GuardCache::default(),
+            replay: None,
+            pass: PassState::new(graph.entry),
         }
     }
 
+    /// Set the order in which pending nodes are explored (default: depth-first).
+    pub fn with_order(mut self, order: TraversalOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Set the logical thread this engine runs as (default: 0).
+    /// Emitted signals carry it for the coordinator's total ordering.
+    pub fn with_thread_id(mut self, thread_id: u32) -> Self {
+        self.thread_id = thread_id;
+        self
+    }
+
+    /// Draw up to `retries` further vectors when a guard rejects the drawn
+    /// one (default: 0).
+    pub fn with_vector_retries(mut self, retries: u32) -> Self {
+        self.vector_retries = retries;
+        self
+    }
+
+    /// Track coverage in `covered`, shared by every pass of a campaign.
+    pub fn with_campaign_coverage(mut self, covered: &'a mut CampaignCoverage) -> Self {
+        self.covered = Some(covered);
+        self
+    }
+
+    /// Attach a replay capsule built from `context` to every finding.
+    pub fn with_replay_context(mut self, context: ReplayContext) -> Self {
+        self.replay = Some(context);
+        self
+    }
+
+    /// Continue a pass previously detached with `into_pass_state`.
+    pub fn with_pass_state(mut self, pass: PassState) -> Self {
+        self.pass = pass;
+        self
+    }
+
+    /// Detach the pass progress so another engine can continue it.
+    pub fn into_pass_state(self) -> PassState {
+        self.pass
+    }
+
+    /// Hand directives emitted since the previous pass to the current strategy.
+    pub fn with_directives(self, directives: &[Directive]) -> Self {
+        self.strategy_stack.accept_directives(directives);
+        self
+    }
+
     /// Run one traversal pass through the graph (entry to exit).
     ///
-    /// Uses an explicit object stack (not recursion):
+    /// Uses an explicit object stack (not recursion), popped from the back
+    /// for `DepthFirst` or the front for `BreadthFirst`:
     /// - Pop node from stack
     /// - Terminal (call) -> execute action pipeline
     /// - Branch (alt) -> strategy picks a branch, push target
-    /// - LoopEntry -> strategy picks iteration count, push body N times
+    /// - LoopEntry -> strategy picks iteration count within any active
+    ///   loop limit, push body N times
     /// - Start/End -> trace only, push successors
     pub fn run_pass(mut self, max_steps: u64) -> TraversalResult {
-        let mut object_stack: Vec<NodeId> = vec![self.graph.entry];
+        while self.step_action(max_steps).is_some() {}
+        self.pass.into_result()
+    }
 
-        while let Some(node_id) = object_stack.pop() {
-            if self.step_counter >= max_steps {
-                break;
+    /// Advance the pass until one action node has been processed (executed
+    /// or guard-failed) and return its name. Returns `None` once the pass
+    /// is finished or `max_steps` is reached.
+    pub fn step_action(&mut self, max_steps: u64) -> Option<String> {
+        let mut object_stack = std::mem::take(&mut self.pass.object_stack);
+        let stepped = self.advance(&mut object_stack, max_steps);
+        self.pass.object_stack = object_stack;
+        stepped
+    }
+
+    fn advance(&mut self, object_stack: &mut VecDeque<NodeId>, max_steps: u64) -> Option<String> {
+        while let Some(node_id) = match self.order {
+            TraversalOrder::DepthFirst => object_stack.pop_back(),
+            TraversalOrder::BreadthFirst => object_stack.pop_front(),
+        } {
+            if self.pass.step_counter >= max_steps {
+                object_stack.clear();
+                return None;
             }
 
-            self.visited_nodes.insert(node_id);
+            self.pass.visited_nodes.insert(node_id);
+            if let Some(covered) = self.covered.as_deref_mut() {
+                covered.nodes.insert(node_id);
+            }
             let node = self.graph.nodes[node_id as usize].clone();
 
             match node {
                 GraphNode::Start => {
-                    self.trace.record(node_id, TraceStepKind::Start);
-                    self.push_successors(node_id, &mut object_stack);
+                    self.pass.trace.record(node_id, TraceStepKind::Start);
+                    self.push_successors(node_id, object_stack);
                 }
 
                 GraphNode::End => {
-                    self.trace.record(node_id, TraceStepKind::End);
+                    self.pass.trace.record(node_id, TraceStepKind::End);
                 }
 
-                GraphNode::Terminal { action, guard } => {
-                    self.step_counter += 1;
-
-                    // Action pipeline step 1-2: Check guard against model state
-                    let guard_passed = if let Some(ref guard_expr) = guard {
-                        let bindings = self.make_bindings();
-                        matches!(
-                            fresnel_fir_model::eval::eval_in_model(guard_expr, self.model, &bindings),
-                            Ok(Value::Bool(true))
-                        )
-                    } else {
-                        true
-                    };
+                GraphNode::Terminal {
+                    action,
+                    guard,
+                    source_span,
+                } => {
+                    self.pass.step_counter += 1;
+
+                    // Action pipeline step 1-2: Draw an input vector and check
+                    // the guard against model state plus the drawn inputs,
+                    // redrawing up to `vector_retries` times on rejection.
+                    let mut vector = self.vector_source.next_vector(&action);
+                    let mut guard_passed =
+                        self.guard_passes(node_id, guard.as_ref(), vector.as_ref());
+                    let mut retries = 0;
+                    while !guard_passed && vector.is_some() && retries < self.vector_retries {
+                        vector = self.vector_source.next_vector(&action);
+                        guard_passed = self.guard_passes(node_id, guard.as_ref(), vector.as_ref());
+                        retries += 1;
+                    }
 
                     if !guard_passed {
-                        self.guards_failed += 1;
+                        self.pass.guards_failed += 1;
+                        if !self.pass.coverage.action_counts.contains_key(&action) {
+                            self.pass.coverage.guard_never_passed.insert(action.clone());
+                        }
                         let model_state_hash = self.compute_model_state_hash(&[]);
-                        self.trace.record(
+                        self.pass.trace.record_at(
                             node_id,
                             TraceStepKind::GuardFailed {
                                 action: action.clone(),
                             },
+                            source_span,
                         );
                         self.emit_signal(SignalType::GuardFailure {
                             branch_id: String::new(),
-                            action,
+                            action: action.clone(),
                             model_state_hash,
                         });
                         // Push successors so traversal continues past this node
-                        self.push_successors(node_id, &mut object_stack);
-                        continue;
+                        self.push_successors(node_id, object_stack);
+                        return Some(action);
+                    }
+                    if guard.is_some() {
+                        let model_state_hash = self.compute_model_state_hash(&[]);
+                        self.emit_signal(SignalType::GuardPass {
+                            branch_id: String::new(),
+                            action: action.clone(),
+                            model_state_hash,
+                        });
                     }
 
-                    // Step 3: Get input vector
-                    let vector = self.vector_source.next_vector(&action);
+                    // Protocol sequencing: every action the rules require
+                    // before this one must already be in the model trace.
+                    let missing = self.missing_prior_actions(&action);
+                    if !missing.is_empty() {
+                        self.emit_signal(SignalType::PropertyViolation {
+                            property: format!("sequencing:{action}"),
+                            details: format!(
+                                "'{action}' ran before required prior action(s): {}",
+                                missing.join(", ")
+                            ),
+                        });
+                        self.add_finding(node_id, &action, vector.as_ref());
+                    }
 
-                    // Step 4: Execute against DUT (or model-only)
+                    // Step 3-4: Execute against DUT (or model-only)
                     let outcome = self.executor.execute(&action, vector.as_ref());
 
                     // Step 5: Check for traps/crashes
@@ -241,10 +695,14 @@ impl<'a, V: VectorSource, E: ActionExecutor> TraversalEngine<'a, V, E> {
                                     action: action.clone(),
                                     message: err.clone(),
                                 });
-                                self.add_finding();
+                                self.add_finding(node_id, &action, vector.as_ref());
                             }
                         }
                     }
+                    for signal_type in self.executor.take_signals() {
+                        self.emit_signal(signal_type);
+                        self.add_finding(node_id, &action, vector.as_ref());
+                    }
 
                     // Step 6: Apply effects to model state
                     if let Some(effect) = self.ir.effects.get(&action) {
@@ -255,76 +713,129 @@ impl<'a, V: VectorSource, E: ActionExecutor> TraversalEngine<'a, V, E> {
                     self.model.record_action(&action, &[]);
 
                     // Step 7: Check invariants
-                    let violations = check_invariants(self.model, self.invariants);
-                    for violation in &violations {
+                    let violations = self.pin_invariant_violations();
+                    for (violation, breaking_index) in violations {
                         self.emit_signal(SignalType::PropertyViolation {
-                            property: violation.property_name.clone(),
-                            details: violation.message.clone(),
+                            property: violation.property_name,
+                            details: violation.message,
                         });
-                        self.add_finding();
+                        self.add_finding_broken_at(
+                            node_id,
+                            &action,
+                            vector.as_ref(),
+                            Some(breaking_index),
+                        );
                     }
 
                     // Step 8: Coverage tracking
                     *self
+                        .pass
                         .coverage
                         .action_counts
                         .entry(action.clone())
                         .or_insert(0) += 1;
-                    self.actions_executed += 1;
+                    self.pass.coverage.guard_never_passed.remove(&action);
+                    self.pass.actions_executed += 1;
 
                     // Step 9: Coverage delta signal on first hit
-                    if self.coverage.action_counts[&action] == 1 {
+                    let first_hit = match self.covered.as_deref_mut() {
+                        Some(covered) => covered.actions.insert(action.clone()),
+                        None => self.pass.coverage.action_counts[&action] == 1,
+                    };
+                    if first_hit {
                         self.emit_signal(SignalType::CoverageDelta {
                             node_id,
                             action: action.clone(),
                         });
                     }
 
-                    self.trace.record(
+                    self.pass.trace.record_at(
                         node_id,
                         TraceStepKind::ActionExecuted {
-                            action,
+                            action: action.clone(),
                             guard_passed: true,
                             return_value: outcome.return_value,
                             fuel_consumed: outcome.fuel_consumed,
                         },
+                        source_span,
                     );
 
-                    self.push_successors(node_id, &mut object_stack);
+                    self.push_successors(node_id, object_stack);
+                    return Some(action);
                 }
 
-                GraphNode::Branch { alternatives } => {
+                GraphNode::Branch {
+                    alternatives,
+                    source_span,
+                } => {
                     let model_hash = self.compute_model_state_hash(&alternatives);
-                    let decision = self.strategy_stack.current().select_branch(
-                        &alternatives,
+                    let all_zero = alternatives
+                        .iter()
+                        .all(|b| self.weight_table.get(&b.id, model_hash) <= ZERO_WEIGHT_EPSILON);
+                    // With every weight zeroed the strategy picks uniformly,
+                    // so it is only offered branches whose guard holds.
+                    let guarded: Vec<BranchEdge>;
+                    let offered = if all_zero {
+                        guarded = alternatives
+                            .iter()
+                            .filter(|b| b.guard.as_ref().is_none_or(|g| self.eval_guard(g, None)))
+                            .cloned()
+                            .collect();
+                        &guarded
+                    } else {
+                        &alternatives
+                    };
+                    let Some(mut decision) = self.strategy_stack.current().select_branch(
+                        offered,
                         model_hash,
                         self.weight_table,
-                    );
+                    ) else {
+                        // No branch can be taken; the pass ends here.
+                        continue;
+                    };
+                    decision.branch_index = alternatives
+                        .iter()
+                        .position(|b| b.id == decision.branch_id)
+                        .expect("the strategy picks one of the offered branches");
+                    if all_zero {
+                        self.emit_signal(SignalType::ZeroWeightFallback {
+                            node_id,
+                            branch_id: decision.branch_id.clone(),
+                        });
+                    }
 
                     *self
+                        .pass
                         .coverage
                         .branch_counts
                         .entry(decision.branch_id.clone())
                         .or_insert(0) += 1;
 
-                    self.trace.record(
+                    self.pass.trace.record_at(
                         node_id,
                         TraceStepKind::BranchSelected {
                             branch_id: decision.branch_id.clone(),
                             weight_used: decision.weight_used,
+                            roll: decision.roll,
+                            cumulative_weights: decision.cumulative_weights,
                         },
+                        source_span,
                     );
 
                     // Coverage delta if branch target not visited before
                     let target_node = alternatives[decision.branch_index].target;
-                    if !self.visited_nodes.contains(&target_node) {
+                    let first_visit = match self.covered.as_deref() {
+                        Some(covered) => !covered.nodes.contains(&target_node),
+                        None => !self.pass.visited_nodes.contains(&target_node),
+                    };
+                    if first_visit {
                         self.emit_signal(SignalType::CoverageDelta {
                             node_id: target_node,
                             action: decision.branch_id,
                         });
                     }
 
-                    object_stack.push(target_node);
+                    object_stack.push_back(target_node);
                 }
 
                 GraphNode::LoopEntry {
@@ -332,88 +843,255 @@ impl<'a, V: VectorSource, E: ActionExecutor> TraversalEngine<'a, V, E> {
                     min,
                     max,
                 } => {
-                    let decision = self.strategy_stack.current().choose_iterations(min, max);
+                    // Active loop limits narrow the range before the
+                    // strategy sees it.
+                    let bounds = self.strategy_stack.loop_bounds(node_id, min, max);
+                    if bounds.conflict {
+                        let limit_max = self
+                            .strategy_stack
+                            .loop_limit(node_id)
+                            .map_or(max, |(_, limit_max)| limit_max);
+                        self.emit_signal(SignalType::LoopLimitConflict {
+                            loop_node_id: node_id,
+                            declared_min: min,
+                            limit_max,
+                        });
+                    }
+                    let decision = self
+                        .strategy_stack
+                        .current()
+                        .choose_loop_iterations(node_id, bounds.min, bounds.max);
 
-                    self.trace.record(
+                    self.pass.trace.record(
                         node_id,
                         TraceStepKind::LoopEnter {
                             iterations_chosen: decision.iterations,
                         },
                     );
 
-                    // Push loop exit first (processed after all iterations)
-                    self.push_loop_exit_successors(node_id, &mut object_stack);
+                    // The loop exit is processed after all iterations: pushed
+                    // before the body under LIFO, after it under FIFO.
+                    if self.order == TraversalOrder::DepthFirst {
+                        self.push_loop_exit_successors(node_id, object_stack);
+                    }
 
-                    // Push body N times (last iteration pushed first = LIFO)
                     for _ in 0..decision.iterations {
-                        object_stack.push(body_start);
+                        object_stack.push_back(body_start);
+                    }
+
+                    if self.order == TraversalOrder::BreadthFirst {
+                        self.push_loop_exit_successors(node_id, object_stack);
                     }
                 }
 
                 GraphNode::LoopExit => {
-                    self.trace.record(node_id, TraceStepKind::LoopExit);
-                    self.push_successors(node_id, &mut object_stack);
+                    self.pass.trace.record(node_id, TraceStepKind::LoopExit);
+                    self.push_successors(node_id, object_stack);
                 }
             }
         }
 
-        TraversalResult {
-            findings: self.findings,
-            signals: self.signals,
-            actions_executed: self.actions_executed,
-            guards_failed: self.guards_failed,
-            nodes_visited: self.visited_nodes.len() as u64,
-            coverage: self.coverage,
-            trace: self.trace,
-        }
+        None
+    }
+
+    /// Actions named in `requires_prior` rules for `action`, across all
+    /// protocols, that have not yet run according to the model trace.
+    /// Sorted and deduplicated.
+    fn missing_prior_actions(&self, action: &str) -> Vec<String> {
+        let trace = self.model.trace();
+        let mut missing: Vec<String> = self
+            .ir
+            .protocols
+            .values()
+            .filter_map(|protocol| protocol.sequencing.get(action))
+            .flat_map(|rule| &rule.requires_prior)
+            .filter(|prior| !trace.iter().any(|entry| &entry.action == *prior))
+            .cloned()
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
     }
 
     fn emit_signal(&mut self, signal_type: SignalType) {
-        self.signals.push(SignalEvent {
-            thread_id: 0,
-            local_step: self.step_counter,
+        let model_state_hash = self.compute_model_state_hash(&[]);
+        self.pass.signals.push(SignalEvent {
+            thread_id: self.thread_id,
+            local_step: self.pass.step_counter,
+            model_state_hash,
             signal_type,
         });
     }
 
-    fn add_finding(&mut self) {
+    fn add_finding(&mut self, node_id: NodeId, action: &str, vector: Option<&TestVector>) {
+        self.add_finding_broken_at(node_id, action, vector, None);
+    }
+
+    /// Record the latest signal as a finding raised by `action` run with
+    /// `vector` at `node_id`.
+    fn add_finding_broken_at(
+        &mut self,
+        node_id: NodeId,
+        action: &str,
+        vector: Option<&TestVector>,
+        breaking_trace_index: Option<usize>,
+    ) {
+        let signal = self.pass.signals.last().unwrap().clone();
+        let severity = classify_severity(&signal.signal_type);
+        let model_generation = self.model.generation();
+        let capsule = self.replay.as_ref().map(|context| ReplayCapsule {
+            ir_hash: context.ir_hash.clone(),
+            wasm_hash: context.wasm_hash.clone(),
+            seed: context.seed,
+            finding_description: describe_signal(&signal.signal_type),
+            trigger_action: action.to_string(),
+            trace_step: self.pass.step_counter,
+            model_generation,
+            input_vector: vector
+                .map(|v| {
+                    v.assignments
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            severity,
+        });
         let finding = Finding {
-            id: self.finding_counter,
-            signal: self.signals.last().unwrap().clone(),
-            trace_indices: vec![self.trace.len().saturating_sub(1)],
-            model_generation: self.model.generation(),
+            id: self.pass.finding_counter,
+            severity,
+            signal,
+            trace_indices: vec![self.pass.trace.len().saturating_sub(1)],
+            model_generation,
+            breaking_trace_index,
+            capsule,
+            source_span: self.graph.nodes[node_id as usize].source_span().cloned(),
         };
-        self.findings.push(finding);
-        self.finding_counter += 1;
+        self.pass.findings.push(finding);
+        self.pass.finding_counter += 1;
+    }
+
+    /// Check invariants after the action about to be recorded at the end of
+    /// the trace, pairing each violation with the trace index of the action
+    /// that first broke it.
+    ///
+    /// Invariants are checked after every action, so a property is first
+    /// seen violated right after the action that broke it. That index is
+    /// kept until the property holds again, so later violations of the
+    /// same property point back at the breaking action.
+    fn pin_invariant_violations(&mut self) -> Vec<(Violation, usize)> {
+        if self.invariants.is_empty() {
+            return Vec::new();
+        }
+        let current_index = self.pass.trace.len();
+        let violations = check_invariants(self.model, self.invariants);
+        for property in self.invariants {
+            if violations
+                .iter()
+                .all(|violation| violation.property_name != property.name)
+            {
+                self.pass.invariant_broken_at.remove(&property.name);
+            }
+        }
+        violations
+            .into_iter()
+            .map(|violation| {
+                let breaking_index = *self
+                    .pass
+                    .invariant_broken_at
+                    .entry(violation.property_name.clone())
+                    .or_insert(current_index);
+                (violation, breaking_index)
+            })
+            .collect()
     }
 
-    fn push_successors(&self, node_id: NodeId, stack: &mut Vec<NodeId>) {
+    fn push_successors(&self, node_id: NodeId, stack: &mut VecDeque<NodeId>) {
         for &(from, to) in &self.graph.edges {
             if from == node_id {
-                stack.push(to);
+                stack.push_back(to);
             }
         }
     }
 
     /// Push only LoopExit successors from a LoopEntry node.
-    fn push_loop_exit_successors(&self, node_id: NodeId, stack: &mut Vec<NodeId>) {
+    fn push_loop_exit_successors(&self, node_id: NodeId, stack: &mut VecDeque<NodeId>) {
         for &(from, to) in &self.graph.edges {
             if from == node_id && matches!(self.graph.nodes[to as usize], GraphNode::LoopExit) {
-                stack.push(to);
+                stack.push_back(to);
             }
         }
     }
 
-    /// Build variable bindings for guard evaluation.
+    /// Whether the guard of `node_id` passes, reusing the result from an
+    /// earlier evaluation with the same inputs at this model generation.
+    fn guard_passes(
+        &mut self,
+        node_id: NodeId,
+        guard: Option<&CompiledExpr>,
+        vector: Option<&TestVector>,
+    ) -> bool {
+        let Some(guard) = guard else {
+            return true;
+        };
+        let generation = self.model.generation();
+        if self.guard_cache.generation != generation {
+            self.guard_cache.generation = generation;
+            self.guard_cache.results.clear();
+        }
+        let key = (node_id, vector.cloned());
+        if let Some(&passed) = self.guard_cache.results.get(&key) {
+            return passed;
+        }
+
+        let passed = self.eval_guard(guard, vector);
+        self.pass.guard_evaluations += 1;
+        self.guard_cache.results.insert(key, passed);
+        passed
+    }
+
+    /// Evaluate a guard against model state, binding the drawn vector's
+    /// values as fields of [`INPUT_BINDING`].
+    fn eval_guard(&self, guard: &CompiledExpr, vector: Option<&TestVector>) -> bool {
+        let mut bindings = self.make_bindings();
+        let result = match vector {
+            Some(vector) => {
+                // Inputs live on a throwaway fork; forking is copy-on-write.
+                let mut scope = self.model.fork();
+                let input = scope.create_instance(INPUT_BINDING);
+                for (name, value) in &vector.assignments {
+                    let value = match value {
+                        DomainValue::Bool(b) => Value::Bool(*b),
+                        DomainValue::Int(i) => Value::Int(*i),
+                        DomainValue::Enum(s) => Value::String(s.clone()),
+                    };
+                    scope.set_field(&input, name, value);
+                }
+                bindings.insert(INPUT_BINDING.to_string(), input);
+                fresnel_fir_model::eval::eval_in_model(guard, &scope, &bindings)
+            }
+            None => fresnel_fir_model::eval::eval_in_model(guard, self.model, &bindings),
+        };
+        matches!(result, Ok(Value::Bool(true)))
+    }
+
+    /// Build variable bindings for guard evaluation: `actor`, plus each
+    /// name an effect's `creates` assigns, bound to the newest instance
+    /// created under it. Guards reach other instances through quantifiers.
     fn make_bindings(&self) -> HashMap<String, InstanceId> {
         let mut bindings = HashMap::new();
         bindings.insert("actor".to_string(), self.actor_id.clone());
 
-        // Bind "doc" and "self" to the most recently created Document instance
-        let docs = self.model.all_instances("Document");
-        if let Some(last_doc) = docs.last() {
-            bindings.insert("doc".to_string(), last_doc.id.clone());
-            bindings.insert("self".to_string(), last_doc.id.clone());
+        for create in self.ir.effects.values().filter_map(|e| e.creates.as_ref()) {
+            let Some(newest) = self.model.newest_instance(&create.entity) else {
+                continue;
+            };
+            let newer = bindings
+                .get(&create.assign)
+                .is_none_or(|bound| bound.index < newest.id.index);
+            if newer {
+                bindings.insert(create.assign.clone(), newest.id.clone());
+            }
         }
 
         bindings
@@ -421,40 +1099,68 @@ impl<'a, V: VectorSource, E: ActionExecutor> TraversalEngine<'a, V, E> {
 
     /// Compute abstract model state hash for state-conditioned weights.
     /// Uses model generation as a simplified hash.
-    fn compute_model_state_hash(
-        &self,
-        _alternatives: &[fresnel_fir_compiler::graph::BranchEdge],
-    ) -> u64 {
+    fn compute_model_state_hash(&self, _alternatives: &[BranchEdge]) -> u64 {
         self.model.generation()
     }
 }
 
-/// Convert a TestVector to i32 args for WASM function calls.
-fn vector_to_i32_args(vector: Option<&TestVector>) -> Vec<i32> {
-    match vector {
-        Some(v) => v
-            .assignments
-            .values()
-            .map(|dv| match dv {
-                DomainValue::Bool(b) => {
-                    if *b {
-                        1
-                    } else {
-                        0
-                    }
+/// Convert a TestVector to WASM call args for `binding`: one per entry in
+/// the binding's `args`, looked up by name in the vector and typed by
+/// position from `arg_types` (`i32` where undeclared). Without a vector a
+/// single `1` is passed.
+///
+/// Fails if an argument has no value in the vector, is an enum value
+/// (which has no numeric WASM form), or does not fit its declared type.
+fn vector_to_args(
+    vector: Option<&TestVector>,
+    binding: &ActionBinding,
+) -> Result<Vec<WasmArg>, String> {
+    let arg_type = |i: usize| binding.arg_types.get(i).copied().unwrap_or_default();
+    let Some(vector) = vector else {
+        return typed_arg("arg0", 1, arg_type(0)).map(|arg| vec![arg]);
+    };
+    binding
+        .args
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let value = match vector.assignments.get(name) {
+                Some(DomainValue::Bool(b)) => i64::from(*b),
+                Some(DomainValue::Int(v)) => *v,
+                Some(DomainValue::Enum(label)) => {
+                    return Err(format!(
+                        "argument '{name}' is enum value '{label}', which has no WASM form"
+                    ))
                 }
-                DomainValue::Int(i) => *i as i32,
-                DomainValue::Enum(_) => 0,
-            })
-            .collect(),
-        None => vec![1],
+                None => return Err(format!("no input value for argument '{name}'")),
+            };
+            typed_arg(name, value, arg_type(i))
+        })
+        .collect()
+}
+
+fn typed_arg(name: &str, value: i64, arg_type: ArgType) -> Result<WasmArg, String> {
+    match arg_type {
+        ArgType::I32 => i32::try_from(value)
+            .map(WasmArg::I32)
+            .map_err(|_| format!("argument '{name}' = {value} does not fit in i32")),
+        ArgType::I64 => Ok(WasmArg::I64(value)),
+        ArgType::F64 => {
+            let float = value as f64;
+            if float as i64 == value {
+                Ok(WasmArg::F64(float))
+            } else {
+                Err(format!("argument '{name}' = {value} is not exact as f64"))
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::traversal::strategy::PseudoRandomStrategy;
+    use crate::traversal::signal::Severity;
+    use crate::traversal::strategy::{EvictionPolicy, PseudoRandomStrategy};
     use crate::traversal::vector_source::MockVectorSource;
     use fresnel_fir_compiler::graph::{BranchEdge, GraphNode, NdaGraph};
     use rand::SeedableRng;
@@ -495,10 +1201,11 @@ mod tests {
         .unwrap()
     }
 
+    #[allow(clippy::disallowed_methods)]
     fn make_strategy_stack() -> StrategyStack {
         let rng = ChaCha8Rng::seed_from_u64(42);
         let strategy = PseudoRandomStrategy::new(rng);
-        StrategyStack::new(Box::new(strategy), 4)
+        StrategyStack::new(Box::new(strategy), 4, EvictionPolicy::Fifo)
     }
 
     fn actor_id() -> InstanceId {
@@ -515,10 +1222,12 @@ mod tests {
         let a = graph.add_node(GraphNode::Terminal {
             action: "action_a".to_string(),
             guard: None,
+            source_span: None,
         });
         let b = graph.add_node(GraphNode::Terminal {
             action: "action_b".to_string(),
             guard: None,
+            source_span: None,
         });
         graph.add_edge(graph.entry, a);
         graph.add_edge(a, b);
@@ -557,10 +1266,12 @@ mod tests {
         let term_a = graph.add_node(GraphNode::Terminal {
             action: "branch_a".to_string(),
             guard: None,
+            source_span: None,
         });
         let term_b = graph.add_node(GraphNode::Terminal {
             action: "branch_b".to_string(),
             guard: None,
+            source_span: None,
         });
         let join = graph.add_node(GraphNode::Start); // join placeholder
         graph.add_edge(term_a, join);
@@ -582,6 +1293,7 @@ mod tests {
                     guard: None,
                 },
             ],
+            source_span: None,
         });
         graph.add_edge(graph.entry, branch);
 
@@ -622,6 +1334,7 @@ mod tests {
         let action = graph.add_node(GraphNode::Terminal {
             action: "loop_action".to_string(),
             guard: None,
+            source_span: None,
         });
         let loop_exit = graph.add_node(GraphNode::LoopExit);
         let loop_entry = graph.add_node(GraphNode::LoopEntry {
@@ -666,6 +1379,7 @@ mod tests {
         let action = graph.add_node(GraphNode::Terminal {
             action: "repeated".to_string(),
             guard: None,
+            source_span: None,
         });
         let loop_exit = graph.add_node(GraphNode::LoopExit);
         let loop_entry = graph.add_node(GraphNode::LoopEntry {
@@ -699,17 +1413,150 @@ mod tests {
         assert_eq!(result.actions_executed, 5);
     }
 
-    #[test]
-    fn test_coverage_delta_signals() {
-        // Two different actions should emit two CoverageDelta signals
+    /// Run `passes` passes over Start -> Loop(min..=max) -> End with a
+    /// `LoopLimit` handed to the stack before the first pass, returning the
+    /// body count and signals of each pass.
+    fn run_limited_loop(
+        min: u32,
+        max: u32,
+        limit: (u32, u32),
+        passes: usize,
+    ) -> Vec<(u64, Vec<SignalEvent>)> {
         let mut graph = NdaGraph::new();
-        let a = graph.add_node(GraphNode::Terminal {
-            action: "first".to_string(),
+        let action = graph.add_node(GraphNode::Terminal {
+            action: "body".to_string(),
             guard: None,
+            source_span: None,
         });
-        let b = graph.add_node(GraphNode::Terminal {
-            action: "second".to_string(),
-            guard: None,
+        let loop_exit = graph.add_node(GraphNode::LoopExit);
+        let loop_entry = graph.add_node(GraphNode::LoopEntry {
+            body_start: action,
+            min,
+            max,
+        });
+        graph.add_edge(graph.entry, loop_entry);
+        graph.add_edge(loop_entry, loop_exit);
+        graph.add_edge(loop_exit, graph.exit);
+
+        let mut model = ModelState::new();
+        let ir = minimal_ir();
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+        let directives = [Directive::LoopLimit {
+            loop_node_id: loop_entry,
+            new_min: limit.0,
+            new_max: limit.1,
+        }];
+
+        (0..passes)
+            .map(|pass| {
+                let engine = TraversalEngine::new(
+                    &graph,
+                    &mut model,
+                    ModelOnlyExecutor,
+                    &ir,
+                    &[],
+                    actor_id(),
+                    &mut strategy_stack,
+                    &mut vector_source,
+                    &mut weight_table,
+                );
+                let engine = if pass == 0 {
+                    engine.with_directives(&directives)
+                } else {
+                    engine
+                };
+                let result = engine.run_pass(10_000);
+                (result.actions_executed, result.signals)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_loop_limit_caps_iterations() {
+        let passes = run_limited_loop(1, 100, (1, 2), 50);
+        for (executed, signals) in &passes {
+            assert!((1..=2).contains(executed), "body ran {executed} times");
+            assert!(!signals
+                .iter()
+                .any(|s| matches!(s.signal_type, SignalType::LoopLimitConflict { .. })));
+        }
+    }
+
+    #[test]
+    fn test_loop_limit_below_min_keeps_min_and_signals_conflict() {
+        let passes = run_limited_loop(3, 10, (1, 2), 1);
+        let (executed, signals) = &passes[0];
+        assert_eq!(*executed, 3);
+        assert!(signals.iter().any(|s| matches!(
+            s.signal_type,
+            SignalType::LoopLimitConflict {
+                declared_min: 3,
+                limit_max: 2,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_guard_result_reused_while_model_unchanged() {
+        use fresnel_fir_compiler::predicate::Value as Literal;
+
+        let mut graph = NdaGraph::new();
+        let action = graph.add_node(GraphNode::Terminal {
+            action: "body".to_string(),
+            guard: Some(CompiledExpr::Literal(Literal::Bool(true))),
+            source_span: None,
+        });
+        let loop_exit = graph.add_node(GraphNode::LoopExit);
+        let loop_entry = graph.add_node(GraphNode::LoopEntry {
+            body_start: action,
+            min: 5,
+            max: 5,
+        });
+        graph.add_edge(graph.entry, loop_entry);
+        graph.add_edge(loop_entry, loop_exit);
+        graph.add_edge(loop_exit, graph.exit);
+
+        let mut model = ModelState::new();
+        let ir = minimal_ir();
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+        let engine = TraversalEngine::new(
+            &graph,
+            &mut model,
+            ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        );
+        let result = engine.run_pass(10_000);
+
+        // Same node, same drawn inputs and no model mutation between the
+        // iterations: only the first guard check is evaluated.
+        assert_eq!(result.actions_executed, 5);
+        assert_eq!(result.guards_failed, 0);
+        assert_eq!(result.guard_evaluations, 1);
+    }
+
+    #[test]
+    fn test_coverage_delta_signals() {
+        // Two different actions should emit two CoverageDelta signals
+        let mut graph = NdaGraph::new();
+        let a = graph.add_node(GraphNode::Terminal {
+            action: "first".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        let b = graph.add_node(GraphNode::Terminal {
+            action: "second".to_string(),
+            guard: None,
+            source_span: None,
         });
         graph.add_edge(graph.entry, a);
         graph.add_edge(a, b);
@@ -742,6 +1589,55 @@ mod tests {
         assert_eq!(coverage_signals.len(), 2);
     }
 
+    #[test]
+    fn test_coverage_delta_only_on_first_campaign_coverage() {
+        let mut graph = NdaGraph::new();
+        let a = graph.add_node(GraphNode::Terminal {
+            action: "first".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        let b = graph.add_node(GraphNode::Terminal {
+            action: "second".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        graph.add_edge(graph.entry, a);
+        graph.add_edge(a, b);
+        graph.add_edge(b, graph.exit);
+
+        let mut model = ModelState::new();
+        let ir = minimal_ir();
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+        let mut covered = CampaignCoverage::default();
+
+        let deltas_per_pass: Vec<usize> = (0..3)
+            .map(|_| {
+                let engine = TraversalEngine::new(
+                    &graph,
+                    &mut model,
+                    ModelOnlyExecutor,
+                    &ir,
+                    &[],
+                    actor_id(),
+                    &mut strategy_stack,
+                    &mut vector_source,
+                    &mut weight_table,
+                )
+                .with_campaign_coverage(&mut covered);
+                engine
+                    .run_pass(10_000)
+                    .signals
+                    .iter()
+                    .filter(|s| matches!(s.signal_type, SignalType::CoverageDelta { .. }))
+                    .count()
+            })
+            .collect();
+        assert_eq!(deltas_per_pass, [2, 0, 0]);
+    }
+
     #[test]
     fn test_empty_graph_start_to_end() {
         // Just Start -> End (via default edges in NdaGraph::new)
@@ -824,6 +1720,7 @@ mod tests {
         let a = graph.add_node(GraphNode::Terminal {
             action: "create_document".to_string(),
             guard: None,
+            source_span: None,
         });
         graph.add_edge(graph.entry, a);
         graph.add_edge(a, graph.exit);
@@ -859,16 +1756,160 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_guards_bind_each_created_entity() {
+        let mut ir = minimal_ir();
+        ir.effects = serde_json::from_str(
+            r#"{ "create_folder": {
+                "creates": { "entity": "Folder", "assign": "folder" },
+                "sets": [{ "target": ["folder", "name"], "value": "inbox" }]
+            } }"#,
+        )
+        .unwrap();
+        let guard: fresnel_fir_ir::expr::Expr = serde_json::from_value(serde_json::json!([
+            "eq",
+            ["field", "folder", "name"],
+            "inbox"
+        ]))
+        .unwrap();
+        let guard = fresnel_fir_compiler::predicate::compile_expr(
+            &guard,
+            &fresnel_fir_compiler::predicate::TypeContext::from_ir(&ir),
+        )
+        .unwrap();
+
+        // Graph: Start -> create_folder -> [folder.name == "inbox"] open_folder -> End
+        let mut graph = NdaGraph::new();
+        let create = graph.add_node(GraphNode::Terminal {
+            action: "create_folder".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        let open = graph.add_node(GraphNode::Terminal {
+            action: "open_folder".to_string(),
+            guard: Some(guard),
+            source_span: None,
+        });
+        graph.add_edge(graph.entry, create);
+        graph.add_edge(create, open);
+        graph.add_edge(open, graph.exit);
+
+        let mut model = ModelState::new();
+        let actor = model.create_instance("User");
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+        let engine = TraversalEngine::new(
+            &graph,
+            &mut model,
+            ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor,
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        );
+
+        // `folder` is bound from the effect, so the guard passes.
+        let result = engine.run_pass(10_000);
+        assert_eq!(result.actions_executed, 2);
+        assert!(result.signals.iter().any(|s| matches!(
+            &s.signal_type,
+            SignalType::GuardPass { action, .. } if action == "open_folder"
+        )));
+    }
+
+    #[test]
+    fn test_violation_points_at_breaking_action() {
+        use fresnel_fir_compiler::predicate::Value as Literal;
+        use fresnel_fir_ir::expr::QuantifierKind;
+
+        let mut ir = minimal_ir();
+        ir.effects = serde_json::from_str(
+            r#"{ "create_document": { "creates": { "entity": "Document", "assign": "doc" } } }"#,
+        )
+        .unwrap();
+
+        // Graph: Start -> open -> create_document -> edit -> close -> End
+        let mut graph = NdaGraph::new();
+        let mut prev = graph.entry;
+        for action in ["open", "create_document", "edit", "close"] {
+            let node = graph.add_node(GraphNode::Terminal {
+                action: action.to_string(),
+                guard: None,
+                source_span: None,
+            });
+            graph.add_edge(prev, node);
+            prev = node;
+        }
+        graph.add_edge(prev, graph.exit);
+
+        // No document may exist: broken by create_document and never restored.
+        let invariants = [CompiledProperty {
+            name: "no_documents".to_string(),
+            expr: CompiledExpr::Quantifier {
+                kind: QuantifierKind::Forall,
+                var: "d".to_string(),
+                domain: "Document".to_string(),
+                filter: None,
+                body: Box::new(CompiledExpr::Literal(Literal::Bool(false))),
+            },
+            count: None,
+        }];
+
+        let mut model = ModelState::new();
+        let actor = model.create_instance("User");
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+        let engine = TraversalEngine::new(
+            &graph,
+            &mut model,
+            ModelOnlyExecutor,
+            &ir,
+            &invariants,
+            actor,
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        );
+        let result = engine.run_pass(10_000);
+
+        // Index 0 is the Start node and 1 is "open".
+        let breaking_index = result
+            .trace
+            .steps()
+            .iter()
+            .position(|step| {
+                matches!(
+                    &step.kind,
+                    TraceStepKind::ActionExecuted { action, .. } if action == "create_document"
+                )
+            })
+            .unwrap();
+        assert_eq!(breaking_index, 2);
+
+        // One violation per action from create_document on; the one detected
+        // after close, two actions later, still points at create_document.
+        assert_eq!(result.findings.len(), 3);
+        for finding in &result.findings {
+            assert_eq!(finding.breaking_trace_index, Some(breaking_index));
+        }
+    }
+
     #[test]
     fn test_trace_records_all_steps() {
         let mut graph = NdaGraph::new();
         let a = graph.add_node(GraphNode::Terminal {
             action: "step1".to_string(),
             guard: None,
+            source_span: None,
         });
         let b = graph.add_node(GraphNode::Terminal {
             action: "step2".to_string(),
             guard: None,
+            source_span: None,
         });
         graph.add_edge(graph.entry, a);
         graph.add_edge(a, b);
@@ -911,10 +1952,12 @@ mod tests {
             let term_a = graph.add_node(GraphNode::Terminal {
                 action: "a".to_string(),
                 guard: None,
+                source_span: None,
             });
             let term_b = graph.add_node(GraphNode::Terminal {
                 action: "b".to_string(),
                 guard: None,
+                source_span: None,
             });
             let join = graph.add_node(GraphNode::Start);
             graph.add_edge(term_a, join);
@@ -936,6 +1979,7 @@ mod tests {
                         guard: None,
                     },
                 ],
+                source_span: None,
             });
             graph.add_edge(graph.entry, branch);
             graph
@@ -1025,10 +2069,12 @@ mod tests {
         let a = graph.add_node(GraphNode::Terminal {
             action: "safe_action".to_string(),
             guard: None,
+            source_span: None,
         });
         let b = graph.add_node(GraphNode::Terminal {
             action: "crashing_action".to_string(),
             guard: None,
+            source_span: None,
         });
         graph.add_edge(graph.entry, a);
         graph.add_edge(a, b);
@@ -1063,6 +2109,86 @@ mod tests {
             result.findings[0].signal.signal_type,
             SignalType::Crash { .. }
         ));
+        assert_eq!(result.findings[0].severity, Severity::Critical);
+    }
+
+    /// Executor whose return value alternates between calls.
+    struct FlakyExecutor {
+        calls: i32,
+    }
+
+    impl ActionExecutor for FlakyExecutor {
+        fn execute(&mut self, _action: &str, _vector: Option<&TestVector>) -> ActionOutcome {
+            self.calls += 1;
+            ActionOutcome {
+                return_value: Some(self.calls % 2),
+                trapped: false,
+                fuel_consumed: None,
+                error: None,
+            }
+        }
+    }
+
+    fn run_single_action<E: ActionExecutor>(executor: E) -> TraversalResult {
+        let mut graph = NdaGraph::new();
+        let a = graph.add_node(GraphNode::Terminal {
+            action: "read".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        graph.add_edge(graph.entry, a);
+        graph.add_edge(a, graph.exit);
+
+        let mut model = ModelState::new();
+        let ir = minimal_ir();
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+
+        let engine = TraversalEngine::new(
+            &graph,
+            &mut model,
+            executor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        );
+        engine.run_pass(10_000)
+    }
+
+    #[test]
+    fn test_repeat_executor_flags_nondeterminism() {
+        let executor = RepeatExecutor::new(FlakyExecutor { calls: 0 }, 3, &minimal_ir());
+        let result = run_single_action(executor);
+        assert_eq!(result.actions_executed, 1);
+        assert_eq!(result.findings.len(), 1);
+        assert!(matches!(
+            &result.findings[0].signal.signal_type,
+            SignalType::Discrepancy { action, .. } if action == "read"
+        ));
+    }
+
+    #[test]
+    fn test_repeat_executor_stable_dut_has_no_finding() {
+        let result = run_single_action(RepeatExecutor::new(ModelOnlyExecutor, 3, &minimal_ir()));
+        assert_eq!(result.actions_executed, 1);
+        assert!(result.findings.is_empty());
+    }
+
+    #[test]
+    fn test_repeat_executor_runs_effectful_action_once() {
+        let mut ir = minimal_ir();
+        ir.effects.insert(
+            "read".to_string(),
+            serde_json::from_str(r#"{ "creates": { "entity": "Document", "assign": "doc" } }"#)
+                .unwrap(),
+        );
+        let result = run_single_action(RepeatExecutor::new(FlakyExecutor { calls: 0 }, 3, &ir));
+        assert_eq!(result.actions_executed, 1);
+        assert!(result.findings.is_empty());
     }
 
     /// Custom executor that simulates timeouts for testing.
@@ -1090,12 +2216,161 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sandbox_executor_applies_per_action_fuel_limit() {
+        use fresnel_fir_ir::types::{ActionBinding, Bindings, EventHooks};
+        use fresnel_fir_sandbox::config::SandboxConfig;
+        use fresnel_fir_sandbox::sandbox::Sandbox;
+        use fresnel_fir_vif::adapter::VerificationAdapter;
+
+        let wasm = wat::parse_str(
+            r#"
+            (module
+              (func (export "spin") (param i32) (result i32)
+                (loop $loop (br $loop))
+                local.get 0)
+              (func (export "echo") (param i32) (result i32)
+                local.get 0))
+            "#,
+        )
+        .unwrap();
+        let config = SandboxConfig {
+            fuel_per_action: Some(1_000_000),
+            ..Default::default()
+        };
+        let sandbox = Sandbox::new(&config).unwrap();
+        let module = sandbox.load_module(&wasm).unwrap();
+        let mut instance = sandbox.instantiate(&module).unwrap();
+
+        let binding = |function: &str| ActionBinding {
+            function: function.to_string(),
+            args: vec!["arg0".to_string()],
+            returns: serde_json::json!({ "type": "int" }),
+            mutates: false,
+            idempotent: true,
+            reads: vec![],
+            writes: vec![],
+            arg_types: vec![],
+        };
+        let adapter = VerificationAdapter::from_bindings(&Bindings {
+            runtime: "wasm".to_string(),
+            entry: "main.wasm".to_string(),
+            actions: HashMap::from([
+                ("spin".to_string(), binding("spin")),
+                ("echo".to_string(), binding("echo")),
+            ]),
+            event_hooks: EventHooks {
+                mode: "function_intercept".to_string(),
+                observe: vec![],
+                capture: vec![],
+            },
+        });
+
+        let mut graph = NdaGraph::new();
+        let spin = graph.add_node(GraphNode::Terminal {
+            action: "spin".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        let echo = graph.add_node(GraphNode::Terminal {
+            action: "echo".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        graph.add_edge(graph.entry, spin);
+        graph.add_edge(spin, echo);
+        graph.add_edge(echo, graph.exit);
+
+        let mut model = ModelState::new();
+        let ir = minimal_ir();
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+        let executor = SandboxExecutor::new(&mut instance, &adapter).with_fuel_limit("spin", 1_000);
+
+        let result = TraversalEngine::new(
+            &graph,
+            &mut model,
+            executor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        )
+        .run_pass(10_000);
+
+        let timeouts: Vec<_> = result
+            .signals
+            .iter()
+            .filter_map(|s| match &s.signal_type {
+                SignalType::Timeout {
+                    action,
+                    fuel_consumed,
+                } => Some((action.as_str(), *fuel_consumed)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(timeouts, vec![("spin", Some(1_000))]);
+        assert!(result.findings.is_empty());
+        // Unlisted actions fall back to the campaign-wide budget.
+        assert_eq!(instance.fuel_budget(), Some(1_000_000));
+    }
+
+    #[test]
+    fn test_vector_to_args_passes_wide_ints_as_declared() {
+        let mut vector = TestVector::new();
+        vector
+            .assignments
+            .insert("bytes".to_string(), DomainValue::Int(5_000_000_000));
+        vector
+            .assignments
+            .insert("count".to_string(), DomainValue::Int(7));
+        let binding = |args: &[&str], arg_types: Vec<ArgType>| ActionBinding {
+            function: "write".to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            returns: serde_json::json!({ "type": "int" }),
+            mutates: true,
+            idempotent: false,
+            reads: vec![],
+            writes: vec![],
+            arg_types,
+        };
+
+        // Args follow the binding's order, not the vector's name order.
+        assert_eq!(
+            vector_to_args(
+                Some(&vector),
+                &binding(&["count", "bytes"], vec![ArgType::I32, ArgType::I64])
+            ),
+            Ok(vec![WasmArg::I32(7), WasmArg::I64(5_000_000_000)])
+        );
+        assert_eq!(
+            vector_to_args(
+                Some(&vector),
+                &binding(&["bytes", "count"], vec![ArgType::F64, ArgType::F64])
+            ),
+            Ok(vec![WasmArg::F64(5_000_000_000.0), WasmArg::F64(7.0)])
+        );
+
+        // A wide int is never truncated to an undeclared i32.
+        assert!(vector_to_args(Some(&vector), &binding(&["count", "bytes"], vec![])).is_err());
+        // Missing and enum-valued arguments are errors, not zeros.
+        assert!(vector_to_args(Some(&vector), &binding(&["size"], vec![])).is_err());
+        vector
+            .assignments
+            .insert("role".to_string(), DomainValue::Enum("admin".into()));
+        assert!(vector_to_args(Some(&vector), &binding(&["role"], vec![])).is_err());
+    }
+
     #[test]
     fn test_timeout_emits_signal_not_finding() {
         let mut graph = NdaGraph::new();
         let a = graph.add_node(GraphNode::Terminal {
             action: "slow_action".to_string(),
             guard: None,
+            source_span: None,
         });
         graph.add_edge(graph.entry, a);
         graph.add_edge(a, graph.exit);
@@ -1138,4 +2413,212 @@ mod tests {
             .collect();
         assert!(crash_findings.is_empty());
     }
+
+    /// Run a diamond graph (Start -> {left, right} -> join -> End) under
+    /// the given order and return the visited node ids, in order.
+    fn diamond_visit_order(order: TraversalOrder) -> (Vec<NodeId>, [NodeId; 3]) {
+        let mut graph = NdaGraph::new();
+        let left = graph.add_node(GraphNode::Terminal {
+            action: "left".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        let right = graph.add_node(GraphNode::Terminal {
+            action: "right".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        let join = graph.add_node(GraphNode::Terminal {
+            action: "join".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        graph.add_edge(graph.entry, left);
+        graph.add_edge(graph.entry, right);
+        graph.add_edge(left, join);
+        graph.add_edge(right, join);
+        graph.add_edge(join, graph.exit);
+
+        let mut model = ModelState::new();
+        let ir = minimal_ir();
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+
+        let engine = TraversalEngine::new(
+            &graph,
+            &mut model,
+            ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        )
+        .with_order(order);
+
+        let result = engine.run_pass(10_000);
+        let visited = result.trace.steps().iter().map(|s| s.node_id).collect();
+        (visited, [left, right, join])
+    }
+
+    #[test]
+    fn test_traversal_order_diamond() {
+        let (dfs, [left, right, join]) = diamond_visit_order(TraversalOrder::DepthFirst);
+        let (bfs, _) = diamond_visit_order(TraversalOrder::BreadthFirst);
+        // NdaGraph::new() allocates Start and End as nodes 0 and 1.
+        let (start, end) = (0, 1);
+
+        // Depth-first follows the last-pushed arm all the way to End first.
+        assert_eq!(dfs, vec![start, right, join, end, left, join, end]);
+        // Breadth-first finishes both arms before reaching the join.
+        assert_eq!(bfs, vec![start, left, right, join, join, end, end]);
+        assert_ne!(dfs, bfs);
+    }
+
+    /// Records the `amount` input of every executed action.
+    struct AmountRecorder {
+        amounts: std::rc::Rc<std::cell::RefCell<Vec<i64>>>,
+    }
+
+    impl ActionExecutor for AmountRecorder {
+        fn execute(&mut self, _action: &str, vector: Option<&TestVector>) -> ActionOutcome {
+            if let Some(DomainValue::Int(amount)) = vector.and_then(|v| v.assignments.get("amount"))
+            {
+                self.amounts.borrow_mut().push(*amount);
+            }
+            ActionOutcome {
+                return_value: None,
+                trapped: false,
+                fuel_consumed: None,
+                error: None,
+            }
+        }
+    }
+
+    /// Start -> deposit x `calls` -> End, where deposit requires
+    /// `$input.amount >= 0`. Returns the executed amounts and guard failures.
+    fn run_guarded_deposits(calls: usize, draws: &[i64], retries: u32) -> (Vec<i64>, u64) {
+        use fresnel_fir_compiler::predicate::Value as Literal;
+        use fresnel_fir_ir::expr::OpKind;
+
+        let guard = CompiledExpr::Op {
+            op: OpKind::Gte,
+            args: vec![
+                CompiledExpr::Field {
+                    entity: INPUT_BINDING.to_string(),
+                    field: "amount".to_string(),
+                },
+                CompiledExpr::Literal(Literal::Int(0)),
+            ],
+        };
+        let mut graph = NdaGraph::new();
+        let mut prev = graph.entry;
+        for _ in 0..calls {
+            let deposit = graph.add_node(GraphNode::Terminal {
+                action: "deposit".to_string(),
+                guard: Some(guard.clone()),
+                source_span: None,
+            });
+            graph.add_edge(prev, deposit);
+            prev = deposit;
+        }
+        graph.add_edge(prev, graph.exit);
+
+        let mut model = ModelState::new();
+        let ir = minimal_ir();
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        vector_source.add_vectors(
+            "deposit",
+            draws
+                .iter()
+                .map(|&a| MockVectorSource::vector_from_args(&[("amount", a as i32)]))
+                .collect(),
+        );
+        let mut weight_table = WeightTable::new();
+        let amounts = std::rc::Rc::default();
+        let executor = AmountRecorder {
+            amounts: std::rc::Rc::clone(&amounts),
+        };
+
+        let engine = TraversalEngine::new(
+            &graph,
+            &mut model,
+            executor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        )
+        .with_vector_retries(retries);
+        let result = engine.run_pass(10_000);
+        (amounts.take(), result.guards_failed)
+    }
+
+    #[test]
+    fn test_guard_rejects_negative_input() {
+        let (executed, guards_failed) = run_guarded_deposits(4, &[-5, 3, -1, 7], 0);
+        assert_eq!(executed, vec![3, 7]);
+        assert_eq!(guards_failed, 2);
+    }
+
+    #[test]
+    fn test_always_failing_guard_reported_as_never_passed() {
+        use fresnel_fir_compiler::predicate::Value as Literal;
+
+        let mut graph = NdaGraph::new();
+        let blocked = graph.add_node(GraphNode::Terminal {
+            action: "blocked".to_string(),
+            guard: Some(CompiledExpr::Literal(Literal::Bool(false))),
+            source_span: None,
+        });
+        let open = graph.add_node(GraphNode::Terminal {
+            action: "open".to_string(),
+            guard: None,
+            source_span: None,
+        });
+        graph.add_edge(graph.entry, blocked);
+        graph.add_edge(blocked, open);
+        graph.add_edge(open, graph.exit);
+
+        let mut model = ModelState::new();
+        let ir = minimal_ir();
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+        let engine = TraversalEngine::new(
+            &graph,
+            &mut model,
+            ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        );
+        let result = engine.run_pass(10_000);
+
+        assert_eq!(result.guards_failed, 1);
+        assert!(result.coverage.guard_never_passed.contains("blocked"));
+        assert!(!result.coverage.action_counts.contains_key("blocked"));
+        assert!(result.coverage.action_counts.contains_key("open"));
+        assert!(!result.coverage.guard_never_passed.contains("open"));
+    }
+
+    #[test]
+    fn test_guard_redraws_rejected_input() {
+        let (executed, guards_failed) = run_guarded_deposits(1, &[-5, -1, 4], 2);
+        assert_eq!(executed, vec![4]);
+        assert_eq!(guards_failed, 0);
+
+        // Retries are bounded: out of draws, the guard still fails.
+        let (executed, guards_failed) = run_guarded_deposits(1, &[-5, -1, 4], 1);
+        assert!(executed.is_empty());
+        assert_eq!(guards_failed, 1);
+    }
 }
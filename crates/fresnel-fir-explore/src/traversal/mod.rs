@@ -1,3 +1,4 @@
+pub mod compare;
 pub mod engine;
 pub mod runner;
 pub mod signal;
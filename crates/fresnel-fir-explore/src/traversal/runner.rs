@@ -1,13 +1,28 @@
-use fresnel_fir_compiler::graph::NdaGraph;
-use fresnel_fir_ir::types::FresnelFirIR;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use fresnel_fir_compiler::graph::{GraphNode, NdaGraph};
+use fresnel_fir_ir::types::{CoverageTarget, FresnelFirIR};
 use fresnel_fir_model::invariant::CompiledProperty;
 use fresnel_fir_model::state::{InstanceId, ModelState};
-use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
 
-use super::engine::{ActionExecutor, TraversalEngine};
-use super::signal::Finding;
-use super::strategy::{PseudoRandomStrategy, StrategyStack};
+use crate::adapt::coordinator::Coordinator;
+use crate::adapt::plateau::PlateauDetector;
+use crate::adapt::reachability::uncovered_target_branches;
+use crate::error::ExploreError;
+use crate::solver::rng::RngStreams;
+
+use super::engine::{
+    ActionExecutor, CampaignCoverage, CoverageReport, PassState, ReplayContext, TraversalEngine,
+    TraversalOrder, UnmetMinHits,
+};
+use super::signal::{Finding, Severity};
+use super::strategy::{
+    DirectiveAwareStrategy, EvictionPolicy, PseudoRandomStrategy, Strategy, StrategyStack,
+};
 use super::vector_source::VectorSource;
 use super::weight_table::WeightTable;
 
@@ -20,8 +35,41 @@ pub struct CampaignConfig {
     pub seed: u64,
     /// Strategy stack depth limit.
     pub strategy_depth_limit: usize,
+    /// Which strategy a full strategy stack evicts on push.
+    pub strategy_eviction: EvictionPolicy,
     /// Max steps per pass (prevents infinite loops).
     pub max_steps_per_pass: u64,
+    /// Order in which each pass explores the graph.
+    pub traversal_order: TraversalOrder,
+    /// Cancellation token, checked between passes. Once set, the campaign
+    /// stops and returns the results of the passes completed so far.
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// Stop after the first pass that records a finding at least this
+    /// severe. `None` runs every pass regardless of findings.
+    pub stop_on_finding: Option<Severity>,
+    /// Stop once coverage and findings have both stopped growing. `None`
+    /// runs every pass. Not applied to interleaved campaigns.
+    pub saturation: Option<SaturationConfig>,
+    /// Branch weights the first pass starts from, e.g. boosted from
+    /// cross-campaign memory. `None` starts from an empty table.
+    pub initial_weights: Option<WeightTable>,
+    /// Hash of the WASM module under test, recorded in each finding's
+    /// replay capsule. Empty for model-only runs.
+    pub wasm_hash: String,
+    /// Further input vectors drawn when an action's guard rejects the
+    /// drawn one, before the action counts as a guard failure.
+    pub vector_retries: u32,
+}
+
+impl CampaignConfig {
+    /// Replay context for findings of a campaign over `ir`.
+    fn replay_context(&self, ir: &FresnelFirIR) -> ReplayContext {
+        ReplayContext {
+            seed: self.seed,
+            ir_hash: ir.content_hash(),
+            wasm_hash: self.wasm_hash.clone(),
+        }
+    }
 }
 
 impl Default for CampaignConfig {
@@ -30,11 +78,97 @@ impl Default for CampaignConfig {
             max_passes: 10,
             seed: 42,
             strategy_depth_limit: 4,
+            strategy_eviction: EvictionPolicy::Fifo,
             max_steps_per_pass: 10_000,
+            traversal_order: TraversalOrder::DepthFirst,
+            cancel: None,
+            stop_on_finding: None,
+            saturation: None,
+            initial_weights: None,
+            wasm_hash: String::new(),
+            vector_retries: 0,
+        }
+    }
+}
+
+/// When a campaign counts as saturated. Both thresholds must be met.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaturationConfig {
+    /// Consecutive passes without a newly covered graph node (at least 1).
+    pub no_new_coverage_epochs: u32,
+    /// Consecutive passes without a finding of a new signature.
+    pub no_new_findings_epochs: u32,
+}
+
+/// Passes since coverage and findings last grew. Coverage stalls are
+/// detected by a `PlateauDetector` over the fraction of graph nodes the
+/// campaign has covered; findings are compared by signature.
+#[derive(Debug, Clone)]
+struct SaturationTracker {
+    config: SaturationConfig,
+    plateau: PlateauDetector,
+    /// Latched when the detector fires, cleared when coverage grows.
+    coverage_plateaued: bool,
+    last_coverage: f64,
+    signatures: HashSet<String>,
+    passes_without_findings: u32,
+}
+
+impl SaturationTracker {
+    /// `findings` are those already recorded, e.g. by a checkpoint taken
+    /// without saturation.
+    fn new(config: SaturationConfig, findings: &[Finding]) -> Self {
+        Self {
+            config,
+            plateau: PlateauDetector::new(config.no_new_coverage_epochs as usize, f64::EPSILON),
+            coverage_plateaued: false,
+            last_coverage: 0.0,
+            signatures: findings.iter().map(Finding::signature).collect(),
+            passes_without_findings: 0,
+        }
+    }
+
+    /// Record a completed pass; returns whether the campaign is saturated.
+    fn observe(&mut self, coverage: f64, findings: &[Finding]) -> bool {
+        if coverage > self.last_coverage {
+            self.coverage_plateaued = false;
         }
+        self.last_coverage = coverage;
+        if self.plateau.observe(coverage).is_some() {
+            self.coverage_plateaued = true;
+        }
+
+        let mut new_finding = false;
+        for finding in findings {
+            new_finding |= self.signatures.insert(finding.signature());
+        }
+        self.passes_without_findings = if new_finding {
+            0
+        } else {
+            self.passes_without_findings + 1
+        };
+
+        self.coverage_plateaued
+            && self.passes_without_findings >= self.config.no_new_findings_epochs
     }
 }
 
+/// Why a campaign run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// Every configured pass ran.
+    MaxPasses,
+    /// The cancellation token was set.
+    Cancelled,
+    /// A resumable campaign reached its [`StopCondition`].
+    Paused,
+    /// A finding met [`CampaignConfig::stop_on_finding`].
+    StopOnFinding,
+    /// Coverage and findings stopped growing, per
+    /// [`CampaignConfig::saturation`].
+    Saturated,
+}
+
 /// Result of a complete campaign run.
 #[derive(Debug)]
 pub struct CampaignResult {
@@ -48,9 +182,156 @@ pub struct CampaignResult {
     pub unique_nodes_visited: u64,
     /// Total guard failures.
     pub total_guard_failures: u64,
+    /// Coverage summed over all passes and threads.
+    pub coverage: CoverageReport,
+    /// The IR's `MinHits` coverage targets that `coverage` falls short of.
+    /// Adaptive campaigns register the branches leading to their actions as
+    /// uncovered targets with the coordinator until they are met.
+    pub unmet_min_hits: Vec<UnmetMinHits>,
+    /// Whether the campaign was cancelled before all passes ran.
+    #[deprecated(note = "use `stop_reason == StopReason::Cancelled`")]
+    pub cancelled: bool,
+    /// Whether the campaign stopped early on a finding meeting
+    /// [`CampaignConfig::stop_on_finding`].
+    #[deprecated(note = "use `stop_reason == StopReason::StopOnFinding`")]
+    pub stopped_early: bool,
+    /// The finding that stopped the campaign, if it stopped early.
+    pub stop_finding: Option<Finding>,
+    /// Why the run ended.
+    pub stop_reason: StopReason,
+    /// Branch weights at the end of the run.
+    pub weight_table: WeightTable,
+}
+
+impl CampaignResult {
+    /// Set `stop_reason` and the deprecated flags mirroring it.
+    #[allow(deprecated)]
+    fn set_stop_reason(&mut self, reason: StopReason) {
+        self.stop_reason = reason;
+        self.cancelled = reason == StopReason::Cancelled;
+        self.stopped_early = reason == StopReason::StopOnFinding;
+    }
+}
+
+/// Campaign progress, reported after each completed pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CampaignProgress {
+    /// Passes completed so far (including any before a resume).
+    pub passes_done: u32,
+    /// Passes the campaign will run in total.
+    pub passes_total: u32,
+    /// Actions executed so far.
+    pub total_actions: u64,
+    /// Findings recorded so far.
+    pub findings_so_far: usize,
+    /// `MinHits` coverage targets not met yet.
+    pub unmet_min_hits: usize,
+}
+
+/// When a resumable campaign should pause.
+#[derive(Default)]
+pub struct StopCondition<'s> {
+    /// Pause once this much wall time has elapsed (checked between passes).
+    pub max_wall_time: Option<Duration>,
+    /// Pause when this returns true; called with the number of passes
+    /// completed so far, before each pass.
+    pub should_stop: Option<&'s dyn Fn(u32) -> bool>,
+}
+
+impl StopCondition<'_> {
+    fn reached(&self, passes_completed: u32, started: Instant) -> bool {
+        self.max_wall_time
+            .is_some_and(|limit| started.elapsed() >= limit)
+            || self.should_stop.is_some_and(|f| f(passes_completed))
+    }
+}
+
+/// Snapshot of a paused campaign, sufficient to resume it exactly.
+///
+/// The executor and vector source are owned by the caller and are not
+/// captured; resuming with them in the same state they were left in gives
+/// results identical to an uninterrupted run.
+///
+/// Adaptive campaigns are not checkpointed: neither the coordinator's
+/// pending epoch nor the directives the strategy stack has accepted are
+/// captured, so `run_campaign_adaptive` flushes its coordinator and ends
+/// when cancelled, and `resume_campaign` runs without a coordinator.
+#[derive(Debug, Clone)]
+pub struct CampaignCheckpoint {
+    /// Index of the next pass to run (= passes completed).
+    pub next_pass: u32,
+    /// Base strategy RNG state at the pause point (the campaign's seed
+    /// RNG if the base strategy keeps none).
+    pub rng: ChaCha8Rng,
+    /// Model state at the pause point.
+    pub model: ModelState,
+    /// Adapted weights at the pause point.
+    pub weight_table: WeightTable,
+    /// Coverage accumulated over the completed passes.
+    pub coverage: CoverageReport,
+    /// Nodes and actions covered so far, for campaign-wide coverage deltas.
+    pub covered: CampaignCoverage,
+    /// Findings from the completed passes.
+    pub findings: Vec<Finding>,
+    /// Actions executed over the completed passes.
+    pub total_actions: u64,
+    /// Guard failures over the completed passes.
+    pub total_guard_failures: u64,
+    /// Most nodes visited in any completed pass.
+    pub unique_nodes_visited: u64,
+    /// Saturation progress, so a resumed run saturates on the same pass
+    /// as an uninterrupted one.
+    saturation: Option<SaturationTracker>,
+}
+
+impl CampaignCheckpoint {
+    /// State before the first pass of a fresh campaign.
+    fn start(config: &CampaignConfig, model: &ModelState) -> Self {
+        Self {
+            next_pass: 0,
+            rng: RngStreams::new(config.seed).thread(0),
+            model: model.clone(),
+            weight_table: config.initial_weights.clone().unwrap_or_default(),
+            coverage: CoverageReport::default(),
+            covered: CampaignCoverage::default(),
+            findings: Vec::new(),
+            total_actions: 0,
+            total_guard_failures: 0,
+            unique_nodes_visited: 0,
+            saturation: config
+                .saturation
+                .map(|saturation| SaturationTracker::new(saturation, &[])),
+        }
+    }
+
+    /// Aggregate result over the passes completed so far.
+    #[allow(deprecated)] // Fills the flags mirroring `stop_reason`.
+    fn result(&self, stop_reason: StopReason, targets: &[CoverageTarget]) -> CampaignResult {
+        CampaignResult {
+            findings: self.findings.clone(),
+            total_actions: self.total_actions,
+            passes_completed: self.next_pass,
+            unique_nodes_visited: self.unique_nodes_visited,
+            total_guard_failures: self.total_guard_failures,
+            coverage: self.coverage.clone(),
+            unmet_min_hits: self.coverage.unmet_min_hits(targets),
+            cancelled: stop_reason == StopReason::Cancelled,
+            stopped_early: stop_reason == StopReason::StopOnFinding,
+            stop_finding: None,
+            stop_reason,
+            weight_table: self.weight_table.clone(),
+        }
+    }
 }
 
 /// Run a single-threaded campaign: create engine per pass, aggregate results.
+/// `progress`, if given, is called after each pass.
+///
+/// Early stops are not errors: they are reported through the result's
+/// `stop_reason`, together with what the passes completed. So is
+/// cancellation once a pass has completed; cancelled before the first
+/// pass, there is no result and the run fails with
+/// [`ExploreError::Cancelled`].
 #[allow(clippy::too_many_arguments)]
 pub fn run_campaign<V: VectorSource, E: ActionExecutor>(
     graph: &NdaGraph,
@@ -61,18 +342,203 @@ pub fn run_campaign<V: VectorSource, E: ActionExecutor>(
     actor_id: InstanceId,
     vector_source: &mut V,
     config: &CampaignConfig,
-) -> CampaignResult {
-    let rng = ChaCha8Rng::seed_from_u64(config.seed);
-    let base_strategy = Box::new(PseudoRandomStrategy::new(rng));
-    let mut strategy_stack = StrategyStack::new(base_strategy, config.strategy_depth_limit);
-    let mut weight_table = WeightTable::new();
-
-    let mut all_findings = Vec::new();
-    let mut total_actions = 0u64;
-    let mut total_guard_failures = 0u64;
-    let mut max_nodes_visited = 0u64;
-
-    for _pass in 0..config.max_passes {
+    progress: Option<&dyn Fn(CampaignProgress)>,
+) -> Result<CampaignResult, ExploreError> {
+    let (result, _) = run_campaign_resumable(
+        graph,
+        model,
+        executor,
+        ir,
+        invariants,
+        actor_id,
+        vector_source,
+        config,
+        &StopCondition::default(),
+        progress,
+    );
+    completed(result)
+}
+
+/// Like `run_campaign`, but pauses when `stop` is reached (or the config's
+/// cancellation token is set) and returns a checkpoint alongside the
+/// partial result. The checkpoint is `None` when all passes completed.
+///
+/// Infallible: even a campaign cancelled before its first pass yields a
+/// checkpoint to resume from.
+#[allow(clippy::too_many_arguments)]
+pub fn run_campaign_resumable<V: VectorSource, E: ActionExecutor>(
+    graph: &NdaGraph,
+    model: &mut ModelState,
+    executor: &mut E,
+    ir: &FresnelFirIR,
+    invariants: &[CompiledProperty],
+    actor_id: InstanceId,
+    vector_source: &mut V,
+    config: &CampaignConfig,
+    stop: &StopCondition<'_>,
+    progress: Option<&dyn Fn(CampaignProgress)>,
+) -> (CampaignResult, Option<CampaignCheckpoint>) {
+    let start = CampaignCheckpoint::start(config, model);
+    run_from(
+        start,
+        graph,
+        model,
+        executor,
+        ir,
+        invariants,
+        actor_id,
+        vector_source,
+        config,
+        stop,
+        None,
+        progress,
+    )
+}
+
+/// Continue a paused campaign from `checkpoint`. `model` is overwritten
+/// with the checkpointed model state. `config` should match the original
+/// run; its seed is ignored in favour of the checkpointed RNG.
+#[allow(clippy::too_many_arguments)]
+pub fn resume_campaign<V: VectorSource, E: ActionExecutor>(
+    checkpoint: CampaignCheckpoint,
+    graph: &NdaGraph,
+    model: &mut ModelState,
+    executor: &mut E,
+    ir: &FresnelFirIR,
+    invariants: &[CompiledProperty],
+    actor_id: InstanceId,
+    vector_source: &mut V,
+    config: &CampaignConfig,
+    stop: &StopCondition<'_>,
+    progress: Option<&dyn Fn(CampaignProgress)>,
+) -> (CampaignResult, Option<CampaignCheckpoint>) {
+    *model = checkpoint.model.clone();
+    run_from(
+        checkpoint,
+        graph,
+        model,
+        executor,
+        ir,
+        invariants,
+        actor_id,
+        vector_source,
+        config,
+        stop,
+        None,
+        progress,
+    )
+}
+
+/// Like `run_campaign`, but adapts between passes: each pass's signals are
+/// fed to `coordinator`, and the directives it emits go to the strategy
+/// stack. Before the signals go in, the coordinator's uncovered target
+/// branches are recomputed from the campaign coverage, so the coverage
+/// floor protects exactly the reachable branches not yet selected.
+///
+/// Fails with [`ExploreError::Cancelled`] as `run_campaign` does. A
+/// campaign cancelled later ends there, with its coordinator flushed; it
+/// cannot be resumed (see [`CampaignCheckpoint`]).
+#[allow(clippy::too_many_arguments)]
+pub fn run_campaign_adaptive<V: VectorSource, E: ActionExecutor>(
+    graph: &NdaGraph,
+    model: &mut ModelState,
+    executor: &mut E,
+    ir: &FresnelFirIR,
+    invariants: &[CompiledProperty],
+    actor_id: InstanceId,
+    vector_source: &mut V,
+    config: &CampaignConfig,
+    coordinator: &mut Coordinator,
+    progress: Option<&dyn Fn(CampaignProgress)>,
+) -> Result<CampaignResult, ExploreError> {
+    let start = CampaignCheckpoint::start(config, model);
+    let (result, _) = run_from(
+        start,
+        graph,
+        model,
+        executor,
+        ir,
+        invariants,
+        actor_id,
+        vector_source,
+        config,
+        &StopCondition::default(),
+        Some(coordinator),
+        progress,
+    );
+    completed(result)
+}
+
+/// `result`, unless the campaign was cancelled before completing a pass.
+fn completed(result: CampaignResult) -> Result<CampaignResult, ExploreError> {
+    if result.stop_reason == StopReason::Cancelled && result.passes_completed == 0 {
+        return Err(ExploreError::Cancelled);
+    }
+    Ok(result)
+}
+
+/// Run passes from `state.next_pass` until done or paused.
+/// `state.model` is only refreshed when producing a checkpoint.
+#[allow(clippy::too_many_arguments)]
+fn run_from<V: VectorSource, E: ActionExecutor>(
+    mut state: CampaignCheckpoint,
+    graph: &NdaGraph,
+    model: &mut ModelState,
+    executor: &mut E,
+    ir: &FresnelFirIR,
+    invariants: &[CompiledProperty],
+    actor_id: InstanceId,
+    vector_source: &mut V,
+    config: &CampaignConfig,
+    stop: &StopCondition<'_>,
+    mut coordinator: Option<&mut Coordinator>,
+    progress: Option<&dyn Fn(CampaignProgress)>,
+) -> (CampaignResult, Option<CampaignCheckpoint>) {
+    // Directives only reach a strategy that acts on them.
+    let mut base_strategy: Box<dyn Strategy> =
+        Box::new(PseudoRandomStrategy::new(state.rng.clone()));
+    if coordinator.is_some() {
+        base_strategy = Box::new(DirectiveAwareStrategy::new(base_strategy, graph));
+    }
+    let mut strategy_stack = StrategyStack::new(
+        base_strategy,
+        config.strategy_depth_limit,
+        config.strategy_eviction,
+    );
+    let started = Instant::now();
+    let targets = &ir.inputs.coverage.targets;
+    let alt_blocks = alt_block_branches(graph);
+    let replay = config.replay_context(ir);
+    let mut saturation = state.saturation.take().or_else(|| {
+        config
+            .saturation
+            .map(|saturation| SaturationTracker::new(saturation, &state.findings))
+    });
+
+    while state.next_pass < config.max_passes {
+        let cancelled = config
+            .cancel
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed));
+        if cancelled || stop.reached(state.next_pass, started) {
+            let reason = if cancelled {
+                StopReason::Cancelled
+            } else {
+                StopReason::Paused
+            };
+            // Adaptive campaigns cannot resume (see `CampaignCheckpoint`).
+            if coordinator.is_some() {
+                flush_coordinator(coordinator, &mut state.weight_table, &alt_blocks);
+                return (state.result(reason, targets), None);
+            }
+            if let Some(rng) = strategy_stack.rng_state() {
+                state.rng = rng;
+            }
+            state.model = model.clone();
+            state.saturation = saturation;
+            return (state.result(reason, targets), Some(state));
+        }
+
         let engine = TraversalEngine::new(
             graph,
             model,
@@ -82,29 +548,308 @@ pub fn run_campaign<V: VectorSource, E: ActionExecutor>(
             actor_id.clone(),
             &mut strategy_stack,
             vector_source,
-            &mut weight_table,
-        );
+            &mut state.weight_table,
+        )
+        .with_order(config.traversal_order)
+        .with_vector_retries(config.vector_retries)
+        .with_campaign_coverage(&mut state.covered)
+        .with_replay_context(replay.clone());
 
         let result = engine.run_pass(config.max_steps_per_pass);
 
-        total_actions += result.actions_executed;
-        total_guard_failures += result.guards_failed;
-        if result.nodes_visited > max_nodes_visited {
-            max_nodes_visited = result.nodes_visited;
+        state.total_actions += result.actions_executed;
+        state.total_guard_failures += result.guards_failed;
+        if result.nodes_visited > state.unique_nodes_visited {
+            state.unique_nodes_visited = result.nodes_visited;
+        }
+        state.coverage.merge(&result.coverage);
+        let node_coverage = state.covered.nodes.len() as f64 / graph.nodes.len().max(1) as f64;
+        if let Some(coordinator) = coordinator.as_deref_mut() {
+            coordinator.set_uncovered_target_branches(uncovered_target_branches(
+                graph,
+                &state.coverage,
+                targets,
+            ));
+            for signal in result.signals {
+                let directives =
+                    coordinator.feed_signal(signal, &mut state.weight_table, &alt_blocks);
+                strategy_stack.accept_directives(&directives);
+            }
+            let directives =
+                coordinator.observe_coverage(node_coverage, &mut state.weight_table, &alt_blocks);
+            strategy_stack.accept_directives(&directives);
+        }
+        let pass_findings_start = state.findings.len();
+        append_findings(&mut state.findings, result.findings);
+        let pass_findings = &state.findings[pass_findings_start..];
+        let stop_finding = first_stopping_finding(pass_findings, config);
+        let saturated = saturation
+            .as_mut()
+            .is_some_and(|tracker| tracker.observe(node_coverage, pass_findings));
+        state.next_pass += 1;
+
+        if let Some(report) = progress {
+            report(CampaignProgress {
+                passes_done: state.next_pass,
+                passes_total: config.max_passes,
+                total_actions: state.total_actions,
+                findings_so_far: state.findings.len(),
+                unmet_min_hits: state.coverage.unmet_min_hits(targets).len(),
+            });
         }
 
-        all_findings.extend(result.findings);
+        if let Some(finding) = stop_finding {
+            flush_coordinator(coordinator, &mut state.weight_table, &alt_blocks);
+            let mut result = state.result(StopReason::StopOnFinding, targets);
+            result.stop_finding = Some(finding);
+            return (result, None);
+        }
+        if saturated {
+            flush_coordinator(coordinator, &mut state.weight_table, &alt_blocks);
+            return (state.result(StopReason::Saturated, targets), None);
+        }
     }
 
-    CampaignResult {
-        findings: all_findings,
-        total_actions,
-        passes_completed: config.max_passes,
-        unique_nodes_visited: max_nodes_visited,
-        total_guard_failures,
+    flush_coordinator(coordinator, &mut state.weight_table, &alt_blocks);
+    (state.result(StopReason::MaxPasses, targets), None)
+}
+
+/// Close the coordinator's partial epoch when a campaign ends, so the
+/// signals of its last passes still reach the weights the result reports.
+fn flush_coordinator(
+    coordinator: Option<&mut Coordinator>,
+    weight_table: &mut WeightTable,
+    alt_blocks: &[Vec<String>],
+) {
+    if let Some(coordinator) = coordinator {
+        coordinator.flush(weight_table, alt_blocks);
     }
 }
 
+/// Append a pass's `findings` to the campaign's, renumbering them so ids
+/// stay unique across passes and threads: each pass's engine counts from 0.
+fn append_findings(campaign: &mut Vec<Finding>, findings: Vec<Finding>) {
+    for mut finding in findings {
+        finding.id = campaign.len() as u64;
+        campaign.push(finding);
+    }
+}
+
+/// Branch ids of every alt block in the graph, for weight normalization.
+fn alt_block_branches(graph: &NdaGraph) -> Vec<Vec<String>> {
+    graph
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            GraphNode::Branch { alternatives, .. } => {
+                Some(alternatives.iter().map(|alt| alt.id.clone()).collect())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// First finding that meets the campaign's `stop_on_finding` threshold.
+/// Severities order most severe first, so "at least" means `<=`.
+fn first_stopping_finding(findings: &[Finding], config: &CampaignConfig) -> Option<Finding> {
+    let threshold = config.stop_on_finding?;
+    findings.iter().find(|f| f.severity <= threshold).cloned()
+}
+
+/// One scheduled action of an interleaved campaign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterleavedStep {
+    /// Pass the step belongs to.
+    pub pass: u32,
+    /// Logical thread that took the step.
+    pub thread_id: u32,
+    /// Action executed (or blocked by its guard) in this step.
+    pub action: String,
+}
+
+/// Result of an interleaved campaign run.
+#[derive(Debug)]
+pub struct InterleavedCampaignResult {
+    /// Aggregate result over all threads and passes.
+    pub result: CampaignResult,
+    /// Every scheduled step, in execution order.
+    pub schedule: Vec<InterleavedStep>,
+}
+
+/// Run a campaign with `ir.exploration.concurrency.threads` logical
+/// threads interleaved over the shared model.
+///
+/// Every pass, each thread walks the graph with its own strategy stack,
+/// seeded from `config.seed` and its thread id. A deterministic
+/// round-robin scheduler steps one action per thread per tick, in
+/// thread-id order, until every thread has finished its walk, so the
+/// interleaving is reproducible for a given seed. Signals carry the id
+/// of the thread that emitted them.
+///
+/// With a `coordinator`, every thread's signals are fed to it after each
+/// pass, ordered by `(thread_id, local_step)`, and its directives reach
+/// every thread's strategy stack, as in [`run_campaign_adaptive`].
+///
+/// Fails with [`ExploreError::Cancelled`] as `run_campaign` does.
+#[allow(clippy::too_many_arguments)]
+pub fn run_campaign_interleaved<V: VectorSource, E: ActionExecutor>(
+    graph: &NdaGraph,
+    model: &mut ModelState,
+    executor: &mut E,
+    ir: &FresnelFirIR,
+    invariants: &[CompiledProperty],
+    actor_id: InstanceId,
+    vector_source: &mut V,
+    config: &CampaignConfig,
+    mut coordinator: Option<&mut Coordinator>,
+) -> Result<InterleavedCampaignResult, ExploreError> {
+    let threads = ir.exploration.concurrency.threads.max(1);
+    let mut strategy_stacks: Vec<StrategyStack> = (0..threads)
+        .map(|thread_id| {
+            let rng = RngStreams::new(config.seed).thread(thread_id);
+            let mut base_strategy: Box<dyn Strategy> = Box::new(PseudoRandomStrategy::new(rng));
+            if coordinator.is_some() {
+                base_strategy = Box::new(DirectiveAwareStrategy::new(base_strategy, graph));
+            }
+            StrategyStack::new(
+                base_strategy,
+                config.strategy_depth_limit,
+                config.strategy_eviction,
+            )
+        })
+        .collect();
+    let targets = &ir.inputs.coverage.targets;
+    let alt_blocks = alt_block_branches(graph);
+    let mut weight_table = config.initial_weights.clone().unwrap_or_default();
+    let mut covered = CampaignCoverage::default();
+    let replay = config.replay_context(ir);
+    let mut thread_coverage = vec![CoverageReport::default(); threads as usize];
+
+    #[allow(deprecated)] // Kept in step with `stop_reason` by `set_stop_reason`.
+    let mut result = CampaignResult {
+        findings: Vec::new(),
+        total_actions: 0,
+        passes_completed: 0,
+        unique_nodes_visited: 0,
+        total_guard_failures: 0,
+        coverage: CoverageReport::default(),
+        unmet_min_hits: Vec::new(),
+        cancelled: false,
+        stopped_early: false,
+        stop_finding: None,
+        stop_reason: StopReason::MaxPasses,
+        weight_table: WeightTable::new(),
+    };
+    let mut schedule = Vec::new();
+
+    for pass in 0..config.max_passes {
+        if config
+            .cancel
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed))
+        {
+            result.set_stop_reason(StopReason::Cancelled);
+            break;
+        }
+
+        let mut states: Vec<Option<PassState>> = (0..threads).map(|_| None).collect();
+        let mut finished = vec![false; states.len()];
+
+        while finished.contains(&false) {
+            for thread_id in 0..threads {
+                let slot = thread_id as usize;
+                if finished[slot] {
+                    continue;
+                }
+
+                let mut engine = TraversalEngine::new(
+                    graph,
+                    model,
+                    ExecutorRef(executor),
+                    ir,
+                    invariants,
+                    actor_id.clone(),
+                    &mut strategy_stacks[slot],
+                    vector_source,
+                    &mut weight_table,
+                )
+                .with_order(config.traversal_order)
+                .with_vector_retries(config.vector_retries)
+                .with_thread_id(thread_id)
+                .with_campaign_coverage(&mut covered)
+                .with_replay_context(replay.clone());
+                if let Some(state) = states[slot].take() {
+                    engine = engine.with_pass_state(state);
+                }
+
+                match engine.step_action(config.max_steps_per_pass) {
+                    Some(action) => schedule.push(InterleavedStep {
+                        pass,
+                        thread_id,
+                        action,
+                    }),
+                    None => finished[slot] = true,
+                }
+                states[slot] = Some(engine.into_pass_state());
+            }
+        }
+
+        let pass_findings_start = result.findings.len();
+        let mut pass_signals = Vec::new();
+        for (slot, state) in states.into_iter().enumerate() {
+            let Some(state) = state else { continue };
+            let pass_result = state.into_result();
+            result.total_actions += pass_result.actions_executed;
+            result.total_guard_failures += pass_result.guards_failed;
+            result.unique_nodes_visited =
+                result.unique_nodes_visited.max(pass_result.nodes_visited);
+            thread_coverage[slot].merge(&pass_result.coverage);
+            append_findings(&mut result.findings, pass_result.findings);
+            pass_signals.extend(pass_result.signals);
+        }
+        result.passes_completed += 1;
+
+        if let Some(coordinator) = coordinator.as_deref_mut() {
+            let coverage = CoverageReport::merge_all(&thread_coverage);
+            coordinator.set_uncovered_target_branches(uncovered_target_branches(
+                graph, &coverage, targets,
+            ));
+            let mut directives = Vec::new();
+            for signal in pass_signals {
+                directives.extend(coordinator.feed_signal(signal, &mut weight_table, &alt_blocks));
+            }
+            let node_coverage = covered.nodes.len() as f64 / graph.nodes.len().max(1) as f64;
+            directives.extend(coordinator.observe_coverage(
+                node_coverage,
+                &mut weight_table,
+                &alt_blocks,
+            ));
+            for strategy_stack in &mut strategy_stacks {
+                strategy_stack.accept_directives(&directives);
+            }
+        }
+
+        if let Some(finding) =
+            first_stopping_finding(&result.findings[pass_findings_start..], config)
+        {
+            result.stop_finding = Some(finding);
+            result.set_stop_reason(StopReason::StopOnFinding);
+            break;
+        }
+    }
+    if result.stop_reason != StopReason::Cancelled {
+        flush_coordinator(coordinator, &mut weight_table, &alt_blocks);
+    }
+    result.coverage = CoverageReport::merge_all(&thread_coverage);
+    result.weight_table = weight_table;
+    result.unmet_min_hits = result.coverage.unmet_min_hits(&ir.inputs.coverage.targets);
+
+    Ok(InterleavedCampaignResult {
+        result: completed(result)?,
+        schedule,
+    })
+}
+
 /// Wrapper to delegate ActionExecutor through a mutable reference.
 /// This lets run_campaign reuse a single executor across passes.
 struct ExecutorRef<'a, E: ActionExecutor>(&'a mut E);
@@ -117,4 +862,8 @@ impl<'a, E: ActionExecutor> ActionExecutor for ExecutorRef<'a, E> {
     ) -> super::engine::ActionOutcome {
         self.0.execute(action, vector)
     }
+
+    fn take_signals(&mut self) -> Vec<super::signal::SignalType> {
+        self.0.take_signals()
+    }
 }
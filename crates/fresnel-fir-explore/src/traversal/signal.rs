@@ -1,7 +1,12 @@
+use std::collections::HashMap;
+
+use fresnel_fir_compiler::graph::SourceSpan;
+use serde::{Deserialize, Serialize};
+
 /// Signals emitted by the traversal engine during action execution.
 /// These drive the adaptation layer (signal -> directive mappings).
 /// All signal types the engine can emit.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SignalType {
     /// A new state or transition was covered for the first time.
     CoverageDelta { node_id: u32, action: String },
@@ -27,26 +32,68 @@ pub enum SignalType {
         /// Abstract hash of the model state when the guard failed.
         model_state_hash: u64,
     },
+    /// Guard allowed the transition from current state.
+    GuardPass {
+        branch_id: String,
+        action: String,
+        /// Abstract hash of the model state when the guard passed.
+        model_state_hash: u64,
+    },
     /// Coverage delta rate approaching zero.
     CoveragePlateau {
         current_coverage: f64,
         delta_rate: f64,
     },
+    /// A `LoopLimit` capped a loop below its declared minimum; the
+    /// declared minimum was kept.
+    LoopLimitConflict {
+        loop_node_id: u32,
+        declared_min: u32,
+        limit_max: u32,
+    },
+    /// Every alternative at a branch node had zero weight, so the branch
+    /// was chosen uniformly instead of by weight.
+    ZeroWeightFallback { node_id: u32, branch_id: String },
 }
 
 /// A signal event with metadata for replay capsule construction.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SignalEvent {
     /// Thread ID that emitted this signal (0 for single-threaded).
     pub thread_id: u32,
     /// Monotonic step counter within the thread.
     pub local_step: u64,
+    /// Abstract hash of the model state when the signal was emitted.
+    pub model_state_hash: u64,
     /// The signal itself.
     pub signal_type: SignalType,
 }
 
+/// How urgently a finding needs attention, most severe first.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    #[default]
+    Low,
+}
+
+/// Classify a signal by the severity of the finding it represents.
+pub fn classify_severity(signal_type: &SignalType) -> Severity {
+    match signal_type {
+        SignalType::Crash { .. } | SignalType::PropertyViolation { .. } => Severity::Critical,
+        SignalType::Discrepancy { .. } => Severity::High,
+        SignalType::Timeout { .. } => Severity::Medium,
+        _ => Severity::Low,
+    }
+}
+
 /// A finding — a significant signal that should be reported to the user.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Finding {
     /// Unique ID for this finding within the campaign.
     pub id: u64,
@@ -56,4 +103,97 @@ pub struct Finding {
     pub trace_indices: Vec<usize>,
     /// Model generation at the time of the finding.
     pub model_generation: u64,
+    /// Severity derived from the triggering signal.
+    pub severity: Severity,
+    /// For property violations, the trace index of the action whose effect
+    /// first made the property false, which may precede the detecting step.
+    #[serde(default)]
+    pub breaking_trace_index: Option<usize>,
+    /// State needed to reproduce the finding, when the engine was given
+    /// a replay context.
+    #[serde(default)]
+    pub capsule: Option<ReplayCapsule>,
+    /// The spec element of the action that raised the finding.
+    #[serde(default)]
+    pub source_span: Option<SourceSpan>,
+}
+
+impl Finding {
+    /// Root-cause signature: the signal's type, action and details with
+    /// numbers blanked out, so the same crash reached along different
+    /// paths compares equal.
+    pub fn signature(&self) -> String {
+        let (kind, action, details) = match &self.signal.signal_type {
+            SignalType::Crash { action, message } => ("crash", action.as_str(), message.clone()),
+            SignalType::PropertyViolation { property, details } => {
+                ("property_violation", property.as_str(), details.clone())
+            }
+            SignalType::Discrepancy {
+                action,
+                model_value,
+                observed_value,
+            } => (
+                "discrepancy",
+                action.as_str(),
+                format!("model={model_value} observed={observed_value}"),
+            ),
+            SignalType::Timeout { action, .. } => ("timeout", action.as_str(), String::new()),
+            other => ("signal", "", format!("{other:?}")),
+        };
+        format!("{kind}|{action}|{}", normalize_details(&details))
+    }
+}
+
+/// Replace each run of digits (and a `0x` hex literal as a whole) with `#`
+/// and collapse whitespace.
+pub fn normalize_details(details: &str) -> String {
+    let mut out = String::with_capacity(details.len());
+    let mut chars = details.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            let hex = c == '0' && chars.peek() == Some(&'x');
+            if hex {
+                chars.next();
+            }
+            while chars
+                .peek()
+                .is_some_and(|d| d.is_ascii_digit() || (hex && d.is_ascii_hexdigit()))
+            {
+                chars.next();
+            }
+            out.push('#');
+        } else if c.is_whitespace() {
+            if !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out.trim().to_string()
+}
+
+/// A replay capsule — everything needed to reproduce a finding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayCapsule {
+    /// Hash of the IR that produced this finding.
+    pub ir_hash: String,
+    /// Hash of the WASM module that was under test.
+    pub wasm_hash: String,
+    /// RNG seed used in the campaign.
+    pub seed: u64,
+    /// Description of the finding.
+    pub finding_description: String,
+    /// Action that triggered the finding.
+    pub trigger_action: String,
+    /// Step number in the traversal trace.
+    pub trace_step: u64,
+    /// Model generation at finding time.
+    pub model_generation: u64,
+    /// Input vector assignments (serialized).
+    pub input_vector: HashMap<String, String>,
+    /// Severity of the finding. Capsules saved before severities were
+    /// recorded load as `Low`.
+    #[serde(default)]
+    pub severity: Severity,
 }
@@ -1,8 +1,13 @@
-use fresnel_fir_compiler::graph::BranchEdge;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+
+use fresnel_fir_compiler::graph::{BranchEdge, GraphNode, NdaGraph, NodeId};
 use rand::Rng;
 use rand_chacha::ChaCha8Rng;
 
-use super::weight_table::WeightTable;
+use super::engine::CoverageReport;
+use super::weight_table::{WeightTable, ZERO_WEIGHT_EPSILON};
+use crate::adapt::directive::Directive;
 
 /// Strategy decision at an alt node — which branch to take.
 #[derive(Debug, Clone)]
@@ -10,6 +15,12 @@ pub struct BranchDecision {
     pub branch_index: usize,
     pub branch_id: String,
     pub weight_used: f64,
+    /// The raw draw, scaled to `[0, total weight)`. Meaningless when
+    /// `cumulative_weights` is empty.
+    pub roll: f64,
+    /// Running weight totals of the live candidates, in roll order. Empty
+    /// when the decision was not a weighted draw (forced or uniform fallback).
+    pub cumulative_weights: Vec<(String, f64)>,
 }
 
 /// Strategy decision at a repeat node — how many iterations.
@@ -21,16 +32,33 @@ pub struct RepeatDecision {
 /// A traversal strategy — the "brain" that makes decisions at branch/loop points.
 pub trait Strategy {
     /// Select a branch at an alt node, given the current model state hash.
+    /// `None` when `branches` is empty.
     fn select_branch(
         &mut self,
         branches: &[BranchEdge],
         model_state_hash: u64,
         weight_table: &WeightTable,
-    ) -> BranchDecision;
+    ) -> Option<BranchDecision>;
 
     /// Choose iteration count at a repeat node.
     fn choose_iterations(&mut self, min: u32, max: u32) -> RepeatDecision;
 
+    /// Choose iteration count at a specific loop node.
+    /// Defaults to `choose_iterations`, ignoring the node.
+    fn choose_loop_iterations(&mut self, _loop_node: NodeId, min: u32, max: u32) -> RepeatDecision {
+        self.choose_iterations(min, max)
+    }
+
+    /// Receive directives emitted by the adaptation coordinator.
+    /// Ignored by default.
+    fn accept_directives(&mut self, _directives: &[Directive]) {}
+
+    /// Snapshot of the strategy's RNG, for checkpointing a campaign.
+    /// `None` for strategies without random state.
+    fn rng_state(&self) -> Option<ChaCha8Rng> {
+        None
+    }
+
     /// Name of this strategy (for tracing).
     fn name(&self) -> &str;
 }
@@ -55,45 +83,100 @@ impl Strategy for PseudoRandomStrategy {
         branches: &[BranchEdge],
         model_state_hash: u64,
         weight_table: &WeightTable,
-    ) -> BranchDecision {
-        // Collect state-conditioned weights
-        let weights: Vec<f64> = branches
-            .iter()
-            .map(|b| weight_table.get(&b.id, model_state_hash).max(0.0))
-            .collect();
-
-        let total: f64 = weights.iter().sum();
-        if total <= 0.0 {
-            // Fallback: pick first branch
-            return BranchDecision {
-                branch_index: 0,
-                branch_id: branches[0].id.clone(),
-                weight_used: 0.0,
-            };
-        }
+    ) -> Option<BranchDecision> {
+        let all: Vec<usize> = (0..branches.len()).collect();
+        weighted_select(
+            &mut self.rng,
+            branches,
+            &all,
+            model_state_hash,
+            weight_table,
+        )
+    }
 
-        // Weighted random selection
-        let mut roll: f64 = self.rng.gen::<f64>() * total;
-        for (i, (branch, &weight)) in branches.iter().zip(weights.iter()).enumerate() {
-            roll -= weight;
-            if roll <= 0.0 {
-                return BranchDecision {
-                    branch_index: i,
-                    branch_id: branch.id.clone(),
-                    weight_used: weight,
-                };
-            }
-        }
+    fn choose_iterations(&mut self, min: u32, max: u32) -> RepeatDecision {
+        let iterations = if min == max {
+            min
+        } else {
+            self.rng.gen_range(min..=max)
+        };
+        RepeatDecision { iterations }
+    }
+
+    fn rng_state(&self) -> Option<ChaCha8Rng> {
+        Some(self.rng.clone())
+    }
+
+    fn name(&self) -> &str {
+        "pseudo_random"
+    }
+}
 
-        // Shouldn't reach here, but fallback to last
-        let last = branches.len() - 1;
-        BranchDecision {
-            branch_index: last,
-            branch_id: branches[last].id.clone(),
-            weight_used: weights[last],
+/// Coverage-guided traversal strategy.
+///
+/// At alt nodes, restricts the choice to branches that still lead somewhere
+/// new: branches never selected according to the shared coverage report,
+/// or branches registered as reaching an uncovered target. Among those it
+/// picks by weight like `PseudoRandomStrategy`; once every local branch is
+/// covered it falls back to the full weight table.
+pub struct CoverageGuidedStrategy {
+    rng: ChaCha8Rng,
+    coverage: Arc<RwLock<CoverageReport>>,
+    uncovered_target_branches: Vec<String>,
+}
+
+impl CoverageGuidedStrategy {
+    pub fn new(
+        rng: ChaCha8Rng,
+        coverage: Arc<RwLock<CoverageReport>>,
+        uncovered_target_branches: Vec<String>,
+    ) -> Self {
+        Self {
+            rng,
+            coverage,
+            uncovered_target_branches,
         }
     }
 
+    /// Replace the set of branches known to reach uncovered targets.
+    pub fn set_uncovered_target_branches(&mut self, branches: Vec<String>) {
+        self.uncovered_target_branches = branches;
+    }
+
+    fn is_uncovered(&self, branch: &BranchEdge, coverage: &CoverageReport) -> bool {
+        !coverage.branch_counts.contains_key(&branch.id)
+            || self.uncovered_target_branches.contains(&branch.id)
+    }
+}
+
+impl Strategy for CoverageGuidedStrategy {
+    fn select_branch(
+        &mut self,
+        branches: &[BranchEdge],
+        model_state_hash: u64,
+        weight_table: &WeightTable,
+    ) -> Option<BranchDecision> {
+        let uncovered: Vec<usize> = {
+            let coverage = self.coverage.read().expect("coverage lock poisoned");
+            (0..branches.len())
+                .filter(|&i| self.is_uncovered(&branches[i], &coverage))
+                .collect()
+        };
+
+        let candidates = if uncovered.is_empty() {
+            (0..branches.len()).collect()
+        } else {
+            uncovered
+        };
+        weighted_select(
+            &mut self.rng,
+            branches,
+            &candidates,
+            model_state_hash,
+            weight_table,
+        )
+    }
+
     fn choose_iterations(&mut self, min: u32, max: u32) -> RepeatDecision {
         let iterations = if min == max {
             min
@@ -103,57 +186,399 @@ impl Strategy for PseudoRandomStrategy {
         RepeatDecision { iterations }
     }
 
+    fn rng_state(&self) -> Option<ChaCha8Rng> {
+        Some(self.rng.clone())
+    }
+
     fn name(&self) -> &str {
-        "pseudo_random"
+        "coverage_guided"
+    }
+}
+
+/// Strategy wrapper that acts on `Force` and `LoopLimit` directives.
+///
+/// While a `Force` is active, alt nodes take the first branch leading to the
+/// forced action (by branch id, or by an action reachable from the branch
+/// target), spending one unit of budget per forced selection. Forces are
+/// honoured in the order received. `LoopLimit` narrows the iteration range
+/// of its loop node within the declared min/max. Everything else is
+/// delegated to the wrapped strategy.
+pub struct DirectiveAwareStrategy {
+    inner: Box<dyn Strategy>,
+    /// Active forces: (action, remaining budget).
+    forces: VecDeque<(String, u32)>,
+    /// Active loop limits: loop node -> (min, max).
+    loop_limits: HashMap<NodeId, (u32, u32)>,
+    /// Actions reachable from each branch target.
+    leads_to: HashMap<NodeId, BTreeSet<String>>,
+}
+
+impl DirectiveAwareStrategy {
+    pub fn new(inner: Box<dyn Strategy>, graph: &NdaGraph) -> Self {
+        let mut leads_to = HashMap::new();
+        for node in &graph.nodes {
+            if let GraphNode::Branch { alternatives, .. } = node {
+                for alt in alternatives {
+                    leads_to
+                        .entry(alt.target)
+                        .or_insert_with(|| reachable_actions(graph, alt.target));
+                }
+            }
+        }
+        Self {
+            inner,
+            forces: VecDeque::new(),
+            loop_limits: HashMap::new(),
+            leads_to,
+        }
+    }
+
+    /// Active forces and their remaining budgets, in priority order.
+    pub fn active_forces(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.forces.iter().map(|(a, b)| (a.as_str(), *b))
+    }
+
+    /// The active loop limit for a loop node, if any.
+    pub fn loop_limit(&self, loop_node: NodeId) -> Option<(u32, u32)> {
+        self.loop_limits.get(&loop_node).copied()
+    }
+
+    fn leads_to(&self, branch: &BranchEdge, action: &str) -> bool {
+        branch.id == action
+            || self
+                .leads_to
+                .get(&branch.target)
+                .is_some_and(|actions| actions.contains(action))
     }
 }
 
+impl Strategy for DirectiveAwareStrategy {
+    fn select_branch(
+        &mut self,
+        branches: &[BranchEdge],
+        model_state_hash: u64,
+        weight_table: &WeightTable,
+    ) -> Option<BranchDecision> {
+        let forced = self.forces.iter().enumerate().find_map(|(f, (action, _))| {
+            branches
+                .iter()
+                .position(|b| self.leads_to(b, action))
+                .map(|i| (f, i))
+        });
+
+        if let Some((f, i)) = forced {
+            self.forces[f].1 -= 1;
+            if self.forces[f].1 == 0 {
+                self.forces.remove(f);
+            }
+            return Some(BranchDecision {
+                branch_index: i,
+                branch_id: branches[i].id.clone(),
+                weight_used: weight_table.get(&branches[i].id, model_state_hash),
+                roll: 0.0,
+                cumulative_weights: Vec::new(),
+            });
+        }
+
+        self.inner
+            .select_branch(branches, model_state_hash, weight_table)
+    }
+
+    fn choose_iterations(&mut self, min: u32, max: u32) -> RepeatDecision {
+        self.inner.choose_iterations(min, max)
+    }
+
+    fn choose_loop_iterations(&mut self, loop_node: NodeId, min: u32, max: u32) -> RepeatDecision {
+        let (min, max) = match self.loop_limits.get(&loop_node) {
+            Some(&(new_min, new_max)) => {
+                let lo = new_min.clamp(min, max);
+                (lo, new_max.clamp(lo, max))
+            }
+            None => (min, max),
+        };
+        self.inner.choose_loop_iterations(loop_node, min, max)
+    }
+
+    fn accept_directives(&mut self, directives: &[Directive]) {
+        for directive in directives {
+            match directive {
+                Directive::Force { action, budget } if *budget > 0 => {
+                    self.forces.push_back((action.clone(), *budget));
+                }
+                Directive::LoopLimit {
+                    loop_node_id,
+                    new_min,
+                    new_max,
+                } => {
+                    self.loop_limits.insert(*loop_node_id, (*new_min, *new_max));
+                }
+                _ => {}
+            }
+        }
+        self.inner.accept_directives(directives);
+    }
+
+    fn rng_state(&self) -> Option<ChaCha8Rng> {
+        self.inner.rng_state()
+    }
+
+    fn name(&self) -> &str {
+        "directive_aware"
+    }
+}
+
+/// Collect every action reachable from `start` (inclusive).
+pub(crate) fn reachable_actions(graph: &NdaGraph, start: NodeId) -> BTreeSet<String> {
+    let mut actions = BTreeSet::new();
+    let mut seen = BTreeSet::new();
+    let mut pending = vec![start];
+
+    while let Some(node_id) = pending.pop() {
+        if !seen.insert(node_id) {
+            continue;
+        }
+        match &graph.nodes[node_id as usize] {
+            GraphNode::Terminal { action, .. } => {
+                actions.insert(action.clone());
+            }
+            GraphNode::Branch { alternatives, .. } => {
+                pending.extend(alternatives.iter().map(|a| a.target));
+            }
+            GraphNode::LoopEntry { body_start, .. } => pending.push(*body_start),
+            _ => {}
+        }
+        pending.extend(
+            graph
+                .edges
+                .iter()
+                .filter(|&&(from, _)| from == node_id)
+                .map(|&(_, to)| to),
+        );
+    }
+
+    actions
+}
+
+/// Weighted random choice among `candidates` (indices into `branches`),
+/// using state-conditioned weights from the table. `None` when there are
+/// no candidates.
+fn weighted_select(
+    rng: &mut ChaCha8Rng,
+    branches: &[BranchEdge],
+    candidates: &[usize],
+    model_state_hash: u64,
+    weight_table: &WeightTable,
+) -> Option<BranchDecision> {
+    // Collect state-conditioned weights, dropping zeroed branches so the
+    // roll can never land on them.
+    let live: Vec<(usize, f64)> = candidates
+        .iter()
+        .map(|&i| (i, weight_table.get(&branches[i].id, model_state_hash)))
+        .filter(|&(_, weight)| weight > ZERO_WEIGHT_EPSILON)
+        .collect();
+
+    if live.is_empty() {
+        // Fallback: every candidate is zeroed, pick uniformly among them.
+        // The engine only offers branches whose guard holds here.
+        if candidates.is_empty() {
+            return None;
+        }
+        let pick = candidates[rng.gen_range(0..candidates.len())];
+        return Some(BranchDecision {
+            branch_index: pick,
+            branch_id: branches[pick].id.clone(),
+            weight_used: 0.0,
+            roll: 0.0,
+            cumulative_weights: Vec::new(),
+        });
+    }
+
+    // Weighted random selection over the running totals, so the decision
+    // can be replayed from the recorded roll alone.
+    let mut total = 0.0;
+    let cumulative_weights: Vec<(String, f64)> = live
+        .iter()
+        .map(|&(i, weight)| {
+            total += weight;
+            (branches[i].id.clone(), total)
+        })
+        .collect();
+    let roll: f64 = rng.gen::<f64>() * total;
+    let pos = roll_position(&cumulative_weights, roll);
+    let (i, weight) = live[pos];
+    Some(BranchDecision {
+        branch_index: i,
+        branch_id: branches[i].id.clone(),
+        weight_used: weight,
+        roll,
+        cumulative_weights,
+    })
+}
+
+/// Replay a weighted draw: the branch a `roll` lands on in a cumulative
+/// weight table, as recorded in `BranchDecision` and the trace. `None` for
+/// an empty table.
+pub fn select_by_roll(cumulative_weights: &[(String, f64)], roll: f64) -> Option<&str> {
+    if cumulative_weights.is_empty() {
+        return None;
+    }
+    let pos = roll_position(cumulative_weights, roll);
+    Some(cumulative_weights[pos].0.as_str())
+}
+
+/// First entry whose running total reaches `roll`; the last entry if
+/// rounding leaves the roll past the end.
+fn roll_position(cumulative_weights: &[(String, f64)], roll: f64) -> usize {
+    cumulative_weights
+        .iter()
+        .position(|&(_, cumulative)| roll <= cumulative)
+        .unwrap_or(cumulative_weights.len() - 1)
+}
+
+/// Which non-base strategy a full `StrategyStack` evicts on push.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvictionPolicy {
+    /// Evict the oldest pushed strategy.
+    #[default]
+    Fifo,
+    /// Evict the strategy least recently returned by `current()`.
+    /// Strategies never consulted count as least recent; ties go to the
+    /// oldest.
+    Lru,
+}
+
+/// Iteration bounds for a loop after applying any active `LoopLimit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopBounds {
+    pub min: u32,
+    pub max: u32,
+    /// The limit's cap was below the declared minimum. The declared
+    /// minimum wins, so `min == max` and the limit is not honoured.
+    pub conflict: bool,
+}
+
 /// Strategy stack — supports push/pop for nested strategy changes.
 /// Depth limit prevents unbounded growth.
+///
+/// The stack also keeps the `LoopLimit` directives it has been handed, so
+/// they stay in force whichever strategy is on top.
 pub struct StrategyStack {
     stack: Vec<Box<dyn Strategy>>,
+    /// Logical time each frame was last returned by `current()`
+    /// (0 = never). Parallel to `stack`.
+    last_used: Vec<u64>,
+    /// Logical clock, advanced on every `current()` call.
+    clock: u64,
     depth_limit: usize,
+    eviction: EvictionPolicy,
+    /// Active loop limits: loop node -> (min, max).
+    loop_limits: HashMap<NodeId, (u32, u32)>,
 }
 
 impl StrategyStack {
-    pub fn new(base: Box<dyn Strategy>, depth_limit: usize) -> Self {
+    pub fn new(base: Box<dyn Strategy>, depth_limit: usize, eviction: EvictionPolicy) -> Self {
         Self {
             stack: vec![base],
+            last_used: vec![0],
+            clock: 0,
             depth_limit,
+            eviction,
+            loop_limits: HashMap::new(),
         }
     }
 
     /// Get the current (top) strategy.
     pub fn current(&mut self) -> &mut dyn Strategy {
+        self.clock += 1;
+        *self
+            .last_used
+            .last_mut()
+            .expect("strategy stack is never empty") = self.clock;
         self.stack
             .last_mut()
             .expect("strategy stack is never empty")
             .as_mut()
     }
 
-    /// Push a new strategy. If depth limit exceeded, pop the oldest.
+    /// Push a new strategy. If the depth limit is reached, evict a
+    /// non-base strategy according to the eviction policy.
     pub fn push(&mut self, strategy: Box<dyn Strategy>) {
-        if self.stack.len() >= self.depth_limit {
-            // Remove the second-oldest (keep base)
-            if self.stack.len() > 1 {
-                self.stack.remove(1);
-            }
+        if self.stack.len() >= self.depth_limit && self.stack.len() > 1 {
+            // Index 0 is the base strategy and is never evicted.
+            let victim = match self.eviction {
+                EvictionPolicy::Fifo => 1,
+                EvictionPolicy::Lru => (1..self.stack.len())
+                    .min_by_key(|&i| self.last_used[i])
+                    .expect("stack has a non-base frame"),
+            };
+            self.stack.remove(victim);
+            self.last_used.remove(victim);
         }
         self.stack.push(strategy);
+        self.last_used.push(0);
     }
 
     /// Pop the current strategy, returning to the previous one.
     /// Never pops the base strategy.
     pub fn pop(&mut self) -> Option<Box<dyn Strategy>> {
         if self.stack.len() > 1 {
+            self.last_used.pop();
             self.stack.pop()
         } else {
             None
         }
     }
 
+    /// Record loop limits and hand coordinator directives to the current
+    /// strategy.
+    pub fn accept_directives(&mut self, directives: &[Directive]) {
+        for directive in directives {
+            if let Directive::LoopLimit {
+                loop_node_id,
+                new_min,
+                new_max,
+            } = directive
+            {
+                self.loop_limits.insert(*loop_node_id, (*new_min, *new_max));
+            }
+        }
+        self.current().accept_directives(directives);
+    }
+
+    /// The active loop limit for a loop node, if any.
+    pub fn loop_limit(&self, loop_node: NodeId) -> Option<(u32, u32)> {
+        self.loop_limits.get(&loop_node).copied()
+    }
+
+    /// Narrow a loop's declared `min..=max` by its active `LoopLimit`.
+    ///
+    /// The limit's cap never pushes the range below the declared minimum;
+    /// when it would, the range collapses to `min` and `conflict` is set.
+    pub fn loop_bounds(&self, loop_node: NodeId, min: u32, max: u32) -> LoopBounds {
+        let Some(&(new_min, new_max)) = self.loop_limits.get(&loop_node) else {
+            return LoopBounds {
+                min,
+                max,
+                conflict: false,
+            };
+        };
+        let hi = new_max.min(max).max(min);
+        LoopBounds {
+            min: new_min.clamp(min, hi),
+            max: hi,
+            conflict: new_max < min,
+        }
+    }
+
     /// Current stack depth.
     pub fn depth(&self) -> usize {
         self.stack.len()
     }
+
+    /// RNG state of the base strategy, for checkpointing a campaign.
+    /// Frames pushed on top are not checkpointed. `None` when the base
+    /// strategy has no random state.
+    pub fn rng_state(&self) -> Option<ChaCha8Rng> {
+        self.stack[0].rng_state()
+    }
 }
@@ -1,7 +1,8 @@
-use fresnel_fir_compiler::graph::NodeId;
+use fresnel_fir_compiler::graph::{NodeId, SourceSpan};
+use serde::{Deserialize, Serialize};
 
 /// A single step in the traversal trace, for replay capsule construction.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TraceStep {
     /// The graph node that was visited.
     pub node_id: NodeId,
@@ -9,17 +10,30 @@ pub struct TraceStep {
     pub kind: TraceStepKind,
     /// Step number (monotonic within the traversal).
     pub step_number: u64,
+    /// The spec element the visited node was compiled from, if known.
+    #[serde(default)]
+    pub source_span: Option<SourceSpan>,
 }
 
 /// The kind of traversal step taken.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TraceStepKind {
     /// Entered a start node.
     Start,
     /// Reached an end node.
     End,
     /// Selected a branch in an alt block.
-    BranchSelected { branch_id: String, weight_used: f64 },
+    BranchSelected {
+        branch_id: String,
+        weight_used: f64,
+        /// The draw that picked the branch; see `BranchDecision::roll`.
+        #[serde(default)]
+        roll: f64,
+        /// Running weight totals the roll was applied to. Empty when the
+        /// branch was not picked by a weighted draw.
+        #[serde(default)]
+        cumulative_weights: Vec<(String, f64)>,
+    },
     /// Entered a loop.
     LoopEnter { iterations_chosen: u32 },
     /// Exited a loop.
@@ -36,7 +50,7 @@ pub enum TraceStepKind {
 }
 
 /// Full traversal trace for a campaign run.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TraversalTrace {
     steps: Vec<TraceStep>,
     next_step: u64,
@@ -51,10 +65,21 @@ impl TraversalTrace {
     }
 
     pub fn record(&mut self, node_id: NodeId, kind: TraceStepKind) {
+        self.record_at(node_id, kind, None);
+    }
+
+    /// Record a step together with the spec element of its node.
+    pub fn record_at(
+        &mut self,
+        node_id: NodeId,
+        kind: TraceStepKind,
+        source_span: Option<SourceSpan>,
+    ) {
         self.steps.push(TraceStep {
             node_id,
             kind,
             step_number: self.next_step,
+            source_span,
         });
         self.next_step += 1;
     }
@@ -1,10 +1,15 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::solver::coverage::CoveragePoint;
+use crate::solver::pool::VectorPool;
 use crate::solver::{DomainValue, TestVector};
 
 /// Abstract source of input vectors for action execution.
 ///
 /// Abstracted behind a trait so we can:
 /// - Use MockVectorSource for testing (returns predefined vectors)
-/// - Plug in the real solver's vector pool later
+/// - Draw from the solver's vector pool in real campaigns (PoolVectorSource)
 pub trait VectorSource {
     /// Get the next input vector for the given action.
     /// Returns None if no vectors are available.
@@ -70,3 +75,84 @@ impl VectorSource for MockVectorSource {
         Some(tv)
     }
 }
+
+/// Vector source backed by the solver's pre-generated `VectorPool`.
+///
+/// Actions mapped to a coverage target draw from that target's queue,
+/// falling back to the general queue; all other actions draw from the
+/// general queue. Returns `None` once the pool is exhausted, leaving it
+/// to the caller whether to wait for the producer or run without inputs.
+#[derive(Debug)]
+pub struct PoolVectorSource {
+    pool: Arc<VectorPool>,
+    /// Action name -> coverage target whose queue it draws from.
+    action_targets: HashMap<String, CoveragePoint>,
+}
+
+impl PoolVectorSource {
+    pub fn new(pool: Arc<VectorPool>) -> Self {
+        Self {
+            pool,
+            action_targets: HashMap::new(),
+        }
+    }
+
+    /// Draw vectors for `action` from `target`'s queue first.
+    pub fn with_action_target(mut self, action: &str, target: CoveragePoint) -> Self {
+        self.action_targets.insert(action.to_string(), target);
+        self
+    }
+}
+
+impl VectorSource for PoolVectorSource {
+    fn next_vector(&mut self, action: &str) -> Option<TestVector> {
+        match self.action_targets.get(action) {
+            Some(target) => self.pool.pop_targeted(target),
+            None => self.pool.pop_general(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(amount: i64) -> TestVector {
+        let mut tv = TestVector::new();
+        tv.assignments
+            .insert("amount".to_string(), DomainValue::Int(amount));
+        tv
+    }
+
+    #[test]
+    fn test_pool_source_drains_general_in_order() {
+        let pool = VectorPool::new(8);
+        assert_eq!(pool.push_many(vec![vector(1), vector(2), vector(3)]), 3);
+        let mut source = PoolVectorSource::new(Arc::new(pool));
+
+        assert_eq!(source.next_vector("deposit"), Some(vector(1)));
+        assert_eq!(source.next_vector("withdraw"), Some(vector(2)));
+        assert_eq!(source.next_vector("deposit"), Some(vector(3)));
+        assert_eq!(source.next_vector("deposit"), None);
+    }
+
+    #[test]
+    fn test_pool_source_prefers_action_target() {
+        let target = CoveragePoint::Boundary {
+            var: "amount".into(),
+            value: DomainValue::Int(0),
+        };
+        let mut pool = VectorPool::new(8);
+        pool.register_target(target.clone());
+        assert!(pool.push_general(vector(5)));
+        assert!(pool.push_targeted(&target, vector(0)));
+
+        let mut source =
+            PoolVectorSource::new(Arc::new(pool)).with_action_target("deposit", target);
+
+        // Targeted queue first, then the general fallback.
+        assert_eq!(source.next_vector("deposit"), Some(vector(0)));
+        assert_eq!(source.next_vector("deposit"), Some(vector(5)));
+        assert_eq!(source.next_vector("deposit"), None);
+    }
+}
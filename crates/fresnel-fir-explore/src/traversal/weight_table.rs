@@ -1,5 +1,12 @@
 use std::collections::HashMap;
 
+/// Weights at or below this are treated as zero: the branch is unreachable.
+pub const ZERO_WEIGHT_EPSILON: f64 = 1e-9;
+
+/// Default ceiling for any single weight, so repeated boosts cannot
+/// overflow to infinity.
+pub const DEFAULT_MAX_WEIGHT: f64 = 1e6;
+
 /// Key for the weight table: (AltBranchId, AbstractModelStateId).
 ///
 /// Weights are state-conditioned — "branch B is unproductive WHEN model is in
@@ -19,6 +26,10 @@ pub struct WeightTable {
     weights: HashMap<WeightKey, f64>,
     /// Default weights per branch ID (from protocol definition).
     defaults: HashMap<String, f64>,
+    /// Floor applied by `set` and `adjust`.
+    min_weight: f64,
+    /// Ceiling applied by `set` and `adjust`.
+    max_weight: f64,
 }
 
 impl WeightTable {
@@ -26,9 +37,28 @@ impl WeightTable {
         Self {
             weights: HashMap::new(),
             defaults: HashMap::new(),
+            min_weight: 0.0,
+            max_weight: DEFAULT_MAX_WEIGHT,
         }
     }
 
+    /// Bound every weight written through `set` and `adjust` to
+    /// `[min_weight, max_weight]`. Defaults to `[0.0, DEFAULT_MAX_WEIGHT]`.
+    pub fn with_bounds(mut self, min_weight: f64, max_weight: f64) -> Self {
+        assert!(
+            min_weight <= max_weight,
+            "min_weight {min_weight} exceeds max_weight {max_weight}"
+        );
+        self.min_weight = min_weight;
+        self.max_weight = max_weight;
+        self
+    }
+
+    /// The `(min_weight, max_weight)` bounds applied on write.
+    pub fn bounds(&self) -> (f64, f64) {
+        (self.min_weight, self.max_weight)
+    }
+
     /// Set the default weight for a branch (from protocol definition).
     pub fn set_default(&mut self, branch_id: &str, weight: f64) {
         self.defaults.insert(branch_id.to_string(), weight);
@@ -48,16 +78,17 @@ impl WeightTable {
         }
     }
 
-    /// Set a state-conditioned weight.
+    /// Set a state-conditioned weight, clamped to the table's bounds.
     pub fn set(&mut self, branch_id: &str, model_state_hash: u64, weight: f64) {
         let key = WeightKey {
             branch_id: branch_id.to_string(),
             model_state_hash,
         };
-        self.weights.insert(key, weight);
+        self.weights
+            .insert(key, weight.clamp(self.min_weight, self.max_weight));
     }
 
-    /// Adjust a weight by a multiplier.
+    /// Adjust a weight by a multiplier. The result is clamped like `set`.
     pub fn adjust(&mut self, branch_id: &str, model_state_hash: u64, multiplier: f64) {
         let current = self.get(branch_id, model_state_hash);
         self.set(branch_id, model_state_hash, current * multiplier);
@@ -66,6 +97,8 @@ impl WeightTable {
     /// Normalize all weights for branches sharing the same alt block.
     /// Branch IDs within the same alt block should share a common prefix.
     /// Takes a set of branch IDs to normalize together, target sum defaults to 100.
+    /// Operates on the clamped weights, so a branch pinned at the ceiling
+    /// cannot push the sum to infinity.
     pub fn normalize(&mut self, branch_ids: &[&str], model_state_hash: u64) {
         let total: f64 = branch_ids
             .iter()
@@ -89,6 +122,16 @@ impl WeightTable {
         }
     }
 
+    /// Subtract `amount` from all positive state-conditioned weights,
+    /// never taking them below `floor`. Zero weights are preserved.
+    pub fn reduce_all(&mut self, amount: f64, floor: f64) {
+        for weight in self.weights.values_mut() {
+            if *weight > 0.0 {
+                *weight = (*weight - amount).max(floor);
+            }
+        }
+    }
+
     /// Clamp all state-conditioned weights to a minimum value.
     /// Weights already at 0.0 (provably unreachable) are preserved.
     pub fn clamp_min(&mut self, min_weight: f64) {
@@ -99,6 +142,54 @@ impl WeightTable {
         }
     }
 
+    /// All weights as `(branch_id, model_state_hash, weight)`, heaviest
+    /// first, ties by branch id then state hash.
+    ///
+    /// Default weights are listed under state hash 0, unless a weight set
+    /// for that state overrides them.
+    pub fn ranked_entries(&self) -> Vec<(String, u64, f64)> {
+        let mut entries: Vec<(String, u64, f64)> = self
+            .weights
+            .iter()
+            .map(|(key, &w)| (key.branch_id.clone(), key.model_state_hash, w))
+            .collect();
+        for (branch_id, &w) in &self.defaults {
+            let key = WeightKey {
+                branch_id: branch_id.clone(),
+                model_state_hash: 0,
+            };
+            if !self.weights.contains_key(&key) {
+                entries.push((branch_id.clone(), 0, w));
+            }
+        }
+        entries.sort_by(|a, b| {
+            b.2.total_cmp(&a.2)
+                .then_with(|| (&a.0, a.1).cmp(&(&b.0, b.1)))
+        });
+        entries
+    }
+
+    /// The `n` heaviest branches in a model state, with the weight `get`
+    /// would return for each. Heaviest first, ties by branch id.
+    pub fn top_n(&self, n: usize, model_state_hash: u64) -> Vec<(String, f64)> {
+        let mut branches: Vec<&String> = self
+            .weights
+            .keys()
+            .map(|key| &key.branch_id)
+            .chain(self.defaults.keys())
+            .collect();
+        branches.sort();
+        branches.dedup();
+
+        let mut top: Vec<(String, f64)> = branches
+            .into_iter()
+            .map(|id| (id.clone(), self.get(id, model_state_hash)))
+            .collect();
+        top.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top.truncate(n);
+        top
+    }
+
     /// Get all state-conditioned weight entries (for serialization).
     pub fn entries(&self) -> &HashMap<WeightKey, f64> {
         &self.weights
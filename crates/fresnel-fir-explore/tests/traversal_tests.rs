@@ -1,15 +1,32 @@
-use fresnel_fir_compiler::graph::{BranchEdge, GraphNode, NdaGraph};
-use fresnel_fir_explore::traversal::engine::{ModelOnlyExecutor, TraversalEngine};
-use fresnel_fir_explore::traversal::runner::{run_campaign, CampaignConfig};
-use fresnel_fir_explore::traversal::signal::SignalType;
-use fresnel_fir_explore::traversal::strategy::{PseudoRandomStrategy, StrategyStack};
+use fresnel_fir_compiler::graph::{BranchEdge, GraphNode, NdaGraph, SourceSpan};
+use fresnel_fir_explore::adapt::coordinator::{Coordinator, CoordinatorConfig};
+use fresnel_fir_explore::adapt::directive::Directive;
+use fresnel_fir_explore::adapt::policy::StandardPolicy;
+use fresnel_fir_explore::solver::TestVector;
+use fresnel_fir_explore::traversal::engine::{
+    ActionExecutor, ActionOutcome, CoverageReport, ModelOnlyExecutor, TraversalEngine,
+    TraversalOrder, TraversalResult, UnmetMinHits,
+};
+use fresnel_fir_explore::traversal::runner::{
+    resume_campaign, run_campaign, run_campaign_adaptive, run_campaign_interleaved,
+    run_campaign_resumable, CampaignConfig, CampaignProgress, SaturationConfig, StopCondition,
+    StopReason,
+};
+use fresnel_fir_explore::traversal::signal::{Severity, SignalType};
+use fresnel_fir_explore::traversal::strategy::{
+    select_by_roll, BranchDecision, CoverageGuidedStrategy, DirectiveAwareStrategy, EvictionPolicy,
+    PseudoRandomStrategy, RepeatDecision, Strategy, StrategyStack,
+};
 use fresnel_fir_explore::traversal::trace::TraceStepKind;
 use fresnel_fir_explore::traversal::vector_source::MockVectorSource;
-use fresnel_fir_explore::traversal::weight_table::WeightTable;
-use fresnel_fir_ir::types::FresnelFirIR;
+use fresnel_fir_explore::traversal::weight_table::{WeightTable, DEFAULT_MAX_WEIGHT};
+use fresnel_fir_explore::ExploreError;
+use fresnel_fir_ir::types::{CoverageTarget, FresnelFirIR, Protocol, ProtocolNode, SequencingRule};
 use fresnel_fir_model::state::{InstanceId, ModelState, Value};
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 
 fn minimal_ir() -> FresnelFirIR {
     serde_json::from_str(
@@ -46,10 +63,11 @@ fn minimal_ir() -> FresnelFirIR {
     .unwrap()
 }
 
+#[allow(clippy::disallowed_methods)]
 fn make_strategy_stack() -> StrategyStack {
     let rng = ChaCha8Rng::seed_from_u64(42);
     let strategy = PseudoRandomStrategy::new(rng);
-    StrategyStack::new(Box::new(strategy), 4)
+    StrategyStack::new(Box::new(strategy), 4, EvictionPolicy::Fifo)
 }
 
 fn actor_id() -> InstanceId {
@@ -65,6 +83,7 @@ fn build_linear_graph() -> NdaGraph {
     let terminal = g.add_node(GraphNode::Terminal {
         action: "create_document".to_string(),
         guard: None,
+        source_span: None,
     });
     g.add_edge(g.entry, terminal);
     g.add_edge(terminal, g.exit);
@@ -77,10 +96,12 @@ fn build_branching_graph() -> NdaGraph {
     let create = g.add_node(GraphNode::Terminal {
         action: "create_document".to_string(),
         guard: None,
+        source_span: None,
     });
     let read = g.add_node(GraphNode::Terminal {
         action: "read".to_string(),
         guard: None,
+        source_span: None,
     });
     let branch = g.add_node(GraphNode::Branch {
         alternatives: vec![
@@ -97,6 +118,7 @@ fn build_branching_graph() -> NdaGraph {
                 guard: None,
             },
         ],
+        source_span: None,
     });
     g.add_edge(g.entry, branch);
     g.add_edge(create, g.exit);
@@ -110,6 +132,7 @@ fn build_loop_graph() -> NdaGraph {
     let body = g.add_node(GraphNode::Terminal {
         action: "create_document".to_string(),
         guard: None,
+        source_span: None,
     });
     let loop_exit = g.add_node(GraphNode::LoopExit);
     let loop_entry = g.add_node(GraphNode::LoopEntry {
@@ -129,14 +152,17 @@ fn build_sequence_graph() -> NdaGraph {
     let create = g.add_node(GraphNode::Terminal {
         action: "create_document".to_string(),
         guard: None,
+        source_span: None,
     });
     let read = g.add_node(GraphNode::Terminal {
         action: "read".to_string(),
         guard: None,
+        source_span: None,
     });
     let delete = g.add_node(GraphNode::Terminal {
         action: "delete".to_string(),
         guard: None,
+        source_span: None,
     });
     g.add_edge(g.entry, create);
     g.add_edge(create, read);
@@ -145,6 +171,51 @@ fn build_sequence_graph() -> NdaGraph {
     g
 }
 
+/// Build a skewed branching graph:
+/// Start -> Alt(common_path -> common, rare_path -> rare) -> End
+fn build_rare_branch_graph() -> NdaGraph {
+    let mut g = NdaGraph::new();
+    let common = g.add_node(GraphNode::Terminal {
+        action: "common".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    let rare = g.add_node(GraphNode::Terminal {
+        action: "rare".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    let branch = g.add_node(GraphNode::Branch {
+        alternatives: vec![
+            BranchEdge {
+                id: "common_path".to_string(),
+                weight: 1.0,
+                target: common,
+                guard: None,
+            },
+            BranchEdge {
+                id: "rare_path".to_string(),
+                weight: 1.0,
+                target: rare,
+                guard: None,
+            },
+        ],
+        source_span: None,
+    });
+    g.add_edge(g.entry, branch);
+    g.add_edge(common, g.exit);
+    g.add_edge(rare, g.exit);
+    g
+}
+
+/// Starting weights under which `rare_path` is practically never picked.
+fn rare_branch_weights() -> WeightTable {
+    let mut weights = WeightTable::new();
+    weights.set_default("common_path", 1e6);
+    weights.set_default("rare_path", 1e-6);
+    weights
+}
+
 #[test]
 fn test_linear_traversal_executes_action() {
     let graph = build_linear_graph();
@@ -220,6 +291,157 @@ fn test_branching_traversal_selects_branch() {
     );
 }
 
+#[test]
+#[allow(clippy::disallowed_methods)]
+fn test_coverage_guided_picks_uncovered_branch_first() {
+    let graph = build_branching_graph();
+    let ir = minimal_ir();
+    let mut weight_table = WeightTable::new();
+    // Heavily favour the branch that is already covered.
+    weight_table.set_default("create_path", 99.0);
+    weight_table.set_default("read_path", 1.0);
+
+    let mut covered = CoverageReport::default();
+    covered.branch_counts.insert("create_path".to_string(), 3);
+    let coverage = Arc::new(RwLock::new(covered));
+
+    for seed in 0..10 {
+        let strategy = CoverageGuidedStrategy::new(
+            ChaCha8Rng::seed_from_u64(seed),
+            Arc::clone(&coverage),
+            vec![],
+        );
+        let mut strategy_stack = StrategyStack::new(Box::new(strategy), 4, EvictionPolicy::Fifo);
+        let mut model = ModelState::new();
+        let mut vector_source = MockVectorSource::new();
+
+        let engine = TraversalEngine::new(
+            &graph,
+            &mut model,
+            ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        );
+        let result = engine.run_pass(10_000);
+
+        assert_eq!(result.coverage.branch_counts.get("read_path"), Some(&1));
+        assert!(result.coverage.action_counts.contains_key("read"));
+    }
+
+    // Once every branch is covered, selection falls back to the weight table.
+    coverage
+        .write()
+        .unwrap()
+        .branch_counts
+        .insert("read_path".to_string(), 1);
+    let mut strategy =
+        CoverageGuidedStrategy::new(ChaCha8Rng::seed_from_u64(42), Arc::clone(&coverage), vec![]);
+    let alternatives = match &graph.nodes[graph.nodes.len() - 1] {
+        GraphNode::Branch { alternatives, .. } => alternatives.clone(),
+        other => panic!("expected branch node, got {other:?}"),
+    };
+    let picks = (0..100)
+        .filter(|_| {
+            strategy
+                .select_branch(&alternatives, 0, &weight_table)
+                .unwrap()
+                .branch_id
+                == "create_path"
+        })
+        .count();
+    assert!(picks > 80, "expected weight-table bias, got {picks}/100");
+}
+
+#[test]
+#[allow(clippy::disallowed_methods)]
+fn test_force_directive_selects_forced_branch() {
+    let graph = build_branching_graph();
+    let ir = minimal_ir();
+    let mut weight_table = WeightTable::new();
+    // The forced branch would almost never win on weight alone.
+    weight_table.set_default("create_path", 99.0);
+    weight_table.set_default("read_path", 1.0);
+
+    for seed in 0..10 {
+        let base = PseudoRandomStrategy::new(ChaCha8Rng::seed_from_u64(seed));
+        let strategy = DirectiveAwareStrategy::new(Box::new(base), &graph);
+        let mut strategy_stack = StrategyStack::new(Box::new(strategy), 4, EvictionPolicy::Fifo);
+        let mut model = ModelState::new();
+        let mut vector_source = MockVectorSource::new();
+
+        let engine = TraversalEngine::new(
+            &graph,
+            &mut model,
+            ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        )
+        .with_directives(&[Directive::Force {
+            action: "read".into(),
+            budget: 1,
+        }]);
+        let result = engine.run_pass(10_000);
+
+        assert_eq!(result.coverage.branch_counts.get("read_path"), Some(&1));
+        assert!(result.coverage.action_counts.contains_key("read"));
+    }
+}
+
+#[test]
+#[allow(clippy::disallowed_methods)]
+fn test_force_budget_and_loop_limit() {
+    let graph = build_branching_graph();
+    let alternatives = match &graph.nodes[graph.nodes.len() - 1] {
+        GraphNode::Branch { alternatives, .. } => alternatives.clone(),
+        other => panic!("expected branch node, got {other:?}"),
+    };
+    let mut weight_table = WeightTable::new();
+    weight_table.set_default("create_path", 0.0);
+    weight_table.set_default("read_path", 1.0);
+
+    let base = PseudoRandomStrategy::new(ChaCha8Rng::seed_from_u64(42));
+    let mut strategy = DirectiveAwareStrategy::new(Box::new(base), &graph);
+    strategy.accept_directives(&[
+        Directive::Force {
+            action: "create_path".into(),
+            budget: 2,
+        },
+        Directive::LoopLimit {
+            loop_node_id: 7,
+            new_min: 2,
+            new_max: 2,
+        },
+    ]);
+
+    // Two forced selections, then back to the weight table.
+    for _ in 0..2 {
+        let decision = strategy
+            .select_branch(&alternatives, 0, &weight_table)
+            .unwrap();
+        assert_eq!(decision.branch_id, "create_path");
+    }
+    assert_eq!(strategy.active_forces().count(), 0);
+    let decision = strategy
+        .select_branch(&alternatives, 0, &weight_table)
+        .unwrap();
+    assert_eq!(decision.branch_id, "read_path");
+
+    // The loop limit narrows the declared range for its node only.
+    for _ in 0..10 {
+        assert_eq!(strategy.choose_loop_iterations(7, 1, 5).iterations, 2);
+    }
+    assert_eq!(strategy.loop_limit(7), Some((2, 2)));
+    assert_eq!(strategy.loop_limit(8), None);
+}
+
 #[test]
 fn test_loop_traversal_iterates() {
     let graph = build_loop_graph();
@@ -285,116 +507,1229 @@ fn test_sequence_traversal_executes_all_actions() {
     // Should execute 3 actions in sequence
     assert_eq!(result.actions_executed, 3);
 
-    // Trace should have all 3 actions
-    let actions: Vec<String> = result
-        .trace
-        .steps()
-        .iter()
-        .filter_map(|s| match &s.kind {
-            TraceStepKind::ActionExecuted { action, .. } => Some(action.clone()),
-            _ => None,
-        })
-        .collect();
-    assert_eq!(actions.len(), 3);
+    // Trace should have all 3 actions
+    let actions: Vec<String> = result
+        .trace
+        .steps()
+        .iter()
+        .filter_map(|s| match &s.kind {
+            TraceStepKind::ActionExecuted { action, .. } => Some(action.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(actions.len(), 3);
+}
+
+#[test]
+fn test_model_records_actions() {
+    let graph = build_sequence_graph();
+    let mut model = ModelState::new();
+    let ir = minimal_ir();
+    let mut strategy_stack = make_strategy_stack();
+    let mut vector_source = MockVectorSource::new();
+    let mut weight_table = WeightTable::new();
+
+    let engine = TraversalEngine::new(
+        &graph,
+        &mut model,
+        ModelOnlyExecutor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut strategy_stack,
+        &mut vector_source,
+        &mut weight_table,
+    );
+
+    engine.run_pass(10_000);
+
+    // Model should have recorded actions in its trace
+    let trace = model.trace();
+    assert_eq!(trace.len(), 3);
+    assert_eq!(trace[0].action, "create_document");
+    assert_eq!(trace[1].action, "read");
+    assert_eq!(trace[2].action, "delete");
+}
+
+#[test]
+fn test_read_before_required_create_is_violation() {
+    let mut g = NdaGraph::new();
+    let read = g.add_node(GraphNode::Terminal {
+        action: "read".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    let create = g.add_node(GraphNode::Terminal {
+        action: "create_document".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    g.add_edge(g.entry, read);
+    g.add_edge(read, create);
+    g.add_edge(create, g.exit);
+
+    let mut ir = minimal_ir();
+    ir.protocols.insert(
+        "document_lifecycle".to_string(),
+        Protocol {
+            root: ProtocolNode::Call {
+                action: "read".to_string(),
+            },
+            sequencing: [(
+                "read".to_string(),
+                SequencingRule {
+                    requires_prior: vec!["create_document".to_string()],
+                },
+            )]
+            .into(),
+        },
+    );
+
+    let run = |graph: &NdaGraph| {
+        let mut model = ModelState::new();
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+        TraversalEngine::new(
+            graph,
+            &mut model,
+            ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        )
+        .run_pass(10_000)
+    };
+
+    let result = run(&g);
+    assert_eq!(result.findings.len(), 1);
+    match &result.findings[0].signal.signal_type {
+        SignalType::PropertyViolation { property, details } => {
+            assert_eq!(property, "sequencing:read");
+            assert!(details.contains("create_document"), "{details}");
+        }
+        other => panic!("expected a property violation, got {other:?}"),
+    }
+
+    // create_document -> read -> delete satisfies the rule.
+    assert!(run(&build_sequence_graph()).findings.is_empty());
+}
+
+#[test]
+fn test_coverage_delta_signals() {
+    let graph = build_branching_graph();
+    let mut model = ModelState::new();
+    let ir = minimal_ir();
+    let mut strategy_stack = make_strategy_stack();
+    let mut vector_source = MockVectorSource::new();
+    let mut weight_table = WeightTable::new();
+    weight_table.set_default("create_path", 60.0);
+    weight_table.set_default("read_path", 40.0);
+
+    let engine = TraversalEngine::new(
+        &graph,
+        &mut model,
+        ModelOnlyExecutor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut strategy_stack,
+        &mut vector_source,
+        &mut weight_table,
+    );
+
+    let result = engine.run_pass(10_000);
+
+    // First traversal should emit at least one CoverageDelta
+    let coverage_signals: Vec<_> = result
+        .signals
+        .iter()
+        .filter(|s| matches!(&s.signal_type, SignalType::CoverageDelta { .. }))
+        .collect();
+    assert!(
+        !coverage_signals.is_empty(),
+        "should emit coverage delta on first visit"
+    );
+}
+
+#[test]
+fn test_traversal_result_json_round_trip() {
+    let graph = build_branching_graph();
+    let mut model = ModelState::new();
+    let ir = minimal_ir();
+    let mut strategy_stack = make_strategy_stack();
+    let mut vector_source = MockVectorSource::new();
+    let mut weight_table = WeightTable::new();
+    weight_table.set_default("create_path", 60.0);
+    weight_table.set_default("read_path", 40.0);
+
+    let engine = TraversalEngine::new(
+        &graph,
+        &mut model,
+        ModelOnlyExecutor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut strategy_stack,
+        &mut vector_source,
+        &mut weight_table,
+    );
+    let result = engine.run_pass(10_000);
+    assert!(!result.signals.is_empty());
+    assert!(!result.trace.is_empty());
+
+    let json = result.to_json().unwrap();
+    let reloaded = TraversalResult::from_json(&json).unwrap();
+    assert_eq!(reloaded.findings, result.findings);
+    assert_eq!(reloaded.signals, result.signals);
+    assert_eq!(reloaded.actions_executed, result.actions_executed);
+    assert_eq!(reloaded.guards_failed, result.guards_failed);
+    assert_eq!(reloaded.nodes_visited, result.nodes_visited);
+    assert_eq!(reloaded.coverage, result.coverage);
+    assert_eq!(reloaded.trace, result.trace);
+
+    // Coverage maps serialize in key order, so re-serializing is stable.
+    assert_eq!(reloaded.to_json().unwrap(), json);
+}
+
+#[test]
+fn test_thread_ids_order_signals_in_coordinator() {
+    let ir = minimal_ir();
+    let run_thread = |graph: &NdaGraph, thread_id: u32| {
+        let mut model = ModelState::new();
+        let mut strategy_stack = make_strategy_stack();
+        let mut vector_source = MockVectorSource::new();
+        let mut weight_table = WeightTable::new();
+        let engine = TraversalEngine::new(
+            graph,
+            &mut model,
+            ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        )
+        .with_thread_id(thread_id);
+        engine.run_pass(10_000)
+    };
+
+    // Same graph shape, different actions, so the signals of both
+    // threads land on equal local steps.
+    let create_graph = build_linear_graph();
+    let mut read_graph = NdaGraph::new();
+    let read = read_graph.add_node(GraphNode::Terminal {
+        action: "read".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    read_graph.add_edge(read_graph.entry, read);
+    read_graph.add_edge(read, read_graph.exit);
+
+    let thread0 = run_thread(&create_graph, 0);
+    let thread1 = run_thread(&read_graph, 1);
+    assert!(thread0.signals.iter().all(|s| s.thread_id == 0));
+    assert!(thread1.signals.iter().all(|s| s.thread_id == 1));
+    let steps0: Vec<u64> = thread0.signals.iter().map(|s| s.local_step).collect();
+    let steps1: Vec<u64> = thread1.signals.iter().map(|s| s.local_step).collect();
+    assert!(!steps0.is_empty());
+    assert_eq!(steps0, steps1);
+
+    // Feed thread 1 first: the epoch must still order thread 0 first.
+    let mut coordinator = Coordinator::new(CoordinatorConfig::default(), Box::new(StandardPolicy));
+    let mut weight_table = WeightTable::new();
+    for signal in thread1.signals.into_iter().chain(thread0.signals) {
+        coordinator.feed_signal(signal, &mut weight_table, &[]);
+    }
+    coordinator.flush(&mut weight_table, &[]);
+
+    let triggers: Vec<&str> = coordinator
+        .directive_log()
+        .entries()
+        .iter()
+        .filter_map(|e| match &e.triggered_by {
+            SignalType::CoverageDelta { action, .. } => Some(action.as_str()),
+            _ => None,
+        })
+        .collect();
+    let first_read = triggers.iter().position(|a| *a == "read").unwrap();
+    assert!(first_read > 0);
+    assert!(triggers[..first_read]
+        .iter()
+        .all(|a| *a == "create_document"));
+    assert!(triggers[first_read..].iter().all(|a| *a == "read"));
+}
+
+#[test]
+fn test_campaign_runner_multi_pass() {
+    let graph = build_linear_graph();
+    let mut model = ModelState::new();
+    let ir = minimal_ir();
+    let mut vector_source = MockVectorSource::new();
+    let mut executor = ModelOnlyExecutor;
+
+    let campaign_config = CampaignConfig {
+        max_passes: 5,
+        seed: 42,
+        strategy_depth_limit: 4,
+        strategy_eviction: EvictionPolicy::Fifo,
+        max_steps_per_pass: 10_000,
+        traversal_order: TraversalOrder::DepthFirst,
+        cancel: None,
+        stop_on_finding: None,
+        saturation: None,
+        initial_weights: None,
+        wasm_hash: String::new(),
+        vector_retries: 0,
+    };
+
+    let result = run_campaign(
+        &graph,
+        &mut model,
+        &mut executor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut vector_source,
+        &campaign_config,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(result.passes_completed, 5);
+    assert_eq!(result.total_actions, 5); // 1 action per pass * 5 passes
+    assert!(result.findings.is_empty());
+}
+
+#[test]
+fn test_campaign_config_vector_retries_redraw_rejected_inputs() {
+    use fresnel_fir_compiler::predicate::{CompiledExpr, Value as Literal};
+    use fresnel_fir_explore::traversal::engine::INPUT_BINDING;
+    use fresnel_fir_ir::expr::OpKind;
+
+    // Start -> deposit (requires `$input.amount >= 0`) -> End.
+    let mut graph = NdaGraph::new();
+    let deposit = graph.add_node(GraphNode::Terminal {
+        action: "deposit".into(),
+        guard: Some(CompiledExpr::Op {
+            op: OpKind::Gte,
+            args: vec![
+                CompiledExpr::Field {
+                    entity: INPUT_BINDING.into(),
+                    field: "amount".into(),
+                },
+                CompiledExpr::Literal(Literal::Int(0)),
+            ],
+        }),
+        source_span: None,
+    });
+    graph.add_edge(graph.entry, deposit);
+    graph.add_edge(deposit, graph.exit);
+
+    let run = |vector_retries| {
+        let mut vector_source = MockVectorSource::new();
+        vector_source.add_vectors(
+            "deposit",
+            vec![
+                MockVectorSource::vector_from_args(&[("amount", -5)]),
+                MockVectorSource::vector_from_args(&[("amount", 3)]),
+            ],
+        );
+        run_campaign(
+            &graph,
+            &mut ModelState::new(),
+            &mut ModelOnlyExecutor,
+            &minimal_ir(),
+            &[],
+            actor_id(),
+            &mut vector_source,
+            &CampaignConfig {
+                max_passes: 1,
+                vector_retries,
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap()
+    };
+
+    let rejected = run(0);
+    assert_eq!(
+        (rejected.total_actions, rejected.total_guard_failures),
+        (0, 1)
+    );
+    let redrawn = run(1);
+    assert_eq!(
+        (redrawn.total_actions, redrawn.total_guard_failures),
+        (1, 0)
+    );
+}
+
+#[test]
+fn test_campaign_progress_reported_per_pass() {
+    let graph = build_linear_graph();
+    let ir = minimal_ir();
+    let config = CampaignConfig {
+        max_passes: 5,
+        ..Default::default()
+    };
+
+    let reports = std::cell::RefCell::new(Vec::new());
+    let record = |p: CampaignProgress| reports.borrow_mut().push(p);
+    let result = run_campaign(
+        &graph,
+        &mut ModelState::new(),
+        &mut ModelOnlyExecutor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut MockVectorSource::new(),
+        &config,
+        Some(&record),
+    )
+    .unwrap();
+
+    let reports = reports.into_inner();
+    assert_eq!(reports.len(), 5);
+    for (i, p) in reports.iter().enumerate() {
+        assert_eq!(p.passes_done, i as u32 + 1);
+        assert_eq!(p.passes_total, 5);
+        assert_eq!(p.total_actions, i as u64 + 1); // 1 action per pass
+    }
+    assert_eq!(reports.last().unwrap().total_actions, result.total_actions);
+}
+
+#[test]
+fn test_min_hits_target_unmet_until_count_reached() {
+    let mut graph = NdaGraph::new();
+    let terminal = graph.add_node(GraphNode::Terminal {
+        action: "increment".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    graph.add_edge(graph.entry, terminal);
+    graph.add_edge(terminal, graph.exit);
+    let mut ir = minimal_ir();
+    ir.inputs.coverage.targets.push(CoverageTarget::MinHits {
+        action: "increment".to_string(),
+        count: 50,
+    });
+
+    let run = |max_passes: u32, record: &dyn Fn(CampaignProgress)| {
+        run_campaign(
+            &graph,
+            &mut ModelState::new(),
+            &mut ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut MockVectorSource::new(),
+            &CampaignConfig {
+                max_passes,
+                ..Default::default()
+            },
+            Some(record),
+        )
+        .unwrap()
+    };
+
+    let short = run(30, &|_| {});
+    assert_eq!(
+        short.unmet_min_hits,
+        vec![UnmetMinHits {
+            action: "increment".to_string(),
+            hits: 30,
+            required: 50,
+        }]
+    );
+
+    // One increment per pass: unmet through pass 49, met from pass 50.
+    let reports = std::cell::RefCell::new(Vec::new());
+    let full = run(60, &|p| reports.borrow_mut().push(p));
+    for p in reports.into_inner() {
+        let expected = usize::from(p.total_actions < 50);
+        assert_eq!(p.unmet_min_hits, expected, "after pass {}", p.passes_done);
+    }
+    assert_eq!(full.coverage.action_counts["increment"], 60);
+    assert!(full.unmet_min_hits.is_empty());
+}
+
+#[test]
+fn test_adaptive_campaign_takes_forced_branch() {
+    use fresnel_fir_compiler::predicate::{CompiledExpr, Value as Literal};
+    use fresnel_fir_model::invariant::CompiledProperty;
+
+    let graph = build_rare_branch_graph();
+
+    // Violated after every action; the standard policy answers with a
+    // `Force` of the property's name, here the rare branch id.
+    let invariants = [CompiledProperty {
+        name: "rare_path".to_string(),
+        expr: CompiledExpr::Literal(Literal::Bool(false)),
+        count: None,
+    }];
+    let config = CampaignConfig {
+        max_passes: 3,
+        initial_weights: Some(rare_branch_weights()),
+        ..Default::default()
+    };
+    let ir = minimal_ir();
+
+    let plain = run_campaign(
+        &graph,
+        &mut ModelState::new(),
+        &mut ModelOnlyExecutor,
+        &ir,
+        &invariants,
+        actor_id(),
+        &mut MockVectorSource::new(),
+        &config,
+        None,
+    )
+    .unwrap();
+    assert!(!plain.coverage.action_counts.contains_key("rare"));
+
+    let mut coordinator = Coordinator::new(
+        CoordinatorConfig {
+            epoch_size: 1,
+            ..Default::default()
+        },
+        Box::new(StandardPolicy),
+    );
+    let adaptive = run_campaign_adaptive(
+        &graph,
+        &mut ModelState::new(),
+        &mut ModelOnlyExecutor,
+        &ir,
+        &invariants,
+        actor_id(),
+        &mut MockVectorSource::new(),
+        &config,
+        &mut coordinator,
+        None,
+    )
+    .unwrap();
+    assert_eq!(adaptive.coverage.action_counts.get("rare"), Some(&2));
+}
+
+#[test]
+fn test_adaptive_campaign_forces_uncovered_branch_on_plateau() {
+    let graph = build_rare_branch_graph();
+    let config = CampaignConfig {
+        max_passes: 3,
+        initial_weights: Some(rare_branch_weights()),
+        ..Default::default()
+    };
+    let mut coordinator = Coordinator::new(
+        CoordinatorConfig {
+            epoch_size: 1,
+            plateau_window: 1,
+            ..Default::default()
+        },
+        Box::new(StandardPolicy),
+    );
+
+    // Pass 2 adds no coverage over pass 1; the plateau forces pass 3 down
+    // the branch nothing has selected yet.
+    let result = run_campaign_adaptive(
+        &graph,
+        &mut ModelState::new(),
+        &mut ModelOnlyExecutor,
+        &minimal_ir(),
+        &[],
+        actor_id(),
+        &mut MockVectorSource::new(),
+        &config,
+        &mut coordinator,
+        None,
+    )
+    .unwrap();
+    assert!(coordinator
+        .directive_log()
+        .entries()
+        .iter()
+        .any(|entry| matches!(entry.triggered_by, SignalType::CoveragePlateau { .. })));
+    assert_eq!(result.coverage.action_counts.get("rare"), Some(&1));
+}
+
+#[test]
+fn test_adaptive_campaign_keeps_floor_on_uncovered_branch() {
+    let graph = build_branching_graph();
+    let ir = minimal_ir();
+    let config = CoordinatorConfig {
+        epoch_size: 1,
+        coverage_floor_threshold: 0.6,
+        ..Default::default()
+    };
+    let mut coordinator = Coordinator::new(config, Box::new(StandardPolicy));
+
+    // A single pass takes one of the two branches.
+    let result = run_campaign_adaptive(
+        &graph,
+        &mut ModelState::new(),
+        &mut ModelOnlyExecutor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut MockVectorSource::new(),
+        &CampaignConfig {
+            max_passes: 1,
+            ..Default::default()
+        },
+        &mut coordinator,
+        None,
+    )
+    .unwrap();
+
+    let uncovered = ["create_path", "read_path"]
+        .into_iter()
+        .find(|b| !result.coverage.branch_counts.contains_key(*b))
+        .expect("one branch stays uncovered");
+    assert_eq!(coordinator.uncovered_target_branches(), [uncovered]);
+    assert!(coordinator.current_epoch() > 0);
+    // Normalization alone would leave it at most half the budget.
+    assert!(result.weight_table.get(uncovered, 0) >= 60.0 - 1e-9);
+}
+
+#[test]
+fn test_campaign_checkpoint_resume_matches_uninterrupted() {
+    let graph = build_loop_graph();
+    let ir = minimal_ir();
+    let config = CampaignConfig {
+        max_passes: 50,
+        ..Default::default()
+    };
+
+    // Uninterrupted run.
+    let mut model = ModelState::new();
+    let straight = run_campaign(
+        &graph,
+        &mut model,
+        &mut ModelOnlyExecutor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut MockVectorSource::new(),
+        &config,
+        None,
+    )
+    .unwrap();
+
+    // 25 passes, pause, then resume for the remaining 25.
+    let mut paused_model = ModelState::new();
+    let mut vector_source = MockVectorSource::new();
+    let halfway = |completed: u32| completed == 25;
+    let (first, checkpoint) = run_campaign_resumable(
+        &graph,
+        &mut paused_model,
+        &mut ModelOnlyExecutor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut vector_source,
+        &config,
+        &StopCondition {
+            should_stop: Some(&halfway),
+            ..Default::default()
+        },
+        None,
+    );
+    let checkpoint = checkpoint.expect("campaign should pause at pass 25");
+    assert_eq!(first.passes_completed, 25);
+    assert_eq!(checkpoint.next_pass, 25);
+    assert_eq!(checkpoint.coverage.total_actions(), first.total_actions);
+
+    let mut resumed_model = ModelState::new();
+    let (resumed, done) = resume_campaign(
+        checkpoint,
+        &graph,
+        &mut resumed_model,
+        &mut ModelOnlyExecutor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut vector_source,
+        &config,
+        &StopCondition::default(),
+        None,
+    );
+    assert!(done.is_none());
+    assert_eq!(resumed.passes_completed, 50);
+    assert_eq!(resumed.total_actions, straight.total_actions);
+    assert_eq!(resumed.total_guard_failures, straight.total_guard_failures);
+    assert_eq!(resumed.findings.len(), straight.findings.len());
+    assert_eq!(resumed_model.generation(), model.generation());
+}
+
+#[test]
+fn test_campaign_cancelled_between_passes() {
+    let graph = build_linear_graph();
+    let ir = minimal_ir();
+    let token = Arc::new(AtomicBool::new(false));
+    let config = CampaignConfig {
+        max_passes: 50,
+        cancel: Some(Arc::clone(&token)),
+        ..Default::default()
+    };
+
+    // Set the token after three passes; it is observed at the next pass boundary.
+    let cancel_after_three = |completed: u32| {
+        if completed == 3 {
+            token.store(true, Ordering::Relaxed);
+        }
+        false
+    };
+    let (result, checkpoint) = run_campaign_resumable(
+        &graph,
+        &mut ModelState::new(),
+        &mut ModelOnlyExecutor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut MockVectorSource::new(),
+        &config,
+        &StopCondition {
+            should_stop: Some(&cancel_after_three),
+            ..Default::default()
+        },
+        None,
+    );
+
+    assert_eq!(result.stop_reason, StopReason::Cancelled);
+    assert_eq!(result.passes_completed, 4);
+    assert_eq!(result.total_actions, 4);
+    assert_eq!(checkpoint.map(|c| c.next_pass), Some(4));
+}
+
+/// Executor that traps on every call to `read`.
+struct TrapOnRead;
+
+impl ActionExecutor for TrapOnRead {
+    fn execute(&mut self, action: &str, _vector: Option<&TestVector>) -> ActionOutcome {
+        ActionOutcome {
+            return_value: None,
+            trapped: action == "read",
+            fuel_consumed: None,
+            error: (action == "read").then(|| "WASM trap: unreachable".to_string()),
+        }
+    }
+}
+
+#[test]
+fn test_campaign_stops_on_first_critical_finding() {
+    let mut graph = NdaGraph::new();
+    let read = graph.add_node(GraphNode::Terminal {
+        action: "read".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    graph.add_edge(graph.entry, read);
+    graph.add_edge(read, graph.exit);
+    let ir = minimal_ir();
+
+    let run = |stop_on_finding| {
+        let config = CampaignConfig {
+            max_passes: 10,
+            stop_on_finding,
+            ..Default::default()
+        };
+        run_campaign(
+            &graph,
+            &mut ModelState::new(),
+            &mut TrapOnRead,
+            &ir,
+            &[],
+            actor_id(),
+            &mut MockVectorSource::new(),
+            &config,
+            None,
+        )
+        .unwrap()
+    };
+
+    let result = run(Some(Severity::Critical));
+    assert_eq!(result.passes_completed, 1);
+    assert_eq!(result.stop_reason, StopReason::StopOnFinding);
+    let finding = result.stop_finding.expect("trap should stop the campaign");
+    assert_eq!(finding.severity, Severity::Critical);
+    assert_eq!(result.findings.len(), 1);
+    assert_eq!(result.findings[0].id, finding.id);
+
+    let full = run(None);
+    assert_eq!(full.passes_completed, 10);
+    assert_eq!(full.stop_reason, StopReason::MaxPasses);
+    assert!(full.stop_finding.is_none());
+}
+
+#[test]
+fn test_crash_finding_carries_replay_capsule() {
+    let mut graph = NdaGraph::new();
+    let read = graph.add_node(GraphNode::Terminal {
+        action: "read".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    graph.add_edge(graph.entry, read);
+    graph.add_edge(read, graph.exit);
+    let ir = minimal_ir();
+
+    let mut vector_source = MockVectorSource::new();
+    vector_source.add_vectors(
+        "read",
+        vec![MockVectorSource::vector_from_args(&[("doc_id", 7)])],
+    );
+    let config = CampaignConfig {
+        max_passes: 1,
+        seed: 99,
+        wasm_hash: "wasm-abc".to_string(),
+        ..Default::default()
+    };
+    let result = run_campaign(
+        &graph,
+        &mut ModelState::new(),
+        &mut TrapOnRead,
+        &ir,
+        &[],
+        actor_id(),
+        &mut vector_source,
+        &config,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(result.findings.len(), 1);
+    let capsule = result.findings[0]
+        .capsule
+        .as_ref()
+        .expect("crash finding should carry a capsule");
+    assert_eq!(capsule.trigger_action, "read");
+    assert_eq!(capsule.seed, 99);
+    assert_eq!(capsule.ir_hash, ir.content_hash());
+    assert_eq!(capsule.wasm_hash, "wasm-abc");
+    assert_eq!(capsule.severity, Severity::Critical);
+    assert_eq!(
+        capsule.input_vector,
+        std::collections::HashMap::from([("doc_id".to_string(), "7".to_string())])
+    );
+}
+
+#[test]
+fn test_crash_finding_carries_source_span() {
+    let span = SourceSpan::protocol_root("lifecycle").item("children", 1);
+    let mut graph = NdaGraph::new();
+    let read = graph.add_node(GraphNode::Terminal {
+        action: "read".to_string(),
+        guard: None,
+        source_span: Some(span.clone()),
+    });
+    graph.add_edge(graph.entry, read);
+    graph.add_edge(read, graph.exit);
+
+    let config = CampaignConfig {
+        max_passes: 1,
+        ..Default::default()
+    };
+    let result = run_campaign(
+        &graph,
+        &mut ModelState::new(),
+        &mut TrapOnRead,
+        &minimal_ir(),
+        &[],
+        actor_id(),
+        &mut MockVectorSource::new(),
+        &config,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(result.findings.len(), 1);
+    assert_eq!(result.findings[0].source_span, Some(span));
+    assert_eq!(
+        result.findings[0].source_span.as_ref().unwrap().to_string(),
+        "protocols.lifecycle.root.children[1]"
+    );
+}
+
+#[test]
+fn test_campaign_starts_from_initial_weights() {
+    let mut initial = WeightTable::new();
+    initial.set_default("create_path", 10.0);
+    initial.set("create_path", 7, 40.0);
+
+    let config = CampaignConfig {
+        max_passes: 1,
+        initial_weights: Some(initial),
+        ..Default::default()
+    };
+    let result = run_campaign(
+        &build_linear_graph(),
+        &mut ModelState::new(),
+        &mut ModelOnlyExecutor,
+        &minimal_ir(),
+        &[],
+        actor_id(),
+        &mut MockVectorSource::new(),
+        &config,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(result.weight_table.get("create_path", 7), 40.0);
+    assert_eq!(result.weight_table.get("create_path", 0), 10.0);
+}
+
+#[test]
+fn test_campaign_reports_stop_reason() {
+    let mut graph = NdaGraph::new();
+    let read = graph.add_node(GraphNode::Terminal {
+        action: "read".to_string(),
+        guard: None,
+        source_span: None,
+    });
+    graph.add_edge(graph.entry, read);
+    graph.add_edge(read, graph.exit);
+    let ir = minimal_ir();
+
+    let run = |config: CampaignConfig| {
+        run_campaign(
+            &graph,
+            &mut ModelState::new(),
+            &mut TrapOnRead,
+            &ir,
+            &[],
+            actor_id(),
+            &mut MockVectorSource::new(),
+            &config,
+            None,
+        )
+    };
+
+    let exhausted = run(CampaignConfig {
+        max_passes: 3,
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(exhausted.stop_reason, StopReason::MaxPasses);
+    assert_eq!(exhausted.passes_completed, 3);
+
+    // Cancelled before the first pass, there is no result to report.
+    let cancelled = run(CampaignConfig {
+        max_passes: 3,
+        cancel: Some(Arc::new(AtomicBool::new(true))),
+        ..Default::default()
+    });
+    assert!(matches!(cancelled, Err(ExploreError::Cancelled)));
+
+    let stopped = run(CampaignConfig {
+        max_passes: 3,
+        stop_on_finding: Some(Severity::Critical),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(stopped.stop_reason, StopReason::StopOnFinding);
+    assert_eq!(stopped.passes_completed, 1);
+}
+
+#[test]
+fn test_adaptive_stop_on_finding_flushes_coordinator() {
+    let graph = build_sequence_graph();
+    // The default epoch is far longer than one pass.
+    let mut coordinator = Coordinator::new(CoordinatorConfig::default(), Box::new(StandardPolicy));
+    let result = run_campaign_adaptive(
+        &graph,
+        &mut ModelState::new(),
+        &mut TrapOnRead,
+        &minimal_ir(),
+        &[],
+        actor_id(),
+        &mut MockVectorSource::new(),
+        &CampaignConfig {
+            max_passes: 10,
+            stop_on_finding: Some(Severity::Critical),
+            ..Default::default()
+        },
+        &mut coordinator,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(result.stop_reason, StopReason::StopOnFinding);
+    assert_eq!(coordinator.current_epoch(), 1);
+    assert!(coordinator
+        .directive_log()
+        .entries()
+        .iter()
+        .any(|entry| matches!(entry.triggered_by, SignalType::Crash { .. })));
+}
+
+#[test]
+fn test_saturated_campaign_stops_before_max_passes() {
+    // One pass covers every node and nothing ever fails.
+    let graph = build_linear_graph();
+    let ir = minimal_ir();
+    let run = |saturation| {
+        run_campaign(
+            &graph,
+            &mut ModelState::new(),
+            &mut ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut MockVectorSource::new(),
+            &CampaignConfig {
+                max_passes: 50,
+                saturation,
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap()
+    };
+
+    let saturated = run(Some(SaturationConfig {
+        no_new_coverage_epochs: 3,
+        no_new_findings_epochs: 3,
+    }));
+    assert_eq!(saturated.stop_reason, StopReason::Saturated);
+    // The first pass sets the baseline; three flat passes follow.
+    assert_eq!(saturated.passes_completed, 4);
+    assert!(saturated.findings.is_empty());
+
+    let unbounded = run(None);
+    assert_eq!(unbounded.stop_reason, StopReason::MaxPasses);
+    assert_eq!(unbounded.passes_completed, 50);
 }
 
 #[test]
-fn test_model_records_actions() {
-    let graph = build_sequence_graph();
-    let mut model = ModelState::new();
+fn test_resumed_campaign_saturates_on_the_same_pass() {
+    let graph = build_linear_graph();
     let ir = minimal_ir();
-    let mut strategy_stack = make_strategy_stack();
-    let mut vector_source = MockVectorSource::new();
-    let mut weight_table = WeightTable::new();
+    let config = CampaignConfig {
+        max_passes: 50,
+        saturation: Some(SaturationConfig {
+            no_new_coverage_epochs: 3,
+            no_new_findings_epochs: 3,
+        }),
+        ..Default::default()
+    };
 
-    let engine = TraversalEngine::new(
+    let mut vector_source = MockVectorSource::new();
+    let after_two = |completed: u32| completed == 2;
+    let (_, checkpoint) = run_campaign_resumable(
         &graph,
-        &mut model,
-        ModelOnlyExecutor,
+        &mut ModelState::new(),
+        &mut ModelOnlyExecutor,
         &ir,
         &[],
         actor_id(),
-        &mut strategy_stack,
         &mut vector_source,
-        &mut weight_table,
+        &config,
+        &StopCondition {
+            should_stop: Some(&after_two),
+            ..Default::default()
+        },
+        None,
+    );
+    let (resumed, done) = resume_campaign(
+        checkpoint.expect("campaign should pause at pass 2"),
+        &graph,
+        &mut ModelState::new(),
+        &mut ModelOnlyExecutor,
+        &ir,
+        &[],
+        actor_id(),
+        &mut vector_source,
+        &config,
+        &StopCondition::default(),
+        None,
     );
 
-    engine.run_pass(10_000);
-
-    // Model should have recorded actions in its trace
-    let trace = model.trace();
-    assert_eq!(trace.len(), 3);
-    assert_eq!(trace[0].action, "create_document");
-    assert_eq!(trace[1].action, "read");
-    assert_eq!(trace[2].action, "delete");
+    // Same pass as uninterrupted: the flat passes before the pause count.
+    assert!(done.is_none());
+    assert_eq!(resumed.stop_reason, StopReason::Saturated);
+    assert_eq!(resumed.passes_completed, 4);
 }
 
 #[test]
-fn test_coverage_delta_signals() {
+fn test_cancelled_adaptive_campaign_flushes_coordinator() {
     let graph = build_branching_graph();
-    let mut model = ModelState::new();
-    let ir = minimal_ir();
-    let mut strategy_stack = make_strategy_stack();
-    let mut vector_source = MockVectorSource::new();
-    let mut weight_table = WeightTable::new();
-    weight_table.set_default("create_path", 60.0);
-    weight_table.set_default("read_path", 40.0);
+    let token = Arc::new(AtomicBool::new(false));
+    // No epoch closes on its own within the campaign.
+    let mut coordinator = Coordinator::new(
+        CoordinatorConfig {
+            epoch_size: 10_000,
+            ..Default::default()
+        },
+        Box::new(StandardPolicy),
+    );
 
-    let engine = TraversalEngine::new(
+    let cancel = |_: CampaignProgress| token.store(true, Ordering::Relaxed);
+    let result = run_campaign_adaptive(
         &graph,
-        &mut model,
-        ModelOnlyExecutor,
-        &ir,
+        &mut ModelState::new(),
+        &mut ModelOnlyExecutor,
+        &minimal_ir(),
         &[],
         actor_id(),
-        &mut strategy_stack,
-        &mut vector_source,
-        &mut weight_table,
-    );
+        &mut MockVectorSource::new(),
+        &CampaignConfig {
+            max_passes: 50,
+            cancel: Some(Arc::clone(&token)),
+            ..Default::default()
+        },
+        &mut coordinator,
+        Some(&cancel),
+    )
+    .unwrap();
 
-    let result = engine.run_pass(10_000);
+    // Adaptive campaigns are not checkpointed, so the pending signals of
+    // the completed pass are processed rather than kept for a resume.
+    assert_eq!(result.stop_reason, StopReason::Cancelled);
+    assert_eq!(result.passes_completed, 1);
+    assert_eq!(coordinator.current_epoch(), 1);
+}
 
-    // First traversal should emit at least one CoverageDelta
-    let coverage_signals: Vec<_> = result
-        .signals
+#[test]
+fn test_interleaved_campaign_is_deterministic() {
+    let graph = build_branching_graph();
+    let mut ir = minimal_ir();
+    ir.exploration.concurrency.threads = 3;
+    let config = CampaignConfig {
+        max_passes: 8,
+        seed: 7,
+        ..CampaignConfig::default()
+    };
+
+    let run = || {
+        let mut model = ModelState::new();
+        let mut vector_source = MockVectorSource::new();
+        run_campaign_interleaved(
+            &graph,
+            &mut model,
+            &mut TrapOnRead,
+            &ir,
+            &[],
+            actor_id(),
+            &mut vector_source,
+            &config,
+            None,
+        )
+        .unwrap()
+    };
+    let first = run();
+    let second = run();
+
+    // One action per thread per pass, scheduled round-robin.
+    assert_eq!(first.schedule.len(), 24);
+    assert_eq!(first.result.passes_completed, 8);
+    let thread_order: Vec<u32> = first.schedule.iter().map(|s| s.thread_id).collect();
+    assert_eq!(thread_order, [0, 1, 2].repeat(8));
+
+    assert_eq!(first.schedule, second.schedule);
+    assert_eq!(
+        first.result.coverage.total_actions(),
+        first.result.total_actions
+    );
+    let finding_keys = |findings: &[fresnel_fir_explore::traversal::signal::Finding]| {
+        findings
+            .iter()
+            .map(|f| (f.id, f.signal.thread_id, f.signal.local_step))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        finding_keys(&first.result.findings),
+        finding_keys(&second.result.findings)
+    );
+    // Ids are unique across passes and threads.
+    let ids: Vec<u64> = first.result.findings.iter().map(|f| f.id).collect();
+    assert_eq!(ids, (0..ids.len() as u64).collect::<Vec<_>>());
+
+    // Every crash is attributed to the thread that ran `read`.
+    let reads: Vec<(u32, u32)> = first
+        .schedule
         .iter()
-        .filter(|s| matches!(&s.signal_type, SignalType::CoverageDelta { .. }))
+        .filter(|s| s.action == "read")
+        .map(|s| (s.pass, s.thread_id))
         .collect();
-    assert!(
-        !coverage_signals.is_empty(),
-        "should emit coverage delta on first visit"
-    );
+    assert!(!reads.is_empty());
+    assert_eq!(first.result.findings.len(), reads.len());
+    let mut crash_threads: Vec<u32> = first
+        .result
+        .findings
+        .iter()
+        .map(|f| f.signal.thread_id)
+        .collect();
+    let mut read_threads: Vec<u32> = reads.iter().map(|(_, t)| *t).collect();
+    crash_threads.sort_unstable();
+    read_threads.sort_unstable();
+    assert_eq!(crash_threads, read_threads);
 }
 
 #[test]
-fn test_campaign_runner_multi_pass() {
-    let graph = build_linear_graph();
-    let mut model = ModelState::new();
-    let ir = minimal_ir();
-    let mut vector_source = MockVectorSource::new();
-    let mut executor = ModelOnlyExecutor;
-
-    let campaign_config = CampaignConfig {
-        max_passes: 5,
-        seed: 42,
-        strategy_depth_limit: 4,
-        max_steps_per_pass: 10_000,
-    };
+fn test_interleaved_campaign_feeds_coordinator() {
+    let graph = build_branching_graph();
+    let mut ir = minimal_ir();
+    ir.exploration.concurrency.threads = 3;
+    let mut coordinator = Coordinator::new(
+        CoordinatorConfig {
+            epoch_size: 1,
+            ..Default::default()
+        },
+        Box::new(StandardPolicy),
+    );
 
-    let result = run_campaign(
+    let interleaved = run_campaign_interleaved(
         &graph,
-        &mut model,
-        &mut executor,
+        &mut ModelState::new(),
+        &mut TrapOnRead,
         &ir,
         &[],
         actor_id(),
-        &mut vector_source,
-        &campaign_config,
-    );
+        &mut MockVectorSource::new(),
+        &CampaignConfig {
+            max_passes: 8,
+            seed: 7,
+            ..Default::default()
+        },
+        Some(&mut coordinator),
+    )
+    .unwrap();
 
-    assert_eq!(result.passes_completed, 5);
-    assert_eq!(result.total_actions, 5); // 1 action per pass * 5 passes
-    assert!(result.findings.is_empty());
+    assert!(!interleaved.result.findings.is_empty());
+    assert!(coordinator.total_signals_processed() >= interleaved.result.findings.len() as u64);
+    assert!(coordinator
+        .directive_log()
+        .entries()
+        .iter()
+        .any(|entry| matches!(entry.triggered_by, SignalType::Crash { .. })));
+}
+
+#[test]
+fn test_coverage_report_merge_sums_counts() {
+    let report = |actions: &[(&str, u64)], branches: &[(&str, u64)]| CoverageReport {
+        action_counts: actions.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+        branch_counts: branches.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+        guard_never_passed: Default::default(),
+    };
+    let first = report(&[("read", 3), ("write", 1)], &[("alt_0", 2)]);
+    let second = report(&[("read", 2), ("delete", 4)], &[("alt_0", 1), ("alt_1", 5)]);
+
+    let mut merged = first.clone();
+    merged.merge(&second);
+    assert_eq!(merged.action_counts["read"], 5);
+    assert_eq!(merged.action_counts["write"], 1);
+    assert_eq!(merged.action_counts["delete"], 4);
+    assert_eq!(merged.branch_counts["alt_0"], 3);
+    assert_eq!(merged.branch_counts["alt_1"], 5);
+    assert_eq!(merged.unique_actions(), 3);
+    assert_eq!(merged.total_actions(), 10);
+
+    assert_eq!(CoverageReport::merge_all(&[first, second]), merged);
+    assert_eq!(CoverageReport::merge_all(&[]), CoverageReport::default());
 }
 
 #[test]
@@ -424,9 +1759,249 @@ fn test_weight_table_state_conditioned() {
 }
 
 #[test]
+fn test_weight_table_boosts_clamp_at_ceiling() {
+    let mut wt = WeightTable::new();
+    wt.set_default("hot", 10.0);
+    for _ in 0..40 {
+        wt.adjust("hot", 0, 2.0);
+    }
+    assert_eq!(wt.get("hot", 0), DEFAULT_MAX_WEIGHT);
+
+    let mut bounded = WeightTable::new().with_bounds(1.0, 50.0);
+    bounded.set("cold", 0, -3.0);
+    bounded.set("hot", 0, 80.0);
+    assert_eq!(bounded.get("cold", 0), 1.0);
+    assert_eq!(bounded.get("hot", 0), 50.0);
+}
+
+#[test]
+fn test_weight_table_top_n_and_ranked_entries() {
+    let mut wt = WeightTable::new();
+    wt.set_default("browse", 10.0);
+    wt.set_default("edit", 30.0);
+    wt.set_default("delete", 5.0);
+    wt.set("share", 0, 20.0);
+    wt.adjust("delete", 0, 8.0); // 5 * 8 = 40
+    wt.set("edit", 7, 90.0); // another state; must not affect state 0
+
+    assert_eq!(
+        wt.top_n(2, 0),
+        vec![("delete".to_string(), 40.0), ("edit".to_string(), 30.0)]
+    );
+    assert_eq!(wt.top_n(1, 7), vec![("edit".to_string(), 90.0)]);
+
+    assert_eq!(
+        wt.ranked_entries(),
+        vec![
+            ("edit".to_string(), 7, 90.0),
+            ("delete".to_string(), 0, 40.0),
+            ("edit".to_string(), 0, 30.0),
+            ("share".to_string(), 0, 20.0),
+            ("browse".to_string(), 0, 10.0),
+        ]
+    );
+}
+
+fn three_way_branches() -> Vec<BranchEdge> {
+    ["a", "b", "c"]
+        .iter()
+        .enumerate()
+        .map(|(i, id)| BranchEdge {
+            id: id.to_string(),
+            weight: 1.0,
+            target: i as u32 + 1,
+            guard: None,
+        })
+        .collect()
+}
+
+#[test]
+#[allow(clippy::disallowed_methods)]
+fn test_pseudo_random_never_selects_zero_weight_branch() {
+    let branches = three_way_branches();
+    let mut weight_table = WeightTable::new();
+    weight_table.set_default("a", 0.0);
+    weight_table.set_default("b", 1.0);
+    weight_table.set_default("c", 1.0);
+
+    for seed in 0..500 {
+        let mut strategy = PseudoRandomStrategy::new(ChaCha8Rng::seed_from_u64(seed));
+        for _ in 0..20 {
+            let decision = strategy.select_branch(&branches, 0, &weight_table).unwrap();
+            assert_ne!(
+                decision.branch_id, "a",
+                "seed {seed} picked a zero-weight branch"
+            );
+        }
+    }
+}
+
+#[test]
+#[allow(clippy::disallowed_methods)]
+fn test_pseudo_random_all_zero_weights_falls_back_to_uniform() {
+    let branches = three_way_branches();
+    let mut weight_table = WeightTable::new();
+    for id in ["a", "b", "c"] {
+        weight_table.set_default(id, 0.0);
+    }
+
+    let mut strategy = PseudoRandomStrategy::new(ChaCha8Rng::seed_from_u64(3));
+    let mut counts = [0u32; 3];
+    for _ in 0..3000 {
+        let decision = strategy.select_branch(&branches, 0, &weight_table).unwrap();
+        assert_eq!(decision.weight_used, 0.0);
+        counts[decision.branch_index] += 1;
+    }
+    for count in counts {
+        assert!(
+            (800..1200).contains(&count),
+            "non-uniform fallback: {counts:?}"
+        );
+    }
+
+    // The engine reports the fallback as a diagnostic signal.
+    let graph = build_branching_graph();
+    weight_table.set_default("create_path", 0.0);
+    weight_table.set_default("read_path", 0.0);
+    let mut strategy_stack = make_strategy_stack();
+    let result = TraversalEngine::new(
+        &graph,
+        &mut ModelState::new(),
+        ModelOnlyExecutor,
+        &minimal_ir(),
+        &[],
+        actor_id(),
+        &mut strategy_stack,
+        &mut MockVectorSource::new(),
+        &mut weight_table,
+    )
+    .run_pass(10_000);
+    assert_eq!(result.actions_executed, 1);
+    assert!(result
+        .signals
+        .iter()
+        .any(|s| matches!(s.signal_type, SignalType::ZeroWeightFallback { .. })));
+    assert!(result.findings.is_empty());
+}
+
+#[test]
+#[allow(clippy::disallowed_methods)]
+fn test_all_zero_fallback_skips_guard_failing_branches() {
+    use fresnel_fir_compiler::predicate::{CompiledExpr, Value as Literal};
+
+    let mut strategy = PseudoRandomStrategy::new(ChaCha8Rng::seed_from_u64(0));
+    assert!(strategy
+        .select_branch(&[], 0, &WeightTable::new())
+        .is_none());
+
+    let mut graph = build_branching_graph();
+    let set_guard = |graph: &mut NdaGraph, id: &str| {
+        for node in &mut graph.nodes {
+            if let GraphNode::Branch { alternatives, .. } = node {
+                for branch in alternatives.iter_mut().filter(|b| b.id == id) {
+                    branch.guard = Some(CompiledExpr::Literal(Literal::Bool(false)));
+                }
+            }
+        }
+    };
+    set_guard(&mut graph, "create_path");
+    let mut weight_table = WeightTable::new();
+    weight_table.set_default("create_path", 0.0);
+    weight_table.set_default("read_path", 0.0);
+    let run = |graph: &NdaGraph, weight_table: &mut WeightTable, seed: u64| {
+        let strategy = PseudoRandomStrategy::new(ChaCha8Rng::seed_from_u64(seed));
+        let mut strategy_stack = StrategyStack::new(Box::new(strategy), 4, EvictionPolicy::Fifo);
+        TraversalEngine::new(
+            graph,
+            &mut ModelState::new(),
+            ModelOnlyExecutor,
+            &minimal_ir(),
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut MockVectorSource::new(),
+            weight_table,
+        )
+        .run_pass(10_000)
+    };
+
+    for seed in 0..50 {
+        let result = run(&graph, &mut weight_table, seed);
+        assert_eq!(result.coverage.action_counts.get("read"), Some(&1));
+        assert!(!result
+            .coverage
+            .action_counts
+            .contains_key("create_document"));
+    }
+
+    // With every guard failing there is nothing to take: the pass ends.
+    set_guard(&mut graph, "read_path");
+    let result = run(&graph, &mut weight_table, 0);
+    assert_eq!(result.actions_executed, 0);
+}
+
+#[test]
+#[allow(clippy::disallowed_methods)]
+fn test_recorded_roll_replays_branch_selection() {
+    let graph = build_branching_graph();
+    let ir = minimal_ir();
+    let mut weight_table = WeightTable::new();
+    weight_table.set_default("create_path", 60.0);
+    weight_table.set_default("read_path", 40.0);
+
+    for seed in 0..50 {
+        let mut model = ModelState::new();
+        let strategy = PseudoRandomStrategy::new(ChaCha8Rng::seed_from_u64(seed));
+        let mut strategy_stack = StrategyStack::new(Box::new(strategy), 4, EvictionPolicy::Fifo);
+        let mut vector_source = MockVectorSource::new();
+        let engine = TraversalEngine::new(
+            &graph,
+            &mut model,
+            ModelOnlyExecutor,
+            &ir,
+            &[],
+            actor_id(),
+            &mut strategy_stack,
+            &mut vector_source,
+            &mut weight_table,
+        );
+        let result = engine.run_pass(10_000);
+
+        let (branch_id, roll, cumulative_weights) = result
+            .trace
+            .steps()
+            .iter()
+            .find_map(|s| match &s.kind {
+                TraceStepKind::BranchSelected {
+                    branch_id,
+                    roll,
+                    cumulative_weights,
+                    ..
+                } => Some((branch_id, *roll, cumulative_weights)),
+                _ => None,
+            })
+            .expect("trace should contain branch selection");
+
+        let total = cumulative_weights.last().expect("weighted draw").1;
+        assert_eq!(total, 100.0);
+        assert!((0.0..=total).contains(&roll), "seed {seed}: roll {roll}");
+        assert_eq!(
+            select_by_roll(cumulative_weights, roll),
+            Some(branch_id.as_str()),
+            "seed {seed}: replay diverged"
+        );
+    }
+}
+
+#[test]
+#[allow(clippy::disallowed_methods)]
 fn test_strategy_stack_depth_limit() {
     let rng = ChaCha8Rng::seed_from_u64(42);
-    let mut stack = StrategyStack::new(Box::new(PseudoRandomStrategy::new(rng)), 3);
+    let mut stack = StrategyStack::new(
+        Box::new(PseudoRandomStrategy::new(rng)),
+        3,
+        EvictionPolicy::Fifo,
+    );
 
     assert_eq!(stack.depth(), 1);
 
@@ -457,7 +2032,86 @@ fn test_strategy_stack_depth_limit() {
     assert_eq!(stack.depth(), 1);
 }
 
+/// Strategy that reports a fixed name, so tests can tell frames apart.
+struct NamedStrategy(&'static str);
+
+impl Strategy for NamedStrategy {
+    fn select_branch(
+        &mut self,
+        branches: &[BranchEdge],
+        _model_state_hash: u64,
+        _weight_table: &WeightTable,
+    ) -> Option<BranchDecision> {
+        Some(BranchDecision {
+            branch_index: 0,
+            branch_id: branches.first()?.id.clone(),
+            weight_used: 1.0,
+            roll: 0.0,
+            cumulative_weights: Vec::new(),
+        })
+    }
+
+    fn choose_iterations(&mut self, min: u32, _max: u32) -> RepeatDecision {
+        RepeatDecision { iterations: min }
+    }
+
+    fn name(&self) -> &str {
+        self.0
+    }
+}
+
+/// Push base + a, touch a, then push b, c, d into a depth-4 stack and
+/// return the surviving frame names, bottom to top.
+fn frames_after_overflow(eviction: EvictionPolicy) -> Vec<String> {
+    let mut stack = StrategyStack::new(Box::new(NamedStrategy("base")), 4, eviction);
+    stack.push(Box::new(NamedStrategy("a")));
+    assert_eq!(stack.current().name(), "a");
+    stack.push(Box::new(NamedStrategy("b")));
+    stack.push(Box::new(NamedStrategy("c")));
+    stack.push(Box::new(NamedStrategy("d")));
+    assert_eq!(stack.depth(), 4);
+
+    let mut names = vec![stack.current().name().to_string()];
+    while stack.pop().is_some() {
+        names.push(stack.current().name().to_string());
+    }
+    names.reverse();
+    names
+}
+
+#[test]
+#[allow(clippy::disallowed_methods)]
+fn test_strategy_stack_checkpoints_base_rng() {
+    let seeded = ChaCha8Rng::seed_from_u64(42);
+    let mut stack = StrategyStack::new(
+        Box::new(PseudoRandomStrategy::new(seeded.clone())),
+        4,
+        EvictionPolicy::Fifo,
+    );
+    stack.push(Box::new(NamedStrategy("top")));
+    assert_eq!(stack.current().rng_state(), None);
+    assert_eq!(stack.rng_state(), Some(seeded));
+
+    let unseeded = StrategyStack::new(Box::new(NamedStrategy("base")), 4, EvictionPolicy::Fifo);
+    assert_eq!(unseeded.rng_state(), None);
+}
+
+#[test]
+fn test_strategy_stack_eviction_policies() {
+    // FIFO drops the oldest pushed frame, even though it was used.
+    assert_eq!(
+        frames_after_overflow(EvictionPolicy::Fifo),
+        vec!["base", "b", "c", "d"]
+    );
+    // LRU keeps the touched frame and drops the least recently used one.
+    assert_eq!(
+        frames_after_overflow(EvictionPolicy::Lru),
+        vec!["base", "a", "c", "d"]
+    );
+}
+
 #[test]
+#[allow(clippy::disallowed_methods)]
 fn test_deterministic_traversal() {
     let ir = minimal_ir();
     let graph = build_branching_graph();
@@ -465,7 +2119,11 @@ fn test_deterministic_traversal() {
     let run = |seed: u64| -> Vec<String> {
         let mut model = ModelState::new();
         let rng = ChaCha8Rng::seed_from_u64(seed);
-        let mut strategy_stack = StrategyStack::new(Box::new(PseudoRandomStrategy::new(rng)), 4);
+        let mut strategy_stack = StrategyStack::new(
+            Box::new(PseudoRandomStrategy::new(rng)),
+            4,
+            EvictionPolicy::Fifo,
+        );
         let mut vector_source = MockVectorSource::new();
         let mut weight_table = WeightTable::new();
         weight_table.set_default("create_path", 60.0);
@@ -556,6 +2214,7 @@ fn test_effects_applied_during_traversal() {
     let a = graph.add_node(GraphNode::Terminal {
         action: "create_document".to_string(),
         guard: None,
+        source_span: None,
     });
     graph.add_edge(graph.entry, a);
     graph.add_edge(a, graph.exit);
@@ -605,7 +2264,8 @@ fn test_document_lifecycle_model_only_campaign() {
     let ir: FresnelFirIR = serde_json::from_str(ir_json).expect("fixture should parse");
 
     // 2. Compile the IR into NDA graphs
-    let compiled = fresnel_fir_compiler::compile(&ir).expect("fixture should compile");
+    let compiled =
+        fresnel_fir_explore::solver::search::compile_checked(&ir).expect("fixture should compile");
     let graph = compiled
         .graphs
         .get("document_lifecycle")
@@ -625,6 +2285,14 @@ fn test_document_lifecycle_model_only_campaign() {
         max_steps_per_pass: 200,
         seed: 42,
         strategy_depth_limit: 4,
+        strategy_eviction: EvictionPolicy::Fifo,
+        traversal_order: TraversalOrder::DepthFirst,
+        cancel: None,
+        stop_on_finding: None,
+        saturation: None,
+        initial_weights: None,
+        wasm_hash: String::new(),
+        vector_retries: 0,
     };
 
     let mut executor = ModelOnlyExecutor;
@@ -637,7 +2305,9 @@ fn test_document_lifecycle_model_only_campaign() {
         actor,
         &mut vector_source,
         &config,
-    );
+        None,
+    )
+    .unwrap();
 
     // 5. Verify the campaign completed
     assert_eq!(result.passes_completed, 50);
@@ -695,7 +2365,9 @@ fn test_document_lifecycle_model_only_campaign() {
         actor2,
         &mut vs2,
         &config,
-    );
+        None,
+    )
+    .unwrap();
 
     assert_eq!(
         result.total_actions, result2.total_actions,
@@ -15,6 +15,8 @@ pub enum Expr {
         kind: QuantifierKind,
         var: String,
         domain: String,
+        /// Narrows `domain` to some of its instances; `None` ranges over all.
+        filter: Option<InstanceFilter>,
         body: Box<Expr>,
     },
     FnCall {
@@ -46,10 +48,22 @@ pub enum OpKind {
     Or,
     Not,
     Implies,
+    Xor,
+    Iff,
     Lt,
     Lte,
     Gt,
     Gte,
+    /// Integer sum of two or more operands; `add` parses to this too.
+    Sum,
+    /// Integer difference of exactly two operands.
+    Sub,
+    /// Membership: the first operand equals one of the rest. Written
+    /// `["in", x, [v1, v2, ...]]` and stored with the values flattened
+    /// into the operand list.
+    In,
+    /// Complement of `In`: the first operand equals none of the rest.
+    NotIn,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -57,6 +71,20 @@ pub enum OpKind {
 pub enum QuantifierKind {
     Forall,
     Exists,
+    /// Binds the variable to the newest matching instance; false if none
+    /// match.
+    Newest,
+}
+
+/// Which instances of a quantifier's entity it ranges over.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceFilter {
+    /// Instances whose `field` equals `value`.
+    Where { field: String, value: Literal },
+    /// Instances whose reference `field` holds the `id` of the instance
+    /// bound to `owner`, e.g. documents whose `owner_id` is `actor.id`.
+    OwnedBy { field: String, owner: String },
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -128,8 +156,9 @@ fn parse_expr_inner(value: &serde_json::Value, depth: usize) -> Result<Expr, Str
                     Ok(Expr::Field { entity, field })
                 }
 
-                // Quantifiers: ["forall"|"exists", var, domain, body]
-                "forall" | "exists" => {
+                // Quantifiers: ["forall"|"exists"|"newest", var, domain, body],
+                // where domain is an entity name or {"entity", "where"|"owned_by"}.
+                "forall" | "exists" | "newest" => {
                     if arr.len() != 4 {
                         return Err(format!(
                             "{tag} expression requires 4 elements, got {}",
@@ -139,21 +168,20 @@ fn parse_expr_inner(value: &serde_json::Value, depth: usize) -> Result<Expr, Str
                     let kind = match tag {
                         "forall" => QuantifierKind::Forall,
                         "exists" => QuantifierKind::Exists,
+                        "newest" => QuantifierKind::Newest,
                         _ => unreachable!(),
                     };
                     let var = arr[1]
                         .as_str()
                         .ok_or("quantifier var must be a string")?
                         .to_string();
-                    let domain = arr[2]
-                        .as_str()
-                        .ok_or("quantifier domain must be a string")?
-                        .to_string();
+                    let (domain, filter) = parse_quantifier_domain(&arr[2])?;
                     let body = Box::new(parse_expr_inner(&arr[3], depth + 1)?);
                     Ok(Expr::Quantifier {
                         kind,
                         var,
                         domain,
+                        filter,
                         body,
                     })
                 }
@@ -225,7 +253,36 @@ fn parse_expr_inner(value: &serde_json::Value, depth: usize) -> Result<Expr, Str
                     })
                 }
 
-                // Operators: ["eq"|"neq"|"and"|"or"|"not"|"implies"|"lt"|"lte"|"gt"|"gte", ...args]
+                // Membership: ["in"|"not_in", x, [v1, v2, ...]]
+                "in" | "not_in" => {
+                    if arr.len() != 3 {
+                        return Err(format!(
+                            "'{tag}' requires exactly 2 arguments, got {}",
+                            arr.len() - 1
+                        ));
+                    }
+                    let values = arr[2]
+                        .as_array()
+                        .ok_or_else(|| format!("'{tag}' values must be a list"))?;
+                    if values.is_empty() {
+                        return Err(format!("'{tag}' requires at least 1 value"));
+                    }
+                    let mut args = vec![parse_expr_inner(&arr[1], depth + 1)?];
+                    for value in values {
+                        match parse_expr_inner(value, depth + 1)? {
+                            lit @ Expr::Literal(_) => args.push(lit),
+                            _ => return Err(format!("'{tag}' values must be literals")),
+                        }
+                    }
+                    let op = if tag == "in" {
+                        OpKind::In
+                    } else {
+                        OpKind::NotIn
+                    };
+                    Ok(Expr::Op { op, args })
+                }
+
+                // Operators: ["eq"|"neq"|"and"|"or"|"not"|"implies"|"xor"|"iff"|"lt"|"lte"|"gt"|"gte"|"sum"|"add"|"sub", ...args]
                 _ => {
                     let op = match tag {
                         "eq" => OpKind::Eq,
@@ -234,10 +291,14 @@ fn parse_expr_inner(value: &serde_json::Value, depth: usize) -> Result<Expr, Str
                         "or" => OpKind::Or,
                         "not" => OpKind::Not,
                         "implies" => OpKind::Implies,
+                        "xor" => OpKind::Xor,
+                        "iff" => OpKind::Iff,
                         "lt" => OpKind::Lt,
                         "lte" => OpKind::Lte,
                         "gt" => OpKind::Gt,
                         "gte" => OpKind::Gte,
+                        "sum" | "add" => OpKind::Sum,
+                        "sub" => OpKind::Sub,
                         other => return Err(format!("unknown expression operator: {other}")),
                     };
                     let arg_count = arr.len() - 1;
@@ -253,10 +314,12 @@ fn parse_expr_inner(value: &serde_json::Value, depth: usize) -> Result<Expr, Str
                         OpKind::Eq
                         | OpKind::Neq
                         | OpKind::Implies
+                        | OpKind::Iff
                         | OpKind::Lt
                         | OpKind::Lte
                         | OpKind::Gt
-                        | OpKind::Gte => {
+                        | OpKind::Gte
+                        | OpKind::Sub => {
                             if arg_count != 2 {
                                 return Err(format!(
                                     "'{tag}' requires exactly 2 arguments, got {arg_count}"
@@ -270,6 +333,13 @@ fn parse_expr_inner(value: &serde_json::Value, depth: usize) -> Result<Expr, Str
                                 ));
                             }
                         }
+                        OpKind::Xor | OpKind::Sum | OpKind::In | OpKind::NotIn => {
+                            if arg_count < 2 {
+                                return Err(format!(
+                                    "'{tag}' requires at least 2 arguments, got {arg_count}"
+                                ));
+                            }
+                        }
                     }
                     let args = arr[1..]
                         .iter()
@@ -283,3 +353,201 @@ fn parse_expr_inner(value: &serde_json::Value, depth: usize) -> Result<Expr, Str
         other => Err(format!("unsupported expression value: {other}")),
     }
 }
+
+/// Parse a quantifier domain: an entity name, or an object naming the
+/// entity and at most one of `"where": {"field", "value"}` and
+/// `"owned_by": {"field", "owner"}`.
+fn parse_quantifier_domain(
+    value: &serde_json::Value,
+) -> Result<(String, Option<InstanceFilter>), String> {
+    if let Some(entity) = value.as_str() {
+        return Ok((entity.to_string(), None));
+    }
+    let obj = value
+        .as_object()
+        .ok_or("quantifier domain must be a string or an object")?;
+    let entity = obj
+        .get("entity")
+        .and_then(|e| e.as_str())
+        .ok_or("quantifier domain object requires an 'entity' string")?
+        .to_string();
+    let mut filters = obj.iter().filter(|(key, _)| key.as_str() != "entity");
+    let filter = match (filters.next(), filters.next()) {
+        (None, _) => None,
+        (Some((key, spec)), None) => {
+            let wrapped = serde_json::json!({ key: spec });
+            Some(
+                serde_json::from_value(wrapped)
+                    .map_err(|e| format!("invalid quantifier filter '{key}': {e}"))?,
+            )
+        }
+        (Some(_), Some(_)) => return Err("quantifier domain takes at most one filter".to_string()),
+    };
+    Ok((entity, filter))
+}
+
+// Binding strength of each infix form, loosest first.
+const PREC_QUANTIFIER: u8 = 0;
+const PREC_IMPLIES: u8 = 1;
+const PREC_OR: u8 = 2;
+const PREC_XOR: u8 = 3;
+const PREC_AND: u8 = 4;
+const PREC_COMPARE: u8 = 5;
+const PREC_SUM: u8 = 6;
+const PREC_NOT: u8 = 7;
+const PREC_ATOM: u8 = 8;
+
+impl Expr {
+    /// Render the expression in conventional infix notation, e.g.
+    /// `implies(eq(role, guest), neq(role, admin))` as
+    /// `(role == "guest") -> (role != "admin")`.
+    ///
+    /// Strings in boolean position, on the left of a comparison or inside
+    /// a `sum` name input domains and render bare; other strings are quoted.
+    pub fn to_infix(&self) -> String {
+        self.infix(true).0
+    }
+
+    /// Infix text and its binding strength. `names_domain` says whether a
+    /// string literal in this position refers to a domain.
+    fn infix(&self, names_domain: bool) -> (String, u8) {
+        match self {
+            Expr::Literal(Literal::Bool(b)) => (b.to_string(), PREC_ATOM),
+            Expr::Literal(Literal::Int(i)) => (i.to_string(), PREC_ATOM),
+            Expr::Literal(Literal::String(s)) if names_domain => (s.clone(), PREC_ATOM),
+            Expr::Literal(Literal::String(s)) => (format!("{s:?}"), PREC_ATOM),
+            Expr::Field { entity, field } => (format!("{entity}.{field}"), PREC_ATOM),
+            Expr::FnCall { name, args, .. } => (format!("{name}({})", args.join(", ")), PREC_ATOM),
+            Expr::Is {
+                entity,
+                refinement,
+                params,
+            } => {
+                let mut out = format!("{entity} is {refinement}");
+                if !params.is_empty() {
+                    let mut params: Vec<_> = params.iter().collect();
+                    params.sort();
+                    let params: Vec<String> =
+                        params.iter().map(|(k, v)| format!("{k}: {v:?}")).collect();
+                    out.push_str(&format!(" {{ {} }}", params.join(", ")));
+                }
+                (out, PREC_COMPARE)
+            }
+            Expr::Quantifier {
+                kind,
+                var,
+                domain,
+                filter,
+                body,
+            } => {
+                let kind = match kind {
+                    QuantifierKind::Forall => "forall",
+                    QuantifierKind::Exists => "exists",
+                    QuantifierKind::Newest => "newest",
+                };
+                let domain = match filter {
+                    None => domain.clone(),
+                    Some(InstanceFilter::Where { field, value }) => {
+                        let value = Expr::Literal(value.clone()).infix(false).0;
+                        format!("{domain} where {field} == {value}")
+                    }
+                    Some(InstanceFilter::OwnedBy { field, owner }) => {
+                        format!("{domain} owned by {owner} via {field}")
+                    }
+                };
+                let body = body.infix(true).0;
+                (format!("{kind} {var} in {domain}: {body}"), PREC_QUANTIFIER)
+            }
+            Expr::Op { op, args } => op_infix(op, args),
+        }
+    }
+}
+
+fn op_infix(op: &OpKind, args: &[Expr]) -> (String, u8) {
+    // Render `arg`, parenthesized if it binds looser than `min_prec`.
+    let operand = |arg: &Expr, names_domain: bool, min_prec: u8| {
+        let (text, prec) = arg.infix(names_domain);
+        if prec < min_prec {
+            format!("({text})")
+        } else {
+            text
+        }
+    };
+    let chain = |symbol: &str, prec: u8, names_domain: bool, empty: &str| {
+        let text = match args {
+            [] => return (empty.to_string(), PREC_ATOM),
+            [only] => return only.infix(names_domain),
+            _ => args
+                .iter()
+                .map(|a| operand(a, names_domain, prec))
+                .collect::<Vec<_>>()
+                .join(&format!(" {symbol} ")),
+        };
+        (text, prec)
+    };
+
+    match op {
+        OpKind::And => chain("&&", PREC_AND, true, "true"),
+        OpKind::Or => chain("||", PREC_OR, true, "false"),
+        OpKind::Xor => chain("^", PREC_XOR, true, "false"),
+        OpKind::Sum => chain("+", PREC_SUM, true, "0"),
+        OpKind::Sub => {
+            // Left-associative: only a right-hand sum or difference needs
+            // parentheses.
+            let text = args
+                .iter()
+                .enumerate()
+                .map(|(i, a)| operand(a, true, PREC_SUM + u8::from(i > 0)))
+                .collect::<Vec<_>>()
+                .join(" - ");
+            (text, PREC_SUM)
+        }
+        OpKind::In | OpKind::NotIn => {
+            let symbol = if *op == OpKind::In { "in" } else { "not in" };
+            let (subject, values) = args
+                .split_first()
+                .map_or((String::new(), &[][..]), |(x, rest)| {
+                    (operand(x, true, PREC_COMPARE + 1), rest)
+                });
+            let values: Vec<String> = values.iter().map(|v| v.infix(false).0).collect();
+            (
+                format!("{subject} {symbol} {{{}}}", values.join(", ")),
+                PREC_COMPARE,
+            )
+        }
+        OpKind::Not => {
+            let inner = args
+                .first()
+                .map_or_else(String::new, |a| operand(a, true, PREC_NOT));
+            (format!("!{inner}"), PREC_NOT)
+        }
+        OpKind::Implies | OpKind::Iff => {
+            let symbol = if *op == OpKind::Implies { "->" } else { "<->" };
+            // Only atoms go unparenthesized, so the arrow stands out.
+            let text = args
+                .iter()
+                .map(|a| operand(a, true, PREC_ATOM))
+                .collect::<Vec<_>>()
+                .join(&format!(" {symbol} "));
+            (text, PREC_IMPLIES)
+        }
+        OpKind::Eq | OpKind::Neq | OpKind::Lt | OpKind::Lte | OpKind::Gt | OpKind::Gte => {
+            let symbol = match op {
+                OpKind::Eq => "==",
+                OpKind::Neq => "!=",
+                OpKind::Lt => "<",
+                OpKind::Lte => "<=",
+                OpKind::Gt => ">",
+                _ => ">=",
+            };
+            // Comparisons do not chain, so equal-strength operands get parens.
+            let text = args
+                .iter()
+                .enumerate()
+                .map(|(i, a)| operand(a, i == 0, PREC_COMPARE + 1))
+                .collect::<Vec<_>>()
+                .join(&format!(" {symbol} "));
+            (text, PREC_COMPARE)
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Content-addressed IR hashing.
+//!
+//! Campaign memory and replay capsules are keyed on the IR hash, so the
+//! hash must depend only on what the IR means, not on how its JSON was
+//! written. The IR is canonicalized by round-tripping it through its own
+//! serde model: expressions come out in their normalized form, and every
+//! object (including `HashMap`-backed sections) is emitted with sorted
+//! keys. The canonical JSON is then hashed with SHA-256.
+
+use sha2::{Digest, Sha256};
+
+use crate::types::FresnelFirIR;
+
+/// Canonical JSON encoding of an IR: normalized expressions, sorted keys,
+/// no insignificant whitespace.
+pub fn canonical_json(ir: &FresnelFirIR) -> String {
+    // `serde_json::Value` objects are BTreeMap-backed, so converting
+    // through a `Value` sorts keys at every nesting level.
+    let value = serde_json::to_value(ir).expect("IR serialization is infallible");
+    value.to_string()
+}
+
+impl FresnelFirIR {
+    /// Stable content hash of this IR (lowercase hex SHA-256 of its
+    /// canonical JSON). Logically identical IRs hash identically
+    /// regardless of key order or expression shorthand in the source.
+    pub fn content_hash(&self) -> String {
+        let digest = Sha256::digest(canonical_json(self).as_bytes());
+        digest.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
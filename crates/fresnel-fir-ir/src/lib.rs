@@ -1,3 +1,5 @@
 pub mod expr;
+pub mod hash;
+pub mod merge;
 pub mod parse;
 pub mod types;
@@ -0,0 +1,45 @@
+//! Layering of input spaces.
+//!
+//! Large specs keep a shared base input space and put scenario-specific
+//! domains and constraints in overlay files. Merging an overlay is purely
+//! additive: a name already defined in the base may only be redefined
+//! identically, so an overlay can never silently change what a base
+//! domain means.
+
+use crate::types::InputSpace;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MergeError {
+    #[error("domain '{0}' is defined differently in base and overlay")]
+    ConflictingDomain(String),
+}
+
+impl InputSpace {
+    /// Layer `overlay` on top of this input space.
+    ///
+    /// Domains are unioned, constraints and forbidden
+    /// combinations concatenated, and coverage targets unioned without
+    /// duplicates. The base keeps its coverage seed and reproducibility
+    /// flag. On error `self` is left unchanged.
+    pub fn merge(&mut self, overlay: &InputSpace) -> Result<(), MergeError> {
+        for (name, domain) in &overlay.domains {
+            if self.domains.get(name).is_some_and(|base| base != domain) {
+                return Err(MergeError::ConflictingDomain(name.clone()));
+            }
+        }
+
+        for (name, domain) in &overlay.domains {
+            self.domains
+                .entry(name.clone())
+                .or_insert_with(|| domain.clone());
+        }
+        self.constraints.extend(overlay.constraints.iter().cloned());
+        self.forbidden.extend(overlay.forbidden.iter().cloned());
+        for target in &overlay.coverage.targets {
+            if !self.coverage.targets.contains(target) {
+                self.coverage.targets.push(target.clone());
+            }
+        }
+        Ok(())
+    }
+}
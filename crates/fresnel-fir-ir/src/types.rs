@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::expr::Expr;
+use crate::expr::{Expr, Literal};
 
 /// Top-level FresnelFir IR — all 9 sections.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -97,6 +97,18 @@ pub enum FnClassification {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Protocol {
     pub root: ProtocolNode,
+    /// Sequencing rules keyed by the action they constrain.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sequencing: HashMap<String, SequencingRule>,
+}
+
+/// Ordering constraint checked against the model's action trace before
+/// the constrained action runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SequencingRule {
+    /// Actions that must each have run at least once earlier in the trace.
+    #[serde(default)]
+    pub requires_prior: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -244,21 +256,40 @@ pub struct ConcurrencyConfig {
 pub struct InputSpace {
     pub domains: HashMap<String, Domain>,
     pub constraints: Vec<InputConstraint>,
+    /// Value combinations never to generate. Each entry pairs domain names
+    /// with one of their values; a vector matching all pairs is excluded.
+    #[serde(default)]
+    pub forbidden: Vec<Vec<(String, Literal)>>,
     pub coverage: CoverageConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Domain {
     #[serde(flatten)]
     pub domain_type: DomainType,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum DomainType {
-    Enum { values: Vec<String> },
+    Enum {
+        values: Vec<String>,
+        /// Declared order of `values` is meaningful (e.g. `private < shared < public`).
+        #[serde(default)]
+        ordered: bool,
+    },
     Bool,
-    Int { min: i64, max: i64 },
+    Int {
+        min: i64,
+        max: i64,
+        /// Only `min, min + stride, ...` up to `max` are in the domain.
+        #[serde(default = "default_stride")]
+        stride: i64,
+    },
+}
+
+fn default_stride() -> i64 {
+    1
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -274,7 +305,7 @@ pub struct CoverageConfig {
     pub reproducible: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum CoverageTarget {
     AllPairs {
@@ -287,6 +318,11 @@ pub enum CoverageTarget {
         domain: String,
         values: Vec<serde_json::Value>,
     },
+    /// Execute `action` at least `count` times over a campaign.
+    MinHits {
+        action: String,
+        count: u64,
+    },
 }
 
 // ── Section 9: Bindings ──────────────────────────────────────────────
@@ -308,6 +344,19 @@ pub struct ActionBinding {
     pub idempotent: bool,
     pub reads: Vec<String>,
     pub writes: Vec<String>,
+    /// WASM type of each entry in `args`, by position; missing entries are `i32`.
+    #[serde(default)]
+    pub arg_types: Vec<ArgType>,
+}
+
+/// WASM value type an action argument is passed as.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArgType {
+    #[default]
+    I32,
+    I64,
+    F64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
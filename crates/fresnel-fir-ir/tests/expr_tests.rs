@@ -14,7 +14,9 @@ fn test_parse_literal_bool() {
 fn test_parse_literal_string() {
     let json = serde_json::json!("public");
     let expr: Expr = serde_json::from_value(json).unwrap();
-    assert!(matches!(expr, Expr::Literal(fresnel_fir_ir::expr::Literal::String(s)) if s == "public"));
+    assert!(
+        matches!(expr, Expr::Literal(fresnel_fir_ir::expr::Literal::String(s)) if s == "public")
+    );
 }
 
 #[test]
@@ -57,3 +59,241 @@ fn test_parse_forall_quantifier() {
     let expr: Expr = serde_json::from_value(json).unwrap();
     assert!(matches!(expr, Expr::Quantifier { .. }));
 }
+
+#[test]
+fn test_parse_xor_and_iff() {
+    use fresnel_fir_ir::expr::OpKind;
+
+    let xor: Expr = serde_json::from_value(serde_json::json!(["xor", "a", "b", "c"])).unwrap();
+    assert!(matches!(xor, Expr::Op { op: OpKind::Xor, ref args } if args.len() == 3));
+
+    let iff: Expr = serde_json::from_value(serde_json::json!(["iff", "a", "b"])).unwrap();
+    assert!(matches!(
+        iff,
+        Expr::Op {
+            op: OpKind::Iff,
+            ..
+        }
+    ));
+
+    assert!(serde_json::from_value::<Expr>(serde_json::json!(["xor", "a"])).is_err());
+    assert!(serde_json::from_value::<Expr>(serde_json::json!(["iff", "a", "b", "c"])).is_err());
+}
+
+#[test]
+fn test_parse_sum_inside_comparison() {
+    use fresnel_fir_ir::expr::OpKind;
+
+    let json = serde_json::json!(["lte", ["sum", "cpu", "mem_gb"], 16]);
+    let expr: Expr = serde_json::from_value(json).unwrap();
+    let Expr::Op {
+        op: OpKind::Lte,
+        args,
+    } = expr
+    else {
+        panic!("expected lte, got {expr:?}");
+    };
+    assert!(matches!(&args[0], Expr::Op { op: OpKind::Sum, args } if args.len() == 2));
+
+    assert!(serde_json::from_value::<Expr>(serde_json::json!(["sum", "cpu"])).is_err());
+}
+
+#[test]
+fn test_parse_add_and_sub() {
+    use fresnel_fir_ir::expr::OpKind;
+
+    let add: Expr = serde_json::from_value(serde_json::json!(["add", "cpu", 1])).unwrap();
+    assert!(matches!(
+        add,
+        Expr::Op {
+            op: OpKind::Sum,
+            ..
+        }
+    ));
+    let sub: Expr = serde_json::from_value(serde_json::json!(["sub", "quota", "used"])).unwrap();
+    assert!(matches!(
+        sub,
+        Expr::Op {
+            op: OpKind::Sub,
+            ..
+        }
+    ));
+
+    assert!(serde_json::from_value::<Expr>(serde_json::json!(["sub", "a", "b", "c"])).is_err());
+    assert_eq!(
+        infix(serde_json::json!([
+            "sub",
+            ["sub", "a", "b"],
+            ["sum", "c", "d"]
+        ])),
+        "a - b - (c + d)"
+    );
+}
+
+fn infix(json: serde_json::Value) -> String {
+    serde_json::from_value::<Expr>(json).unwrap().to_infix()
+}
+
+#[test]
+fn test_to_infix_comparisons() {
+    use serde_json::json;
+
+    assert_eq!(infix(json!(["eq", "role", "guest"])), r#"role == "guest""#);
+    assert_eq!(infix(json!(["neq", "role", "admin"])), r#"role != "admin""#);
+    assert_eq!(infix(json!(["lt", "tier", "gold"])), r#"tier < "gold""#);
+    assert_eq!(infix(json!(["lte", "cpu", 4])), "cpu <= 4");
+    assert_eq!(infix(json!(["gt", "cpu", 1])), "cpu > 1");
+    assert_eq!(infix(json!(["gte", "cpu", 2])), "cpu >= 2");
+    assert_eq!(
+        infix(json!(["eq", ["field", "self", "authenticated"], true])),
+        "self.authenticated == true"
+    );
+    assert_eq!(
+        infix(json!(["lte", ["sum", "cpu", "mem_gb", 1], 16])),
+        "cpu + mem_gb + 1 <= 16"
+    );
+}
+
+#[test]
+fn test_to_infix_connectives() {
+    use serde_json::json;
+
+    assert_eq!(infix(json!(["and", "a", "b", "c"])), "a && b && c");
+    assert_eq!(infix(json!(["or", "a", "b"])), "a || b");
+    assert_eq!(infix(json!(["xor", "a", "b"])), "a ^ b");
+    assert_eq!(infix(json!(["not", "a"])), "!a");
+    assert_eq!(infix(json!(["implies", "a", "b"])), "a -> b");
+    assert_eq!(infix(json!(["iff", "a", "b"])), "a <-> b");
+    assert_eq!(infix(json!(["and", "a"])), "a");
+}
+
+#[test]
+fn test_to_infix_nested_parenthesization() {
+    use serde_json::json;
+
+    assert_eq!(
+        infix(json!([
+            "implies",
+            ["eq", "role", "guest"],
+            ["neq", "role", "admin"]
+        ])),
+        r#"(role == "guest") -> (role != "admin")"#
+    );
+    assert_eq!(
+        infix(json!([
+            "or",
+            ["eq", "visibility", "public"],
+            [
+                "and",
+                ["eq", "visibility", "shared"],
+                ["not", ["eq", "role", "guest"]]
+            ]
+        ])),
+        r#"visibility == "public" || visibility == "shared" && !(role == "guest")"#
+    );
+    assert_eq!(
+        infix(json!(["and", ["or", "a", "b"], ["xor", "c", "d"]])),
+        "(a || b) && (c ^ d)"
+    );
+    assert_eq!(
+        infix(json!(["not", ["and", "a", ["implies", "b", "c"]]])),
+        "!(a && (b -> c))"
+    );
+}
+
+#[test]
+fn test_parse_in_flattens_value_list() {
+    use fresnel_fir_ir::expr::{Literal, OpKind};
+
+    let expr: Expr =
+        serde_json::from_value(serde_json::json!(["in", "role", ["admin", "member"]])).unwrap();
+    let Expr::Op { op, args } = expr else {
+        panic!("expected an op");
+    };
+    assert_eq!(op, OpKind::In);
+    assert_eq!(
+        args,
+        vec![
+            Expr::Literal(Literal::String("role".into())),
+            Expr::Literal(Literal::String("admin".into())),
+            Expr::Literal(Literal::String("member".into())),
+        ]
+    );
+
+    assert!(serde_json::from_value::<Expr>(serde_json::json!(["in", "role", []])).is_err());
+    assert!(serde_json::from_value::<Expr>(serde_json::json!(["in", "role", "admin"])).is_err());
+    assert_eq!(
+        infix(serde_json::json!(["not_in", "role", ["guest", "banned"]])),
+        "role not in {\"guest\", \"banned\"}"
+    );
+}
+
+#[test]
+fn test_parse_quantifier_domain_filters() {
+    use fresnel_fir_ir::expr::{InstanceFilter, Literal, QuantifierKind};
+    use serde_json::json;
+
+    let expr: Expr = serde_json::from_value(json!([
+        "newest",
+        "d",
+        { "entity": "Document", "owned_by": { "field": "owner_id", "owner": "actor" } },
+        ["eq", ["field", "d", "visibility"], "private"]
+    ]))
+    .unwrap();
+    let Expr::Quantifier {
+        kind,
+        domain,
+        filter,
+        ..
+    } = &expr
+    else {
+        panic!("expected a quantifier, got {expr:?}");
+    };
+    assert_eq!(*kind, QuantifierKind::Newest);
+    assert_eq!(domain, "Document");
+    assert_eq!(
+        *filter,
+        Some(InstanceFilter::OwnedBy {
+            field: "owner_id".into(),
+            owner: "actor".into()
+        })
+    );
+    assert_eq!(
+        expr.to_infix(),
+        r#"newest d in Document owned by actor via owner_id: d.visibility == "private""#
+    );
+
+    let expr: Expr = serde_json::from_value(json!([
+        "exists",
+        "d",
+        { "entity": "Document", "where": { "field": "visibility", "value": "public" } },
+        true
+    ]))
+    .unwrap();
+    assert!(matches!(
+        &expr,
+        Expr::Quantifier {
+            filter: Some(InstanceFilter::Where {
+                value: Literal::String(v),
+                ..
+            }),
+            ..
+        } if v == "public"
+    ));
+    assert_eq!(
+        expr.to_infix(),
+        r#"exists d in Document where visibility == "public": true"#
+    );
+
+    for bad in [
+        json!(["forall", "d", { "where": { "field": "f", "value": 1 } }, true]),
+        json!(["forall", "d", { "entity": "Document", "sorted_by": "f" }, true]),
+        json!(["forall", "d", {
+            "entity": "Document",
+            "where": { "field": "f", "value": 1 },
+            "owned_by": { "field": "owner_id", "owner": "actor" }
+        }, true]),
+    ] {
+        assert!(serde_json::from_value::<Expr>(bad).is_err());
+    }
+}
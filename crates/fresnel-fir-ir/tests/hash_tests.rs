@@ -0,0 +1,57 @@
+use fresnel_fir_ir::parse::parse_ir;
+use serde_json::Value;
+
+/// Re-serialize JSON with every object's keys in reverse order.
+fn write_reversed(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            out.push('{');
+            for (i, (key, child)) in map.iter().rev().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_reversed(child, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, child) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_reversed(child, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+#[test]
+fn test_content_hash_ignores_key_order() {
+    let json_str = include_str!("fixtures/document_lifecycle.json");
+    let value: Value = serde_json::from_str(json_str).unwrap();
+    let mut reversed = String::new();
+    write_reversed(&value, &mut reversed);
+    assert_ne!(reversed, value.to_string());
+
+    let original = parse_ir(json_str).unwrap();
+    let reordered = parse_ir(&reversed).unwrap();
+    assert_eq!(original.content_hash(), reordered.content_hash());
+}
+
+#[test]
+fn test_content_hash_is_stable_and_content_sensitive() {
+    let json_str = include_str!("fixtures/document_lifecycle.json");
+    let ir = parse_ir(json_str).unwrap();
+    let hash = ir.content_hash();
+    assert_eq!(hash.len(), 64);
+    assert_eq!(hash, parse_ir(json_str).unwrap().content_hash());
+
+    let mut changed = ir.clone();
+    changed.exploration.epoch_size += 1;
+    assert_ne!(changed.content_hash(), hash);
+}
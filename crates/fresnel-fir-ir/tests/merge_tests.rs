@@ -0,0 +1,72 @@
+use fresnel_fir_ir::merge::MergeError;
+use fresnel_fir_ir::types::*;
+
+fn enum_domain(values: &[&str]) -> Domain {
+    Domain {
+        domain_type: DomainType::Enum {
+            values: values.iter().map(|v| v.to_string()).collect(),
+            ordered: false,
+        },
+    }
+}
+
+fn space(domains: &[(&str, Domain)], targets: Vec<CoverageTarget>) -> InputSpace {
+    InputSpace {
+        domains: domains
+            .iter()
+            .map(|(name, domain)| (name.to_string(), domain.clone()))
+            .collect(),
+        constraints: vec![],
+        forbidden: vec![],
+        coverage: CoverageConfig {
+            targets,
+            seed: 42,
+            reproducible: true,
+        },
+    }
+}
+
+#[test]
+fn test_merge_unions_domains_and_dedups_targets() {
+    let pairs = CoverageTarget::AllPairs {
+        over: vec!["role".into(), "vis".into()],
+    };
+    let mut base = space(
+        &[
+            ("role", enum_domain(&["admin", "guest"])),
+            ("vis", enum_domain(&["private", "public"])),
+        ],
+        vec![pairs.clone()],
+    );
+    let mut overlay = space(
+        &[
+            ("role", enum_domain(&["admin", "guest"])),
+            ("tier", enum_domain(&["free", "paid"])),
+        ],
+        vec![pairs],
+    );
+    overlay.coverage.seed = 7;
+
+    base.merge(&overlay).unwrap();
+    assert_eq!(base.domains.len(), 3);
+    assert_eq!(base.coverage.targets.len(), 1);
+    assert_eq!(base.coverage.seed, 42, "base keeps its seed");
+}
+
+#[test]
+fn test_merge_rejects_conflicting_domain() {
+    let mut base = space(&[("role", enum_domain(&["admin", "guest"]))], vec![]);
+    let overlay = space(
+        &[
+            ("role", enum_domain(&["admin", "root"])),
+            ("tier", enum_domain(&["free"])),
+        ],
+        vec![],
+    );
+
+    assert!(matches!(
+        base.merge(&overlay),
+        Err(MergeError::ConflictingDomain(name)) if name == "role"
+    ));
+    assert_eq!(base.domains.len(), 1, "failed merge leaves base unchanged");
+}
@@ -80,6 +80,28 @@ fn test_parse_protocol_with_grammar_constructs() {
     ));
 }
 
+#[test]
+fn test_parse_protocol_sequencing_rules() {
+    let json = serde_json::json!({
+        "root": { "type": "call", "action": "read" },
+        "sequencing": {
+            "read": { "requires_prior": ["create"] }
+        }
+    });
+    let proto: fresnel_fir_ir::types::Protocol = serde_json::from_value(json).unwrap();
+    assert_eq!(proto.sequencing["read"].requires_prior, vec!["create"]);
+
+    // Protocols without rules serialize exactly as before.
+    let plain: fresnel_fir_ir::types::Protocol =
+        serde_json::from_value(serde_json::json!({ "root": { "type": "call", "action": "read" } }))
+            .unwrap();
+    assert!(plain.sequencing.is_empty());
+    assert!(serde_json::to_value(&plain)
+        .unwrap()
+        .get("sequencing")
+        .is_none());
+}
+
 #[test]
 fn test_parse_effect() {
     let json = serde_json::json!({
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use fresnel_fir_compiler::predicate::CompiledExpr;
-use fresnel_fir_ir::expr::{OpKind, QuantifierKind};
+use fresnel_fir_ir::expr::{InstanceFilter, Literal, OpKind, QuantifierKind};
 
 use crate::state::{InstanceId, ModelState, Value};
 
@@ -18,6 +18,9 @@ pub enum ModelEvalError {
 
     #[error("Cannot evaluate: {reason}")]
     Unsupported { reason: String },
+
+    #[error("Integer overflow in {op}")]
+    Overflow { op: String },
 }
 
 /// Variable bindings mapping variable names to entity instance IDs.
@@ -64,33 +67,11 @@ pub fn eval_in_model(
             kind,
             var,
             domain,
+            filter,
             body,
         } => {
-            let instances = state.all_instances(domain);
-            match kind {
-                QuantifierKind::Forall => {
-                    for inst in instances {
-                        let mut new_bindings = bindings.clone();
-                        new_bindings.insert(var.clone(), inst.id.clone());
-                        let result = eval_in_model(body, state, &new_bindings)?;
-                        if result == Value::Bool(false) {
-                            return Ok(Value::Bool(false));
-                        }
-                    }
-                    Ok(Value::Bool(true))
-                }
-                QuantifierKind::Exists => {
-                    for inst in instances {
-                        let mut new_bindings = bindings.clone();
-                        new_bindings.insert(var.clone(), inst.id.clone());
-                        let result = eval_in_model(body, state, &new_bindings)?;
-                        if result == Value::Bool(true) {
-                            return Ok(Value::Bool(true));
-                        }
-                    }
-                    Ok(Value::Bool(false))
-                }
-            }
+            let query = InstanceQuery::for_domain(domain, filter.as_ref());
+            eval_quantified(kind, var, &query, body, state, bindings)
         }
 
         CompiledExpr::FnCall { name, .. } => {
@@ -110,6 +91,148 @@ pub fn eval_in_model(
     }
 }
 
+/// A set of instances for a guard or invariant to range over or bind
+/// from, beyond every instance of an entity.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstanceQuery {
+    /// Every instance of `entity`, like a plain quantifier domain.
+    All { entity: String },
+    /// Instances of `entity` whose `field` equals `value`.
+    Where {
+        entity: String,
+        field: String,
+        value: Value,
+    },
+    /// Instances of `entity` whose `owner_field` references the instance
+    /// bound to `owner_var` (see `ModelState::instances_owned_by`).
+    OwnedBy {
+        entity: String,
+        owner_field: String,
+        owner_var: String,
+    },
+}
+
+impl InstanceQuery {
+    /// The query a quantifier over `entity`, narrowed by `filter`, ranges
+    /// over.
+    pub fn for_domain(entity: &str, filter: Option<&InstanceFilter>) -> Self {
+        let entity = entity.to_string();
+        match filter {
+            None => InstanceQuery::All { entity },
+            Some(InstanceFilter::Where { field, value }) => InstanceQuery::Where {
+                entity,
+                field: field.clone(),
+                value: match value {
+                    Literal::Bool(b) => Value::Bool(*b),
+                    Literal::Int(i) => Value::Int(*i),
+                    Literal::String(s) => Value::String(s.clone()),
+                },
+            },
+            Some(InstanceFilter::OwnedBy { field, owner }) => InstanceQuery::OwnedBy {
+                entity,
+                owner_field: field.clone(),
+                owner_var: owner.clone(),
+            },
+        }
+    }
+
+    /// The matching instances, in creation order.
+    pub fn resolve(
+        &self,
+        state: &ModelState,
+        bindings: &Bindings,
+    ) -> Result<Vec<InstanceId>, ModelEvalError> {
+        let instances = match self {
+            InstanceQuery::All { entity } => state.all_instances(entity).iter().collect(),
+            InstanceQuery::Where {
+                entity,
+                field,
+                value,
+            } => state.instances_where(entity, field, value),
+            InstanceQuery::OwnedBy {
+                entity,
+                owner_field,
+                owner_var,
+            } => {
+                let owner =
+                    bindings
+                        .get(owner_var)
+                        .ok_or_else(|| ModelEvalError::UnboundVariable {
+                            var: owner_var.clone(),
+                        })?;
+                state.instances_owned_by(entity, owner_field, owner)
+            }
+        };
+        Ok(instances.into_iter().map(|inst| inst.id.clone()).collect())
+    }
+}
+
+/// Evaluate a quantifier over the instances `query` matches, e.g. "every
+/// Document owned by actor is private". `Newest` evaluates `body` with
+/// `var` bound by `bind_newest`, and is false when nothing matches.
+pub fn eval_quantified(
+    kind: &QuantifierKind,
+    var: &str,
+    query: &InstanceQuery,
+    body: &CompiledExpr,
+    state: &ModelState,
+    bindings: &Bindings,
+) -> Result<Value, ModelEvalError> {
+    if *kind == QuantifierKind::Newest {
+        let mut scope = bindings.clone();
+        if !bind_newest(&mut scope, var, query, state)? {
+            return Ok(Value::Bool(false));
+        }
+        return eval_in_model(body, state, &scope);
+    }
+    let instances = query.resolve(state, bindings)?;
+    quantify(kind, var, instances.iter(), body, state, bindings)
+}
+
+/// Bind `var` to the newest instance `query` matches, e.g. "the newest
+/// Document owned by actor". Returns whether anything matched; `var` is
+/// left untouched if not.
+pub fn bind_newest(
+    bindings: &mut Bindings,
+    var: &str,
+    query: &InstanceQuery,
+    state: &ModelState,
+) -> Result<bool, ModelEvalError> {
+    let newest = query
+        .resolve(state, bindings)?
+        .into_iter()
+        .max_by_key(|id| id.index);
+    match newest {
+        Some(id) => {
+            bindings.insert(var.to_string(), id);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Evaluate `body` with `var` bound to each of `instances` in turn,
+/// stopping at the first instance that decides the quantifier.
+fn quantify<'a>(
+    kind: &QuantifierKind,
+    var: &str,
+    instances: impl Iterator<Item = &'a InstanceId>,
+    body: &CompiledExpr,
+    state: &ModelState,
+    bindings: &Bindings,
+) -> Result<Value, ModelEvalError> {
+    // Forall stops at the first false, exists at the first true.
+    let decisive = Value::Bool(matches!(kind, QuantifierKind::Exists));
+    for id in instances {
+        let mut new_bindings = bindings.clone();
+        new_bindings.insert(var.to_string(), id.clone());
+        if eval_in_model(body, state, &new_bindings)? == decisive {
+            return Ok(decisive);
+        }
+    }
+    Ok(Value::Bool(matches!(kind, QuantifierKind::Forall)))
+}
+
 fn eval_op(
     op: &OpKind,
     args: &[CompiledExpr],
@@ -118,13 +241,11 @@ fn eval_op(
 ) -> Result<Value, ModelEvalError> {
     match op {
         OpKind::Eq => {
-            let left = eval_in_model(&args[0], state, bindings)?;
-            let right = eval_in_model(&args[1], state, bindings)?;
+            let (left, right) = eval_same_type(args, state, bindings)?;
             Ok(Value::Bool(left == right))
         }
         OpKind::Neq => {
-            let left = eval_in_model(&args[0], state, bindings)?;
-            let right = eval_in_model(&args[1], state, bindings)?;
+            let (left, right) = eval_same_type(args, state, bindings)?;
             Ok(Value::Bool(left != right))
         }
         OpKind::And => {
@@ -166,11 +287,100 @@ fn eval_op(
                 }),
             }
         }
+        OpKind::Xor => {
+            let mut parity = false;
+            for arg in args {
+                match eval_in_model(arg, state, bindings)? {
+                    Value::Bool(b) => parity ^= b,
+                    other => {
+                        return Err(ModelEvalError::TypeError {
+                            expected: "bool".to_string(),
+                            actual: format!("{other:?}"),
+                        })
+                    }
+                }
+            }
+            Ok(Value::Bool(parity))
+        }
+        OpKind::Iff => {
+            let left = eval_in_model(&args[0], state, bindings)?;
+            let right = eval_in_model(&args[1], state, bindings)?;
+            match (&left, &right) {
+                (Value::Bool(a), Value::Bool(b)) => Ok(Value::Bool(a == b)),
+                _ => Err(ModelEvalError::TypeError {
+                    expected: "bool".to_string(),
+                    actual: format!("{left:?}, {right:?}"),
+                }),
+            }
+        }
         OpKind::Lt => eval_int_cmp(args, state, bindings, |a, b| a < b),
         OpKind::Lte => eval_int_cmp(args, state, bindings, |a, b| a <= b),
         OpKind::Gt => eval_int_cmp(args, state, bindings, |a, b| a > b),
         OpKind::Gte => eval_int_cmp(args, state, bindings, |a, b| a >= b),
+        OpKind::Sum => {
+            let mut total: i64 = 0;
+            for arg in args {
+                match eval_in_model(arg, state, bindings)? {
+                    Value::Int(i) => {
+                        total = total
+                            .checked_add(i)
+                            .ok_or_else(|| ModelEvalError::Overflow {
+                                op: "sum".to_string(),
+                            })?
+                    }
+                    other => {
+                        return Err(ModelEvalError::TypeError {
+                            expected: "int".to_string(),
+                            actual: format!("{other:?}"),
+                        })
+                    }
+                }
+            }
+            Ok(Value::Int(total))
+        }
+        OpKind::Sub => {
+            let (a, b) = eval_int_operands(args, state, bindings)?;
+            a.checked_sub(b)
+                .map(Value::Int)
+                .ok_or_else(|| ModelEvalError::Overflow {
+                    op: "sub".to_string(),
+                })
+        }
+        OpKind::In | OpKind::NotIn => {
+            let needle = eval_in_model(&args[0], state, bindings)?;
+            let mut found = false;
+            for arg in &args[1..] {
+                let value = eval_in_model(arg, state, bindings)?;
+                if std::mem::discriminant(&value) != std::mem::discriminant(&needle) {
+                    return Err(ModelEvalError::TypeError {
+                        expected: "operands of the same type".to_string(),
+                        actual: format!("{needle:?}, {value:?}"),
+                    });
+                }
+                found |= value == needle;
+            }
+            Ok(Value::Bool(found == (*op == OpKind::In)))
+        }
+    }
+}
+
+/// Evaluate both operands of an equality. Values of different types are a
+/// type error rather than merely unequal, so a mistyped guard fails instead
+/// of quietly evaluating.
+fn eval_same_type(
+    args: &[CompiledExpr],
+    state: &ModelState,
+    bindings: &Bindings,
+) -> Result<(Value, Value), ModelEvalError> {
+    let left = eval_in_model(&args[0], state, bindings)?;
+    let right = eval_in_model(&args[1], state, bindings)?;
+    if std::mem::discriminant(&left) != std::mem::discriminant(&right) {
+        return Err(ModelEvalError::TypeError {
+            expected: "operands of the same type".to_string(),
+            actual: format!("{left:?}, {right:?}"),
+        });
     }
+    Ok((left, right))
 }
 
 fn eval_int_cmp(
@@ -179,10 +389,19 @@ fn eval_int_cmp(
     bindings: &Bindings,
     cmp: fn(i64, i64) -> bool,
 ) -> Result<Value, ModelEvalError> {
+    let (a, b) = eval_int_operands(args, state, bindings)?;
+    Ok(Value::Bool(cmp(a, b)))
+}
+
+fn eval_int_operands(
+    args: &[CompiledExpr],
+    state: &ModelState,
+    bindings: &Bindings,
+) -> Result<(i64, i64), ModelEvalError> {
     let left = eval_in_model(&args[0], state, bindings)?;
     let right = eval_in_model(&args[1], state, bindings)?;
     match (&left, &right) {
-        (Value::Int(a), Value::Int(b)) => Ok(Value::Bool(cmp(*a, *b))),
+        (Value::Int(a), Value::Int(b)) => Ok((*a, *b)),
         _ => Err(ModelEvalError::TypeError {
             expected: "int".to_string(),
             actual: format!("{left:?}, {right:?}"),
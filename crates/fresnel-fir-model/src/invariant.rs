@@ -1,13 +1,53 @@
 use fresnel_fir_compiler::predicate::CompiledExpr;
+use fresnel_fir_ir::expr::OpKind;
 
-use crate::eval::eval_in_model;
-use crate::state::ModelState;
+use crate::eval::{eval_in_model, Bindings, ModelEvalError};
+use crate::state::{ModelState, Value};
 
 /// A compiled property ready for checking.
 #[derive(Debug, Clone)]
 pub struct CompiledProperty {
     pub name: String,
+    /// The invariant itself, or with `count` set, the per-instance predicate.
     pub expr: CompiledExpr,
+    /// Turns the property into `count_satisfying(entity, expr) <op> bound`.
+    pub count: Option<CountBound>,
+}
+
+/// Bound on the number of `entity` instances satisfying a predicate.
+#[derive(Debug, Clone)]
+pub struct CountBound {
+    pub entity: String,
+    /// Variable the predicate uses for the instance being counted.
+    pub var: String,
+    /// One of `Eq`, `Neq`, `Lt`, `Lte`, `Gt`, `Gte`.
+    pub op: OpKind,
+    pub bound: usize,
+}
+
+impl CompiledProperty {
+    /// A property that holds when the number of `entity` instances for
+    /// which `predicate` is true, with `var` bound to each in turn,
+    /// compares to `bound` under `op`.
+    pub fn count_satisfying(
+        name: impl Into<String>,
+        entity: impl Into<String>,
+        var: impl Into<String>,
+        predicate: CompiledExpr,
+        op: OpKind,
+        bound: usize,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            expr: predicate,
+            count: Some(CountBound {
+                entity: entity.into(),
+                var: var.into(),
+                op,
+                bound,
+            }),
+        }
+    }
 }
 
 /// A violation found during invariant checking.
@@ -25,6 +65,12 @@ pub fn check_invariants(state: &ModelState, properties: &[CompiledProperty]) ->
     let bindings = Default::default();
 
     for prop in properties {
+        if let Some(count) = &prop.count {
+            if let Some(violation) = check_count(state, prop, count) {
+                violations.push(violation);
+            }
+            continue;
+        }
         match eval_in_model(&prop.expr, state, &bindings) {
             Ok(crate::state::Value::Bool(true)) => {
                 // Invariant holds
@@ -55,3 +101,63 @@ pub fn check_invariants(state: &ModelState, properties: &[CompiledProperty]) ->
 
     violations
 }
+
+/// Count the instances satisfying a count property's predicate and compare
+/// the count against its bound.
+fn check_count(
+    state: &ModelState,
+    prop: &CompiledProperty,
+    count: &CountBound,
+) -> Option<Violation> {
+    let violation = |message: String| Violation {
+        property_name: prop.name.clone(),
+        message,
+    };
+
+    let mut satisfying = 0usize;
+    for inst in state.all_instances(&count.entity) {
+        let mut bindings = Bindings::new();
+        bindings.insert(count.var.clone(), inst.id.clone());
+        match eval_in_model(&prop.expr, state, &bindings) {
+            Ok(Value::Bool(true)) => satisfying += 1,
+            Ok(Value::Bool(false)) => {}
+            Ok(other) => {
+                return Some(violation(format!(
+                    "Invariant '{}' predicate evaluated to non-boolean: {:?}",
+                    prop.name, other
+                )))
+            }
+            Err(e) => {
+                return Some(violation(format!(
+                    "Invariant '{}' evaluation error: {}",
+                    prop.name, e
+                )))
+            }
+        }
+    }
+
+    let (holds, symbol) = match count.op {
+        OpKind::Eq => (satisfying == count.bound, "=="),
+        OpKind::Neq => (satisfying != count.bound, "!="),
+        OpKind::Lt => (satisfying < count.bound, "<"),
+        OpKind::Lte => (satisfying <= count.bound, "<="),
+        OpKind::Gt => (satisfying > count.bound, ">"),
+        OpKind::Gte => (satisfying >= count.bound, ">="),
+        ref op => {
+            let e = ModelEvalError::Unsupported {
+                reason: format!("{:?} is not a count comparison", op),
+            };
+            return Some(violation(format!(
+                "Invariant '{}' evaluation error: {}",
+                prop.name, e
+            )));
+        }
+    };
+    if holds {
+        return None;
+    }
+    Some(violation(format!(
+        "Invariant '{}' violated: {} {} instance(s) satisfy the predicate, expected {} {}",
+        prop.name, satisfying, count.entity, symbol, count.bound
+    )))
+}
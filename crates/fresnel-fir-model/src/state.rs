@@ -17,7 +17,7 @@ pub struct InstanceId {
 }
 
 /// A single entity instance with typed fields.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EntityInstance {
     pub entity_type: String,
     pub id: InstanceId,
@@ -43,26 +43,48 @@ impl EntityInstance {
 }
 
 /// A trace entry recording an action that was executed.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TraceEntry {
     pub action: String,
     pub args: Vec<(String, String)>,
     pub generation: u64,
 }
 
-/// Snapshot token for rollback.
-#[derive(Debug, Clone)]
-pub struct Snapshot {
+/// One field that differs between two model states.
+///
+/// A created instance reports each of its fields with `before: None`, a
+/// destroyed one each field with `after: None`. An instance created or
+/// destroyed without any fields set is reported once with an empty
+/// `field` and both values `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub instance: InstanceId,
+    pub field: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// Snapshot of a model state for rollback: all instances and their
+/// fields, the action trace, and the generation and instance counters.
+///
+/// Shares storage with the live state through `Arc`, but is logically a
+/// deep copy: later mutations of the state copy-on-write and never
+/// reach the snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelSnapshot {
     instances: HashMap<String, Arc<Vec<EntityInstance>>>,
     trace: Arc<Vec<TraceEntry>>,
     generation: u64,
     next_instance_id: u64,
 }
 
+/// Former name of [`ModelSnapshot`].
+pub type Snapshot = ModelSnapshot;
+
 /// Copy-on-Write model state.
 ///
 /// Uses Arc for efficient forking — data is shared until mutation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ModelState {
     /// Instances grouped by entity type. Arc for CoW sharing.
     instances: HashMap<String, Arc<Vec<EntityInstance>>>,
@@ -140,14 +162,51 @@ impl ModelState {
             .unwrap_or(&[])
     }
 
+    /// Instances of `entity_type` whose `field` equals `value`, in
+    /// creation order.
+    pub fn instances_where(
+        &self,
+        entity_type: &str,
+        field: &str,
+        value: &Value,
+    ) -> Vec<&EntityInstance> {
+        self.all_instances(entity_type)
+            .iter()
+            .filter(|inst| inst.get_field(field) == Some(value))
+            .collect()
+    }
+
+    /// The most recently created instance of `entity_type`.
+    pub fn newest_instance(&self, entity_type: &str) -> Option<&EntityInstance> {
+        self.all_instances(entity_type)
+            .iter()
+            .max_by_key(|inst| inst.id.index)
+    }
+
+    /// Instances of `entity_type` whose reference field `owner_field`
+    /// points at `owner`, in creation order. References hold the owner's
+    /// `id` field, as set by effects like `owner_id := actor.id`; an owner
+    /// without one owns nothing.
+    pub fn instances_owned_by(
+        &self,
+        entity_type: &str,
+        owner_field: &str,
+        owner: &InstanceId,
+    ) -> Vec<&EntityInstance> {
+        match self.get_instance(owner).and_then(|o| o.get_field("id")) {
+            Some(owner_ref) => self.instances_where(entity_type, owner_field, owner_ref),
+            None => Vec::new(),
+        }
+    }
+
     /// Fork this state — creates a cheap CoW clone.
     pub fn fork(&self) -> Self {
         self.clone()
     }
 
     /// Take a snapshot for later rollback.
-    pub fn snapshot(&self) -> Snapshot {
-        Snapshot {
+    pub fn snapshot(&self) -> ModelSnapshot {
+        ModelSnapshot {
             instances: self.instances.clone(),
             trace: self.trace.clone(),
             generation: self.generation,
@@ -155,14 +214,57 @@ impl ModelState {
         }
     }
 
-    /// Rollback to a previously captured snapshot.
-    pub fn rollback(&mut self, snapshot: Snapshot) {
+    /// Roll back to a previously captured snapshot. Afterwards the state
+    /// is indistinguishable from the one the snapshot was taken of.
+    pub fn restore(&mut self, snapshot: ModelSnapshot) {
         self.instances = snapshot.instances;
         self.trace = snapshot.trace;
         self.generation = snapshot.generation;
         self.next_instance_id = snapshot.next_instance_id;
     }
 
+    /// Roll back to a previously captured snapshot.
+    #[deprecated(note = "renamed to `restore`")]
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        self.restore(snapshot);
+    }
+
+    /// Field-level changes from `other` to this state, ordered by entity
+    /// type, instance index and field name.
+    pub fn diff(&self, other: &ModelSnapshot) -> Vec<FieldChange> {
+        let mut types: Vec<&String> = self
+            .instances
+            .keys()
+            .chain(other.instances.keys())
+            .collect();
+        types.sort();
+        types.dedup();
+
+        let mut changes = Vec::new();
+        for entity_type in types {
+            let after = self.instances.get(entity_type);
+            let before = other.instances.get(entity_type);
+            // Untouched types still share storage with the snapshot.
+            if let (Some(after), Some(before)) = (after, before) {
+                if Arc::ptr_eq(after, before) {
+                    continue;
+                }
+            }
+            let after = after.map(|v| v.as_slice()).unwrap_or(&[]);
+            let before = before.map(|v| v.as_slice()).unwrap_or(&[]);
+
+            let mut indices: Vec<u64> = after.iter().chain(before).map(|i| i.id.index).collect();
+            indices.sort_unstable();
+            indices.dedup();
+            for index in indices {
+                let new = after.iter().find(|i| i.id.index == index);
+                let old = before.iter().find(|i| i.id.index == index);
+                diff_instance(old, new, &mut changes);
+            }
+        }
+        changes
+    }
+
     /// Record an action in the trace.
     pub fn record_action(&mut self, action: &str, args: &[(&str, &str)]) {
         let entry = TraceEntry {
@@ -187,3 +289,40 @@ impl Default for ModelState {
         Self::new()
     }
 }
+
+fn diff_instance(
+    before: Option<&EntityInstance>,
+    after: Option<&EntityInstance>,
+    changes: &mut Vec<FieldChange>,
+) {
+    let Some(id) = after.or(before).map(|inst| &inst.id) else {
+        return;
+    };
+    let empty = HashMap::new();
+    let old = before.map_or(&empty, |inst| &inst.fields);
+    let new = after.map_or(&empty, |inst| &inst.fields);
+
+    let mut fields: Vec<&String> = old.keys().chain(new.keys()).collect();
+    fields.sort();
+    fields.dedup();
+    let start = changes.len();
+    for field in fields {
+        let (b, a) = (old.get(field), new.get(field));
+        if b != a {
+            changes.push(FieldChange {
+                instance: id.clone(),
+                field: field.clone(),
+                before: b.cloned(),
+                after: a.cloned(),
+            });
+        }
+    }
+    if changes.len() == start && before.is_some() != after.is_some() {
+        changes.push(FieldChange {
+            instance: id.clone(),
+            field: String::new(),
+            before: None,
+            after: None,
+        });
+    }
+}
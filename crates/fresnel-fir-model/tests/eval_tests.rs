@@ -1,7 +1,9 @@
 use fresnel_fir_compiler::predicate::{compile_expr, TypeContext};
 use fresnel_fir_ir::parse::parse_ir;
 use fresnel_fir_model::effect::apply_effect;
-use fresnel_fir_model::eval::eval_in_model;
+use fresnel_fir_model::eval::{
+    bind_newest, eval_in_model, eval_quantified, InstanceQuery, ModelEvalError,
+};
 use fresnel_fir_model::state::{ModelState, Value};
 
 fn setup() -> (fresnel_fir_ir::types::FresnelFirIR, TypeContext) {
@@ -171,3 +173,204 @@ fn test_eval_nested_forall_ownership_isolation() {
         assert_eq!(result, Value::Bool(true));
     }
 }
+
+fn eval_on_doc_size(size: Value, expr: serde_json::Value) -> Result<Value, ModelEvalError> {
+    let (_ir, ctx) = setup();
+    let mut state = ModelState::new();
+    let doc_id = state.create_instance("Document");
+    state.set_field(&doc_id, "size", size);
+    let expr: fresnel_fir_ir::expr::Expr = serde_json::from_value(expr).unwrap();
+    let compiled = compile_expr(&expr, &ctx).unwrap();
+
+    let mut bindings = std::collections::HashMap::new();
+    bindings.insert("doc".to_string(), doc_id);
+    eval_in_model(&compiled, &state, &bindings)
+}
+
+#[test]
+fn test_eval_gt_field_against_literal() {
+    let gt = serde_json::json!(["gt", ["field", "doc", "size"], 10]);
+    assert_eq!(
+        eval_on_doc_size(Value::Int(12), gt.clone()).unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        eval_on_doc_size(Value::Int(10), gt).unwrap(),
+        Value::Bool(false)
+    );
+}
+
+#[test]
+fn test_eval_add_and_sub_on_fields() {
+    let within = serde_json::json!([
+        "lte",
+        ["add", ["field", "doc", "size"], 5],
+        ["sub", 20, ["field", "doc", "size"]]
+    ]);
+    assert_eq!(
+        eval_on_doc_size(Value::Int(7), within.clone()).unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        eval_on_doc_size(Value::Int(8), within).unwrap(),
+        Value::Bool(false)
+    );
+}
+
+#[test]
+fn test_eval_arithmetic_overflow_is_error() {
+    let max = Value::Int(i64::MAX);
+    assert!(matches!(
+        eval_on_doc_size(
+            max.clone(),
+            serde_json::json!(["gt", ["add", ["field", "doc", "size"], 1], 0])
+        ),
+        Err(ModelEvalError::Overflow { ref op }) if op == "sum"
+    ));
+    assert!(matches!(
+        eval_on_doc_size(
+            max,
+            serde_json::json!(["gt", ["sub", -2, ["field", "doc", "size"]], 0])
+        ),
+        Err(ModelEvalError::Overflow { ref op }) if op == "sub"
+    ));
+}
+
+#[test]
+fn test_eval_type_mismatch_is_error() {
+    let size = Value::String("big".to_string());
+    assert!(matches!(
+        eval_on_doc_size(
+            size.clone(),
+            serde_json::json!(["gt", ["field", "doc", "size"], 10])
+        ),
+        Err(ModelEvalError::TypeError { .. })
+    ));
+    assert!(matches!(
+        eval_on_doc_size(
+            size,
+            serde_json::json!(["eq", ["field", "doc", "size"], 10])
+        ),
+        Err(ModelEvalError::TypeError { .. })
+    ));
+}
+
+#[test]
+fn test_eval_over_instance_queries() {
+    let (ir, ctx) = setup();
+    let create = ir.effects.get("create_document").unwrap();
+    let mut state = ModelState::new();
+    let alice = state.create_instance("User");
+    state.set_field(&alice, "id", Value::String("alice".to_string()));
+    let bob = state.create_instance("User");
+    state.set_field(&bob, "id", Value::String("bob".to_string()));
+
+    apply_effect(&mut state, create, &alice).unwrap();
+    apply_effect(&mut state, create, &alice).unwrap();
+    apply_effect(&mut state, create, &bob).unwrap();
+    let bobs_doc = state.newest_instance("Document").unwrap().id.clone();
+    state.set_field(&bobs_doc, "visibility", Value::String("public".to_string()));
+
+    let mut bindings = std::collections::HashMap::new();
+    bindings.insert("actor".to_string(), alice.clone());
+    let owned_by_actor = InstanceQuery::OwnedBy {
+        entity: "Document".to_string(),
+        owner_field: "owner_id".to_string(),
+        owner_var: "actor".to_string(),
+    };
+
+    // Every document the actor owns is private, though not every document is.
+    let expr: fresnel_fir_ir::expr::Expr = serde_json::from_value(serde_json::json!([
+        "eq",
+        ["field", "d", "visibility"],
+        "private"
+    ]))
+    .unwrap();
+    let is_private = compile_expr(&expr, &ctx).unwrap();
+    let forall = fresnel_fir_ir::expr::QuantifierKind::Forall;
+    let result = eval_quantified(
+        &forall,
+        "d",
+        &owned_by_actor,
+        &is_private,
+        &state,
+        &bindings,
+    )
+    .unwrap();
+    assert_eq!(result, Value::Bool(true));
+    let every_doc = InstanceQuery::All {
+        entity: "Document".to_string(),
+    };
+    let result = eval_quantified(&forall, "d", &every_doc, &is_private, &state, &bindings).unwrap();
+    assert_eq!(result, Value::Bool(false));
+
+    // "The newest Document owned by actor" is alice's second document.
+    assert!(bind_newest(&mut bindings, "doc", &owned_by_actor, &state).unwrap());
+    let alices_docs = state.instances_owned_by("Document", "owner_id", &alice);
+    assert_eq!(bindings["doc"], alices_docs[1].id);
+
+    let shared = InstanceQuery::Where {
+        entity: "Document".to_string(),
+        field: "visibility".to_string(),
+        value: Value::String("shared".to_string()),
+    };
+    assert!(!bind_newest(&mut bindings, "shared", &shared, &state).unwrap());
+    assert!(!bindings.contains_key("shared"));
+
+    bindings.remove("actor");
+    assert!(matches!(
+        owned_by_actor.resolve(&state, &bindings),
+        Err(ModelEvalError::UnboundVariable { .. })
+    ));
+}
+
+#[test]
+fn test_eval_quantifiers_over_filtered_domains() {
+    let (ir, ctx) = setup();
+    let create = ir.effects.get("create_document").unwrap();
+    let mut state = ModelState::new();
+    let alice = state.create_instance("User");
+    state.set_field(&alice, "id", Value::String("alice".to_string()));
+    let bob = state.create_instance("User");
+    state.set_field(&bob, "id", Value::String("bob".to_string()));
+    let carol = state.create_instance("User");
+    state.set_field(&carol, "id", Value::String("carol".to_string()));
+
+    apply_effect(&mut state, create, &alice).unwrap();
+    apply_effect(&mut state, create, &bob).unwrap();
+    let bobs_doc = state.newest_instance("Document").unwrap().id.clone();
+    state.set_field(&bobs_doc, "visibility", Value::String("public".to_string()));
+
+    let eval = |json: serde_json::Value, actor: &fresnel_fir_model::state::InstanceId| {
+        let expr: fresnel_fir_ir::expr::Expr = serde_json::from_value(json).unwrap();
+        let compiled = compile_expr(&expr, &ctx).unwrap();
+        let mut bindings = std::collections::HashMap::new();
+        bindings.insert("actor".to_string(), actor.clone());
+        eval_in_model(&compiled, &state, &bindings).unwrap()
+    };
+    let owned = serde_json::json!({
+        "entity": "Document",
+        "owned_by": { "field": "owner_id", "owner": "actor" }
+    });
+    let is_private = serde_json::json!(["eq", ["field", "d", "visibility"], "private"]);
+
+    // "The newest Document owned by actor is private."
+    let newest_private = serde_json::json!(["newest", "d", owned, is_private]);
+    assert_eq!(eval(newest_private.clone(), &alice), Value::Bool(true));
+    assert_eq!(eval(newest_private.clone(), &bob), Value::Bool(false));
+    // Nothing matches for carol, so `newest` is false.
+    assert_eq!(eval(newest_private, &carol), Value::Bool(false));
+
+    let forall_private = serde_json::json!(["forall", "d", owned, is_private]);
+    assert_eq!(eval(forall_private.clone(), &alice), Value::Bool(true));
+    assert_eq!(eval(forall_private, &carol), Value::Bool(true));
+
+    let public = |value: &str| {
+        serde_json::json!(["exists", "d", {
+            "entity": "Document",
+            "where": { "field": "visibility", "value": value }
+        }, true])
+    };
+    assert_eq!(eval(public("public"), &alice), Value::Bool(true));
+    assert_eq!(eval(public("shared"), &alice), Value::Bool(false));
+}
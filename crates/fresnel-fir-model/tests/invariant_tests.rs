@@ -1,4 +1,5 @@
 use fresnel_fir_compiler::predicate::{compile_expr, TypeContext};
+use fresnel_fir_ir::expr::OpKind;
 use fresnel_fir_ir::parse::parse_ir;
 use fresnel_fir_model::effect::apply_effect;
 use fresnel_fir_model::invariant::{check_invariants, CompiledProperty};
@@ -20,6 +21,7 @@ fn setup() -> (
             properties.push(CompiledProperty {
                 name: name.clone(),
                 expr: compiled,
+                count: None,
             });
         }
     }
@@ -76,6 +78,7 @@ fn test_invariant_passes_with_multiple_users() {
     let custom_props = vec![CompiledProperty {
         name: "admin_is_authenticated".to_string(),
         expr: compiled,
+        count: None,
     }];
 
     // User 1 is admin and authenticated (satisfies)
@@ -125,6 +128,7 @@ fn test_simple_invariant_violation() {
     let custom_props = vec![CompiledProperty {
         name: "all_authenticated".to_string(),
         expr: compiled,
+        count: None,
     }];
 
     let mut state = ModelState::new();
@@ -137,3 +141,48 @@ fn test_simple_invariant_violation() {
     assert_eq!(violations.len(), 1);
     assert_eq!(violations[0].property_name, "all_authenticated");
 }
+
+#[test]
+fn test_count_satisfying_reports_actual_count() {
+    let (_ir, ctx, _properties) = setup();
+
+    // At most one public document.
+    let expr: fresnel_fir_ir::expr::Expr = serde_json::from_value(serde_json::json!([
+        "eq",
+        ["field", "d", "visibility"],
+        "public"
+    ]))
+    .unwrap();
+    let predicate = compile_expr(&expr, &ctx).unwrap();
+    let props = vec![CompiledProperty::count_satisfying(
+        "at_most_one_public",
+        "Document",
+        "d",
+        predicate,
+        OpKind::Lte,
+        1,
+    )];
+
+    let mut state = ModelState::new();
+    for visibility in ["public", "private", "public"] {
+        let doc = state.create_instance("Document");
+        state.set_field(&doc, "visibility", Value::String(visibility.to_string()));
+    }
+
+    let violations = check_invariants(&state, &props);
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].property_name, "at_most_one_public");
+    assert!(
+        violations[0].message.contains("2 Document instance(s)"),
+        "message should report the count: {}",
+        violations[0].message
+    );
+
+    // Dropping one public document brings the count within the bound.
+    let mut state = ModelState::new();
+    for visibility in ["public", "private"] {
+        let doc = state.create_instance("Document");
+        state.set_field(&doc, "visibility", Value::String(visibility.to_string()));
+    }
+    assert!(check_invariants(&state, &props).is_empty());
+}
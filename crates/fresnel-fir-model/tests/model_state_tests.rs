@@ -1,4 +1,6 @@
-use fresnel_fir_model::state::{ModelState, Value};
+use fresnel_fir_ir::parse::parse_ir;
+use fresnel_fir_model::effect::apply_effect;
+use fresnel_fir_model::state::{FieldChange, ModelState, Value};
 
 #[test]
 fn test_new_model_state_is_empty() {
@@ -63,6 +65,7 @@ fn test_fork_creates_independent_copy() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_snapshot_and_rollback() {
     let mut state = ModelState::new();
     let id = state.create_instance("User");
@@ -85,6 +88,42 @@ fn test_snapshot_and_rollback() {
     assert!(state.all_instances("Document").is_empty());
 }
 
+#[test]
+fn test_restore_matches_snapshot_exactly() {
+    let mut state = ModelState::new();
+    let user = state.create_instance("User");
+    state.set_field(&user, "role", Value::String("admin".to_string()));
+    state.record_action("login", &[("user", "0")]);
+
+    let before = state.clone();
+    let snapshot = state.snapshot();
+
+    // Mutate fields, create instances and extend the trace.
+    state.set_field(&user, "role", Value::String("guest".to_string()));
+    state.set_field(&user, "active", Value::Bool(false));
+    let doc = state.create_instance("Document");
+    state.set_field(&doc, "visibility", Value::String("public".to_string()));
+    state.create_instance("User");
+    state.record_action("publish", &[("doc", "1")]);
+
+    // The snapshot is unaffected by the mutations.
+    assert_ne!(state.snapshot(), snapshot);
+    let mut replay = ModelState::new();
+    replay.restore(snapshot.clone());
+    assert_eq!(replay, before);
+
+    state.restore(snapshot.clone());
+    assert_eq!(state, before);
+    assert_eq!(state.snapshot(), snapshot);
+    assert_eq!(state.generation(), before.generation());
+    assert_eq!(state.trace().len(), 1);
+    assert_eq!(state.all_instances("User").len(), 1);
+    assert!(state.all_instances("Document").is_empty());
+
+    // Instance ids continue from where the snapshot left off.
+    assert_eq!(state.create_instance("Document").index, 1);
+}
+
 #[test]
 fn test_multiple_entity_types() {
     let mut state = ModelState::new();
@@ -124,3 +163,131 @@ fn test_trace_records_actions() {
     assert_eq!(trace[0].action, "create_document");
     assert_eq!(trace[1].action, "read");
 }
+
+#[test]
+fn test_diff_reports_created_instance_fields() {
+    let json = include_str!("../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json");
+    let ir = parse_ir(json).unwrap();
+    let mut state = ModelState::new();
+    let actor_id = state.create_instance("User");
+    state.set_field(&actor_id, "id", Value::String("user-123".to_string()));
+
+    let before = state.snapshot();
+    assert!(state.diff(&before).is_empty());
+    apply_effect(
+        &mut state,
+        ir.effects.get("create_document").unwrap(),
+        &actor_id,
+    )
+    .unwrap();
+
+    let doc_id = state.all_instances("Document")[0].id.clone();
+    let changes = state.diff(&before);
+    assert!(changes
+        .iter()
+        .all(|c| c.instance == doc_id && c.before.is_none()));
+    assert!(changes.contains(&FieldChange {
+        instance: doc_id.clone(),
+        field: "visibility".to_string(),
+        before: None,
+        after: Some(Value::String("private".to_string())),
+    }));
+    assert!(changes.iter().any(|c| c.field == "owner_id"));
+}
+
+#[test]
+fn test_diff_reports_changed_field_and_bare_instance() {
+    let mut state = ModelState::new();
+    let id = state.create_instance("Document");
+    state.set_field(&id, "deleted", Value::Bool(false));
+    let before = state.snapshot();
+
+    state.set_field(&id, "deleted", Value::Bool(true));
+    let bare = state.create_instance("Tag");
+    assert_eq!(
+        state.diff(&before),
+        vec![
+            FieldChange {
+                instance: id,
+                field: "deleted".to_string(),
+                before: Some(Value::Bool(false)),
+                after: Some(Value::Bool(true)),
+            },
+            FieldChange {
+                instance: bare,
+                field: String::new(),
+                before: None,
+                after: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_instance_queries_return_matching_subsets() {
+    let ir = parse_ir(include_str!(
+        "../../fresnel-fir-ir/tests/fixtures/document_lifecycle.json"
+    ))
+    .unwrap();
+    let create = ir.effects.get("create_document").unwrap();
+
+    let mut state = ModelState::new();
+    let alice = state.create_instance("User");
+    state.set_field(&alice, "id", Value::String("alice".to_string()));
+    let bob = state.create_instance("User");
+    state.set_field(&bob, "id", Value::String("bob".to_string()));
+    let anonymous = state.create_instance("User");
+
+    apply_effect(&mut state, create, &alice).unwrap();
+    apply_effect(&mut state, create, &bob).unwrap();
+    apply_effect(&mut state, create, &alice).unwrap();
+    let docs: Vec<_> = state
+        .all_instances("Document")
+        .iter()
+        .map(|d| d.id.clone())
+        .collect();
+    state.set_field(&docs[1], "visibility", Value::String("public".to_string()));
+
+    let ids = |instances: Vec<&fresnel_fir_model::state::EntityInstance>| {
+        instances
+            .into_iter()
+            .map(|i| i.id.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let private = Value::String("private".to_string());
+    assert_eq!(
+        ids(state.instances_where("Document", "visibility", &private)),
+        vec![docs[0].clone(), docs[2].clone()]
+    );
+    assert!(state
+        .instances_where(
+            "Document",
+            "visibility",
+            &Value::String("shared".to_string())
+        )
+        .is_empty());
+
+    assert_eq!(
+        state.newest_instance("Document").map(|d| d.id.clone()),
+        Some(docs[2].clone())
+    );
+    assert_eq!(
+        state.newest_instance("User").map(|u| u.id.clone()),
+        Some(anonymous.clone())
+    );
+    assert!(state.newest_instance("Folder").is_none());
+
+    assert_eq!(
+        ids(state.instances_owned_by("Document", "owner_id", &alice)),
+        vec![docs[0].clone(), docs[2].clone()]
+    );
+    assert_eq!(
+        ids(state.instances_owned_by("Document", "owner_id", &bob)),
+        vec![docs[1].clone()]
+    );
+    // No `id` field, so nothing can reference it.
+    assert!(state
+        .instances_owned_by("Document", "owner_id", &anonymous)
+        .is_empty());
+}
@@ -113,6 +113,12 @@ impl From<i64> for WasmVal {
     }
 }
 
+impl From<f64> for WasmVal {
+    fn from(v: f64) -> Self {
+        WasmVal(Val::F64(v.to_bits()))
+    }
+}
+
 impl From<Val> for WasmVal {
     fn from(v: Val) -> Self {
         WasmVal(v)
@@ -254,4 +260,13 @@ impl SandboxInstance {
     pub fn fuel_budget(&self) -> Option<u64> {
         self.fuel_per_action
     }
+
+    /// Replace the fuel budget applied from the next call on.
+    ///
+    /// Has no effect when the sandbox was created without fuel metering.
+    pub fn set_fuel_budget(&mut self, fuel: u64) {
+        if self.fuel_per_action.is_some() {
+            self.fuel_per_action = Some(fuel);
+        }
+    }
 }
@@ -124,6 +124,56 @@ fn test_fuel_exhaustion() {
     }
 }
 
+#[test]
+fn test_set_fuel_budget_applies_to_next_call() {
+    let spin_wat = r#"
+    (module
+      (func (export "spin") (param i32) (result i32)
+        (loop $loop
+          (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+          (br_if $loop (i32.gt_s (local.get 0) (i32.const 0)))
+        )
+        local.get 0
+      )
+    )
+    "#;
+    let wasm = wat_to_wasm(spin_wat);
+    let config = SandboxConfig {
+        fuel_per_action: Some(1_000_000),
+        ..Default::default()
+    };
+    let sandbox = Sandbox::new(&config).unwrap();
+    let module = sandbox.load_module(&wasm).unwrap();
+    let mut instance = sandbox.instantiate(&module).unwrap();
+
+    assert!(instance.call_func("spin", &[10_000i32.into()]).is_ok());
+
+    instance.set_fuel_budget(100);
+    assert_eq!(instance.fuel_budget(), Some(100));
+    match instance.call_func("spin", &[10_000i32.into()]) {
+        Err(SandboxError::FuelExhausted) => {}
+        other => panic!("expected FuelExhausted, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_set_fuel_budget_ignored_without_metering() {
+    let wasm = wat_to_wasm(TEST_DUT_WAT);
+    let config = SandboxConfig {
+        fuel_per_action: None,
+        ..Default::default()
+    };
+    let sandbox = Sandbox::new(&config).unwrap();
+    let module = sandbox.load_module(&wasm).unwrap();
+    let mut instance = sandbox.instantiate(&module).unwrap();
+
+    instance.set_fuel_budget(100);
+    assert_eq!(instance.fuel_budget(), None);
+    assert!(instance
+        .call_func("create_document", &[1i32.into()])
+        .is_ok());
+}
+
 #[test]
 fn test_no_wasi_access() {
     // A module that tries to import WASI functions should fail to instantiate
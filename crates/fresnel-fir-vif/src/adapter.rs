@@ -10,8 +10,8 @@ pub struct ActionResult {
     pub action: String,
     /// The WASM function that was called.
     pub function: String,
-    /// The arguments passed (as i32 values).
-    pub args: Vec<i32>,
+    /// The arguments passed.
+    pub args: Vec<WasmArg>,
     /// The return value (None for void functions).
     pub return_value: Option<i32>,
     /// Whether the call trapped/panicked.
@@ -22,6 +22,24 @@ pub struct ActionResult {
     pub error: Option<String>,
 }
 
+/// A typed argument for an action call.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WasmArg {
+    I32(i32),
+    I64(i64),
+    F64(f64),
+}
+
+impl From<WasmArg> for WasmVal {
+    fn from(arg: WasmArg) -> Self {
+        match arg {
+            WasmArg::I32(v) => WasmVal::from(v),
+            WasmArg::I64(v) => WasmVal::from(v),
+            WasmArg::F64(v) => WasmVal::from(v),
+        }
+    }
+}
+
 /// Observer result — explicitly tagged to never be confused with model truth.
 #[derive(Debug)]
 pub struct ObserverResult {
@@ -81,7 +99,7 @@ impl VerificationAdapter {
         &self,
         instance: &mut SandboxInstance,
         action: &str,
-        args: &[i32],
+        args: &[WasmArg],
     ) -> ActionResult {
         let binding = match self.action_bindings.get(action) {
             Some(b) => b,
@@ -100,7 +118,6 @@ impl VerificationAdapter {
 
         let func_name = &binding.function;
 
-        // Serialize args as i32 WasmVals
         let wasm_args: Vec<WasmVal> = args.iter().map(|&a| WasmVal::from(a)).collect();
 
         // call_func resets fuel before executing, so measure AFTER the call
@@ -199,6 +216,11 @@ impl VerificationAdapter {
         self.action_bindings.contains_key(action)
     }
 
+    /// The binding for the given action, if any.
+    pub fn action_binding(&self, action: &str) -> Option<&ActionBinding> {
+        self.action_bindings.get(action)
+    }
+
     /// Check if a binding exists for the given observer.
     pub fn has_observer(&self, observer: &str) -> bool {
         self.observer_bindings.contains_key(observer)
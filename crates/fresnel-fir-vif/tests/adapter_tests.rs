@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 
-use fresnel_fir_ir::types::{ActionBinding, Bindings, EventHooks};
+use fresnel_fir_ir::types::{ActionBinding, ArgType, Bindings, EventHooks};
 use fresnel_fir_sandbox::config::SandboxConfig;
 use fresnel_fir_sandbox::sandbox::Sandbox;
-use fresnel_fir_vif::adapter::{ObserverResult, VerificationAdapter};
+use fresnel_fir_vif::adapter::{ObserverResult, VerificationAdapter, WasmArg};
 
 fn wat_to_wasm(wat: &str) -> Vec<u8> {
     wat::parse_str(wat).expect("valid WAT")
@@ -61,6 +61,7 @@ fn make_test_bindings() -> Bindings {
             idempotent: false,
             reads: vec![],
             writes: vec!["Document".to_string()],
+            arg_types: vec![],
         },
     );
     actions.insert(
@@ -73,6 +74,7 @@ fn make_test_bindings() -> Bindings {
             idempotent: true,
             reads: vec!["Document".to_string()],
             writes: vec![],
+            arg_types: vec![],
         },
     );
     actions.insert(
@@ -85,6 +87,7 @@ fn make_test_bindings() -> Bindings {
             idempotent: false,
             reads: vec![],
             writes: vec!["Document".to_string()],
+            arg_types: vec![],
         },
     );
 
@@ -125,11 +128,11 @@ fn test_execute_action_create() {
     let adapter = VerificationAdapter::from_bindings(&bindings);
 
     // Execute create_document with actor_id=42
-    let result = adapter.execute_action(&mut instance, "create_document", &[42]);
+    let result = adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(42)]);
 
     assert_eq!(result.action, "create_document");
     assert_eq!(result.function, "create_document");
-    assert_eq!(result.args, vec![42]);
+    assert_eq!(result.args, vec![WasmArg::I32(42)]);
     assert_eq!(result.return_value, Some(1)); // First doc created = 1
     assert!(!result.trapped);
     assert!(result.error.is_none());
@@ -149,7 +152,7 @@ fn test_execute_action_with_fuel_tracking() {
     let bindings = make_test_bindings();
     let adapter = VerificationAdapter::from_bindings(&bindings);
 
-    let result = adapter.execute_action(&mut instance, "create_document", &[1]);
+    let result = adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(1)]);
     assert!(result.fuel_consumed.is_some());
     assert!(result.fuel_consumed.unwrap() > 0);
 }
@@ -165,12 +168,49 @@ fn test_execute_void_action() {
     let bindings = make_test_bindings();
     let adapter = VerificationAdapter::from_bindings(&bindings);
 
-    let result = adapter.execute_action(&mut instance, "delete", &[1, 1]);
+    let result =
+        adapter.execute_action(&mut instance, "delete", &[WasmArg::I32(1), WasmArg::I32(1)]);
     assert_eq!(result.action, "delete");
     assert_eq!(result.return_value, None); // void
     assert!(!result.trapped);
 }
 
+#[test]
+fn test_execute_action_passes_i64_untruncated() {
+    let wasm = wat_to_wasm(
+        r#"
+        (module
+          (func (export "high_word") (param i64) (result i32)
+            (i32.wrap_i64 (i64.shr_u (local.get 0) (i64.const 32)))))
+        "#,
+    );
+    let config = SandboxConfig::default();
+    let sandbox = Sandbox::new(&config).unwrap();
+    let module = sandbox.load_module(&wasm).unwrap();
+    let mut instance = sandbox.instantiate(&module).unwrap();
+
+    let mut bindings = make_test_bindings();
+    bindings.actions.insert(
+        "quota".to_string(),
+        ActionBinding {
+            function: "high_word".to_string(),
+            args: vec!["bytes".to_string()],
+            returns: serde_json::json!({ "type": "int" }),
+            mutates: false,
+            idempotent: true,
+            reads: vec![],
+            writes: vec![],
+            arg_types: vec![ArgType::I64],
+        },
+    );
+    let adapter = VerificationAdapter::from_bindings(&bindings);
+
+    // 5_000_000_000 = 0x1_2A05_F200: the high word survives only as an i64.
+    let result = adapter.execute_action(&mut instance, "quota", &[WasmArg::I64(5_000_000_000)]);
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.return_value, Some(1));
+}
+
 #[test]
 fn test_execute_missing_action() {
     let wasm = wat_to_wasm(TEST_DUT_WAT);
@@ -199,9 +239,9 @@ fn test_sequential_actions() {
     let adapter = VerificationAdapter::from_bindings(&bindings);
 
     // Create 3 documents
-    let r1 = adapter.execute_action(&mut instance, "create_document", &[1]);
-    let r2 = adapter.execute_action(&mut instance, "create_document", &[2]);
-    let r3 = adapter.execute_action(&mut instance, "create_document", &[3]);
+    let r1 = adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(1)]);
+    let r2 = adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(2)]);
+    let r3 = adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(3)]);
 
     assert_eq!(r1.return_value, Some(1));
     assert_eq!(r2.return_value, Some(2));
@@ -228,7 +268,7 @@ fn test_observer_query() {
     );
 
     // Create a document
-    adapter.execute_action(&mut instance, "create_document", &[1]);
+    adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(1)]);
 
     // Query observer: doc count should be 1
     let obs = adapter.query_observer(&mut instance, "get_doc_count", &[]);
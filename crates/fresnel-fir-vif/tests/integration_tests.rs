@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use fresnel_fir_ir::types::{ActionBinding, Bindings, EventHooks};
 use fresnel_fir_sandbox::config::SandboxConfig;
 use fresnel_fir_sandbox::sandbox::Sandbox;
-use fresnel_fir_vif::adapter::VerificationAdapter;
+use fresnel_fir_vif::adapter::{VerificationAdapter, WasmArg};
 use fresnel_fir_vif::validate::{validate_interface, validate_signatures};
 
 fn wat_to_wasm(wat: &str) -> Vec<u8> {
@@ -86,6 +86,7 @@ fn make_document_bindings() -> Bindings {
             idempotent: false,
             reads: vec![],
             writes: vec!["Document".to_string()],
+            arg_types: vec![],
         },
     );
     actions.insert(
@@ -98,6 +99,7 @@ fn make_document_bindings() -> Bindings {
             idempotent: true,
             reads: vec!["Document".to_string()],
             writes: vec![],
+            arg_types: vec![],
         },
     );
     actions.insert(
@@ -110,6 +112,7 @@ fn make_document_bindings() -> Bindings {
             idempotent: false,
             reads: vec![],
             writes: vec!["Document".to_string()],
+            arg_types: vec![],
         },
     );
 
@@ -157,12 +160,12 @@ fn test_full_dut_lifecycle() {
     adapter.register_observer("get_owner", "get_owner", &["doc_id".to_string()]);
 
     // Step 5: Execute actions — create two documents
-    let r1 = adapter.execute_action(&mut instance, "create_document", &[100]);
+    let r1 = adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(100)]);
     assert_eq!(r1.return_value, Some(0)); // first doc ID = 0
     assert!(!r1.trapped);
     assert!(r1.fuel_consumed.unwrap() > 0);
 
-    let r2 = adapter.execute_action(&mut instance, "create_document", &[200]);
+    let r2 = adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(200)]);
     assert_eq!(r2.return_value, Some(1)); // second doc ID = 1
 
     // Step 6: Query observers — verify DUT state
@@ -179,7 +182,11 @@ fn test_full_dut_lifecycle() {
     assert_eq!(obs_owner2.value, Some(200)); // doc 1 owned by actor 200
 
     // Step 7: Delete a document
-    let r3 = adapter.execute_action(&mut instance, "delete", &[100, 0]);
+    let r3 = adapter.execute_action(
+        &mut instance,
+        "delete",
+        &[WasmArg::I32(100), WasmArg::I32(0)],
+    );
     assert!(r3.error.is_none());
     assert!(!r3.trapped);
 
@@ -205,14 +212,18 @@ fn test_snapshot_restore_with_adapter() {
     adapter.register_observer("get_visibility", "get_visibility", &["doc_id".to_string()]);
 
     // Create a document
-    adapter.execute_action(&mut instance, "create_document", &[100]);
+    adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(100)]);
 
     // Snapshot at model generation 1
     let snap = instance.snapshot(1).unwrap();
 
     // Create another document and delete the first
-    adapter.execute_action(&mut instance, "create_document", &[200]);
-    adapter.execute_action(&mut instance, "delete", &[100, 0]);
+    adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(200)]);
+    adapter.execute_action(
+        &mut instance,
+        "delete",
+        &[WasmArg::I32(100), WasmArg::I32(0)],
+    );
 
     // Verify current state
     let obs = adapter.query_observer(&mut instance, "get_doc_count", &[]);
@@ -271,9 +282,9 @@ fn test_fuel_metering_reports_consumption() {
     let adapter = VerificationAdapter::from_bindings(&bindings);
 
     // Execute multiple actions, verify fuel consumption is tracked
-    let r1 = adapter.execute_action(&mut instance, "create_document", &[1]);
-    let r2 = adapter.execute_action(&mut instance, "read", &[1, 0]);
-    let r3 = adapter.execute_action(&mut instance, "delete", &[1, 0]);
+    let r1 = adapter.execute_action(&mut instance, "create_document", &[WasmArg::I32(1)]);
+    let r2 = adapter.execute_action(&mut instance, "read", &[WasmArg::I32(1), WasmArg::I32(0)]);
+    let r3 = adapter.execute_action(&mut instance, "delete", &[WasmArg::I32(1), WasmArg::I32(0)]);
 
     // All should have fuel consumed > 0
     assert!(r1.fuel_consumed.unwrap() > 0);
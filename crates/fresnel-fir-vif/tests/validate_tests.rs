@@ -16,6 +16,7 @@ fn make_bindings(actions: Vec<(&str, &str, Vec<&str>, &str)>) -> Bindings {
                 idempotent: false,
                 reads: vec![],
                 writes: vec![],
+                arg_types: vec![],
             },
         );
     }